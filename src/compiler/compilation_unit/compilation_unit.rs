@@ -1,4 +1,4 @@
-use std::cell::RefMut;
+use std::cell::{Ref, RefMut};
 use crate::ns::*;
 use hydroper_source_text::SourceText;
 
@@ -6,7 +6,15 @@ use hydroper_source_text::SourceText;
 /// a source text.
 pub struct CompilationUnit {
     pub(crate) file_path: Option<String>,
-    pub(crate) source_text: SourceText,
+    pub(crate) source_text: Rc<SourceText>,
+    /// Source text after the text edits applied to the compilation unit.
+    pub(crate) current_source_text: RefCell<Rc<SourceText>>,
+    /// Text replacements applied to the source text since the last full
+    /// reparse, in order, used for mapping the offsets of previously built locations.
+    pub(crate) text_shifts: RefCell<Vec<TextShift>>,
+    /// Number of text replacements applied to the source text
+    /// before the last full reparse.
+    pub(crate) text_shifts_base: Cell<usize>,
     pub(crate) already_tokenized: Cell<bool>,
     diagnostics: RefCell<Vec<Diagnostic>>,
     pub(crate) error_count: Cell<u32>,
@@ -18,9 +26,13 @@ pub struct CompilationUnit {
 
 impl Default for CompilationUnit {
     fn default() -> Self {
+        let source_text = Rc::new(SourceText::new("".into()));
         Self {
             file_path: None,
-            source_text: SourceText::new("".into()),
+            current_source_text: RefCell::new(source_text.clone()),
+            source_text,
+            text_shifts: RefCell::new(vec![]),
+            text_shifts_base: Cell::new(0),
             already_tokenized: Cell::new(false),
             diagnostics: RefCell::new(vec![]),
             invalidated: Cell::new(false),
@@ -35,9 +47,13 @@ impl Default for CompilationUnit {
 impl CompilationUnit {
     /// Constructs a source file in unparsed and non verified state.
     pub fn new(file_path: Option<String>, text: String, compiler_options: &Rc<CompilerOptions>) -> Rc<Self> {
+        let source_text = Rc::new(SourceText::new(text));
         Rc::new(Self {
             file_path,
-            source_text: SourceText::new(text),
+            current_source_text: RefCell::new(source_text.clone()),
+            source_text,
+            text_shifts: RefCell::new(vec![]),
+            text_shifts_base: Cell::new(0),
             already_tokenized: Cell::new(false),
             diagnostics: RefCell::new(vec![]),
             invalidated: Cell::new(false),
@@ -53,11 +69,137 @@ impl CompilationUnit {
         self.file_path.clone()
     }
 
-    /// Source text.
+    /// Options that the verifier applies to this compilation unit.
+    pub fn compiler_options(&self) -> Rc<CompilerOptions> {
+        self.compiler_options.clone()
+    }
+
+    /// Source text the compilation unit was constructed with.
+    ///
+    /// The text resulting from `apply_edits()` is given by `current_text()`.
     pub fn text(&self) -> &String {
         &self.source_text.contents
     }
 
+    /// Source text after the text edits applied by `apply_edits()`, which
+    /// locations and line numbers refer to. Equals `text()` if no edits were applied.
+    pub fn current_text(&self) -> Ref<'_, String> {
+        Ref::map(self.current_source_text.borrow(), |source_text| &source_text.contents)
+    }
+
+    /// 64-bit FNV-1a hash of the current UTF-8 source text, identifying
+    /// the content of the compilation unit.
+    pub fn text_hash(&self) -> u64 {
+        fnv1a_hash(self.current_text().as_bytes())
+    }
+
+    /// Applies text edits to the source text and reparses the parts of `program`
+    /// affected by them, returning the updated program.
+    ///
+    /// Each edit is given in the offsets of the text resulting from the
+    /// previous edits. Unchanged directives are reused, and the locations previously
+    /// built for the compilation unit are mapped to the edited text. Comments
+    /// and diagnostics within a reparsed region are replaced by those produced
+    /// by reparsing it.
+    ///
+    /// Edits that cannot be reparsed locally, such as edits touching comments, cause
+    /// the whole program to be reparsed. Locations built before a full reparse are
+    /// no longer mapped to the edited text and should not be used afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, without applying any edit, if the range of an edit
+    /// is reversed, extends past the end of the text or does not lie on
+    /// UTF-8 character boundaries.
+    pub fn apply_edits(self: &Rc<Self>, program: &Rc<Program>, edits: &[TextEdit]) -> Result<Rc<Program>, TextEditError> {
+        let mut text = self.current_text().clone();
+        for (index, edit) in edits.iter().enumerate() {
+            edit.check(&text, index)?;
+            text.replace_range(edit.range.clone(), &edit.replacement);
+        }
+        let mut program = program.clone();
+        for edit in edits {
+            program = IncrementalParser::new(self).apply_edit(&program, edit);
+        }
+        Ok(program)
+    }
+
+    /// Replaces a range of the source text, recording the shift
+    /// of offsets it causes.
+    pub(crate) fn replace_text(&self, edit: &TextEdit) {
+        let mut text = self.current_text().clone();
+        text.replace_range(edit.range.clone(), &edit.replacement);
+        self.current_source_text.replace(Rc::new(SourceText::new(text)));
+        self.text_shifts.borrow_mut().push(TextShift {
+            offset: edit.range.start,
+            replaced_length: edit.range.len(),
+            replacement_length: edit.replacement.len(),
+        });
+    }
+
+    /// Discards the recorded text replacements once every location
+    /// in use has been built for the current source text.
+    pub(crate) fn clear_text_shifts(&self) {
+        let mut text_shifts = self.text_shifts.borrow_mut();
+        self.text_shifts_base.set(self.text_shifts_base.get() + text_shifts.len());
+        text_shifts.clear();
+    }
+
+    /// The number of text replacements applied to the source text.
+    pub(crate) fn text_version(&self) -> usize {
+        self.text_shifts_base.get() + self.text_shifts.borrow().len()
+    }
+
+    /// Maps the first offset of a location built at a given text version
+    /// to the current source text.
+    pub(crate) fn map_first_offset(&self, mut offset: usize, version: usize) -> usize {
+        let Some(shifts) = self.text_shifts_since(version) else {
+            return self.clamp_offset(offset);
+        };
+        for shift in shifts.iter() {
+            if offset >= shift.offset + shift.replaced_length {
+                offset = offset - shift.replaced_length + shift.replacement_length;
+            } else if offset > shift.offset {
+                offset = shift.offset + shift.replacement_length;
+            }
+        }
+        offset
+    }
+
+    /// Maps the last offset of a location built at a given text version
+    /// to the current source text.
+    pub(crate) fn map_last_offset(&self, mut offset: usize, version: usize) -> usize {
+        let Some(shifts) = self.text_shifts_since(version) else {
+            return self.clamp_offset(offset);
+        };
+        for shift in shifts.iter() {
+            if offset > shift.offset {
+                if offset >= shift.offset + shift.replaced_length {
+                    offset = offset - shift.replaced_length + shift.replacement_length;
+                } else {
+                    offset = shift.offset + shift.replacement_length;
+                }
+            }
+        }
+        offset
+    }
+
+    fn text_shifts_since(&self, version: usize) -> Option<Ref<'_, [TextShift]>> {
+        let index = version.checked_sub(self.text_shifts_base.get())?;
+        Some(Ref::map(self.text_shifts.borrow(), |text_shifts| &text_shifts[index..]))
+    }
+
+    /// Clamps an offset of a location built before the last full reparse,
+    /// which can no longer be mapped, to the current source text.
+    fn clamp_offset(&self, offset: usize) -> usize {
+        let text = self.current_text();
+        let mut offset = offset.min(text.len());
+        while !text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        offset
+    }
+
     /// Whether the source contains any errors after parsing
     /// and/or verification.
     pub fn invalidated(&self) -> bool {
@@ -75,7 +217,7 @@ impl CompilationUnit {
     }
 
     /// The comments present in the source file, as a mutable collection.
-    pub fn comments_mut(&self) -> RefMut<Vec<Rc<Comment>>> {
+    pub fn comments_mut(&self) -> RefMut<'_, Vec<Rc<Comment>>> {
         self.comments.borrow_mut()
     }
 
@@ -90,7 +232,36 @@ impl CompilationUnit {
         self.diagnostics.borrow_mut().sort();
     }
 
-    pub fn add_diagnostic(&self, diagnostic: Diagnostic) {
+    /// Replaces the diagnostics of the source file, updating the error
+    /// and warning counts.
+    pub(crate) fn set_diagnostics(&self, diagnostics: Vec<Diagnostic>) {
+        let warning_count = diagnostics.iter().filter(|d| d.is_warning()).count() as u32;
+        self.warning_count.set(warning_count);
+        self.error_count.set(diagnostics.len() as u32 - warning_count);
+        self.invalidated.set(self.error_count.get() != 0);
+        self.diagnostics.replace(diagnostics);
+    }
+
+    /// Removes the diagnostics reported by the verifier, keeping those
+    /// of the parser, so that the program can be verified again.
+    pub fn clear_verify_diagnostics(&self) {
+        let diagnostics = self.diagnostics().into_iter().filter(|diagnostic| !diagnostic.from_verifier()).collect();
+        self.set_diagnostics(diagnostics);
+    }
+
+    /// Adds a diagnostic, ignoring or promoting it to an error according
+    /// to the warning levels of the compiler options.
+    pub fn add_diagnostic(&self, mut diagnostic: Diagnostic) {
+        if diagnostic.is_warning() {
+            match self.compiler_options.warning_level(diagnostic.kind()) {
+                WarningLevel::Ignore => return,
+                WarningLevel::Warning => {},
+                WarningLevel::Error => {
+                    diagnostic.is_warning = false;
+                    diagnostic.is_verify_error = true;
+                },
+            }
+        }
         if diagnostic.is_warning() {
             self.warning_count.set(self.warning_count.get() + 1);
         } else {
@@ -111,27 +282,71 @@ impl CompilationUnit {
     /// Retrieves line number from an offset. The resulting line number
     /// is counted from one.
     pub fn get_line_number(&self, offset: usize) -> usize {
-        self.source_text.get_line_number(offset)
+        self.current_source_text.borrow().get_line_number(offset)
     }
 
     /// Returns the zero based column of an offset.
     pub fn get_column(&self, offset: usize) -> usize {
-        self.source_text.get_column(offset)
+        self.current_source_text.borrow().get_column(offset)
     }
 
     /// Retrieves offset from line number (counted from one).
     pub fn get_line_offset(&self, line: usize) -> Option<usize> {
-        self.source_text.get_line_offset(line)
+        self.current_source_text.borrow().get_line_offset(line)
     }
 
     /// Retrieves the offset from the corresponding line of an offset.
     pub fn get_line_offset_from_offset(&self, offset: usize) -> usize {
-        self.source_text.get_line_offset_from_offset(offset)
+        self.current_source_text.borrow().get_line_offset_from_offset(offset)
     }
 
+    /// Returns the number of whitespace characters that indent
+    /// a line (counted from one).
     pub fn get_line_indent(&self, line: usize) -> usize {
         let line_offset = self.get_line_offset(line).unwrap();
-        let indent = CharacterValidator::indent_count(&self.source_text.contents[line_offset..]);
-        indent - line_offset
+        CharacterValidator::indent_count(&self.current_text()[line_offset..])
     }
-}
\ No newline at end of file
+}
+
+/// A replacement of a range of UTF-8 offsets in a source text.
+#[derive(Clone, Debug)]
+pub struct TextEdit {
+    pub range: std::ops::Range<usize>,
+    pub replacement: String,
+}
+
+impl TextEdit {
+    pub fn new(range: std::ops::Range<usize>, replacement: &str) -> Self {
+        Self { range, replacement: replacement.into() }
+    }
+
+    fn check(&self, text: &str, index: usize) -> Result<(), TextEditError> {
+        if self.range.start > self.range.end {
+            Err(TextEditError::ReversedRange(index))
+        } else if self.range.end > text.len() {
+            Err(TextEditError::OutOfBounds(index))
+        } else if !text.is_char_boundary(self.range.start) || !text.is_char_boundary(self.range.end) {
+            Err(TextEditError::NotCharBoundary(index))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Error applying text edits, holding the index of the invalid edit.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TextEditError {
+    /// The range ends before it starts.
+    ReversedRange(usize),
+    /// The range extends past the end of the text.
+    OutOfBounds(usize),
+    /// An offset of the range does not lie on a UTF-8 character boundary.
+    NotCharBoundary(usize),
+}
+
+#[derive(Copy, Clone)]
+pub(crate) struct TextShift {
+    offset: usize,
+    replaced_length: usize,
+    replacement_length: usize,
+}
@@ -1,24 +1,71 @@
 use std::cmp::Ordering;
 use std::fmt::Debug;
-use serde::{Serialize, Deserialize};
+use serde::{Serialize, Serializer, Deserialize, Deserializer, de::Error, ser::SerializeStruct};
 use std::rc::Rc;
+use std::cell::RefCell;
 use crate::compilation_unit::*;
 
+thread_local! {
+    static SERIALIZATION_CONTEXT: RefCell<Option<Rc<CompilationUnit>>> = const { RefCell::new(None) };
+}
+
 /// Represents a source location. This location includes
 /// spanning lines and columns and the reference compilation unit.
-#[derive(Clone, Serialize, Deserialize)]
+///
+/// A location is serialized with its offsets only within
+/// `Location::with_serialization_context`, as when reading or writing
+/// an AST JSON document; otherwise it is serialized as an empty struct.
+#[derive(Clone)]
 pub struct Location {
     /// The compilation unit that this location belongs to.
-    #[serde(skip)]
     pub(crate) compilation_unit: Rc<CompilationUnit>,
 
     /// First UTF-8 offset.
-    #[serde(skip)]
     pub(crate) first_offset: usize,
 
     /// Last UTF-8 offset.
-    #[serde(skip)]
     pub(crate) last_offset: usize,
+
+    /// Number of text edits applied to the compilation unit
+    /// when this location was built.
+    pub(crate) version: usize,
+}
+
+impl Serialize for Location {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if Self::serialization_context().is_none() {
+            return serializer.serialize_struct("Location", 0)?.end();
+        }
+        let mut state = serializer.serialize_struct("Location", 2)?;
+        state.serialize_field("first_offset", &self.first_offset())?;
+        state.serialize_field("last_offset", &self.last_offset())?;
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Location {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Empty {}
+
+        #[derive(Deserialize)]
+        struct Offsets {
+            first_offset: usize,
+            last_offset: usize,
+        }
+
+        let Some(compilation_unit) = Self::serialization_context() else {
+            Empty::deserialize(deserializer)?;
+            return Ok(Self::with_offset(&Rc::new(CompilationUnit::default()), 0));
+        };
+        let Offsets { first_offset, last_offset } = Offsets::deserialize(deserializer)?;
+        let text = compilation_unit.current_text();
+        if first_offset > last_offset || !text.is_char_boundary(first_offset) || !text.is_char_boundary(last_offset) {
+            return Err(D::Error::custom(format!("invalid location offsets {first_offset}..{last_offset}")));
+        }
+        drop(text);
+        Ok(Self::with_offsets(&compilation_unit, first_offset, last_offset))
+    }
 }
 
 impl Debug for Location {
@@ -27,10 +74,10 @@ impl Debug for Location {
             "Location(first_line_number={}, first_column={}, first_offset={}, last_line_number={}, last_column={}, last_offset={})",
             self.first_line_number(),
             self.first_column(),
-            self.first_offset,
+            self.first_offset(),
             self.last_line_number(),
             self.last_column(),
-            self.last_offset
+            self.last_offset()
         )
     }
 }
@@ -40,24 +87,43 @@ impl Eq for Location {}
 impl PartialEq for Location {
     fn eq(&self, other: &Self) -> bool {
         Rc::ptr_eq(&self.compilation_unit, &other.compilation_unit) &&
-            self.first_offset == other.first_offset &&
-            self.last_offset == other.last_offset
+            self.first_offset() == other.first_offset() &&
+            self.last_offset() == other.last_offset()
     }
 }
 
 impl Ord for Location {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap_or(Ordering::Equal)
+        self.first_offset().cmp(&other.first_offset())
     }
 }
 
 impl PartialOrd for Location {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.first_offset.partial_cmp(&other.first_offset)
+        Some(self.cmp(other))
     }
 }
 
 impl Location {
+    /// Runs a function within which locations are serialized with their
+    /// offsets and deserialized into the given compilation unit.
+    pub(crate) fn with_serialization_context<T>(compilation_unit: &Rc<CompilationUnit>, f: impl FnOnce() -> T) -> T {
+        struct Restore(Option<Rc<CompilationUnit>>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                SERIALIZATION_CONTEXT.with(|context| *context.borrow_mut() = self.0.take());
+            }
+        }
+
+        let _restore = Restore(SERIALIZATION_CONTEXT.with(|context| context.borrow_mut().replace(compilation_unit.clone())));
+        f()
+    }
+
+    fn serialization_context() -> Option<Rc<CompilationUnit>> {
+        SERIALIZATION_CONTEXT.with(|context| context.borrow().clone())
+    }
+
     /// Builds a location.
     pub fn with_offsets(
         compilation_unit: &Rc<CompilationUnit>,
@@ -68,6 +134,7 @@ impl Location {
             compilation_unit: compilation_unit.clone(),
             first_offset,
             last_offset,
+            version: compilation_unit.text_version(),
         }
     }
 
@@ -79,23 +146,18 @@ impl Location {
     /// Build a location by combining two locations. `self`
     /// serves as the first location, while `other` serves as the
     /// last location.
+    ///
+    /// If `other` ends before `self` starts, as for a node that
+    /// consumed no tokens, the result is empty at the start of `self`.
     pub fn combine_with(&self, other: Location) -> Self {
-        Self {
-            compilation_unit: self.compilation_unit.clone(),
-            first_offset: self.first_offset,
-            last_offset: other.last_offset,
-        }
+        Self::with_offsets(&self.compilation_unit, self.first_offset(), other.last_offset().max(self.first_offset()))
     }
 
     /// Build a location by combining two locations. `self`
     /// serves as the first location, while the first column and first line
     /// of `other` serve as the last location.
     pub fn combine_with_start_of(&self, other: Location) -> Self {
-        Self {
-            compilation_unit: self.compilation_unit.clone(),
-            first_offset: self.first_offset,
-            last_offset: other.first_offset,
-        }
+        Self::with_offsets(&self.compilation_unit, self.first_offset(), other.first_offset().max(self.first_offset()))
     }
 
     /// The compilation unit that this location belongs to.
@@ -105,12 +167,12 @@ impl Location {
 
     /// First line number, counted from one.
     pub fn first_line_number(&self) -> usize {
-        self.compilation_unit.get_line_number(self.first_offset)
+        self.compilation_unit.get_line_number(self.first_offset())
     }
 
     /// Last line number, counted from one.
     pub fn last_line_number(&self) -> usize {
-        self.compilation_unit.get_line_number(self.last_offset)
+        self.compilation_unit.get_line_number(self.last_offset())
     }
 
     /// First line offset, counted from one.
@@ -123,28 +185,30 @@ impl Location {
         self.compilation_unit.get_line_offset_from_offset(self.last_offset())
     }
 
-    // The first byte offset of this location.
+    // The first byte offset of this location, in the current source text
+    // of the compilation unit.
     pub fn first_offset(&self) -> usize {
-        self.first_offset
+        self.compilation_unit.map_first_offset(self.first_offset, self.version)
     }
 
-    // The last byte offset of this location.
+    // The last byte offset of this location, in the current source text
+    // of the compilation unit.
     pub fn last_offset(&self) -> usize {
-        self.last_offset
+        self.compilation_unit.map_last_offset(self.last_offset, self.version)
     }
 
     /// Zero based first column of the location in code points.
     pub fn first_column(&self) -> usize {
-        self.compilation_unit.get_column(self.first_offset)
+        self.compilation_unit.get_column(self.first_offset())
     }
 
     /// Zero based last column of the location in code points.
     pub fn last_column(&self) -> usize {
-        self.compilation_unit.get_column(self.last_offset)
+        self.compilation_unit.get_column(self.last_offset())
     }
 
     pub fn character_count(&self) -> usize {
-        self.compilation_unit.text()[self.first_offset..self.last_offset].chars().count()
+        self.compilation_unit.current_text()[self.first_offset()..self.last_offset()].chars().count()
     }
 
     /// Indicates whether a previous location and a next location
@@ -1,9 +1,372 @@
-use std::rc::Rc;
+use crate::ns::*;
+use file_paths::FlexPath;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::str::FromStr;
 
-pub struct CompilerOptions {}
+/// Options shared by the compilation units of a project, read
+/// by the verifier.
+///
+/// Options are loaded from a project configuration file and
+/// from command-line flags, with flags applied last:
+///
+/// ```
+/// use hydroper_jet_compiler::ns::*;
+///
+/// let mut options = CompilerOptions::default();
+/// options.apply_config(r#"{ "mode": "dynamic", "warnings": { "1120": "error" } }"#, None).unwrap();
+/// options.apply_arguments(&["--define".into(), "CONFIG::DEBUG=true".into()]).unwrap();
+/// assert!(!options.is_strict());
+/// assert_eq!(options.warning_level(DiagnosticKind::UnreachableCode), WarningLevel::Error);
+/// assert_eq!(options.constants.get("CONFIG::DEBUG").map(|v| v.as_str()), Some("true"));
+/// ```
+///
+/// # Configuration file
+///
+/// The configuration file is a JSON object whose fields are all optional:
+///
+/// ```json
+/// {
+///     "mode": "strict",
+///     "warningsAsErrors": false,
+///     "warnings": { "1120": "ignore" },
+///     "constants": { "CONFIG::DEBUG": "true" },
+///     "sourceRoots": ["src"],
+///     "locale": "pt-BR",
+///     "messageCatalogs": ["messages"]
+/// }
+/// ```
+///
+/// Relative source roots and message catalog directories are resolved
+/// from the directory of the configuration file.
+///
+/// # Command-line flags
+///
+/// | Flag | Effect |
+/// | ---- | ------ |
+/// | `--config PATH` | Applies a configuration file. |
+/// | `--strict`, `--dynamic` | Sets the mode. |
+/// | `--warnings-as-errors` | Reports warnings as errors. |
+/// | `--warning ID=LEVEL` | Sets the level of a warning, where `LEVEL` is one of `ignore`, `warning` or `error`. |
+/// | `--define NAME=VALUE` | Defines a configuration constant. `--define NAME` defines it as `true`. |
+/// | `--source-root PATH` | Adds a source root. |
+/// | `--locale LOCALE` | Sets the locale of diagnostic messages. |
+/// | `--message-catalog PATH` | Adds a directory of message catalogs. |
+///
+/// Flags taking a value also accept the `--flag=value` form.
+#[derive(Clone, Debug, Default)]
+pub struct CompilerOptions {
+    pub mode: CompilerMode,
+    /// Reports every warning as an error, unless its level
+    /// is set to `WarningLevel::Ignore`.
+    pub warnings_as_errors: bool,
+    /// Levels of specific warnings, overriding their default level.
+    pub warning_levels: HashMap<DiagnosticKind, WarningLevel>,
+    /// Configuration constants by name, such as `CONFIG::DEBUG`, tested
+    /// by the conditions of `configuration` directives.
+    pub constants: BTreeMap<String, String>,
+    /// Directories containing the sources of the project.
+    pub source_roots: Vec<String>,
+    /// Locale of diagnostic messages, such as `pt-BR`, or `None` for English.
+    pub locale: Option<String>,
+    /// Directories containing message catalogs named after their
+    /// locale, such as `pt-BR.json` or `ja.ftl`.
+    pub message_catalogs: Vec<String>,
+}
 
 impl CompilerOptions {
     pub fn new() -> Rc<Self> {
-        Rc::new(Self {})
+        Rc::new(Self::default())
     }
-}
\ No newline at end of file
+
+    /// 64-bit FNV-1a hash of the options, identifying the configuration
+    /// under which a program was parsed and verified.
+    pub fn fingerprint(&self) -> u64 {
+        let mut warning_levels: Vec<(i32, WarningLevel)> = self.warning_levels.iter().map(|(kind, level)| (kind.id(), *level)).collect();
+        warning_levels.sort_by_key(|(id, _)| *id);
+        let description = format!("{:?}|{}|{:?}|{:?}|{:?}|{:?}|{:?}",
+            self.mode, self.warnings_as_errors, warning_levels, self.constants, self.source_roots, self.locale, self.message_catalogs);
+        fnv1a_hash(description.as_bytes())
+    }
+
+    /// Loads options from a project configuration file.
+    pub fn from_config_file(path: &str) -> Result<Self, CompilerOptionsError> {
+        let mut options = Self::default();
+        options.apply_config_file(path)?;
+        Ok(options)
+    }
+
+    /// Applies the options of a project configuration file.
+    pub fn apply_config_file(&mut self, path: &str) -> Result<(), CompilerOptionsError> {
+        let json = std::fs::read_to_string(path).map_err(|error| CompilerOptionsError::Io(format!("{path}: {error}")))?;
+        let directory = FlexPath::new_native(path).resolve("..").to_string_with_flex_separator();
+        self.apply_config(&json, Some(&directory))
+    }
+
+    /// Applies the options of a project configuration given as JSON.
+    /// Relative source roots are resolved from `base_directory` if given.
+    pub fn apply_config(&mut self, json: &str, base_directory: Option<&str>) -> Result<(), CompilerOptionsError> {
+        let config: ConfigFile = serde_json::from_str(json).map_err(|error| CompilerOptionsError::Malformed(error.to_string()))?;
+        if let Some(mode) = config.mode {
+            self.mode = parse_value("mode", &mode)?;
+        }
+        if let Some(warnings_as_errors) = config.warnings_as_errors {
+            self.warnings_as_errors = warnings_as_errors;
+        }
+        for (id, level) in config.warnings {
+            self.set_warning_level(&id, &level)?;
+        }
+        self.constants.extend(config.constants);
+        let resolve = |path: String| match base_directory {
+            Some(base_directory) => FlexPath::new_native(base_directory).resolve(&path).to_string_with_flex_separator(),
+            None => path,
+        };
+        self.source_roots.extend(config.source_roots.into_iter().map(resolve));
+        if let Some(locale) = config.locale {
+            self.locale = Some(locale);
+        }
+        self.message_catalogs.extend(config.message_catalogs.into_iter().map(resolve));
+        Ok(())
+    }
+
+    /// Applies command-line flags, in order.
+    pub fn apply_arguments(&mut self, arguments: &[String]) -> Result<(), CompilerOptionsError> {
+        let mut arguments = arguments.iter();
+        while let Some(argument) = arguments.next() {
+            let (flag, inline_value) = match argument.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_owned())),
+                _ => (argument.as_str(), None),
+            };
+            match flag {
+                "--strict" => self.mode = CompilerMode::Strict,
+                "--dynamic" => self.mode = CompilerMode::Dynamic,
+                "--warnings-as-errors" => self.warnings_as_errors = true,
+                "--config" | "--warning" | "--define" | "--source-root" | "--locale" | "--message-catalog" => {
+                    let Some(value) = inline_value.or_else(|| arguments.next().cloned()) else {
+                        return Err(CompilerOptionsError::MissingValue(flag.into()));
+                    };
+                    match flag {
+                        "--config" => self.apply_config_file(&value)?,
+                        "--warning" => {
+                            let Some((id, level)) = value.split_once('=') else {
+                                return Err(CompilerOptionsError::InvalidValue { option: flag.into(), value });
+                            };
+                            self.set_warning_level(id, level)?;
+                        },
+                        "--define" => {
+                            let (name, value) = value.split_once('=').unwrap_or((&value, "true"));
+                            self.constants.insert(name.into(), value.into());
+                        },
+                        "--source-root" => self.source_roots.push(value),
+                        "--locale" => self.locale = Some(value),
+                        _ => self.message_catalogs.push(value),
+                    }
+                },
+                _ => return Err(CompilerOptionsError::UnknownFlag(argument.clone())),
+            }
+        }
+        Ok(())
+    }
+
+    fn set_warning_level(&mut self, id: &str, level: &str) -> Result<(), CompilerOptionsError> {
+        let kind = id.trim_start_matches('#').parse::<i32>().ok().and_then(DiagnosticKind::from_id)
+            .ok_or_else(|| CompilerOptionsError::InvalidValue { option: "warning".into(), value: id.into() })?;
+        self.warning_levels.insert(kind, parse_value("warning", level)?);
+        Ok(())
+    }
+
+    /// Loads the message catalog of the locale, falling back to English.
+    pub fn message_catalog(&self) -> Result<Rc<MessageCatalog>, MessageCatalogError> {
+        match &self.locale {
+            Some(locale) => MessageCatalog::load_locale(&self.message_catalogs, locale),
+            None => Ok(MessageCatalog::english()),
+        }
+    }
+
+    pub fn is_strict(&self) -> bool {
+        self.mode == CompilerMode::Strict
+    }
+
+    /// Returns the level at which a warning of the given kind is reported.
+    pub fn warning_level(&self, kind: DiagnosticKind) -> WarningLevel {
+        match self.warning_levels.get(&kind) {
+            Some(WarningLevel::Ignore) => WarningLevel::Ignore,
+            _ if self.warnings_as_errors => WarningLevel::Error,
+            Some(level) => *level,
+            None => WarningLevel::Warning,
+        }
+    }
+
+    /// Collects the paths of the `.jet` files within the source roots,
+    /// sorted and without duplicates.
+    pub fn source_files(&self) -> Vec<String> {
+        fn collect(directory: &std::path::Path, files: &mut Vec<String>) {
+            let Ok(entries) = std::fs::read_dir(directory) else {
+                return;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    collect(&path, files);
+                } else if path.extension().is_some_and(|extension| extension == "jet") {
+                    files.push(path.to_string_lossy().into_owned());
+                }
+            }
+        }
+        let mut files = vec![];
+        for source_root in &self.source_roots {
+            collect(std::path::Path::new(source_root), &mut files);
+        }
+        files.sort();
+        files.dedup();
+        files
+    }
+}
+
+/// Indicates how strictly programs are verified.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum CompilerMode {
+    /// Accessing an undefined property of a value whose type is known
+    /// is an error, and catch parameters must be typed.
+    #[default]
+    Strict,
+    /// Undefined properties of values are resolved at runtime,
+    /// and untyped catch parameters are of the `*` type.
+    Dynamic,
+}
+
+impl FromStr for CompilerMode {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "strict" => Ok(Self::Strict),
+            "dynamic" => Ok(Self::Dynamic),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Level at which a warning is reported.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum WarningLevel {
+    Ignore,
+    Warning,
+    Error,
+}
+
+impl FromStr for WarningLevel {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ignore" => Ok(Self::Ignore),
+            "warning" => Ok(Self::Warning),
+            "error" => Ok(Self::Error),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Error loading compiler options.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum CompilerOptionsError {
+    /// The configuration file could not be read.
+    Io(String),
+    /// The configuration is not valid JSON or does not follow the schema.
+    Malformed(String),
+    UnknownFlag(String),
+    MissingValue(String),
+    InvalidValue {
+        option: String,
+        value: String,
+    },
+}
+
+fn parse_value<T: FromStr>(option: &str, value: &str) -> Result<T, CompilerOptionsError> {
+    value.parse().map_err(|_| CompilerOptionsError::InvalidValue {
+        option: option.trim_start_matches("--").into(),
+        value: value.into(),
+    })
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct ConfigFile {
+    mode: Option<String>,
+    warnings_as_errors: Option<bool>,
+    #[serde(default)]
+    warnings: BTreeMap<String, String>,
+    #[serde(default)]
+    constants: BTreeMap<String, String>,
+    #[serde(default)]
+    source_roots: Vec<String>,
+    locale: Option<String>,
+    #[serde(default)]
+    message_catalogs: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+
+    #[test]
+    fn loading_options() {
+        let mut options = CompilerOptions::default();
+        options.apply_config(r#"{
+            "mode": "dynamic",
+            "warningsAsErrors": true,
+            "warnings": { "1120": "ignore" },
+            "constants": { "CONFIG::DEBUG": "true" },
+            "sourceRoots": ["src", "/lib"]
+        }"#, Some("/project")).unwrap();
+        assert_eq!(options.mode, CompilerMode::Dynamic);
+        assert_eq!(options.warning_level(DiagnosticKind::UnreachableCode), WarningLevel::Ignore);
+        assert_eq!(options.warning_level(DiagnosticKind::MissingReturn), WarningLevel::Error);
+        assert_eq!(options.source_roots, vec!["/project/src".to_owned(), "/lib".to_owned()]);
+
+        options.apply_arguments(&["--strict", "--warning=1120=warning", "--define", "CONFIG::RELEASE"].map(String::from)).unwrap();
+        assert!(options.is_strict());
+        assert_eq!(options.warning_level(DiagnosticKind::UnreachableCode), WarningLevel::Error);
+        assert_eq!(options.constants.get("CONFIG::RELEASE").map(|v| v.as_str()), Some("true"));
+
+        assert_eq!(options.apply_arguments(&["--mode".into()]), Err(CompilerOptionsError::UnknownFlag("--mode".into())));
+        assert_eq!(options.apply_arguments(&["--define".into()]), Err(CompilerOptionsError::MissingValue("--define".into())));
+        assert_eq!(options.apply_arguments(&["--warning".into(), "9999=error".into()]), Err(CompilerOptionsError::InvalidValue { option: "warning".into(), value: "9999".into() }));
+        assert!(matches!(options.apply_config(r#"{ "strict": true }"#, None), Err(CompilerOptionsError::Malformed(_))));
+
+        // The language has a single edition, which is not an option.
+        assert_eq!(options.apply_arguments(&["--edition".into(), "1.0".into()]), Err(CompilerOptionsError::UnknownFlag("--edition".into())));
+        assert!(matches!(options.apply_config(r#"{ "edition": "1.0" }"#, None), Err(CompilerOptionsError::Malformed(_))));
+    }
+
+    fn verify(text: &str, options: CompilerOptions) -> Vec<String> {
+        let host = SymbolHost::new("", "");
+        let compilation_unit = CompilationUnit::new(None, text.into(), &Rc::new(options));
+        let program = ParserFacade::parse_program(&compilation_unit).unwrap();
+        Verifier::new(&host).verify_programs(vec![program]);
+        compilation_unit.sort_diagnostics();
+        compilation_unit.diagnostics().iter().map(|d| d.format_english()).collect()
+    }
+
+    #[test]
+    fn applying_options() {
+        const JET_LANG: &str = "package jet.lang { public class Object {} public class Boolean {} public class Class {} public class Function {} public class Number {} public class String {} }\n";
+        let text = JET_LANG.to_owned() + "class C {} function f(c: C): void { c.x; try {} catch (e) {} return; c; }";
+
+        assert_eq!(verify(&text, CompilerOptions::default()), vec![
+            "2:39: Verify error #1089: Access of undefined property 'x'".to_owned(),
+            "2:56: Verify error #1119: Catch parameter must have a type annotation".to_owned(),
+            "2:70: Warning #1120: Unreachable code".to_owned(),
+        ]);
+
+        let dynamic = CompilerOptions {
+            mode: CompilerMode::Dynamic,
+            warnings_as_errors: true,
+            ..default()
+        };
+        assert_eq!(verify(&text, dynamic), vec!["2:70: Verify error #1120: Unreachable code".to_owned()]);
+
+        let mut ignoring = CompilerOptions::default();
+        ignoring.warning_levels.insert(DiagnosticKind::UnreachableCode, WarningLevel::Ignore);
+        assert_eq!(verify(&text, ignoring).len(), 2);
+    }
+}
@@ -1,4 +1,10 @@
 mod diagnostics;
 pub use diagnostics::*;
 mod diagnostic_kind;
-pub use diagnostic_kind::*;
\ No newline at end of file
+pub use diagnostic_kind::*;
+mod message_catalog;
+pub use message_catalog::*;
+mod diagnostic_export;
+pub use diagnostic_export::*;
+mod diagnostic_renderer;
+pub use diagnostic_renderer::*;
\ No newline at end of file
@@ -0,0 +1,326 @@
+use crate::ns::*;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Writes diagnostics in structured formats consumed by other tools.
+///
+/// Two formats are supported:
+///
+/// * JSON Lines, with one JSON object per diagnostic.
+/// * SARIF 2.1.0, for code scanning dashboards.
+///
+/// Messages are formatted through a `MessageCatalog`. Lines and columns
+/// are counted from one, and the end column is the column following
+/// the last character of the diagnostic.
+///
+/// # JSON Lines
+///
+/// ```json
+/// {"id":1089,"customId":null,"severity":"verifyError","filePath":"a.jet","start":{"line":1,"column":3},"end":{"line":1,"column":4},"message":"Access of undefined property 'x'","arguments":[{"kind":"string","value":"x"}]}
+/// ```
+///
+/// `severity` is one of `syntaxError`, `verifyError` or `warning`. Arguments
+/// are written unformatted, with a `kind` of `string`, `token` or `symbol`;
+/// tokens are written as their English description, such as `end of program`.
+///
+/// # Example
+///
+/// ```
+/// use hydroper_jet_compiler::ns::*;
+///
+/// let compilation_unit = CompilationUnit::new(Some("a.jet".into()), "x +".into(), &CompilerOptions::new());
+/// ParserFacade::parse_program(&compilation_unit);
+/// let json_lines = DiagnosticExport::to_json_lines(&compilation_unit.diagnostics(), &MessageCatalog::english());
+/// assert!(json_lines.starts_with(r#"{"id":1030,"#));
+/// ```
+pub struct DiagnosticExport;
+
+impl DiagnosticExport {
+    /// Version of the SARIF format.
+    pub const SARIF_VERSION: &'static str = "2.1.0";
+
+    /// JSON schema of the SARIF format.
+    pub const SARIF_SCHEMA: &'static str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+    /// Writes diagnostics as JSON Lines, each line ending with a line feed.
+    pub fn to_json_lines(diagnostics: &[Diagnostic], catalog: &MessageCatalog) -> String {
+        diagnostics.iter().map(|diagnostic| serde_json::to_string(&Self::record(diagnostic, catalog)).unwrap() + "\n").collect()
+    }
+
+    /// Writes diagnostics as a SARIF log with a single run.
+    pub fn to_sarif(diagnostics: &[Diagnostic], catalog: &MessageCatalog) -> String {
+        let mut rules: BTreeMap<i32, SarifRule> = BTreeMap::new();
+        for diagnostic in diagnostics {
+            rules.entry(diagnostic.id()).or_insert_with(|| SarifRule {
+                id: diagnostic.id().to_string(),
+                name: format!("{:?}", diagnostic.kind()),
+                short_description: SarifMessage {
+                    text: catalog.message(diagnostic.kind()).unwrap_or_default(),
+                },
+            });
+        }
+        let rule_indices: HashMap<i32, usize> = rules.keys().enumerate().map(|(i, id)| (*id, i)).collect();
+
+        let results = diagnostics.iter().map(|diagnostic| {
+            let record = Self::record(diagnostic, catalog);
+            let locations = record.file_path.as_ref().map(|file_path| vec![SarifLocation {
+                physical_location: SarifPhysicalLocation {
+                    artifact_location: SarifArtifactLocation {
+                        uri: file_path.replace('\\', "/"),
+                    },
+                    region: SarifRegion {
+                        start_line: record.start.line,
+                        start_column: record.start.column,
+                        end_line: record.end.line,
+                        end_column: record.end.column,
+                    },
+                },
+            }]).unwrap_or_default();
+            SarifResult {
+                rule_id: record.id.to_string(),
+                rule_index: rule_indices[&record.id],
+                level: if diagnostic.is_warning() { "warning" } else { "error" },
+                message: SarifMessage {
+                    text: record.message,
+                },
+                locations,
+                properties: SarifResultProperties {
+                    severity: record.severity,
+                    custom_id: record.custom_id,
+                    arguments: record.arguments,
+                },
+            }
+        }).collect();
+
+        let log = SarifLog {
+            schema: Self::SARIF_SCHEMA,
+            version: Self::SARIF_VERSION,
+            runs: vec![SarifRun {
+                tool: SarifTool {
+                    driver: SarifDriver {
+                        name: "hydroper-jet",
+                        version: env!("CARGO_PKG_VERSION"),
+                        information_uri: env!("CARGO_PKG_REPOSITORY"),
+                        rules: rules.into_values().collect(),
+                    },
+                },
+                results,
+            }],
+        };
+        serde_json::to_string_pretty(&log).unwrap()
+    }
+
+    fn record(diagnostic: &Diagnostic, catalog: &MessageCatalog) -> DiagnosticRecord {
+        let location = diagnostic.location();
+        DiagnosticRecord {
+            id: diagnostic.id(),
+            custom_id: diagnostic.custom_id(),
+            severity: match diagnostic.category() {
+                DiagnosticCategory::SyntaxError => "syntaxError",
+                DiagnosticCategory::VerifyError => "verifyError",
+                DiagnosticCategory::Warning => "warning",
+            },
+            file_path: location.compilation_unit().file_path(),
+            start: RecordPosition {
+                line: location.first_line_number(),
+                column: location.first_column() + 1,
+            },
+            end: RecordPosition {
+                line: location.last_line_number(),
+                column: location.last_column() + 1,
+            },
+            message: diagnostic.format_message(catalog),
+            arguments: diagnostic.arguments().iter().map(|argument| match argument {
+                DiagnosticArgument::String(s) => RecordArgument::String(s.clone()),
+                DiagnosticArgument::Token(t) => RecordArgument::Token(t.to_string()),
+                DiagnosticArgument::Symbol(s) => RecordArgument::Symbol(s.to_string()),
+            }).collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DiagnosticRecord {
+    id: i32,
+    custom_id: Option<String>,
+    severity: &'static str,
+    file_path: Option<String>,
+    start: RecordPosition,
+    end: RecordPosition,
+    message: String,
+    arguments: Vec<RecordArgument>,
+}
+
+#[derive(Serialize)]
+struct RecordPosition {
+    line: usize,
+    column: usize,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", content = "value", rename_all = "camelCase")]
+enum RecordArgument {
+    String(String),
+    Token(String),
+    Symbol(String),
+}
+
+#[derive(Serialize)]
+struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<SarifRun>,
+}
+
+#[derive(Serialize)]
+struct SarifRun {
+    tool: SarifTool,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Serialize)]
+struct SarifTool {
+    driver: SarifDriver,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifDriver {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: Vec<SarifRule>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRule {
+    id: String,
+    name: String,
+    short_description: SarifMessage,
+}
+
+#[derive(Serialize)]
+struct SarifMessage {
+    text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: String,
+    rule_index: usize,
+    level: &'static str,
+    message: SarifMessage,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    locations: Vec<SarifLocation>,
+    properties: SarifResultProperties,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifLocation {
+    physical_location: SarifPhysicalLocation,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifPhysicalLocation {
+    artifact_location: SarifArtifactLocation,
+    region: SarifRegion,
+}
+
+#[derive(Serialize)]
+struct SarifArtifactLocation {
+    uri: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRegion {
+    start_line: usize,
+    start_column: usize,
+    end_line: usize,
+    end_column: usize,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResultProperties {
+    severity: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    custom_id: Option<String>,
+    arguments: Vec<RecordArgument>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+    use serde_json::{json, Value};
+
+    fn diagnostics() -> Vec<Diagnostic> {
+        let compilation_unit = CompilationUnit::new(Some("src\\a.jet".into()), "\nvar x = y\n".into(), &CompilerOptions::new());
+        let location = Location::with_offsets(&compilation_unit, 9, 10);
+        compilation_unit.add_diagnostic(Diagnostic::new_verify_error(&location, DiagnosticKind::UndefinedProperty, diagnostic_arguments![String("y".into())]));
+        compilation_unit.add_diagnostic(Diagnostic::new_warning(&location, DiagnosticKind::UnreachableCode, diagnostic_arguments![]));
+        compilation_unit.add_diagnostic(Diagnostic::new_syntax_error(&Location::with_offset(&compilation_unit, 11), DiagnosticKind::ExpectedExpression, diagnostic_arguments![Token(Token::Eof)]));
+        let diagnostics = compilation_unit.diagnostics();
+        diagnostics[0].set_custom_id(Some("custom"));
+        diagnostics
+    }
+
+    #[test]
+    fn exporting_json_lines() {
+        let json_lines = DiagnosticExport::to_json_lines(&diagnostics(), &MessageCatalog::english());
+        let records: Vec<Value> = json_lines.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0], json!({
+            "id": 1089,
+            "customId": "custom",
+            "severity": "verifyError",
+            "filePath": "src\\a.jet",
+            "start": { "line": 2, "column": 9 },
+            "end": { "line": 2, "column": 10 },
+            "message": "Access of undefined property 'y'",
+            "arguments": [{ "kind": "string", "value": "y" }],
+        }));
+        assert_eq!(records[1]["severity"], json!("warning"));
+        assert_eq!(records[2]["severity"], json!("syntaxError"));
+        assert_eq!(records[2]["start"], json!({ "line": 3, "column": 1 }));
+        assert_eq!(records[2]["arguments"], json!([{ "kind": "token", "value": "end of program" }]));
+    }
+
+    #[test]
+    fn exporting_sarif() {
+        let sarif: Value = serde_json::from_str(&DiagnosticExport::to_sarif(&diagnostics(), &MessageCatalog::english())).unwrap();
+        assert_eq!(sarif["version"], json!("2.1.0"));
+        let run = &sarif["runs"][0];
+        assert_eq!(run["tool"]["driver"]["name"], json!("hydroper-jet"));
+        assert_eq!(run["tool"]["driver"]["rules"][1], json!({
+            "id": "1089",
+            "name": "UndefinedProperty",
+            "shortDescription": { "text": "Access of undefined property '{1}'" },
+        }));
+        assert_eq!(run["results"][0], json!({
+            "ruleId": "1089",
+            "ruleIndex": 1,
+            "level": "error",
+            "message": { "text": "Access of undefined property 'y'" },
+            "locations": [{
+                "physicalLocation": {
+                    "artifactLocation": { "uri": "src/a.jet" },
+                    "region": { "startLine": 2, "startColumn": 9, "endLine": 2, "endColumn": 10 },
+                },
+            }],
+            "properties": {
+                "severity": "verifyError",
+                "customId": "custom",
+                "arguments": [{ "kind": "string", "value": "y" }],
+            },
+        }));
+        assert_eq!(run["results"][1]["level"], json!("warning"));
+        assert_eq!(run["results"][2]["ruleIndex"], json!(0));
+    }
+}
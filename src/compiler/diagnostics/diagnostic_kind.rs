@@ -1,5 +1,12 @@
+// `num_derive` expands to a non-local `impl` block.
+#![allow(non_local_definitions)]
+
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
 #[repr(i32)]
-#[derive(Eq, PartialEq, Clone, Copy)]
+#[derive(FromPrimitive)]
+#[derive(Eq, PartialEq, Hash, Clone, Copy, Debug)]
 pub enum DiagnosticKind {
     UnexpectedOrInvalidToken = 1024,
     UnexpectedEnd = 1025,
@@ -69,10 +76,57 @@ pub enum DiagnosticKind {
     IncompatibleFieldKey = 1090,
     MissingPropertyInLiteral = 1091,
     MalformedDestructuring = 1092,
+    UnresolvedReference = 1093,
+    WrongNumberOfTypeArguments = 1094,
+    UndefinedPackage = 1095,
+    IllegalSuperExpression = 1096,
+    NotCallable = 1097,
+    DuplicateDefinition = 1098,
+    NotAClass = 1099,
+    NotAnInterface = 1100,
+    ExtendingFinalClass = 1101,
+    CircularInheritance = 1102,
+    EnumNumericTypeMustBeNumeric = 1103,
+    AbstractMethodMustBeOverriden = 1104,
+    MustOverrideAMethod = 1105,
+    IncompatibleOverride = 1106,
+    OverridingFinalMethod = 1107,
+    UnimplementedMethod = 1108,
+    UnimplementedGetter = 1109,
+    UnimplementedSetter = 1110,
+    PropertyMustBeMethod = 1111,
+    PropertyMustBeVirtualProperty = 1112,
+    WrongMethodSignature = 1113,
+    WrongGetterSignature = 1114,
+    WrongSetterSignature = 1115,
+    WrongVisibility = 1116,
+    CannotIterateType = 1117,
+    ReturnValueExpected = 1118,
+    CatchParameterMustBeTyped = 1119,
+    UnreachableCode = 1120,
+    MissingReturn = 1121,
+    UseBeforeAssignment = 1122,
+    DuplicateCaseLabel = 1123,
+    TooFewArguments = 1124,
+    TooManyArguments = 1125,
+    CouldNotInferTypeArguments = 1126,
+    TypeArgumentOutOfBounds = 1127,
+    UndefinedConfigurationConstant = 1128,
+    ConfigurationConditionMustBeConstant = 1129,
 }
 
 impl DiagnosticKind {
     pub fn id(&self) -> i32 {
         *self as i32
     }
+
+    /// Returns the diagnostic kind with the given identifier.
+    pub fn from_id(id: i32) -> Option<Self> {
+        FromPrimitive::from_i32(id)
+    }
+
+    /// Returns the diagnostic kind with the given name, such as `UndefinedProperty`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        (1024..2048).filter_map(Self::from_id).find(|kind| format!("{kind:?}") == name)
+    }
 }
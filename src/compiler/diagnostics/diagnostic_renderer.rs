@@ -0,0 +1,298 @@
+use crate::ns::*;
+use std::collections::BTreeMap;
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const BOLD_RED: &str = "\x1b[1;31m";
+const BOLD_YELLOW: &str = "\x1b[1;33m";
+const BOLD_BLUE: &str = "\x1b[1;34m";
+
+/// Renders diagnostics for terminals with the offending source lines,
+/// line number gutters and markers under the diagnostic spans.
+///
+/// ```plain
+/// Verify error #1098: 'f' is already defined
+///  --> a.jet:3:10
+///   |
+/// 1 | function f(): void {}
+///   |          - previously defined here
+/// 2 |
+/// 3 | function f(): void {}
+///   |          ^
+/// ```
+///
+/// The span of the diagnostic is marked with carets and the spans of its
+/// labels are marked with hyphens. A span over several lines is underlined
+/// from its first column to the end of its first line and from the
+/// indentation of the following lines. Spans over more than four lines
+/// only display their first two lines and their last line.
+///
+/// Labels located in another compilation unit are rendered after
+/// a `:::` line indicating their position.
+///
+/// # Example
+///
+/// ```
+/// use hydroper_jet_compiler::ns::*;
+///
+/// let compilation_unit = CompilationUnit::new(Some("a.jet".into()), "x +".into(), &CompilerOptions::new());
+/// ParserFacade::parse_program(&compilation_unit);
+/// let renderer = DiagnosticRenderer::new(&MessageCatalog::english(), false);
+/// assert!(renderer.render(&compilation_unit.diagnostics()[0]).contains("1 | x +\n"));
+/// ```
+pub struct DiagnosticRenderer {
+    catalog: Rc<MessageCatalog>,
+    colors: bool,
+}
+
+/// A span marked under a source line.
+struct Marker {
+    start: usize,
+    /// End column, or `None` for the end of the line.
+    end: Option<usize>,
+    primary: bool,
+    text: Option<String>,
+}
+
+impl DiagnosticRenderer {
+    /// Number of columns that a tab character occupies.
+    pub const TAB_WIDTH: usize = 4;
+
+    const MAX_SPAN_LINES: usize = 4;
+
+    /// Creates a renderer that formats messages through a catalog and
+    /// optionally colors its output with ANSI escape sequences.
+    pub fn new(catalog: &Rc<MessageCatalog>, colors: bool) -> Self {
+        Self {
+            catalog: catalog.clone(),
+            colors,
+        }
+    }
+
+    /// Renders a diagnostic. The result ends with a line feed.
+    pub fn render(&self, diagnostic: &Diagnostic) -> String {
+        let style = if diagnostic.is_warning() { BOLD_YELLOW } else { BOLD_RED };
+        let category = self.catalog.category(diagnostic.category());
+        let mut result = format!("{}{}\n",
+            self.paint(style, &format!("{category} #{}", diagnostic.id())),
+            self.paint(BOLD, &format!(": {}", diagnostic.format_message(&self.catalog))));
+
+        // Group the spans by compilation unit, starting with
+        // the compilation unit of the diagnostic.
+        let mut groups: Vec<Vec<(Location, bool, Option<String>)>> = vec![vec![(diagnostic.location(), true, None)]];
+        for label in diagnostic.labels() {
+            let text = Some(self.catalog.term(&label.text));
+            let group = groups.iter_mut().find(|group| Rc::ptr_eq(&group[0].0.compilation_unit(), &label.location.compilation_unit()));
+            if let Some(group) = group {
+                group.push((label.location.clone(), false, text));
+            } else {
+                groups.push(vec![(label.location.clone(), false, text)]);
+            }
+        }
+
+        let last_line = groups.iter().flatten().map(|(location, ..)| location.last_line_number()).max().unwrap_or(1);
+        let gutter_width = last_line.to_string().len();
+        let pad = " ".repeat(gutter_width);
+
+        for (i, group) in groups.iter().enumerate() {
+            let arrow = if i == 0 { "-->" } else { ":::" };
+            result.push_str(&format!("{pad}{} {}\n", self.paint(BOLD_BLUE, arrow), Self::position(&group[0].0)));
+            result.push_str(&format!("{}\n", self.paint(BOLD_BLUE, &format!("{pad} |"))));
+
+            let compilation_unit = group[0].0.compilation_unit();
+            let mut lines: BTreeMap<usize, Vec<Marker>> = BTreeMap::new();
+            for (location, primary, text) in group {
+                Self::mark(&compilation_unit, location, *primary, text, &mut lines);
+            }
+
+            let mut previous_line: Option<usize> = None;
+            for (line, markers) in lines.iter_mut() {
+                if let Some(previous_line) = previous_line {
+                    if *line == previous_line + 2 {
+                        result.push_str(&self.render_source_line(&compilation_unit, previous_line + 1, gutter_width));
+                    } else if *line > previous_line + 2 {
+                        result.push_str(&format!("{}\n", self.paint(BOLD_BLUE, "...")));
+                    }
+                }
+                previous_line = Some(*line);
+
+                result.push_str(&self.render_source_line(&compilation_unit, *line, gutter_width));
+                let columns = Self::display_columns(&Self::line_text(&compilation_unit, *line));
+                markers.sort_by_key(|marker| (marker.start, !marker.primary));
+                for marker in markers.iter() {
+                    let start = Self::display_column(&columns, marker.start);
+                    let end = Self::display_column(&columns, marker.end.unwrap_or(columns.len() - 1));
+                    let marks = (if marker.primary { "^" } else { "-" }).repeat(end.saturating_sub(start).max(1));
+                    let marks = match &marker.text {
+                        Some(text) => format!("{marks} {text}"),
+                        None => marks,
+                    };
+                    result.push_str(&format!("{} {}{}\n",
+                        self.paint(BOLD_BLUE, &format!("{pad} |")),
+                        " ".repeat(start),
+                        self.paint(if marker.primary { style } else { BOLD_BLUE }, &marks)));
+                }
+            }
+        }
+        result
+    }
+
+    /// Adds the markers of a span to the lines it covers.
+    fn mark(compilation_unit: &Rc<CompilationUnit>, location: &Location, primary: bool, text: &Option<String>, lines: &mut BTreeMap<usize, Vec<Marker>>) {
+        let first_line = location.first_line_number();
+        let mut last_line = location.last_line_number();
+        let mut end = Some(location.last_column());
+
+        // A span that ends at a line terminator ends
+        // at the end of the previous line.
+        if last_line > first_line && end == Some(0) {
+            last_line -= 1;
+            end = None;
+        }
+
+        if first_line == last_line {
+            lines.entry(first_line).or_default().push(Marker { start: location.first_column(), end, primary, text: text.clone() });
+            return;
+        }
+
+        lines.entry(first_line).or_default().push(Marker { start: location.first_column(), end: None, primary, text: None });
+        let middle_end = if last_line - first_line + 1 > Self::MAX_SPAN_LINES { first_line + 1 } else { last_line - 1 };
+        for line in (first_line + 1)..=middle_end {
+            let start = compilation_unit.get_line_indent(line);
+            lines.entry(line).or_default().push(Marker { start, end: None, primary, text: None });
+        }
+        let start = compilation_unit.get_line_indent(last_line).min(end.unwrap_or(usize::MAX));
+        lines.entry(last_line).or_default().push(Marker { start, end, primary, text: text.clone() });
+    }
+
+    fn render_source_line(&self, compilation_unit: &Rc<CompilationUnit>, line: usize, gutter_width: usize) -> String {
+        let text = Self::line_text(compilation_unit, line).replace('\t', &" ".repeat(Self::TAB_WIDTH));
+        let gutter = self.paint(BOLD_BLUE, &format!("{line:>gutter_width$} |"));
+        if text.is_empty() {
+            format!("{gutter}\n")
+        } else {
+            format!("{gutter} {text}\n")
+        }
+    }
+
+    /// Returns the text of a line, excluding its line terminator.
+    fn line_text(compilation_unit: &Rc<CompilationUnit>, line: usize) -> String {
+        let Some(offset) = compilation_unit.get_line_offset(line) else {
+            return "".into();
+        };
+        let text = compilation_unit.current_text();
+        let rest = &text[offset..];
+        rest[..rest.find(['\n', '\r']).unwrap_or(rest.len())].to_owned()
+    }
+
+    /// Returns the display column at which each character of a line
+    /// starts, followed by the display width of the line.
+    fn display_columns(line: &str) -> Vec<usize> {
+        let mut columns = vec![0];
+        for ch in line.chars() {
+            let width = if ch == '\t' { Self::TAB_WIDTH } else { 1 };
+            columns.push(columns.last().unwrap() + width);
+        }
+        columns
+    }
+
+    fn display_column(columns: &[usize], column: usize) -> usize {
+        let width = *columns.last().unwrap();
+        columns.get(column).copied().unwrap_or(width + column + 1 - columns.len())
+    }
+
+    fn position(location: &Location) -> String {
+        let file_path = location.compilation_unit().file_path().map_or("".to_owned(), |s| format!("{s}:"));
+        format!("{file_path}{}:{}", location.first_line_number(), location.first_column() + 1)
+    }
+
+    fn paint(&self, style: &str, text: &str) -> String {
+        if self.colors {
+            format!("{style}{text}{RESET}")
+        } else {
+            text.to_owned()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+
+    const JET_LANG: &str = "package jet.lang { public class Object {} public class Boolean {} public class Class {} public class Function {} public class Number {} public class String {} }\n";
+
+    #[test]
+    fn rendering_single_line_spans() {
+        let compilation_unit = CompilationUnit::new(Some("a.jet".into()), "var x = yy\n".into(), &CompilerOptions::new());
+        let location = Location::with_offsets(&compilation_unit, 8, 10);
+        let diagnostic = Diagnostic::new_verify_error(&location, DiagnosticKind::UndefinedProperty, diagnostic_arguments![String("yy".into())]);
+        assert_eq!(DiagnosticRenderer::new(&MessageCatalog::english(), false).render(&diagnostic), concat!(
+            "Verify error #1089: Access of undefined property 'yy'\n",
+            " --> a.jet:1:9\n",
+            "  |\n",
+            "1 | var x = yy\n",
+            "  |         ^^\n",
+        ));
+
+        let diagnostic = Diagnostic::new_syntax_error(&Location::with_offset(&compilation_unit, 11), DiagnosticKind::ExpectedExpression, diagnostic_arguments![Token(Token::Eof)]);
+        assert_eq!(DiagnosticRenderer::new(&MessageCatalog::english(), true).render(&diagnostic), concat!(
+            "\x1b[1;31mSyntax error #1030\x1b[0m\x1b[1m: Expected expression before end of program\x1b[0m\n",
+            " \x1b[1;34m-->\x1b[0m a.jet:2:1\n",
+            "\x1b[1;34m  |\x1b[0m\n",
+            "\x1b[1;34m2 |\x1b[0m\n",
+            "\x1b[1;34m  |\x1b[0m \x1b[1;31m^\x1b[0m\n",
+        ));
+    }
+
+    #[test]
+    fn rendering_multi_line_spans() {
+        let compilation_unit = CompilationUnit::new(None, "function f() {\n\tx\n\ty\n\tz\n}\n".into(), &CompilerOptions::new());
+        let location = Location::with_offsets(&compilation_unit, 9, 25);
+        let diagnostic = Diagnostic::new_warning(&location, DiagnosticKind::UnreachableCode, diagnostic_arguments![]);
+        assert_eq!(DiagnosticRenderer::new(&MessageCatalog::english(), false).render(&diagnostic), concat!(
+            "Warning #1120: Unreachable code\n",
+            " --> 1:10\n",
+            "  |\n",
+            "1 | function f() {\n",
+            "  |          ^^^^^\n",
+            "2 |     x\n",
+            "  |     ^\n",
+            "...\n",
+            "5 | }\n",
+            "  | ^\n",
+        ));
+    }
+
+    #[test]
+    fn rendering_labels() {
+        let compilation_unit = CompilationUnit::new(Some("a.jet".into()), JET_LANG.to_owned() + "function f(): void {}\n\nfunction f(): void {}", &CompilerOptions::new());
+        let program = ParserFacade::parse_program(&compilation_unit).unwrap();
+        Verifier::new(&SymbolHost::new("", "")).verify_programs(vec![program]);
+        let diagnostics = compilation_unit.diagnostics();
+        assert_eq!(DiagnosticRenderer::new(&MessageCatalog::english(), false).render(&diagnostics[0]), concat!(
+            "Verify error #1098: 'f' is already defined\n",
+            " --> a.jet:4:10\n",
+            "  |\n",
+            "2 | function f(): void {}\n",
+            "  |          - previously defined here\n",
+            "3 |\n",
+            "4 | function f(): void {}\n",
+            "  |          ^\n",
+        ));
+
+        let other = CompilationUnit::new(Some("b.jet".into()), "\n\n\n\n\n\n\n\n\n\nvar x".into(), &CompilerOptions::new());
+        let diagnostic = Diagnostic::new_verify_error(&Location::with_offsets(&compilation_unit, JET_LANG.len() + 9, JET_LANG.len() + 10), DiagnosticKind::DuplicateDefinition, diagnostic_arguments![String("x".into())])
+            .with_label(&Location::with_offsets(&other, 14, 15), "previously defined here");
+        assert_eq!(DiagnosticRenderer::new(&MessageCatalog::english(), false).render(&diagnostic), concat!(
+            "Verify error #1098: 'x' is already defined\n",
+            "  --> a.jet:2:10\n",
+            "   |\n",
+            " 2 | function f(): void {}\n",
+            "   |          ^\n",
+            "  ::: b.jet:11:5\n",
+            "   |\n",
+            "11 | var x\n",
+            "   |     - previously defined here\n",
+        ));
+    }
+}
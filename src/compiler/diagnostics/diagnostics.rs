@@ -1,9 +1,5 @@
-use maplit::hashmap;
 use crate::ns::*;
 
-#[path = "diagnostics_english_resources.rs"]
-mod diagnostics_english_resources;
-
 /// Represents a diagnostic originated from a compilation unit.
 /// 
 /// Arguments are formatted using integer keys counted from 1 (one).
@@ -13,8 +9,12 @@ pub struct Diagnostic {
     pub(crate) kind: DiagnosticKind,
     pub(crate) is_warning: bool,
     pub(crate) is_verify_error: bool,
+    /// Whether the diagnostic was reported by the verifier
+    /// rather than by the parser.
+    pub(crate) from_verifier: bool,
     pub(crate) arguments: Vec<DiagnosticArgument>,
     pub(crate) custom_id: RefCell<Option<String>>,
+    pub(crate) labels: Vec<DiagnosticLabel>,
 }
 
 impl Eq for Diagnostic {}
@@ -34,7 +34,7 @@ impl Ord for Diagnostic {
 
 impl PartialOrd for Diagnostic {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.location.partial_cmp(&other.location)
+        Some(self.cmp(other))
     }
 }
 
@@ -44,9 +44,11 @@ impl Diagnostic {
             location: location.clone(),
             kind,
             is_verify_error: false,
+            from_verifier: false,
             is_warning: false,
             arguments,
             custom_id: RefCell::new(None),
+            labels: vec![],
         }
     }
 
@@ -55,9 +57,11 @@ impl Diagnostic {
             location: location.clone(),
             kind,
             is_verify_error: true,
+            from_verifier: true,
             is_warning: false,
             arguments,
             custom_id: RefCell::new(None),
+            labels: vec![],
         }
     }
 
@@ -66,18 +70,39 @@ impl Diagnostic {
             location: location.clone(),
             kind,
             is_verify_error: false,
+            from_verifier: false,
             is_warning: true,
             arguments,
             custom_id: RefCell::new(None),
+            labels: vec![],
+        }
+    }
+
+    /// Constructs a warning reported by the verifier.
+    pub fn new_verify_warning(location: &Location, kind: DiagnosticKind, arguments: Vec<DiagnosticArgument>) -> Self {
+        Self {
+            from_verifier: true,
+            ..Self::new_warning(location, kind, arguments)
         }
     }
 
+    /// Attaches a secondary label to the diagnostic, such as the location
+    /// of the original definition in a duplicate definition error.
+    /// The label text is an English term that message catalogs may translate.
+    pub fn with_label(mut self, location: &Location, text: &str) -> Self {
+        self.labels.push(DiagnosticLabel {
+            location: location.clone(),
+            text: text.into(),
+        });
+        self
+    }
+
     pub fn location(&self) -> Location {
         self.location.clone()
     }
 
     pub fn kind(&self) -> DiagnosticKind {
-        self.kind.clone()
+        self.kind
     }
 
     pub fn is_warning(&self) -> bool {
@@ -88,10 +113,20 @@ impl Diagnostic {
         self.is_verify_error
     }
 
+    /// Whether the diagnostic was reported by the verifier, including
+    /// warnings, rather than by the parser.
+    pub fn from_verifier(&self) -> bool {
+        self.from_verifier
+    }
+
     pub fn arguments(&self) -> Vec<DiagnosticArgument> {
         self.arguments.clone()
     }
 
+    pub fn labels(&self) -> Vec<DiagnosticLabel> {
+        self.labels.clone()
+    }
+
     pub fn id(&self) -> i32 {
         self.kind.id()
     }
@@ -104,16 +139,28 @@ impl Diagnostic {
         self.custom_id.replace(id.map(|id| id.to_owned()));
     }
 
-    /// Formats the diagnostic by overriding the message text.
-    pub fn format_with_message(&self, message: &str) -> String {
-        let category = (if self.is_verify_error {
-            "Verify error"
+    pub fn category(&self) -> DiagnosticCategory {
+        if self.is_verify_error {
+            DiagnosticCategory::VerifyError
         } else if self.is_warning {
-            "Warning"
+            DiagnosticCategory::Warning
         } else {
-            "Syntax error"
-        }).to_owned();
+            DiagnosticCategory::SyntaxError
+        }
+    }
+
+    /// Formats the diagnostic by overriding the message text.
+    pub fn format_with_message(&self, message: &str) -> String {
+        self.format_with_category_and_message(&self.category().to_string(), message)
+    }
+
+    /// Formats the diagnostic with the messages and category names
+    /// of a catalog.
+    pub fn format_with_catalog(&self, catalog: &MessageCatalog) -> String {
+        self.format_with_category_and_message(&catalog.category(self.category()), &self.format_message(catalog))
+    }
 
+    fn format_with_category_and_message(&self, category: &str, message: &str) -> String {
         let file_path = self.location.compilation_unit.file_path.clone().map_or("".to_owned(), |s| format!("{s}:"));
         let line = self.location.first_line_number();
         let column = self.location.first_column() + 1;
@@ -127,30 +174,18 @@ impl Diagnostic {
     }
 
     pub fn format_message_english(&self) -> String {
-        self.format_message(&diagnostics_english_resources::DATA)
+        self.format_message(&MessageCatalog::english())
     }
 
-    pub fn format_message(&self, messages: &HashMap<i32, String>) -> String {
-        let mut string_arguments: HashMap<String, String> = hashmap!{};
-        let mut i = 1;
-        for argument in &self.arguments {
-            string_arguments.insert(i.to_string(), self.format_argument(argument.clone()));
-            i += 1;
-        }
-        use late_format::LateFormat;
-        let Some(msg) = messages.get(&self.id()) else {
+    /// Formats the message of the diagnostic using a catalog, which
+    /// falls back to English for messages it does not define.
+    pub fn format_message(&self, catalog: &MessageCatalog) -> String {
+        let arguments: Vec<String> = self.arguments.iter().map(|argument| catalog.format_argument(argument)).collect();
+        let Some(msg) = catalog.message(self.kind) else {
             let id = self.id();
             panic!("Message resource is missing for ID {id}");
         };
-        msg.late_format(string_arguments)
-    }
-
-    fn format_argument(&self, argument: DiagnosticArgument) -> String {
-        match argument {
-            DiagnosticArgument::String(s) => s.clone(),
-            DiagnosticArgument::Token(t) => t.to_string(),
-            DiagnosticArgument::Symbol(s) => s.to_string(),
-        }
+        catalog.format(&msg, &arguments)
     }
 }
 
@@ -158,6 +193,13 @@ pub macro diagnostic_arguments {
     ($($variant:ident($value:expr)),*) => { vec![ $(DiagnosticArgument::$variant($value)),* ] },
 }
 
+/// Secondary location referenced by a diagnostic.
+#[derive(Clone)]
+pub struct DiagnosticLabel {
+    pub location: Location,
+    pub text: String,
+}
+
 #[derive(Clone)]
 pub enum DiagnosticArgument {
     String(String),
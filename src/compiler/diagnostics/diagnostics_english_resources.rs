@@ -73,6 +73,43 @@ lazy_static! {
         DiagnosticKind::IncompatibleFieldKey.id() => "Incompatible field key".into(),
         DiagnosticKind::MissingPropertyInLiteral.id() => "Missing property in literal: '{1}'".into(),
         DiagnosticKind::MalformedDestructuring.id() => "Malformed destructuring".into(),
+        DiagnosticKind::UnresolvedReference.id() => "Unresolved reference".into(),
+        DiagnosticKind::WrongNumberOfTypeArguments.id() => "Wrong number of type arguments: expected {1}".into(),
+        DiagnosticKind::UndefinedPackage.id() => "Undefined package '{1}'".into(),
+        DiagnosticKind::IllegalSuperExpression.id() => "Illegal super expression".into(),
+        DiagnosticKind::NotCallable.id() => "Value of type '{1}' is not callable".into(),
+        DiagnosticKind::DuplicateDefinition.id() => "'{1}' is already defined".into(),
+        DiagnosticKind::NotAClass.id() => "'{1}' is not a class".into(),
+        DiagnosticKind::NotAnInterface.id() => "'{1}' is not an interface".into(),
+        DiagnosticKind::ExtendingFinalClass.id() => "Cannot extend final class '{1}'".into(),
+        DiagnosticKind::CircularInheritance.id() => "Circular inheritance involving '{1}'".into(),
+        DiagnosticKind::EnumNumericTypeMustBeNumeric.id() => "Enumeration numeric type must be numeric, got '{1}'".into(),
+        DiagnosticKind::AbstractMethodMustBeOverriden.id() => "Abstract method '{1}' must be overriden".into(),
+        DiagnosticKind::MustOverrideAMethod.id() => "Method '{1}' must override a method".into(),
+        DiagnosticKind::IncompatibleOverride.id() => "Incompatible override: expected '{1}', got '{2}'".into(),
+        DiagnosticKind::OverridingFinalMethod.id() => "Cannot override final method '{1}'".into(),
+        DiagnosticKind::UnimplementedMethod.id() => "Method '{1}' from '{2}' is not implemented".into(),
+        DiagnosticKind::UnimplementedGetter.id() => "Getter '{1}' from '{2}' is not implemented".into(),
+        DiagnosticKind::UnimplementedSetter.id() => "Setter '{1}' from '{2}' is not implemented".into(),
+        DiagnosticKind::PropertyMustBeMethod.id() => "Property '{1}' must be a method".into(),
+        DiagnosticKind::PropertyMustBeVirtualProperty.id() => "Property '{1}' must be a getter or setter".into(),
+        DiagnosticKind::WrongMethodSignature.id() => "Method '{1}' must have signature '{2}'".into(),
+        DiagnosticKind::WrongGetterSignature.id() => "Getter '{1}' must have signature '{2}'".into(),
+        DiagnosticKind::WrongSetterSignature.id() => "Setter '{1}' must have signature '{2}'".into(),
+        DiagnosticKind::WrongVisibility.id() => "Property '{1}' must be {2}".into(),
+        DiagnosticKind::CannotIterateType.id() => "Cannot iterate type '{1}'".into(),
+        DiagnosticKind::ReturnValueExpected.id() => "Return statement must return a value".into(),
+        DiagnosticKind::CatchParameterMustBeTyped.id() => "Catch parameter must have a type annotation".into(),
+        DiagnosticKind::UnreachableCode.id() => "Unreachable code".into(),
+        DiagnosticKind::MissingReturn.id() => "Not all code paths return a value".into(),
+        DiagnosticKind::UseBeforeAssignment.id() => "Constant '{1}' is used before being assigned".into(),
+        DiagnosticKind::DuplicateCaseLabel.id() => "Duplicate case label".into(),
+        DiagnosticKind::TooFewArguments.id() => "Expected at least {1} arguments, got {2}".into(),
+        DiagnosticKind::TooManyArguments.id() => "Expected at most {1} arguments, got {2}".into(),
+        DiagnosticKind::CouldNotInferTypeArguments.id() => "Could not infer type arguments for type parameters {1}".into(),
+        DiagnosticKind::TypeArgumentOutOfBounds.id() => "Type argument '{1}' does not satisfy the bound '{2}' of type parameter '{3}'".into(),
+        DiagnosticKind::UndefinedConfigurationConstant.id() => "Undefined configuration constant '{1}'".into(),
+        DiagnosticKind::ConfigurationConditionMustBeConstant.id() => "Configuration condition must be a constant Boolean".into(),
         // DiagnosticKind::K.id() => "".into(),
     };
 }
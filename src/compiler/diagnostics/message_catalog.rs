@@ -0,0 +1,475 @@
+use crate::ns::*;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+#[path = "diagnostics_english_resources.rs"]
+mod diagnostics_english_resources;
+
+thread_local! {
+    static ENGLISH: Rc<MessageCatalog> = Rc::new(MessageCatalog {
+        locale: "en".into(),
+        messages: diagnostics_english_resources::DATA.clone(),
+        categories: HashMap::new(),
+        terms: HashMap::new(),
+        fallback: None,
+    });
+}
+
+/// Diagnostic messages of a locale.
+///
+/// A catalog is loaded from a message file and falls back to English
+/// for messages, categories and terms that it does not define.
+///
+/// # Messages
+///
+/// Messages refer to diagnostic arguments by their position counted from
+/// one, as in `{1}`. A message may select text according to a numeric
+/// argument, using the plural rules of the catalog locale:
+///
+/// ```plain
+/// Expected at least {1 -> [one] one argument *[other] {1} arguments}
+/// ```
+///
+/// A variant key is either a number, which matches the argument exactly,
+/// or a plural category (`zero`, `one`, `two`, `few`, `many` or `other`).
+/// The variant marked with `*` is selected when no other variant matches.
+///
+/// # Terms
+///
+/// Token arguments are formatted as English terms, such as `end of program`
+/// or `'{'`, which a catalog may translate.
+///
+/// # JSON files
+///
+/// ```json
+/// {
+///     "messages": { "UndefinedProperty": "Acesso a propriedade indefinida '{1}'" },
+///     "categories": { "syntaxError": "Erro de sintaxe", "verifyError": "Erro de verificação", "warning": "Aviso" },
+///     "terms": { "end of program": "fim do programa" }
+/// }
+/// ```
+///
+/// Messages are keyed either by the name or by the ID of their `DiagnosticKind`.
+///
+/// # Fluent-style files
+///
+/// ```plain
+/// # Messages are keyed by the name or ID of their diagnostic kind.
+/// UndefinedProperty = Acesso a propriedade indefinida '{1}'
+/// TooFewArguments = Esperava pelo menos {1 ->
+///         [one] um argumento
+///        *[other] {1} argumentos
+///     }, recebeu {2}
+///
+/// syntax-error = Erro de sintaxe
+/// verify-error = Erro de verificação
+/// warning = Aviso
+///
+/// # Terms are prefixed by a hyphen, with spaces replaced by hyphens.
+/// -end-of-program = fim do programa
+/// ```
+///
+/// Indented lines continue the previous entry.
+pub struct MessageCatalog {
+    locale: String,
+    messages: HashMap<i32, String>,
+    categories: HashMap<DiagnosticCategory, String>,
+    terms: HashMap<String, String>,
+    fallback: Option<Rc<MessageCatalog>>,
+}
+
+impl MessageCatalog {
+    /// The built-in English catalog.
+    pub fn english() -> Rc<Self> {
+        ENGLISH.with(|english| english.clone())
+    }
+
+    /// Loads the catalog of a locale from the first directory containing
+    /// a `<locale>.json` or `<locale>.ftl` file. A regional locale such as
+    /// `pt-BR` falls back to its language, such as `pt`. Returns the English
+    /// catalog if no directory contains a catalog for the locale.
+    pub fn load_locale(directories: &[String], locale: &str) -> Result<Rc<Self>, MessageCatalogError> {
+        let language = locale.split(['-', '_']).next().unwrap_or(locale);
+        for candidate in [locale, language] {
+            for directory in directories {
+                for extension in ["json", "ftl"] {
+                    let path = std::path::Path::new(directory).join(format!("{candidate}.{extension}"));
+                    if path.is_file() {
+                        return Ok(Rc::new(Self::load(&path.to_string_lossy(), locale)?));
+                    }
+                }
+            }
+        }
+        Ok(Self::english())
+    }
+
+    /// Loads a catalog from a `.json` or `.ftl` file.
+    pub fn load(path: &str, locale: &str) -> Result<Self, MessageCatalogError> {
+        let text = std::fs::read_to_string(path).map_err(|error| MessageCatalogError::Io(format!("{path}: {error}")))?;
+        if path.ends_with(".json") {
+            Self::from_json(locale, &text)
+        } else {
+            Self::from_fluent(locale, &text)
+        }
+    }
+
+    pub fn from_json(locale: &str, json: &str) -> Result<Self, MessageCatalogError> {
+        let file: CatalogFile = serde_json::from_str(json).map_err(|error| MessageCatalogError::Malformed(error.to_string()))?;
+        let mut catalog = Self::new(locale);
+        for (key, message) in file.messages {
+            catalog.messages.insert(Self::message_id(&key)?, message);
+        }
+        for (key, name) in file.categories {
+            let category = match key.as_ref() {
+                "syntaxError" => DiagnosticCategory::SyntaxError,
+                "verifyError" => DiagnosticCategory::VerifyError,
+                "warning" => DiagnosticCategory::Warning,
+                _ => return Err(MessageCatalogError::Malformed(format!("Unknown category '{key}'"))),
+            };
+            catalog.categories.insert(category, name);
+        }
+        catalog.terms.extend(file.terms);
+        Ok(catalog)
+    }
+
+    pub fn from_fluent(locale: &str, text: &str) -> Result<Self, MessageCatalogError> {
+        let mut entries: Vec<(usize, String, String)> = vec![];
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.trim_start().starts_with('#') {
+                continue;
+            }
+            if line.starts_with([' ', '\t']) {
+                let Some((_, _, value)) = entries.last_mut() else {
+                    return Err(MessageCatalogError::Malformed(format!("Line {}: continuation without an entry", i + 1)));
+                };
+                if !value.is_empty() {
+                    value.push(' ');
+                }
+                value.push_str(line.trim());
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(MessageCatalogError::Malformed(format!("Line {}: expected '='", i + 1)));
+            };
+            entries.push((i + 1, key.trim().to_owned(), value.trim().to_owned()));
+        }
+
+        let mut catalog = Self::new(locale);
+        for (line, key, value) in entries {
+            if let Some(term) = key.strip_prefix('-') {
+                catalog.terms.insert(term.replace('-', " "), value);
+                continue;
+            }
+            let category = match key.as_ref() {
+                "syntax-error" => Some(DiagnosticCategory::SyntaxError),
+                "verify-error" => Some(DiagnosticCategory::VerifyError),
+                "warning" => Some(DiagnosticCategory::Warning),
+                _ => None,
+            };
+            if let Some(category) = category {
+                catalog.categories.insert(category, value);
+                continue;
+            }
+            let id = Self::message_id(&key).map_err(|_| MessageCatalogError::Malformed(format!("Line {line}: unknown message '{key}'")))?;
+            catalog.messages.insert(id, value);
+        }
+        Ok(catalog)
+    }
+
+    fn new(locale: &str) -> Self {
+        Self {
+            locale: locale.into(),
+            messages: HashMap::new(),
+            categories: HashMap::new(),
+            terms: HashMap::new(),
+            fallback: Some(Self::english()),
+        }
+    }
+
+    /// Resolves a message key, which is either the name or the ID
+    /// of a diagnostic kind.
+    fn message_id(key: &str) -> Result<i32, MessageCatalogError> {
+        let kind = match key.parse::<i32>() {
+            Ok(id) => DiagnosticKind::from_id(id),
+            Err(_) => DiagnosticKind::from_name(key),
+        };
+        kind.map(|kind| kind.id()).ok_or_else(|| MessageCatalogError::Malformed(format!("Unknown message '{key}'")))
+    }
+
+    pub fn locale(&self) -> String {
+        self.locale.clone()
+    }
+
+    /// Returns the message template of a diagnostic kind.
+    pub fn message(&self, kind: DiagnosticKind) -> Option<String> {
+        self.messages.get(&kind.id()).cloned()
+            .or_else(|| self.fallback.as_ref().and_then(|fallback| fallback.message(kind)))
+    }
+
+    /// Returns the name of a diagnostic category, such as `Verify error`.
+    pub fn category(&self, category: DiagnosticCategory) -> String {
+        if let Some(name) = self.categories.get(&category) {
+            return name.clone();
+        }
+        match &self.fallback {
+            Some(fallback) => fallback.category(category),
+            None => category.to_string(),
+        }
+    }
+
+    /// Translates an English term, such as `end of program`.
+    pub fn term(&self, term: &str) -> String {
+        if let Some(translation) = self.terms.get(term) {
+            return translation.clone();
+        }
+        match &self.fallback {
+            Some(fallback) => fallback.term(term),
+            None => term.to_owned(),
+        }
+    }
+
+    /// Formats a diagnostic argument.
+    pub fn format_argument(&self, argument: &DiagnosticArgument) -> String {
+        match argument {
+            DiagnosticArgument::String(s) => s.clone(),
+            DiagnosticArgument::Token(t) => self.term(&t.to_string()),
+            DiagnosticArgument::Symbol(s) => s.to_string(),
+        }
+    }
+
+    /// Formats a message template with formatted arguments.
+    pub fn format(&self, template: &str, arguments: &[String]) -> String {
+        let mut result = String::new();
+        let mut rest = template;
+        while let Some(i) = rest.find('{') {
+            result.push_str(&rest[..i]);
+            let Some(length) = Self::placeholder_length(&rest[i..]) else {
+                result.push_str(&rest[i..]);
+                return result;
+            };
+            let placeholder = &rest[i..i + length];
+            result.push_str(&self.format_placeholder(placeholder, arguments).unwrap_or_else(|| placeholder.to_owned()));
+            rest = &rest[i + length..];
+        }
+        result.push_str(rest);
+        result
+    }
+
+    /// Returns the length of the placeholder at the start of `text`,
+    /// including nested placeholders.
+    fn placeholder_length(text: &str) -> Option<usize> {
+        let mut depth = 0;
+        for (i, ch) in text.char_indices() {
+            match ch {
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(i + 1);
+                    }
+                },
+                _ => {},
+            }
+        }
+        None
+    }
+
+    fn format_placeholder(&self, placeholder: &str, arguments: &[String]) -> Option<String> {
+        let content = placeholder[1..placeholder.len() - 1].trim();
+        let (position, variants) = match content.split_once("->") {
+            Some((position, variants)) => (position.trim(), Some(variants)),
+            None => (content, None),
+        };
+        let argument = arguments.get(position.parse::<usize>().ok()?.checked_sub(1)?)?;
+        let Some(variants) = variants else {
+            return Some(argument.clone());
+        };
+        let variants = Self::parse_variants(variants)?;
+        let number = argument.parse::<f64>().ok();
+        let category = number.map(|number| PluralCategory::of(&self.locale, number).name());
+        let selected = variants.iter().find(|(key, _, _)| key.parse::<f64>().ok().is_some_and(|key| Some(key) == number))
+            .or_else(|| variants.iter().find(|(key, _, _)| Some(key.as_str()) == category || key == argument))
+            .or_else(|| variants.iter().find(|(_, _, default)| *default))
+            .or(variants.last())?;
+        Some(self.format(&selected.1, arguments))
+    }
+
+    /// Parses variants of the form `[key] text` or `*[key] text`
+    /// into (key, text, default).
+    fn parse_variants(text: &str) -> Option<Vec<(String, String, bool)>> {
+        let mut variants: Vec<(String, String, bool)> = vec![];
+        let mut rest = text.trim_start();
+        while !rest.is_empty() {
+            let default = rest.starts_with('*');
+            rest = rest.strip_prefix('*').unwrap_or(rest).strip_prefix('[')?;
+            let (key, after_key) = rest.split_once(']')?;
+            // The variant text extends to the next variant key
+            // outside of nested placeholders.
+            let mut depth = 0;
+            let mut end = after_key.len();
+            let mut previous_is_space = true;
+            for (i, ch) in after_key.char_indices() {
+                match ch {
+                    '{' => depth += 1,
+                    '}' => depth -= 1,
+                    '[' | '*' if depth == 0 && previous_is_space && i > 0 && (ch == '[' || after_key[i..].starts_with("*[")) => {
+                        end = i;
+                        break;
+                    },
+                    _ => {},
+                }
+                previous_is_space = ch.is_whitespace();
+            }
+            variants.push((key.trim().to_owned(), after_key[..end].trim().to_owned(), default));
+            rest = after_key[end..].trim_start();
+        }
+        Some(variants)
+    }
+}
+
+/// Category of a diagnostic as shown when formatting it.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum DiagnosticCategory {
+    SyntaxError,
+    VerifyError,
+    Warning,
+}
+
+impl std::fmt::Display for DiagnosticCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::SyntaxError => "Syntax error",
+            Self::VerifyError => "Verify error",
+            Self::Warning => "Warning",
+        })
+    }
+}
+
+/// Plural category of a number, following the cardinal
+/// plural rules of the Unicode CLDR.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+impl PluralCategory {
+    /// Returns the plural category of a number in a locale.
+    /// Locales without specific rules follow the English rules.
+    pub fn of(locale: &str, number: f64) -> Self {
+        let language = locale.split(['-', '_']).next().unwrap_or(locale).to_lowercase();
+        let integer = number.fract() == 0.0;
+        match language.as_ref() {
+            "ja" | "zh" | "ko" | "vi" | "th" | "id" | "ms" => Self::Other,
+            "pt" if locale.to_lowercase().replace('_', "-") != "pt-pt" => {
+                if integer && (number == 0.0 || number == 1.0) { Self::One } else { Self::Other }
+            },
+            "fr" => {
+                if number.abs() < 2.0 { Self::One } else { Self::Other }
+            },
+            _ => {
+                if integer && number == 1.0 { Self::One } else { Self::Other }
+            },
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Zero => "zero",
+            Self::One => "one",
+            Self::Two => "two",
+            Self::Few => "few",
+            Self::Many => "many",
+            Self::Other => "other",
+        }
+    }
+}
+
+/// Error loading a message catalog.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum MessageCatalogError {
+    /// The catalog file could not be read.
+    Io(String),
+    /// The catalog is malformed or refers to unknown messages.
+    Malformed(String),
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CatalogFile {
+    #[serde(default)]
+    messages: BTreeMap<String, String>,
+    #[serde(default)]
+    categories: BTreeMap<String, String>,
+    #[serde(default)]
+    terms: BTreeMap<String, String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+
+    fn diagnostic(kind: DiagnosticKind, arguments: Vec<DiagnosticArgument>) -> Diagnostic {
+        let compilation_unit = CompilationUnit::new(Some("a.jet".into()), "x".into(), &CompilerOptions::new());
+        Diagnostic::new_verify_error(&Location::with_offset(&compilation_unit, 0), kind, arguments)
+    }
+
+    #[test]
+    fn formatting_messages() {
+        let catalog = MessageCatalog::from_fluent("pt-BR", "
+# Portuguese
+UndefinedProperty = Acesso a propriedade indefinida '{1}'
+1124 = Esperava pelo menos {1 ->
+        [0] nenhum argumento
+        [one] {1} argumento
+       *[other] {1} argumentos
+    }, recebeu {2}
+1025 = Fim inesperado
+verify-error = Erro de verificação
+-end-of-program = fim do programa
+").unwrap();
+        let d = diagnostic(DiagnosticKind::UndefinedProperty, diagnostic_arguments![String("x".into())]);
+        assert_eq!(d.format_with_catalog(&catalog), "a.jet:1:1: Erro de verificação #1089: Acesso a propriedade indefinida 'x'");
+        let message = |n: &str| diagnostic(DiagnosticKind::TooFewArguments, diagnostic_arguments![String(n.into()), String("5".into())]).format_message(&catalog);
+        assert_eq!(message("0"), "Esperava pelo menos nenhum argumento, recebeu 5");
+        assert_eq!(message("1"), "Esperava pelo menos 1 argumento, recebeu 5");
+        assert_eq!(message("2"), "Esperava pelo menos 2 argumentos, recebeu 5");
+
+        // Untranslated messages fall back to English.
+        let d = diagnostic(DiagnosticKind::ExpectedExpression, diagnostic_arguments![Token(Token::Eof)]);
+        assert_eq!(d.format_message(&catalog), "Expected expression before fim do programa");
+        assert_eq!(d.format_english(), "a.jet:1:1: Verify error #1030: Expected expression before end of program");
+
+        let catalog = MessageCatalog::from_json("ja", r#"{
+            "messages": { "1124": "少なくとも{1 -> [one] 1 個 *[other] {1} 個}の引数が必要です" },
+            "categories": { "verifyError": "検証エラー" }
+        }"#).unwrap();
+        let d = diagnostic(DiagnosticKind::TooFewArguments, diagnostic_arguments![String("1".into()), String("0".into())]);
+        assert_eq!(d.format_with_catalog(&catalog), "a.jet:1:1: 検証エラー #1124: 少なくとも1 個の引数が必要です");
+
+        assert!(matches!(MessageCatalog::from_fluent("pt", "NoSuchMessage = x"), Err(MessageCatalogError::Malformed(_))));
+        assert!(matches!(MessageCatalog::from_json("pt", r#"{ "messages": { "9999": "x" } }"#), Err(MessageCatalogError::Malformed(_))));
+    }
+
+    #[test]
+    fn loading_locales() {
+        let directory = std::env::temp_dir().join(format!("jet-message-catalog-test-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(directory.join("pt.ftl"), "UndefinedProperty = Propriedade indefinida '{1}'\n").unwrap();
+        let mut options = CompilerOptions::default();
+        options.apply_arguments(&["--locale", "pt-BR", "--message-catalog", &directory.to_string_lossy()].map(String::from)).unwrap();
+        let catalog = options.message_catalog();
+        options.locale = Some("ja".into());
+        let japanese = options.message_catalog();
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        let catalog = catalog.unwrap();
+        assert_eq!(catalog.locale(), "pt-BR");
+        assert_eq!(catalog.message(DiagnosticKind::UndefinedProperty).as_deref(), Some("Propriedade indefinida '{1}'"));
+        assert_eq!(japanese.unwrap().locale(), "en");
+    }
+}
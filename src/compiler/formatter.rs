@@ -0,0 +1,6 @@
+mod document;
+pub(crate) use document::*;
+mod formatter;
+pub use formatter::*;
+mod formatter_options;
+pub use formatter_options::*;
\ No newline at end of file
@@ -0,0 +1,151 @@
+use crate::ns::*;
+
+/// Document built by the formatter and laid out against a line width.
+///
+/// A group is laid out flat, with its lines as spaces, when its content
+/// fits in the remaining width up to the next line break; otherwise
+/// its lines break.
+#[derive(Clone)]
+pub(crate) enum Doc {
+    Text(String),
+    /// A space, or a line break if the enclosing group is broken.
+    Line,
+    /// Nothing, or a line break if the enclosing group is broken.
+    SoftLine,
+    /// A line break regardless of the enclosing group.
+    HardLine,
+    Indent(Box<Doc>),
+    Group(Box<Doc>),
+    Concat(Vec<Doc>),
+}
+
+impl Doc {
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::Text(text.into())
+    }
+
+    pub fn empty() -> Self {
+        Self::Concat(vec![])
+    }
+
+    pub fn indent(doc: Doc) -> Self {
+        Self::Indent(Box::new(doc))
+    }
+
+    pub fn group(doc: Doc) -> Self {
+        Self::Group(Box::new(doc))
+    }
+
+    /// Joins documents with a separator.
+    pub fn join(docs: Vec<Doc>, separator: Doc) -> Self {
+        let mut result = vec![];
+        for (i, doc) in docs.into_iter().enumerate() {
+            if i != 0 {
+                result.push(separator.clone());
+            }
+            result.push(doc);
+        }
+        Self::Concat(result)
+    }
+
+    /// Lays out the document into a string.
+    pub fn render(&self, options: &FormatterOptions) -> String {
+        let mut output = String::new();
+        let mut column: usize = 0;
+        let mut stack: Vec<(usize, bool, &Doc)> = vec![(0, false, self)];
+        while let Some((indent, flat, doc)) = stack.pop() {
+            match doc {
+                Doc::Text(text) => {
+                    output.push_str(text);
+                    column = match text.rfind('\n') {
+                        Some(i) => text[(i + 1)..].chars().count(),
+                        None => column + text.chars().count(),
+                    };
+                },
+                Doc::Line if flat => {
+                    output.push(' ');
+                    column += 1;
+                },
+                Doc::SoftLine if flat => {},
+                Doc::Line | Doc::SoftLine | Doc::HardLine => {
+                    let trimmed_length = output.trim_end_matches([' ', '\t']).len();
+                    output.truncate(trimmed_length);
+                    output.push('\n');
+                    if options.use_tabs {
+                        output.push_str(&"\t".repeat(indent));
+                    } else {
+                        output.push_str(&" ".repeat(indent * options.indent_width));
+                    }
+                    column = indent * options.indent_width;
+                },
+                Doc::Indent(doc) => {
+                    stack.push((indent + 1, flat, doc));
+                },
+                Doc::Group(doc) => {
+                    let flat = flat || (!doc.contains_line_break() && Self::fits(options.line_width as isize - column as isize, doc, &stack));
+                    stack.push((indent, flat, doc));
+                },
+                Doc::Concat(docs) => {
+                    for doc in docs.iter().rev() {
+                        stack.push((indent, flat, doc));
+                    }
+                },
+            }
+        }
+        let trimmed_length = output.trim_end().len();
+        output.truncate(trimmed_length);
+        output.push('\n');
+        output
+    }
+
+    /// Determines whether the document contains a line break that
+    /// does not depend on groups, which breaks its enclosing groups.
+    fn contains_line_break(&self) -> bool {
+        match self {
+            Doc::Text(text) => text.contains('\n'),
+            Doc::HardLine => true,
+            Doc::Line | Doc::SoftLine => false,
+            Doc::Indent(doc) | Doc::Group(doc) => doc.contains_line_break(),
+            Doc::Concat(docs) => docs.iter().any(|doc| doc.contains_line_break()),
+        }
+    }
+
+    /// Determines whether a group laid out flat, followed by the remaining
+    /// documents, fits in the given width up to the next line break.
+    fn fits(width: isize, group: &Doc, rest: &[(usize, bool, &Doc)]) -> bool {
+        let mut width = width;
+        let mut stack: Vec<(bool, &Doc)> = vec![(true, group)];
+        let mut rest = rest.iter().rev();
+        loop {
+            if width < 0 {
+                return false;
+            }
+            let Some((flat, doc)) = stack.pop().or_else(|| rest.next().map(|(_, flat, doc)| (*flat, *doc))) else {
+                return true;
+            };
+            match doc {
+                Doc::Text(text) => {
+                    if let Some(i) = text.find('\n') {
+                        return width >= text[..i].chars().count() as isize;
+                    }
+                    width -= text.chars().count() as isize;
+                },
+                Doc::Line if flat => {
+                    width -= 1;
+                },
+                Doc::SoftLine if flat => {},
+                Doc::Line | Doc::SoftLine | Doc::HardLine => {
+                    return true;
+                },
+                Doc::Indent(doc) | Doc::Group(doc) => {
+                    stack.push((flat, doc));
+                },
+                Doc::Concat(docs) => {
+                    for doc in docs.iter().rev() {
+                        stack.push((flat, doc));
+                    }
+                },
+            }
+        }
+    }
+}
@@ -0,0 +1,1278 @@
+use crate::ns::*;
+
+/// Formats Jet programs.
+///
+/// The formatter prints every node of a program in a canonical layout,
+/// interleaving the comments of the compilation unit between directives.
+/// A comment within a directive is printed before the expression or
+/// substatement that follows it, such as in `f(/* c */ a)`; a comment
+/// that is followed by no such node, such as in `f(a /* c */)`, is
+/// printed on its own line after the directive.
+/// The resulting source text parses into a program equivalent to the
+/// formatted one, and formatting it again produces the same text.
+///
+/// # Example
+///
+/// ```
+/// use hydroper_jet_compiler::ns::*;
+///
+/// let formatter = Formatter::new(FormatterOptions::default());
+/// let text = formatter.format_text("package{public function f(a:Number){return a}}").unwrap();
+/// assert_eq!(text, "package {\n    public function f(a: Number) {\n        return a;\n    }\n}\n");
+/// ```
+pub struct Formatter {
+    options: FormatterOptions,
+}
+
+impl Formatter {
+    pub fn new(options: FormatterOptions) -> Self {
+        Self { options }
+    }
+
+    pub fn options(&self) -> &FormatterOptions {
+        &self.options
+    }
+
+    /// Formats a program, interleaving the comments of
+    /// its compilation unit.
+    pub fn format_program(&self, program: &Rc<Program>) -> String {
+        let compilation_unit = program.location.compilation_unit();
+        let mut printer = ProgramPrinter::new(&self.options, &compilation_unit);
+        printer.program(program).render(&self.options)
+    }
+
+    /// Parses and formats source text. Returns `None` if the
+    /// source text contains syntax errors.
+    pub fn format_text(&self, text: &str) -> Option<String> {
+        let compilation_unit = CompilationUnit::new(None, text.into(), &CompilerOptions::new());
+        let program = ParserFacade::parse_program(&compilation_unit)?;
+        if compilation_unit.invalidated() {
+            return None;
+        }
+        Some(self.format_program(&program))
+    }
+}
+
+struct ProgramPrinter<'a> {
+    options: &'a FormatterOptions,
+    compilation_unit: Rc<CompilationUnit>,
+    text: String,
+    comments: Vec<Rc<Comment>>,
+    next_comment: usize,
+}
+
+impl<'a> ProgramPrinter<'a> {
+    fn new(options: &'a FormatterOptions, compilation_unit: &Rc<CompilationUnit>) -> Self {
+        Self {
+            options,
+            compilation_unit: compilation_unit.clone(),
+            text: compilation_unit.current_text().clone(),
+            comments: compilation_unit.comments(),
+            next_comment: 0,
+        }
+    }
+
+    fn source(&self, location: &Location) -> String {
+        self.text[location.first_offset()..location.last_offset()].to_owned()
+    }
+
+    fn line_number(&self, offset: usize) -> usize {
+        Location::with_offset(&self.compilation_unit, offset).first_line_number()
+    }
+
+    fn program(&mut self, program: &Program) -> Doc {
+        let mut items = ListItems::default();
+        for package in &program.packages {
+            let start = package.jetdoc.as_ref().map_or(package.location.first_offset(), |jetdoc| Self::jetdoc_offset(jetdoc).min(package.location.first_offset()));
+            self.begin_item(&mut items, start);
+            let doc = self.package_definition(package);
+            self.end_item(&mut items, doc, &package.location);
+        }
+        let content = self.directive_items(items, &program.directives, self.text.len());
+        content.unwrap_or(Doc::empty())
+    }
+
+    fn package_definition(&mut self, package: &PackageDefinition) -> Doc {
+        let mut parts = vec![];
+        if let Some(jetdoc) = &package.jetdoc {
+            parts.push(self.jetdoc(jetdoc));
+            parts.push(Doc::HardLine);
+        }
+        let mut header = "package".to_owned();
+        if !package.name.is_empty() {
+            header.push(' ');
+            header.push_str(&package.name.iter().map(|name| name.0.clone()).collect::<Vec<_>>().join("."));
+        }
+        let content = self.block_content(&package.block);
+        parts.push(self.braced(Doc::text(header), content));
+        Doc::Concat(parts)
+    }
+
+    // Directive lists
+
+    /// Prints a directive list with the comments preceding `end_offset`.
+    /// Returns `None` if there is neither a directive nor a comment.
+    fn directive_list(&mut self, directives: &[Rc<Directive>], end_offset: usize) -> Option<Doc> {
+        self.directive_items(ListItems::default(), directives, end_offset)
+    }
+
+    fn directive_items(&mut self, items: ListItems, directives: &[Rc<Directive>], end_offset: usize) -> Option<Doc> {
+        let mut items = items;
+        for directive in directives {
+            self.begin_item(&mut items, Self::directive_start(directive));
+            let doc = self.directive(directive);
+            self.end_item(&mut items, doc, &directive.location());
+        }
+        self.leading_comments(&mut items, end_offset);
+        if items.docs.is_empty() {
+            None
+        } else {
+            Some(Doc::join(items.docs, Doc::HardLine))
+        }
+    }
+
+    /// Prints the comments preceding an item, separating the item from
+    /// the previous one by a blank line if they are separated by
+    /// blank lines in the source text.
+    fn begin_item(&mut self, items: &mut ListItems, start: usize) {
+        self.leading_comments(items, start);
+        self.separate(items, self.line_number(start));
+    }
+
+    /// Adds an item, followed by a comment starting at the line where it ends.
+    fn end_item(&mut self, items: &mut ListItems, doc: Doc, location: &Location) {
+        let mut doc = doc;
+        let mut last_line = location.last_line_number();
+        if let Some(comment) = self.comments.get(self.next_comment) {
+            let comment_location = comment.location();
+            if comment_location.first_offset() >= location.last_offset() && comment_location.first_line_number() == last_line {
+                doc = Doc::Concat(vec![doc, Doc::text(" "), Doc::text(self.source(&comment_location))]);
+                last_line = comment_location.last_line_number();
+                self.next_comment += 1;
+            }
+        }
+        items.docs.push(doc);
+        items.last_line = Some(last_line);
+    }
+
+    fn leading_comments(&mut self, items: &mut ListItems, end_offset: usize) {
+        while let Some(comment) = self.comments.get(self.next_comment) {
+            let location = comment.location();
+            if location.first_offset() >= end_offset {
+                break;
+            }
+            self.separate(items, location.first_line_number());
+            items.docs.push(Doc::text(self.source(&location)));
+            items.last_line = Some(location.last_line_number());
+            self.next_comment += 1;
+        }
+    }
+
+    /// Prints the comments preceding `start` that were not printed as
+    /// items of a directive list. A line comment is followed by a line break.
+    fn attached_comments(&mut self, start: usize) -> Vec<Doc> {
+        let mut parts = vec![];
+        while let Some(comment) = self.comments.get(self.next_comment) {
+            let location = comment.location();
+            if location.first_offset() >= start {
+                break;
+            }
+            parts.push(Doc::text(self.source(&location)));
+            parts.push(if comment.multiline() { Doc::text(" ") } else { Doc::HardLine });
+            self.next_comment += 1;
+        }
+        parts
+    }
+
+    fn separate(&self, items: &mut ListItems, line: usize) {
+        if items.last_line.is_some_and(|last_line| line > last_line + 1) {
+            items.docs.push(Doc::empty());
+        }
+        items.last_line = None;
+    }
+
+    fn directive_start(directive: &Directive) -> usize {
+        let jetdoc = match directive {
+            Directive::VariableDefinition(defn) => defn.jetdoc.as_ref(),
+            Directive::FunctionDefinition(defn) => defn.jetdoc.as_ref(),
+            Directive::ClassDefinition(defn) => defn.jetdoc.as_ref(),
+            Directive::EnumDefinition(defn) => defn.jetdoc.as_ref(),
+            Directive::InterfaceDefinition(defn) => defn.jetdoc.as_ref(),
+            Directive::TypeDefinition(defn) => defn.jetdoc.as_ref(),
+            _ => None,
+        };
+        let offset = directive.location().first_offset();
+        jetdoc.map_or(offset, |jetdoc| offset.min(Self::jetdoc_offset(jetdoc)))
+    }
+
+    /// Offset of the `/**` sequence of a JetDoc comment.
+    fn jetdoc_offset(jetdoc: &JetDoc) -> usize {
+        jetdoc.location.first_offset() - 3
+    }
+
+    // Blocks
+
+    fn block_content(&mut self, block: &Block) -> Option<Doc> {
+        self.directive_list(&block.directives, block.location.last_offset())
+    }
+
+    fn braces(&self, content: Option<Doc>) -> Doc {
+        match content {
+            Some(content) => Doc::Concat(vec![
+                Doc::text("{"),
+                Doc::indent(Doc::Concat(vec![Doc::HardLine, content])),
+                Doc::HardLine,
+                Doc::text("}"),
+            ]),
+            None => Doc::text("{}"),
+        }
+    }
+
+    /// Prints a header followed by a block, placing the opening
+    /// brace according to the brace style.
+    fn braced(&self, header: Doc, content: Option<Doc>) -> Doc {
+        let separator = if content.is_some() && self.options.brace_style == BraceStyle::NextLine {
+            Doc::HardLine
+        } else {
+            Doc::text(" ")
+        };
+        Doc::Concat(vec![header, separator, self.braces(content)])
+    }
+
+    /// Separator between a closing brace and a continuation
+    /// such as `else`, `catch` or `finally`.
+    fn continuation(&self) -> Doc {
+        match self.options.brace_style {
+            BraceStyle::SameLine => Doc::text(" "),
+            BraceStyle::NextLine => Doc::HardLine,
+        }
+    }
+
+    fn is_plain_block(directive: &Directive) -> bool {
+        matches!(directive, Directive::Block(block) if block.metadata.is_none())
+    }
+
+    /// Prints a header followed by a substatement, indenting
+    /// the substatement if it is not a block.
+    fn substatement(&mut self, header: Doc, body: &Rc<Directive>) -> Doc {
+        match body.as_ref() {
+            Directive::Block(block) if block.metadata.is_none() => {
+                let content = self.block_content(block);
+                self.braced(header, content)
+            },
+            _ => {
+                let body = self.directive(body);
+                Doc::Concat(vec![header, Doc::indent(Doc::Concat(vec![Doc::HardLine, body]))])
+            },
+        }
+    }
+
+    // Directives
+
+    fn directive(&mut self, directive: &Rc<Directive>) -> Doc {
+        let mut parts = self.attached_comments(directive.location().first_offset());
+        parts.push(self.directive_content(directive));
+        Doc::Concat(parts)
+    }
+
+    fn directive_content(&mut self, directive: &Rc<Directive>) -> Doc {
+        match directive.as_ref() {
+            Directive::EmptyStatement(_) => Doc::text(";"),
+            Directive::ExpressionStatement(stmt) => {
+                Doc::Concat(vec![self.expression(&stmt.expression), Doc::text(";")])
+            },
+            Directive::SuperStatement(stmt) => {
+                Doc::Concat(vec![Doc::text("super"), self.arguments(&stmt.arguments), Doc::text(";")])
+            },
+            Directive::Block(block) => {
+                let content = self.block_content(block);
+                let Some(metadata) = &block.metadata else {
+                    return self.braces(content);
+                };
+                let metadata = metadata.iter().map(|attribute| match attribute {
+                    Attribute::Metadata(metadata) => self.metadata(metadata),
+                    _ => String::new(),
+                }).collect::<Vec<_>>().join(" ");
+                self.braced(Doc::text(metadata), content)
+            },
+            Directive::LabeledStatement(stmt) => {
+                let substatement = self.directive(&stmt.substatement);
+                Doc::Concat(vec![Doc::text(format!("{}: ", stmt.label.0)), substatement])
+            },
+            Directive::IfStatement(stmt) => self.if_statement(stmt),
+            Directive::SwitchStatement(stmt) => self.switch_statement(stmt),
+            Directive::SwitchTypeStatement(stmt) => self.switch_type_statement(stmt),
+            Directive::DoStatement(stmt) => {
+                let test = self.expression(&stmt.test);
+                let while_clause = Doc::Concat(vec![Doc::text("while ("), test, Doc::text(");")]);
+                if let Directive::Block(block) = stmt.body.as_ref() {
+                    if block.metadata.is_none() {
+                        let content = self.block_content(block);
+                        return Doc::Concat(vec![self.braced(Doc::text("do"), content), self.continuation(), while_clause]);
+                    }
+                }
+                let body = self.directive(&stmt.body);
+                Doc::Concat(vec![
+                    Doc::text("do"),
+                    Doc::indent(Doc::Concat(vec![Doc::HardLine, body])),
+                    Doc::HardLine,
+                    while_clause,
+                ])
+            },
+            Directive::WhileStatement(stmt) => {
+                let test = self.expression(&stmt.test);
+                let header = Doc::Concat(vec![Doc::text("while ("), test, Doc::text(")")]);
+                self.substatement(header, &stmt.body)
+            },
+            Directive::ForStatement(stmt) => {
+                let mut header = vec![Doc::text("for (")];
+                match &stmt.init {
+                    Some(ForInitializer::Expression(init)) => header.push(self.expression(init)),
+                    Some(ForInitializer::VariableDefinition(defn)) => header.push(self.simple_variable_definition(defn)),
+                    None => {},
+                }
+                header.push(Doc::text(";"));
+                if let Some(test) = &stmt.test {
+                    header.push(Doc::text(" "));
+                    header.push(self.expression(test));
+                }
+                header.push(Doc::text(";"));
+                if let Some(update) = &stmt.update {
+                    header.push(Doc::text(" "));
+                    header.push(self.expression(update));
+                }
+                header.push(Doc::text(")"));
+                self.substatement(Doc::Concat(header), &stmt.body)
+            },
+            Directive::ForInStatement(stmt) => {
+                let mut header = vec![Doc::text(if stmt.each { "for each (" } else { "for (" })];
+                match &stmt.left {
+                    ForInBinding::Expression(left) => header.push(self.expression(left)),
+                    ForInBinding::VariableDefinition(defn) => header.push(self.simple_variable_definition(defn)),
+                }
+                header.push(Doc::text(" in "));
+                header.push(self.expression(&stmt.right));
+                header.push(Doc::text(")"));
+                self.substatement(Doc::Concat(header), &stmt.body)
+            },
+            Directive::BreakStatement(stmt) => {
+                Doc::text(stmt.label.as_ref().map_or("break;".to_owned(), |label| format!("break {};", label.0)))
+            },
+            Directive::ContinueStatement(stmt) => {
+                Doc::text(stmt.label.as_ref().map_or("continue;".to_owned(), |label| format!("continue {};", label.0)))
+            },
+            Directive::WithStatement(stmt) => {
+                let object = self.expression(&stmt.object);
+                let header = Doc::Concat(vec![Doc::text("with ("), object, Doc::text(")")]);
+                self.substatement(header, &stmt.body)
+            },
+            Directive::ReturnStatement(stmt) => {
+                let mut parts = vec![Doc::text("return")];
+                if let Some(expression) = &stmt.expression {
+                    parts.push(Doc::text(" "));
+                    parts.push(self.expression(expression));
+                }
+                parts.push(Doc::text(";"));
+                Doc::Concat(parts)
+            },
+            Directive::ThrowStatement(stmt) => {
+                Doc::Concat(vec![Doc::text("throw "), self.expression(&stmt.expression), Doc::text(";")])
+            },
+            Directive::DefaultXmlNamespaceStatement(stmt) => {
+                Doc::Concat(vec![Doc::text("default xml namespace = "), self.expression(&stmt.right), Doc::text(";")])
+            },
+            Directive::TryStatement(stmt) => {
+                let content = self.block_content(&stmt.block);
+                let mut parts = vec![self.braced(Doc::text("try"), content)];
+                for catch_clause in &stmt.catch_clauses {
+                    let parameter = self.typed_destructuring(&catch_clause.parameter, false);
+                    let header = Doc::Concat(vec![Doc::text("catch ("), parameter, Doc::text(")")]);
+                    let content = self.block_content(&catch_clause.block);
+                    parts.push(self.continuation());
+                    parts.push(self.braced(header, content));
+                }
+                if let Some(finally_clause) = &stmt.finally_clause {
+                    let content = self.block_content(&finally_clause.block);
+                    parts.push(self.continuation());
+                    parts.push(self.braced(Doc::text("finally"), content));
+                }
+                Doc::Concat(parts)
+            },
+            Directive::InvalidatedDirective(defn) => Doc::text(self.source(&defn.location)),
+            Directive::ConfigurationDirective(defn) => {
+                let subdirective = self.configuration_subdirective(&defn.directive);
+                self.braced(Doc::text("configuration"), Some(subdirective))
+            },
+            Directive::ImportDirective(defn) => {
+                Doc::text(format!("import {};", Self::import_name(&defn.alias, &defn.package_name, &defn.import_specifier)))
+            },
+            Directive::UsePackageDirective(defn) => {
+                Doc::text(format!("use package {};", Self::import_name(&defn.alias, &defn.package_name, &defn.import_specifier)))
+            },
+            Directive::VariableDefinition(defn) => {
+                let annotations = self.annotations(defn.jetdoc.as_ref(), &defn.attributes, None);
+                let bindings = self.variable_bindings(&defn.kind.0, &defn.bindings);
+                Doc::Concat(vec![annotations, bindings, Doc::text(";")])
+            },
+            Directive::FunctionDefinition(defn) => {
+                let annotations = self.annotations(defn.jetdoc.as_ref(), &defn.attributes, None);
+                let name = match &defn.name {
+                    FunctionName::Identifier(name) | FunctionName::Constructor(name) | FunctionName::Proxy(_, name) => name.0.clone(),
+                    FunctionName::Getter(name) => format!("get {}", name.0),
+                    FunctionName::Setter(name) => format!("set {}", name.0),
+                };
+                let function = self.function_common(Doc::text(format!("function {name}")), &defn.common);
+                let mut parts = vec![annotations, function];
+                if !defn.common.has_block_body() {
+                    parts.push(Doc::text(";"));
+                }
+                Doc::Concat(parts)
+            },
+            Directive::ClassDefinition(defn) => {
+                let annotations = self.annotations(defn.jetdoc.as_ref(), &defn.attributes, if defn.allow_literal { Some("Literal") } else { None });
+                let mut header = vec![Doc::text(format!("class {}", defn.name.0))];
+                if let Some(type_parameters) = &defn.type_parameters {
+                    header.push(self.type_parameters(type_parameters));
+                }
+                if let Some(extends_clause) = &defn.extends_clause {
+                    header.push(Doc::text(" extends "));
+                    header.push(self.expression(extends_clause));
+                }
+                if let Some(implements_clause) = &defn.implements_clause {
+                    header.push(Doc::text(" implements "));
+                    header.push(self.expression_list(implements_clause));
+                }
+                let content = self.block_content(&defn.block);
+                Doc::Concat(vec![annotations, self.braced(Doc::Concat(header), content)])
+            },
+            Directive::EnumDefinition(defn) => {
+                let annotations = self.annotations(defn.jetdoc.as_ref(), &defn.attributes, if defn.is_set { Some("Set") } else { None });
+                let mut header = vec![Doc::text(format!("enum {}", defn.name.0))];
+                if let Some(as_clause) = &defn.as_clause {
+                    header.push(Doc::text(" as "));
+                    header.push(self.expression(as_clause));
+                }
+                let content = self.block_content(&defn.block);
+                Doc::Concat(vec![annotations, self.braced(Doc::Concat(header), content)])
+            },
+            Directive::InterfaceDefinition(defn) => {
+                let annotations = self.annotations(defn.jetdoc.as_ref(), &defn.attributes, None);
+                let mut header = vec![Doc::text(format!("interface {}", defn.name.0))];
+                if let Some(type_parameters) = &defn.type_parameters {
+                    header.push(self.type_parameters(type_parameters));
+                }
+                if let Some(extends_clause) = &defn.extends_clause {
+                    header.push(Doc::text(" extends "));
+                    header.push(self.expression_list(extends_clause));
+                }
+                let content = self.block_content(&defn.block);
+                Doc::Concat(vec![annotations, self.braced(Doc::Concat(header), content)])
+            },
+            Directive::TypeDefinition(defn) => {
+                let annotations = self.annotations(defn.jetdoc.as_ref(), &defn.attributes, None);
+                let right = self.expression(&defn.right);
+                Doc::Concat(vec![annotations, Doc::text(format!("type {} = ", defn.left.0)), right, Doc::text(";")])
+            },
+        }
+    }
+
+    fn if_statement(&mut self, stmt: &IfStatement) -> Doc {
+        let test = self.expression(&stmt.test);
+        let header = Doc::Concat(vec![Doc::text("if ("), test, Doc::text(")")]);
+        let mut parts = vec![self.substatement(header, &stmt.consequent)];
+        if let Some(alternative) = &stmt.alternative {
+            parts.push(if Self::is_plain_block(&stmt.consequent) { self.continuation() } else { Doc::HardLine });
+            if let Directive::IfStatement(alternative) = alternative.as_ref() {
+                parts.push(Doc::text("else "));
+                parts.push(self.if_statement(alternative));
+            } else {
+                parts.push(self.substatement(Doc::text("else"), alternative));
+            }
+        }
+        Doc::Concat(parts)
+    }
+
+    fn switch_statement(&mut self, stmt: &SwitchStatement) -> Doc {
+        let discriminant = self.expression(&stmt.discriminant);
+        let header = Doc::Concat(vec![Doc::text("switch ("), discriminant, Doc::text(")")]);
+        let mut items = ListItems::default();
+        for (i, case) in stmt.cases.iter().enumerate() {
+            self.begin_item(&mut items, case.location.first_offset());
+            let mut labels = vec![];
+            for label in &case.labels {
+                labels.push(match label {
+                    CaseLabel::Case((expression, _)) => Doc::Concat(vec![Doc::text("case "), self.expression(expression), Doc::text(":")]),
+                    CaseLabel::Default(_) => Doc::text("default:"),
+                });
+            }
+            let end_offset = stmt.cases.get(i + 1).map_or(stmt.location.last_offset(), |case| case.location.first_offset());
+            let mut parts = vec![Doc::join(labels, Doc::HardLine)];
+            if let Some(content) = self.directive_list(&case.directives, end_offset) {
+                parts.push(Doc::indent(Doc::Concat(vec![Doc::HardLine, content])));
+            }
+            self.end_item(&mut items, Doc::Concat(parts), &case.location);
+        }
+        self.leading_comments(&mut items, stmt.location.last_offset());
+        let content = if items.docs.is_empty() { None } else { Some(Doc::join(items.docs, Doc::HardLine)) };
+        self.braced(header, content)
+    }
+
+    fn switch_type_statement(&mut self, stmt: &SwitchTypeStatement) -> Doc {
+        let discriminant = self.expression(&stmt.discriminant);
+        let header = Doc::Concat(vec![Doc::text("switch type ("), discriminant, Doc::text(")")]);
+        let mut items = ListItems::default();
+        for case in &stmt.cases {
+            self.begin_item(&mut items, case.location.first_offset());
+            let label = match &case.parameter {
+                Some(parameter) => Doc::Concat(vec![Doc::text("case ("), self.typed_destructuring(parameter, false), Doc::text(")")]),
+                None => Doc::text("default"),
+            };
+            let content = self.block_content(&case.block);
+            let doc = self.braced(label, content);
+            self.end_item(&mut items, doc, &case.location);
+        }
+        self.leading_comments(&mut items, stmt.location.last_offset());
+        let content = if items.docs.is_empty() { None } else { Some(Doc::join(items.docs, Doc::HardLine)) };
+        self.braced(header, content)
+    }
+
+    fn configuration_subdirective(&mut self, directive: &Rc<Directive>) -> Doc {
+        match directive.as_ref() {
+            Directive::IfStatement(stmt) => {
+                let test = self.configuration_expression(&stmt.test);
+                let header = Doc::Concat(vec![Doc::text("if ("), test, Doc::text(")")]);
+                let mut parts = vec![self.substatement(header, &stmt.consequent)];
+                if let Some(alternative) = &stmt.alternative {
+                    parts.push(self.continuation());
+                    if matches!(alternative.as_ref(), Directive::IfStatement(_)) {
+                        parts.push(Doc::text("else "));
+                        parts.push(self.configuration_subdirective(alternative));
+                    } else {
+                        parts.push(self.substatement(Doc::text("else"), alternative));
+                    }
+                }
+                Doc::Concat(parts)
+            },
+            _ => self.directive(directive),
+        }
+    }
+
+    fn configuration_expression(&mut self, expression: &Rc<Expression>) -> Doc {
+        match expression.as_ref() {
+            Expression::QualifiedIdentifier(QualifiedIdentifier { id: QualifiedIdentifierIdentifier::Id((name, _)), .. }) => {
+                Doc::text(name.clone())
+            },
+            Expression::Binary(BinaryExpression { operator, left, right, .. }) => {
+                let left = self.configuration_expression(left);
+                match operator {
+                    Operator::Equals | Operator::NotEquals => {
+                        let operator = if *operator == Operator::Equals { "=" } else { "!=" };
+                        Doc::Concat(vec![left, Doc::text(operator), Doc::text(self.source(&right.location()))])
+                    },
+                    _ => {
+                        let right = self.configuration_expression(right);
+                        Doc::Concat(vec![left, Doc::text(format!(" {} ", Self::binary_operator(*operator))), right])
+                    },
+                }
+            },
+            Expression::Paren(ParenExpression { expression, .. }) => {
+                Doc::Concat(vec![Doc::text("("), self.configuration_expression(expression), Doc::text(")")])
+            },
+            Expression::Unary(UnaryExpression { operator: Operator::LogicalNot, expression, .. }) => {
+                Doc::Concat(vec![Doc::text("!"), self.configuration_expression(expression)])
+            },
+            _ => self.expression(expression),
+        }
+    }
+
+    fn import_name(alias: &Option<(String, Location)>, package_name: &[(String, Location)], import_specifier: &ImportSpecifier) -> String {
+        let mut name = package_name.iter().map(|name| name.0.clone()).collect::<Vec<_>>();
+        name.push(match import_specifier {
+            ImportSpecifier::Wildcard(_) => "*".to_owned(),
+            ImportSpecifier::Identifier(name) => name.0.clone(),
+        });
+        let name = name.join(".");
+        alias.as_ref().map_or(name.clone(), |alias| format!("{} = {name}", alias.0))
+    }
+
+    // Definitions
+
+    /// Prints the JetDoc comment and meta-data of a definition on their own
+    /// lines, followed by the keyword attributes.
+    fn annotations(&mut self, jetdoc: Option<&Rc<JetDoc>>, attributes: &[Attribute], implied_metadata: Option<&str>) -> Doc {
+        let mut parts = vec![];
+        if let Some(jetdoc) = jetdoc {
+            parts.push(self.jetdoc(jetdoc));
+            parts.push(Doc::HardLine);
+        }
+        if let Some(name) = implied_metadata {
+            parts.push(Doc::text(format!("[{name}]")));
+            parts.push(Doc::HardLine);
+        }
+        for attribute in attributes {
+            parts.push(self.attribute(attribute));
+        }
+        Doc::Concat(parts)
+    }
+
+    fn attribute(&mut self, attribute: &Attribute) -> Doc {
+        let keyword = match attribute {
+            Attribute::Metadata(metadata) => {
+                return Doc::Concat(vec![Doc::text(self.metadata(metadata)), Doc::HardLine]);
+            },
+            Attribute::Public(_) => "public",
+            Attribute::Private(_) => "private",
+            Attribute::Protected(_) => "protected",
+            Attribute::Internal(_) => "internal",
+            Attribute::Proxy(_) => "proxy",
+            Attribute::Final(_) => "final",
+            Attribute::Native(_) => "native",
+            Attribute::Static(_) => "static",
+            Attribute::Abstract(_) => "abstract",
+            Attribute::Override(_) => "override",
+        };
+        Doc::text(format!("{keyword} "))
+    }
+
+    fn metadata(&self, metadata: &UnprocessedMetadata) -> String {
+        let entries = metadata.entries.as_ref().map_or(String::new(), |entries| format!("({})", self.metadata_entries(entries)));
+        format!("[{}{entries}]", metadata.name.0)
+    }
+
+    fn metadata_entries(&self, entries: &[Rc<UnprocessedMetadataEntry>]) -> String {
+        entries.iter().map(|entry| {
+            let value = match entry.value.as_ref() {
+                UnprocessedMetadataValue::IdentifierString((value, _)) => value.clone(),
+                UnprocessedMetadataValue::String((value, location)) => self.string_literal(value, location),
+                UnprocessedMetadataValue::Number((value, _)) => value.clone(),
+                UnprocessedMetadataValue::Boolean((value, _)) => value.to_string(),
+                UnprocessedMetadataValue::File { output, file_path, .. } => {
+                    format!("File({}{})", if *output { "output + " } else { "" }, self.string_literal(&file_path.0, &file_path.1))
+                },
+                UnprocessedMetadataValue::List((entries, _)) => format!("List({})", self.metadata_entries(entries)),
+            };
+            entry.key.as_ref().map_or(value.clone(), |key| format!("{} = {value}", key.0))
+        }).collect::<Vec<_>>().join(", ")
+    }
+
+    /// Prints a JetDoc comment, aligning the asterisks of its lines.
+    fn jetdoc(&self, jetdoc: &JetDoc) -> Doc {
+        let content = self.source(&jetdoc.location);
+        let lines = content.split('\n').map(|line| line.strip_suffix('\r').unwrap_or(line)).collect::<Vec<_>>();
+        if lines.len() == 1 {
+            return Doc::text(format!("/**{content}*/"));
+        }
+        let strip_prefix = |line: &str| -> String {
+            let line = line.trim_start();
+            let line = line.strip_prefix('*').map_or(line, |line| line.strip_prefix([' ', '\t']).unwrap_or(line));
+            line.trim_end().to_owned()
+        };
+        let mut docs = vec![Doc::text(format!("/**{}", lines[0].trim_end()))];
+        for line in &lines[1..(lines.len() - 1)] {
+            let line = strip_prefix(line);
+            docs.push(Doc::text(if line.is_empty() { " *".to_owned() } else { format!(" * {line}") }));
+        }
+        let last_line = strip_prefix(lines.last().unwrap());
+        if !last_line.is_empty() {
+            docs.push(Doc::text(format!(" * {last_line}")));
+        }
+        docs.push(Doc::text(" */"));
+        Doc::join(docs, Doc::HardLine)
+    }
+
+    fn type_parameters(&mut self, type_parameters: &[Rc<TypeParameter>]) -> Doc {
+        let mut docs = vec![];
+        for type_parameter in type_parameters {
+            let mut parts = vec![Doc::text(type_parameter.name.0.clone())];
+            if let Some(extends_clause) = &type_parameter.extends_clause {
+                parts.push(Doc::text(" extends "));
+                parts.push(self.expression(extends_clause));
+            }
+            if let Some(implements_clause) = &type_parameter.implements_clause {
+                parts.push(Doc::text(" implements "));
+                parts.push(self.expression_list(implements_clause));
+            }
+            docs.push(Doc::Concat(parts));
+        }
+        Doc::Concat(vec![Doc::text(".<"), Doc::join(docs, Doc::text(", ")), Doc::text(">")])
+    }
+
+    fn variable_bindings(&mut self, kind: &VariableDefinitionKind, bindings: &[Rc<VariableBinding>]) -> Doc {
+        let keyword = match kind {
+            VariableDefinitionKind::Var => "var ",
+            VariableDefinitionKind::Const => "const ",
+        };
+        let mut docs = vec![];
+        for binding in bindings {
+            let mut parts = vec![self.typed_destructuring(&binding.destructuring, false)];
+            if let Some(initializer) = &binding.initializer {
+                parts.push(Doc::text(" = "));
+                parts.push(self.expression(initializer));
+            }
+            docs.push(Doc::Concat(parts));
+        }
+        Doc::Concat(vec![Doc::text(keyword), Doc::join(docs, Doc::text(", "))])
+    }
+
+    fn simple_variable_definition(&mut self, defn: &SimpleVariableDefinition) -> Doc {
+        self.variable_bindings(&defn.kind.0, &defn.bindings)
+    }
+
+    fn typed_destructuring(&mut self, destructuring: &TypedDestructuring, optional: bool) -> Doc {
+        let mut parts = vec![self.expression(&destructuring.destructuring)];
+        if optional {
+            parts.push(Doc::text("?"));
+        }
+        if let Some(type_annotation) = &destructuring.type_annotation {
+            parts.push(Doc::text(": "));
+            parts.push(self.expression(type_annotation));
+        }
+        Doc::Concat(parts)
+    }
+
+    fn function_common(&mut self, header: Doc, common: &FunctionCommon) -> Doc {
+        let signature = self.function_signature(header, &common.signature, false);
+        match &common.body {
+            None => signature,
+            Some(FunctionBody::Expression(body)) => Doc::Concat(vec![signature, Doc::text(" "), self.expression(body)]),
+            Some(FunctionBody::Block(block)) => {
+                let content = self.block_content(block);
+                self.braced(signature, content)
+            },
+        }
+    }
+
+    /// Prints a function signature. Optional parameters of a function
+    /// type are followed by a question mark.
+    fn function_signature(&mut self, header: Doc, signature: &FunctionSignature, function_type: bool) -> Doc {
+        let mut parameters = vec![];
+        for parameter in &signature.parameters {
+            let mut parts = vec![];
+            if parameter.kind == ParameterKind::Rest {
+                parts.push(Doc::text("..."));
+            }
+            parts.push(self.typed_destructuring(&parameter.destructuring, function_type && parameter.kind == ParameterKind::Optional));
+            if let Some(default_value) = &parameter.default_value {
+                parts.push(Doc::text(" = "));
+                parts.push(self.expression(default_value));
+            }
+            parameters.push(Doc::Concat(parts));
+        }
+        let mut parts = vec![header, Self::delimited("(", parameters, ")")];
+        if let Some(result_type) = &signature.result_type {
+            parts.push(Doc::text(": "));
+            parts.push(self.expression(result_type));
+        }
+        Doc::Concat(parts)
+    }
+
+    // Expressions
+
+    /// Prints a list delimited by brackets, placing each element on
+    /// its own line if the list does not fit in a line.
+    fn delimited(open: &str, docs: Vec<Doc>, close: &str) -> Doc {
+        if docs.is_empty() {
+            return Doc::text(format!("{open}{close}"));
+        }
+        Doc::group(Doc::Concat(vec![
+            Doc::text(open),
+            Doc::indent(Doc::Concat(vec![Doc::SoftLine, Doc::join(docs, Doc::Concat(vec![Doc::text(","), Doc::Line]))])),
+            Doc::SoftLine,
+            Doc::text(close),
+        ]))
+    }
+
+    fn arguments(&mut self, arguments: &[Rc<Expression>]) -> Doc {
+        let docs = arguments.iter().map(|argument| self.expression(argument)).collect::<Vec<_>>();
+
+        // A trailing function expression, array literal or object initializer
+        // hugs the parentheses instead of breaking the argument list.
+        let hugs = |argument: &Rc<Expression>| matches!(argument.as_ref(), Expression::Function(_) | Expression::ArrayLiteral(_) | Expression::ObjectInitializer(_));
+        if let Some((last, rest)) = arguments.split_last() {
+            if hugs(last) && !rest.iter().any(hugs) {
+                return Doc::Concat(vec![Doc::text("("), Doc::join(docs, Doc::text(", ")), Doc::text(")")]);
+            }
+        }
+        Self::delimited("(", docs, ")")
+    }
+
+    fn expression_list(&mut self, expressions: &[Rc<Expression>]) -> Doc {
+        let docs = expressions.iter().map(|expression| self.expression(expression)).collect();
+        Doc::join(docs, Doc::text(", "))
+    }
+
+    fn expression(&mut self, expression: &Rc<Expression>) -> Doc {
+        let mut parts = self.attached_comments(expression.location().first_offset());
+        parts.push(self.expression_content(expression));
+        Doc::Concat(parts)
+    }
+
+    fn expression_content(&mut self, expression: &Rc<Expression>) -> Doc {
+        match expression.as_ref() {
+            Expression::QualifiedIdentifier(id) => self.qualified_identifier(id),
+            Expression::Embed(expr) => Doc::Concat(vec![Doc::text("embed "), self.object_initializer(&expr.description)]),
+            Expression::Paren(expr) => Doc::Concat(vec![Doc::text("("), self.expression(&expr.expression), Doc::text(")")]),
+            Expression::NullLiteral(_) => Doc::text("null"),
+            Expression::BooleanLiteral(literal) => Doc::text(literal.value.to_string()),
+            Expression::NumericLiteral(literal) => Doc::text(self.source(&literal.location)),
+            Expression::StringLiteral(literal) => Doc::text(self.string_literal(&literal.value, &literal.location)),
+            Expression::ThisLiteral(_) => Doc::text("this"),
+            Expression::RegExpLiteral(literal) => Doc::text(format!("/{}/{}", literal.body, literal.flags)),
+            Expression::Xml(expr) => self.xml_element(&expr.element),
+            Expression::XmlMarkup(expr) => Doc::text(expr.markup.clone()),
+            Expression::XmlList(expr) => {
+                let mut parts = vec![Doc::text("<>")];
+                parts.extend(self.xml_content(&expr.content));
+                parts.push(Doc::text("</>"));
+                Doc::Concat(parts)
+            },
+            Expression::ArrayLiteral(literal) => self.array_literal(literal),
+            Expression::ObjectInitializer(initializer) => self.object_initializer(initializer),
+            Expression::Function(expr) => {
+                let header = Doc::text(expr.name.as_ref().map_or("function".to_owned(), |name| format!("function {}", name.0)));
+                self.function_common(header, &expr.common)
+            },
+            Expression::ImportMeta(_) => Doc::text("import.meta"),
+            Expression::New(expr) => {
+                let mut parts = vec![Doc::text("new "), self.expression(&expr.base)];
+                if let Some(arguments) = &expr.arguments {
+                    parts.push(self.arguments(arguments));
+                }
+                Doc::Concat(parts)
+            },
+            Expression::Member(expr) => {
+                let identifier = self.qualified_identifier(&expr.identifier);
+                if Self::is_optional_chaining_placeholder(&expr.base) {
+                    identifier
+                } else {
+                    Doc::Concat(vec![self.expression(&expr.base), Doc::text("."), identifier])
+                }
+            },
+            Expression::ComputedMember(expr) => {
+                let base = self.expression(&expr.base);
+                Doc::Concat(vec![base, Doc::text("["), self.expression(&expr.key), Doc::text("]")])
+            },
+            Expression::Descendants(expr) => {
+                let base = self.expression(&expr.base);
+                Doc::Concat(vec![base, Doc::text(".."), self.qualified_identifier(&expr.identifier)])
+            },
+            Expression::Filter(expr) => {
+                let base = self.expression(&expr.base);
+                Doc::Concat(vec![base, Doc::text(".("), self.expression(&expr.test), Doc::text(")")])
+            },
+            Expression::Super(expr) => {
+                let mut parts = vec![Doc::text("super")];
+                if let Some(object) = &expr.object {
+                    parts.push(self.arguments(object));
+                }
+                Doc::Concat(parts)
+            },
+            Expression::Call(expr) => {
+                let base = self.expression(&expr.base);
+                Doc::Concat(vec![base, self.arguments(&expr.arguments)])
+            },
+            Expression::WithTypeArguments(expr) => {
+                let base = self.expression(&expr.base);
+                Doc::Concat(vec![base, Doc::text(".<"), self.expression_list(&expr.arguments), Doc::text(">")])
+            },
+            Expression::Unary(expr) => self.unary_expression(expr),
+            Expression::OptionalChaining(expr) => {
+                let base = self.expression(&expr.base);
+                Doc::Concat(vec![base, Doc::text("?."), self.expression(&expr.expression)])
+            },
+            Expression::OptionalChainingPlaceholder(_) => Doc::empty(),
+            Expression::Binary(expr) => {
+                let left = self.expression(&expr.left);
+                let right = self.expression(&expr.right);
+                Doc::Concat(vec![left, Doc::text(format!(" {} ", Self::binary_operator(expr.operator))), right])
+            },
+            Expression::Conditional(expr) => {
+                let test = self.expression(&expr.test);
+                let consequent = self.expression(&expr.consequent);
+                let alternative = self.expression(&expr.alternative);
+                Doc::Concat(vec![test, Doc::text(" ? "), consequent, Doc::text(" : "), alternative])
+            },
+            Expression::Assignment(expr) => {
+                let left = self.expression(&expr.left);
+                let right = self.expression(&expr.right);
+                let operator = expr.compound.map_or("=".to_owned(), |operator| format!("{}=", Self::binary_operator(operator)));
+                Doc::Concat(vec![left, Doc::text(format!(" {operator} ")), right])
+            },
+            Expression::Sequence(expr) => {
+                let left = self.expression(&expr.left);
+                Doc::Concat(vec![left, Doc::text(", "), self.expression(&expr.right)])
+            },
+            Expression::NullableType(expr) => {
+                // A postfix question mark would apply to
+                // the result type of a function type.
+                if matches!(expr.base.as_ref(), Expression::FunctionType(_)) {
+                    Doc::Concat(vec![Doc::text("?"), self.expression(&expr.base)])
+                } else {
+                    Doc::Concat(vec![self.expression(&expr.base), Doc::text("?")])
+                }
+            },
+            Expression::NonNullableType(expr) => Doc::Concat(vec![self.expression(&expr.base), Doc::text("!")]),
+            Expression::AnyType(_) => Doc::text("*"),
+            Expression::VoidType(_) => Doc::text("void"),
+            Expression::ArrayType(expr) => Doc::Concat(vec![Doc::text("["), self.expression(&expr.expression), Doc::text("]")]),
+            Expression::TupleType(expr) => Doc::Concat(vec![Doc::text("["), self.expression_list(&expr.expressions), Doc::text("]")]),
+            Expression::FunctionType(expr) => self.function_signature(Doc::text("function"), &expr.signature, true),
+            Expression::Invalidated(expr) => Doc::text(self.source(&expr.location)),
+        }
+    }
+
+    fn is_optional_chaining_placeholder(expression: &Rc<Expression>) -> bool {
+        matches!(expression.as_ref(), Expression::OptionalChainingPlaceholder(_))
+    }
+
+    fn qualified_identifier(&mut self, id: &QualifiedIdentifier) -> Doc {
+        let mut parts = vec![];
+        if id.attribute {
+            parts.push(Doc::text("@"));
+        }
+        if let Some(qualifier) = &id.qualifier {
+            parts.push(self.expression(qualifier));
+            parts.push(Doc::text("::"));
+        }
+        match &id.id {
+            QualifiedIdentifierIdentifier::Id((name, _)) => parts.push(Doc::text(name.clone())),
+            QualifiedIdentifierIdentifier::Brackets(expression) => {
+                parts.push(Doc::text("["));
+                parts.push(self.expression(expression));
+                parts.push(Doc::text("]"));
+            },
+        }
+        Doc::Concat(parts)
+    }
+
+    fn unary_expression(&mut self, expr: &UnaryExpression) -> Doc {
+        let operand = self.expression(&expr.expression);
+        let postfix = match expr.operator {
+            Operator::PostIncrement => Some("++"),
+            Operator::PostDecrement => Some("--"),
+            Operator::NonNull => Some("!"),
+            _ => None,
+        };
+        if let Some(operator) = postfix {
+            return Doc::Concat(vec![operand, Doc::text(operator)]);
+        }
+        let operator = match expr.operator {
+            Operator::Delete => "delete ",
+            Operator::Void => "void ",
+            Operator::Typeof => "typeof ",
+            Operator::Await => "await ",
+            Operator::Yield => "yield ",
+            Operator::PreIncrement => "++",
+            Operator::PreDecrement => "--",
+            Operator::Positive => "+",
+            Operator::Negative => "-",
+            Operator::BitwiseNot => "~",
+            _ => "!",
+        };
+
+        // Separate signs that would otherwise be scanned
+        // as an increment or decrement operator.
+        let sign_follows = matches!(expr.expression.as_ref(), Expression::Unary(UnaryExpression {
+            operator: Operator::Positive | Operator::Negative | Operator::PreIncrement | Operator::PreDecrement,
+            ..
+        }));
+        let separator = if sign_follows && matches!(expr.operator, Operator::Positive | Operator::Negative | Operator::PreIncrement | Operator::PreDecrement) { " " } else { "" };
+        Doc::Concat(vec![Doc::text(format!("{operator}{separator}")), operand])
+    }
+
+    fn binary_operator(operator: Operator) -> &'static str {
+        match operator {
+            Operator::Power => "**",
+            Operator::Multiply => "*",
+            Operator::Divide => "/",
+            Operator::Remainder => "%",
+            Operator::Add => "+",
+            Operator::Subtract => "-",
+            Operator::ShiftLeft => "<<",
+            Operator::ShiftRight => ">>",
+            Operator::ShiftRightUnsigned => ">>>",
+            Operator::Lt => "<",
+            Operator::Gt => ">",
+            Operator::Le => "<=",
+            Operator::Ge => ">=",
+            Operator::In => "in",
+            Operator::NotIn => "not in",
+            Operator::Is => "is",
+            Operator::IsNot => "is not",
+            Operator::As => "as",
+            Operator::Equals => "==",
+            Operator::NotEquals => "!=",
+            Operator::StrictEquals => "===",
+            Operator::StrictNotEquals => "!==",
+            Operator::BitwiseAnd => "&",
+            Operator::BitwiseXor => "^",
+            Operator::BitwiseOr => "|",
+            Operator::LogicalAnd => "&&",
+            Operator::LogicalXor => "^^",
+            Operator::LogicalOr => "||",
+            Operator::NullCoalescing => "??",
+            _ => unreachable!(),
+        }
+    }
+
+    /// Prints a string literal as written in the source text,
+    /// or quoted if it does not appear as a string literal.
+    fn string_literal(&self, value: &str, location: &Location) -> String {
+        let source = self.source(location);
+        if source.starts_with(['"', '\'', '@']) {
+            return source;
+        }
+        let mut result = "\"".to_owned();
+        for ch in value.chars() {
+            match ch {
+                '\\' => result.push_str("\\\\"),
+                '"' => result.push_str("\\\""),
+                '\n' => result.push_str("\\n"),
+                '\r' => result.push_str("\\r"),
+                '\t' => result.push_str("\\t"),
+                '\x08' => result.push_str("\\b"),
+                '\x0C' => result.push_str("\\f"),
+                '\x0B' => result.push_str("\\v"),
+                ch if ch.is_control() => result.push_str(&format!("\\u{{{:x}}}", ch as u32)),
+                ch => result.push(ch),
+            }
+        }
+        result.push('"');
+        result
+    }
+
+    fn array_literal(&mut self, literal: &ArrayLiteral) -> Doc {
+        let element = |printer: &mut Self, element: &Element| match element {
+            Element::Elision => Doc::empty(),
+            Element::Expression(expression) => printer.expression(expression),
+            Element::Rest((expression, _)) => Doc::Concat(vec![Doc::text("..."), printer.expression(expression)]),
+        };
+        if !literal.elements.iter().any(|element| matches!(element, Element::Elision)) {
+            let docs = literal.elements.iter().map(|e| element(self, e)).collect();
+            return Self::delimited("[", docs, "]");
+        }
+
+        // Elisions are written as commas, and an element
+        // followed by an elision is followed by a comma.
+        let mut parts = vec![Doc::text("[")];
+        for (i, e) in literal.elements.iter().enumerate() {
+            if matches!(e, Element::Elision) {
+                parts.push(Doc::text(","));
+                continue;
+            }
+            parts.push(element(self, e));
+            match literal.elements.get(i + 1) {
+                Some(Element::Elision) => parts.push(Doc::text(",")),
+                Some(_) => parts.push(Doc::text(", ")),
+                None => {},
+            }
+        }
+        parts.push(Doc::text("]"));
+        Doc::Concat(parts)
+    }
+
+    fn object_initializer(&mut self, initializer: &ObjectInitializer) -> Doc {
+        let mut docs = vec![];
+        for field in &initializer.fields {
+            docs.push(match field.as_ref() {
+                InitializerField::Field { name, non_null, value } => {
+                    let mut parts = vec![match &name.0 {
+                        FieldName::Identifier(name) => Doc::text(name.clone()),
+                        FieldName::Brackets(expression) => Doc::Concat(vec![Doc::text("["), self.expression(expression), Doc::text("]")]),
+                        FieldName::StringLiteral(expression) | FieldName::NumericLiteral(expression) => self.expression(expression),
+                    }];
+                    if *non_null {
+                        parts.push(Doc::text("!"));
+                    }
+                    if let Some(value) = value {
+                        parts.push(Doc::text(": "));
+                        parts.push(self.expression(value));
+                    }
+                    Doc::Concat(parts)
+                },
+                InitializerField::Rest((expression, _)) => Doc::Concat(vec![Doc::text("..."), self.expression(expression)]),
+            });
+        }
+        Self::delimited("{", docs, "}")
+    }
+
+    // XML
+
+    fn xml_element(&mut self, element: &XmlElement) -> Doc {
+        let mut parts = vec![Doc::text("<"), self.xml_tag_name(&element.name)];
+        for attribute in &element.attributes {
+            parts.push(Doc::text(format!(" {}=", attribute.name.0)));
+            parts.push(match &attribute.value {
+                XmlAttributeValue::Value((value, _)) => {
+                    Doc::text(if value.contains('"') { format!("'{value}'") } else { format!("\"{value}\"") })
+                },
+                XmlAttributeValue::Expression(expression) => Doc::Concat(vec![Doc::text("{"), self.expression(expression), Doc::text("}")]),
+            });
+        }
+        if let Some(expression) = &element.attribute_expression {
+            parts.push(Doc::text(" {"));
+            parts.push(self.expression(expression));
+            parts.push(Doc::text("}"));
+        }
+        let Some(content) = &element.content else {
+            parts.push(Doc::text("/>"));
+            return Doc::Concat(parts);
+        };
+        parts.push(Doc::text(">"));
+        parts.extend(self.xml_content(content));
+        parts.push(Doc::text("</"));
+        if let Some(closing_name) = &element.closing_name {
+            parts.push(self.xml_tag_name(closing_name));
+        }
+        parts.push(Doc::text(">"));
+        Doc::Concat(parts)
+    }
+
+    fn xml_tag_name(&mut self, name: &XmlTagName) -> Doc {
+        match name {
+            XmlTagName::Name((name, _)) => Doc::text(name.clone()),
+            XmlTagName::Expression(expression) => Doc::Concat(vec![Doc::text("{"), self.expression(expression), Doc::text("}")]),
+        }
+    }
+
+    /// Prints XML content as written in the source text, since
+    /// whitespace is significant to it.
+    fn xml_content(&mut self, content: &[Rc<XmlElementContent>]) -> Vec<Doc> {
+        content.iter().map(|content| match content.as_ref() {
+            XmlElementContent::XmlText((text, _)) | XmlElementContent::XmlMarkup((text, _)) => Doc::text(text.clone()),
+            XmlElementContent::XmlElement(element) => self.xml_element(element),
+            XmlElementContent::Expression(expression) => Doc::Concat(vec![Doc::text("{"), self.expression(expression), Doc::text("}")]),
+        }).collect()
+    }
+}
+
+/// Items of a directive list being printed.
+#[derive(Default)]
+struct ListItems {
+    docs: Vec<Doc>,
+    /// Last source line of the previous item.
+    last_line: Option<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+
+    fn parse(text: &str) -> Option<Rc<Program>> {
+        let compilation_unit = CompilationUnit::new(None, text.into(), &CompilerOptions::new());
+        let program = ParserFacade::parse_program(&compilation_unit).unwrap();
+        (!compilation_unit.invalidated()).then_some(program)
+    }
+
+    #[test]
+    fn formatting_parser_tests() {
+        let directory = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/parser");
+        let mut paths = std::fs::read_dir(directory).unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|extension| extension == "jet"))
+            .collect::<Vec<_>>();
+        paths.sort();
+        for options in [FormatterOptions::default(), FormatterOptions { brace_style: BraceStyle::NextLine, use_tabs: true, line_width: 20, ..default() }] {
+            let formatter = Formatter::new(options);
+            for path in &paths {
+                // Skip sources that contain syntax errors.
+                let Some(program) = parse(&std::fs::read_to_string(path).unwrap()) else {
+                    continue;
+                };
+                let formatted = formatter.format_program(&program);
+                let reparsed = parse(&formatted).unwrap_or_else(|| panic!("{}:\n{formatted}", path.display()));
+                assert_eq!(serde_json::to_string(&program).unwrap(), serde_json::to_string(&reparsed).unwrap(), "{}:\n{formatted}", path.display());
+                assert_eq!(formatter.format_program(&reparsed), formatted, "{}", path.display());
+            }
+        }
+    }
+
+    #[test]
+    fn formatting_layout() {
+        let formatter = Formatter::new(FormatterOptions { line_width: 40, ..default() });
+        let formatted = formatter.format_text(r#"
+            // Leading comment
+            package p {
+                import q.*;   // Trailing comment
+
+
+                public class C extends B implements I, J {
+                    /* Member */ private var x: Number = - -1, y = [1,,];
+                    function C(a: Number, ...rest) { super(a) }
+                    function f(): void {
+                        if (x) g(); else if (y) { h() } else return;
+                        for (var i = 0; i < 10; i++) for each (var v in o) continue;
+                        try { g(function() { return 1 }) } catch (e: Error) {} finally { h() }
+                        const list = ["first element", "second element", "third element"];
+                        var t: ?function(a?: T): *;
+                        // Last comment
+                    }
+                }
+            }
+        "#).unwrap();
+        assert_eq!(formatted, r#"// Leading comment
+package p {
+    import q.*; // Trailing comment
+
+    public class C extends B implements I, J {
+        /* Member */
+        private var x: Number = - -1, y = [1,,];
+        function C(a: Number, ...rest) {
+            super(a);
+        }
+        function f(): void {
+            if (x)
+                g();
+            else if (y) {
+                h();
+            } else
+                return;
+            for (var i = 0; i < 10; i++)
+                for each (var v in o)
+                    continue;
+            try {
+                g(function() {
+                    return 1;
+                });
+            } catch (e: Error) {} finally {
+                h();
+            }
+            const list = [
+                "first element",
+                "second element",
+                "third element"
+            ];
+            var t: ?function(a?: T): *;
+            // Last comment
+        }
+    }
+}
+"#);
+        assert_eq!(formatter.format_text(&formatted).unwrap(), formatted);
+
+        let formatter = Formatter::new(FormatterOptions { brace_style: BraceStyle::NextLine, indent_width: 2, ..default() });
+        assert_eq!(formatter.format_text("if (x) { f() } else { g() }").unwrap(), "if (x)\n{\n  f();\n}\nelse\n{\n  g();\n}\n");
+        assert!(formatter.format_text("f(").is_none());
+    }
+
+    #[test]
+    fn formatting_comments_within_directives() {
+        let formatter = Formatter::new(FormatterOptions::default());
+        let formatted = formatter.format_text(r#"
+            f(/* c1 */ a, b /* c2 */);
+            if (a) /* c3 */ b();
+            x = // c4
+                1;
+            g();
+        "#).unwrap();
+        assert_eq!(formatted, r#"f(/* c1 */ a, b);
+/* c2 */
+if (a)
+    /* c3 */ b();
+x = // c4
+1;
+g();
+"#);
+        assert_eq!(formatter.format_text(&formatted).unwrap(), formatted);
+    }
+}
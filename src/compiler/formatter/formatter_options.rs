@@ -0,0 +1,33 @@
+/// Options for formatting Jet source code.
+#[derive(Clone, Debug)]
+pub struct FormatterOptions {
+    /// Number of columns per indentation level.
+    pub indent_width: usize,
+    /// Whether indentation uses tab characters instead of spaces.
+    pub use_tabs: bool,
+    /// Preferred maximum line width. Lines are broken at argument lists,
+    /// parameter lists, array literals and object initializers when
+    /// they do not fit.
+    pub line_width: usize,
+    pub brace_style: BraceStyle,
+}
+
+impl Default for FormatterOptions {
+    fn default() -> Self {
+        Self {
+            indent_width: 4,
+            use_tabs: false,
+            line_width: 100,
+            brace_style: BraceStyle::SameLine,
+        }
+    }
+}
+
+/// Placement of the opening brace of a block.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum BraceStyle {
+    /// The opening brace follows the header on the same line.
+    SameLine,
+    /// The opening brace is placed on the line after the header.
+    NextLine,
+}
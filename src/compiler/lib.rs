@@ -1,13 +1,15 @@
 #![feature(decl_macro)]
 #![feature(try_blocks)]
-
+#![allow(clippy::module_inception)]
 pub mod tree;
 pub mod compilation_unit;
 pub mod compiler_options;
 pub mod diagnostics;
+pub mod formatter;
 pub mod operator;
 pub mod parser;
 pub mod semantics;
+pub mod semantic_tokens;
 pub mod util;
 pub mod verifier;
 
@@ -4,8 +4,10 @@ pub use crate::tree::*;
 pub use crate::compilation_unit::*;
 pub use crate::compiler_options::*;
 pub use crate::diagnostics::*;
+pub use crate::formatter::*;
 pub use crate::operator::*;
 pub use crate::parser::*;
 pub use crate::semantics::*;
+pub use crate::semantic_tokens::*;
 pub use crate::util::*;
 pub use crate::verifier::*;
\ No newline at end of file
@@ -1,3 +1,6 @@
+// `num_derive` expands to a non-local `impl` block.
+#![allow(non_local_definitions)]
+
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
 
@@ -42,6 +45,6 @@ impl OperatorPrecedence {
 impl TryFrom<u32> for OperatorPrecedence {
     type Error = ();
     fn try_from(value: u32) -> Result<Self, Self::Error> {
-        if let Some(v) = FromPrimitive::from_u32(value as u32) { Ok(v) } else { Err(()) }
+        if let Some(v) = FromPrimitive::from_u32(value) { Ok(v) } else { Err(()) }
     }
 }
\ No newline at end of file
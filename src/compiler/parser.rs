@@ -2,12 +2,18 @@ mod character_validator;
 pub use character_validator::*;
 mod contexts;
 pub use contexts::*;
+mod incremental_parser;
+pub(crate) use incremental_parser::*;
 mod jet_reserved_word;
 pub use jet_reserved_word::*;
+mod lexer;
+pub use lexer::*;
 mod parser;
 pub use parser::*;
 mod parsing_failure;
 pub use parsing_failure::*;
+mod syntax_token;
+pub use syntax_token::*;
 mod token;
 pub use token::*;
 mod tokenizer;
@@ -47,7 +47,7 @@ impl ParsingDirectiveContext {
     pub fn super_statement_found(&self) -> bool {
         match self {
             Self::ConstructorBlock { super_statement_found } => super_statement_found.get(),
-            Self::WithControl { super_statement_found, .. } => super_statement_found.as_ref().or(Some(&Rc::new(Cell::new(false)))).unwrap().get(),
+            Self::WithControl { super_statement_found, .. } => super_statement_found.as_ref().unwrap_or(&Rc::new(Cell::new(false))).get(),
             _ => false,
         }
     }
@@ -77,12 +77,7 @@ impl ParsingDirectiveContext {
     }
 
     pub fn is_type_block(&self) -> bool {
-        match self {
-            Self::ClassBlock { .. } |
-            Self::InterfaceBlock |
-            Self::EnumBlock => true,
-            _ => false,
-        }
+        matches!(self, Self::ClassBlock { .. } | Self::InterfaceBlock | Self::EnumBlock)
     }
 
     pub fn clone_control(&self) -> Self {
@@ -145,7 +140,7 @@ impl ParsingDirectiveContext {
     }
 
     pub fn resolve_label(&self, label: String) -> Option<ParsingControlContext> {
-        if let Self::WithControl { labels, .. } = &self { labels.get(&label).map(|c| c.clone()) } else { None }
+        if let Self::WithControl { labels, .. } = &self { labels.get(&label).cloned() } else { None }
     }
 
     pub fn is_break_allowed(&self, label: Option<String>) -> bool {
@@ -0,0 +1,355 @@
+use crate::ns::*;
+use std::ops::Range;
+
+/// Maximum number of text edits applied since the last full reparse. Reaching it
+/// causes the whole program to be reparsed, which bounds the cost of mapping
+/// the offsets of previously built locations.
+const MAX_TEXT_SHIFTS: usize = 32;
+
+/// Reparses the parts of a program affected by a text edit.
+///
+/// The incremental parser attempts, in order, to reparse the block body of the
+/// function enclosing the edit, the directives of the program or package block
+/// that intersect the edit, and finally the whole program, stepping to the next
+/// attempt whenever the reparsed region is not parsed cleanly or its boundaries
+/// are not preserved. Edits that may change comments, including JetDoc comments,
+/// always cause the whole program to be reparsed.
+pub(crate) struct IncrementalParser<'a> {
+    compilation_unit: &'a Rc<CompilationUnit>,
+}
+
+/// Directives resulting from reparsing a region of a directive list.
+struct ReparsedDirectives {
+    directives: Vec<Rc<Directive>>,
+    /// Offset of the first token of the directive list, if the region starts the list.
+    first_token_offset: Option<usize>,
+    /// End offset of the last token of the directive list, if the region ends the list.
+    last_token_end: Option<usize>,
+}
+
+impl ReparsedDirectives {
+    fn new(directives: Vec<Rc<Directive>>) -> Self {
+        Self { directives, first_token_offset: None, last_token_end: None }
+    }
+}
+
+impl<'a> IncrementalParser<'a> {
+    pub fn new(compilation_unit: &'a Rc<CompilationUnit>) -> Self {
+        Self { compilation_unit }
+    }
+
+    /// Applies a text edit to the compilation unit and returns
+    /// the updated program.
+    pub fn apply_edit(&self, program: &Rc<Program>, edit: &TextEdit) -> Rc<Program> {
+        let touches_comment = self.touches_comment(edit);
+        self.compilation_unit.replace_text(edit);
+        if touches_comment || self.compilation_unit.text_shifts.borrow().len() >= MAX_TEXT_SHIFTS {
+            return self.reparse_program();
+        }
+
+        // Edited range in the offsets of the new source text.
+        let range = edit.range.start..(edit.range.start + edit.replacement.len());
+
+        if let Some(program) = self.update_directive_list(program, &range, |directives, _, _| self.reparse_function_body(directives, &range).map(ReparsedDirectives::new)) {
+            return program;
+        }
+        if let Some(program) = self.update_directive_list(program, &range, |directives, context, container| self.reparse_directives(directives, context, container, &range)) {
+            return program;
+        }
+        self.reparse_program()
+    }
+
+    /// Indicates whether an edit, in the offsets of the source text it applies to,
+    /// may change a comment. This is conservative: it holds for any edit that
+    /// involves or neighbours a slash or asterisk, or follows an unterminated
+    /// `/*` or a `//` on the same line.
+    fn touches_comment(&self, edit: &TextEdit) -> bool {
+        let text = self.compilation_unit.current_text();
+        let is_comment_character = |ch: char| ch == '/' || ch == '*';
+        let before = &text[..edit.range.start];
+        if edit.replacement.contains(is_comment_character)
+        || text[edit.range.clone()].contains(is_comment_character)
+        || before.ends_with(is_comment_character)
+        || text[edit.range.end..].starts_with(is_comment_character) {
+            return true;
+        }
+
+        // Within a multi-line comment
+        if let Some(i) = before.rfind("/*") {
+            if before.rfind("*/").is_none_or(|j| j < i) {
+                return true;
+            }
+        }
+
+        // Within a single-line comment
+        let line = before.rsplit(CharacterValidator::is_line_terminator).next().unwrap();
+        if line.contains("//") {
+            return true;
+        }
+
+        self.compilation_unit.comments.borrow().iter().any(|comment| {
+            let location = comment.location();
+            location.first_offset() <= edit.range.end && edit.range.start <= location.last_offset()
+        })
+    }
+
+    /// Updates the directive list of the program or package block that
+    /// contains the edited range.
+    fn update_directive_list(&self, program: &Rc<Program>, range: &Range<usize>, update: impl FnOnce(&[Rc<Directive>], ParsingDirectiveContext, Range<usize>) -> Option<ReparsedDirectives>) -> Option<Rc<Program>> {
+        for (i, package) in program.packages.iter().enumerate() {
+            let block = &package.block;
+            let (first, last) = (block.location.first_offset(), block.location.last_offset());
+            if range.end < first {
+                return None;
+            }
+            if !(first < range.start && range.end < last) {
+                continue;
+            }
+            // A diagnostic at a brace might indicate that the block is not delimited by it.
+            if self.has_diagnostic_at(first) || self.has_diagnostic_at(last) {
+                return None;
+            }
+            let directives = update(&block.directives, ParsingDirectiveContext::PackageBlock, (first + 1)..(last - 1))?.directives;
+            let mut packages = program.packages.clone();
+            packages[i] = Rc::new(PackageDefinition {
+                block: Rc::new(Block { directives, ..(**block).clone() }),
+                ..(**package).clone()
+            });
+            return Some(Rc::new(Program { packages, ..(**program).clone() }));
+        }
+        // An edit touching the end of the last package might have changed it.
+        let start = program.packages.last().map_or(0, |package| package.location.last_offset());
+        if range.start < start || (range.start == start && !program.packages.is_empty()) {
+            return None;
+        }
+        let end = self.compilation_unit.current_text().len();
+        let reparsed = update(&program.directives, ParsingDirectiveContext::TopLevel, start..end)?;
+        let first_offset = reparsed.first_token_offset
+            .filter(|_| program.packages.is_empty())
+            .unwrap_or(program.location.first_offset());
+        let last_offset = reparsed.last_token_end.unwrap_or(program.location.last_offset());
+        Some(Rc::new(Program {
+            location: Location::with_offsets(self.compilation_unit, first_offset, last_offset),
+            directives: reparsed.directives,
+            ..(**program).clone()
+        }))
+    }
+
+    /// Reparses the block body of the function definition that encloses
+    /// the edited range, looking into class, enum and interface blocks.
+    fn reparse_function_body(&self, directives: &[Rc<Directive>], range: &Range<usize>) -> Option<Vec<Rc<Directive>>> {
+        for (i, directive) in directives.iter().enumerate() {
+            let location = directive.location();
+            if !(location.first_offset() <= range.start && range.end <= location.last_offset()) {
+                continue;
+            }
+            let directive = match directive.as_ref() {
+                Directive::FunctionDefinition(defn) => self.reparse_function_definition_body(defn, range),
+                Directive::ClassDefinition(defn) => {
+                    let block = self.update_block(&defn.block, |directives| self.reparse_function_body(directives, range))?;
+                    Some(Rc::new(Directive::ClassDefinition(ClassDefinition { block, ..defn.clone() })))
+                },
+                Directive::EnumDefinition(defn) => {
+                    let block = self.update_block(&defn.block, |directives| self.reparse_function_body(directives, range))?;
+                    Some(Rc::new(Directive::EnumDefinition(EnumDefinition { block, ..defn.clone() })))
+                },
+                Directive::InterfaceDefinition(defn) => {
+                    let block = self.update_block(&defn.block, |directives| self.reparse_function_body(directives, range))?;
+                    Some(Rc::new(Directive::InterfaceDefinition(InterfaceDefinition { block, ..defn.clone() })))
+                },
+                _ => None,
+            }?;
+            let mut directives = directives.to_vec();
+            directives[i] = directive;
+            return Some(directives);
+        }
+        None
+    }
+
+    fn update_block(&self, block: &Rc<Block>, update: impl FnOnce(&[Rc<Directive>]) -> Option<Vec<Rc<Directive>>>) -> Option<Rc<Block>> {
+        let directives = update(&block.directives)?;
+        Some(Rc::new(Block { directives, ..(**block).clone() }))
+    }
+
+    fn reparse_function_definition_body(&self, defn: &FunctionDefinition, range: &Range<usize>) -> Option<Rc<Directive>> {
+        let Some(FunctionBody::Block(block)) = defn.common.body.as_ref() else {
+            return None;
+        };
+        let (first, last) = (block.location.first_offset(), block.location.last_offset());
+        if !(first < range.start && range.end < last) {
+            return None;
+        }
+        let block_context = if defn.is_constructor() {
+            ParsingDirectiveContext::ConstructorBlock { super_statement_found: Rc::new(Cell::new(false)) }
+        } else {
+            ParsingDirectiveContext::Default
+        };
+        let (block, _, _) = self.parse_region(first..last, |parser| {
+            let result = parser.parse_function_body(block_context)?;
+
+            // The block must still end at the same closing brace, and a change
+            // in whether the function is asynchronous or a generator
+            // requires reparsing the definition.
+            if result.0.location.last_offset() != last || result.1 != defn.common.contains_await || result.2 != defn.common.contains_yield {
+                return Err(ParsingFailure);
+            }
+            Ok(result)
+        })?;
+        Some(Rc::new(Directive::FunctionDefinition(FunctionDefinition {
+            common: Rc::new(FunctionCommon {
+                body: Some(FunctionBody::Block(block)),
+                ..(*defn.common).clone()
+            }),
+            ..defn.clone()
+        })))
+    }
+
+    /// Reparses the directives that intersect the edited range, from the end
+    /// of the previous unaffected directive to the start of the next one.
+    ///
+    /// The region is extended backwards until a directive terminated by a semicolon
+    /// or a block, since a directive might otherwise extend into the region or
+    /// require a separator from it, and forwards past directives preceded
+    /// by comments, since their JetDoc comments are parsed along with them.
+    fn reparse_directives(&self, directives: &[Rc<Directive>], context: ParsingDirectiveContext, container: Range<usize>, range: &Range<usize>) -> Option<ReparsedDirectives> {
+        let mut first_affected = directives.iter().position(|d| d.location().last_offset() >= range.start).unwrap_or(directives.len());
+        let mut end_of_affected = directives.iter().rposition(|d| d.location().first_offset() <= range.end).map_or(0, |i| i + 1).max(first_affected);
+        while first_affected != 0 {
+            let previous = &directives[first_affected - 1];
+            let offset = previous.location().last_offset();
+            if Self::ends_with_block(previous) || self.ends_with_semicolon(offset) {
+                break;
+            }
+            first_affected -= 1;
+        }
+        let start = if first_affected == 0 { container.start } else { directives[first_affected - 1].location().last_offset() };
+
+        let text = self.compilation_unit.current_text();
+        let mut end = container.end;
+        while let Some(next) = directives.get(end_of_affected) {
+            let gap_start = if end_of_affected == first_affected { start } else { directives[end_of_affected - 1].location().last_offset() };
+            let next_start = next.location().first_offset();
+            if !text[gap_start.min(next_start)..next_start].contains("/*") {
+                end = next_start;
+                break;
+            }
+            end_of_affected += 1;
+        }
+        drop(text);
+
+        let next_location = directives.get(end_of_affected).map(|d| d.location());
+        let followed_by_directive = next_location.is_some();
+        let reparsed = self.parse_region(start..end, |parser| {
+            let first_token_offset = parser.token_location().first_offset();
+            let directives = parser.parse_directives_before(context, end, followed_by_directive)?;
+
+            // A comment of the region that now precedes the next directive
+            // would be its JetDoc comment.
+            if let Some(next_location) = &next_location {
+                if self.compilation_unit.comments().iter().any(|comment| comment.is_jetdoc(next_location)) {
+                    return Err(ParsingFailure);
+                }
+            }
+            Ok(ReparsedDirectives {
+                directives,
+                first_token_offset: (first_affected == 0).then_some(first_token_offset),
+                last_token_end: (!followed_by_directive).then(|| parser.previous_token_location().last_offset()),
+            })
+        })?;
+
+        let mut result = directives[..first_affected].to_vec();
+        result.extend(reparsed.directives);
+        result.extend_from_slice(&directives[end_of_affected..]);
+        Some(ReparsedDirectives { directives: result, ..reparsed })
+    }
+
+    /// Indicates whether a directive ends with a block, after which
+    /// directives are not required to be separated by a semicolon.
+    fn ends_with_block(directive: &Directive) -> bool {
+        match directive {
+            Directive::FunctionDefinition(defn) => matches!(defn.common.body, Some(FunctionBody::Block(_))),
+            Directive::ClassDefinition(_)
+            | Directive::EnumDefinition(_)
+            | Directive::InterfaceDefinition(_)
+            | Directive::Block(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Indicates whether a directive ending at `offset` is terminated
+    /// by a semicolon, which its location includes.
+    fn ends_with_semicolon(&self, offset: usize) -> bool {
+        self.compilation_unit.current_text()[..offset].ends_with(';')
+    }
+
+    /// Reparses the whole program, after which the locations previously
+    /// built for the compilation unit are no longer mapped.
+    fn reparse_program(&self) -> Rc<Program> {
+        let compilation_unit = self.compilation_unit;
+        compilation_unit.set_diagnostics(vec![]);
+        compilation_unit.comments_mut().clear();
+        compilation_unit.clear_text_shifts();
+        let text = compilation_unit.current_text();
+        let mut parser = Parser::new_at(compilation_unit, &text, 0);
+        parser.next().and_then(|_| parser.parse_program()).unwrap_or_else(|_| Rc::new(Program {
+            location: Location::with_offsets(compilation_unit, 0, text.len()),
+            packages: vec![],
+            directives: vec![],
+        }))
+    }
+
+    /// Indicates whether a diagnostic is located at an offset.
+    fn has_diagnostic_at(&self, offset: usize) -> bool {
+        self.compilation_unit.diagnostics().iter().any(|d| {
+            let location = d.location();
+            location.first_offset() <= offset && offset <= location.last_offset()
+        })
+    }
+
+    /// Parses a region of the source text, replacing the comments and diagnostics
+    /// located within the region. The parser may scan past the end of the region,
+    /// in which case the comments and diagnostics found there are discarded.
+    ///
+    /// Returns `None`, restoring the previous comments and diagnostics, if
+    /// a diagnostic crosses the boundaries of the region, or if parsing fails
+    /// or reports syntax errors.
+    fn parse_region<T>(&self, region: Range<usize>, parse: impl FnOnce(&mut Parser) -> Result<T, ParsingFailure>) -> Option<T> {
+        let compilation_unit = self.compilation_unit;
+        let diagnostics = compilation_unit.diagnostics();
+        let comments = compilation_unit.comments();
+        let within_region = |location: &Location| location.first_offset() >= region.start && location.last_offset() <= region.end;
+
+        if self.has_diagnostic_at(region.start) || self.has_diagnostic_at(region.end) {
+            return None;
+        }
+        let diagnostics_outside: Vec<Diagnostic> = diagnostics.iter().filter(|d| !within_region(&d.location())).cloned().collect();
+        let diagnostic_count = diagnostics_outside.len();
+        compilation_unit.set_diagnostics(diagnostics_outside);
+        compilation_unit.comments_mut().retain(|comment| !within_region(&comment.location()));
+        let comment_count = compilation_unit.comments().len();
+        let error_count = compilation_unit.error_count();
+
+        let text = compilation_unit.current_text();
+        let mut parser = Parser::new_at(compilation_unit, &text, region.start);
+        let result = parser.next().and_then(|_| parse(&mut parser)).ok()
+            .filter(|_| compilation_unit.error_count() == error_count);
+        drop(parser);
+        drop(text);
+
+        if result.is_none() {
+            compilation_unit.set_diagnostics(diagnostics);
+            *compilation_unit.comments_mut() = comments;
+        } else {
+            let mut new_diagnostics = compilation_unit.diagnostics();
+            let scanned: Vec<Diagnostic> = new_diagnostics.split_off(diagnostic_count);
+            new_diagnostics.extend(scanned.into_iter().filter(|d| d.location().first_offset() < region.end));
+            compilation_unit.set_diagnostics(new_diagnostics);
+
+            let mut comments = compilation_unit.comments_mut();
+            let scanned = comments.split_off(comment_count);
+            comments.extend(scanned.into_iter().filter(|comment| comment.location().last_offset() <= region.end));
+            comments.sort_by_key(|comment| comment.location().first_offset());
+        }
+        result
+    }
+}
@@ -0,0 +1,272 @@
+use crate::ns::*;
+
+/// Standalone lexer producing the token stream of a compilation unit
+/// without parsing it.
+///
+/// The tokenizer scans differently depending on the syntactic context,
+/// which the lexer resolves through a heuristic based on the previous token:
+/// where the previous token ends an operand, such as an identifier,
+/// a literal, a closing bracket or a postfix `!`, `/` and `/=` are division operators and
+/// `<` is the less-than operator; elsewhere, they start a regular expression
+/// or a XML literal respectively. The lexer then follows XML literals
+/// through their tags, content and embedded braces.
+///
+/// Like the parser, the lexer reports lexical errors and comments
+/// to the compilation unit, and a compilation unit may only be tokenized once.
+///
+/// # Example
+///
+/// ```
+/// use hydroper_jet_compiler::ns::*;
+///
+/// let compilation_unit = CompilationUnit::new(None, "a / b; /x/g".into(), &CompilerOptions::new());
+/// let tokens = Lexer::tokenize(&compilation_unit);
+/// let tokens: Vec<Token> = tokens.tokens().iter().map(|token| token.token.clone()).collect();
+/// assert_eq!(tokens, [
+///     Token::Identifier("a".into()),
+///     Token::Div,
+///     Token::Identifier("b".into()),
+///     Token::Semicolon,
+///     Token::RegExpLiteral { body: "x".into(), flags: "g".into() },
+///     Token::Eof,
+/// ]);
+/// ```
+pub struct Lexer<'input> {
+    tokenizer: Tokenizer<'input>,
+    text: &'input str,
+    modes: Vec<LexerMode>,
+    previous_ends_operand: bool,
+    finished: bool,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum LexerMode {
+    /// Regular tokens, either at the top level or within braces.
+    Regular,
+    XmlOpeningTag,
+    XmlClosingTag,
+    XmlContent,
+}
+
+impl<'input> Lexer<'input> {
+    /// Constructs a lexer.
+    pub fn new(compilation_unit: &'input Rc<CompilationUnit>) -> Self {
+        Self {
+            tokenizer: Tokenizer::new(compilation_unit),
+            text: compilation_unit.text(),
+            modes: vec![LexerMode::Regular],
+            previous_ends_operand: false,
+            finished: false,
+        }
+    }
+
+    /// Scans every token of a compilation unit, returning them along
+    /// with their trivia, which includes comments.
+    pub fn tokenize(compilation_unit: &Rc<CompilationUnit>) -> SyntaxTokenList {
+        let tokens: Vec<(Token, Location)> = Lexer::new(compilation_unit).collect();
+        SyntaxTokenList::new(compilation_unit, tokens)
+    }
+
+    pub fn compilation_unit(&self) -> &Rc<CompilationUnit> {
+        self.tokenizer.compilation_unit()
+    }
+
+    fn mode(&self) -> LexerMode {
+        *self.modes.last().unwrap()
+    }
+
+    fn replace_mode(&mut self, mode: LexerMode) {
+        *self.modes.last_mut().unwrap() = mode;
+    }
+
+    /// Leaves the current mode, unless it is the top level.
+    fn pop_mode(&mut self) {
+        if self.modes.len() > 1 {
+            self.modes.pop();
+        }
+    }
+
+    fn scan(&mut self) -> Result<(Token, Location), ParsingFailure> {
+        match self.mode() {
+            LexerMode::Regular => self.scan_regular(),
+            LexerMode::XmlOpeningTag | LexerMode::XmlClosingTag => {
+                let token = self.tokenizer.scan_ie_xml_tag()?;
+                match (&token.0, self.mode()) {
+                    (Token::LeftBrace, _) => self.enter_braces(),
+                    (Token::Gt, LexerMode::XmlOpeningTag) => self.replace_mode(LexerMode::XmlContent),
+                    (Token::Gt, _) | (Token::XmlSlashGt, _) => self.leave_xml_element(),
+                    _ => {},
+                }
+                Ok(token)
+            },
+            LexerMode::XmlContent => {
+                let token = self.tokenizer.scan_ie_xml_content()?;
+                match &token.0 {
+                    Token::LeftBrace => self.enter_braces(),
+                    Token::Lt => self.modes.push(LexerMode::XmlOpeningTag),
+                    Token::XmlLtSlash => self.replace_mode(LexerMode::XmlClosingTag),
+                    _ => {},
+                }
+                Ok(token)
+            },
+        }
+    }
+
+    fn scan_regular(&mut self) -> Result<(Token, Location), ParsingFailure> {
+        let (token, location) = self.tokenizer.scan_ie_div()?;
+        let operand_position = !self.previous_ends_operand;
+        let result = match &token {
+            Token::Div | Token::DivideAssign if operand_position => {
+                let body = if token == Token::DivideAssign { "=" } else { "" };
+                self.tokenizer.scan_regexp_literal(location, body.into())?
+            },
+            Token::Lt if operand_position => {
+                if let Some(markup) = self.tokenizer.scan_xml_markup(location.clone())? {
+                    markup
+                } else {
+                    let next = self.text[location.last_offset()..].chars().next().unwrap_or('\x00');
+                    if CharacterValidator::is_xml_name_start(next) || next == '>' || next == '{' {
+                        self.modes.push(LexerMode::XmlOpeningTag);
+                    }
+                    (token, location)
+                }
+            },
+            Token::LeftBrace => {
+                self.modes.push(LexerMode::Regular);
+                (token, location)
+            },
+            Token::RightBrace => {
+                self.pop_mode();
+                (token, location)
+            },
+            _ => (token, location),
+        };
+        // A `!` following an operand is the postfix non-null operator,
+        // which ends the operand; otherwise it is the logical not operator.
+        self.previous_ends_operand = if result.0 == Token::Exclamation {
+            self.previous_ends_operand
+        } else {
+            Self::ends_operand(&result.0)
+        };
+        Ok(result)
+    }
+
+    /// Enters braces embedded in a XML literal.
+    fn enter_braces(&mut self) {
+        self.modes.push(LexerMode::Regular);
+        self.previous_ends_operand = false;
+    }
+
+    /// Leaves a XML element, which ends an operand
+    /// if it is not nested in another element.
+    fn leave_xml_element(&mut self) {
+        self.pop_mode();
+        self.previous_ends_operand = true;
+    }
+
+    /// Indicates whether a token ends an operand, after which
+    /// an operator is expected.
+    fn ends_operand(token: &Token) -> bool {
+        matches!(token,
+            Token::Identifier(_) | Token::StringLiteral(_) | Token::NumericLiteral(_) | Token::RegExpLiteral { .. } |
+            Token::XmlMarkup(_) | Token::RightParen | Token::RightBracket | Token::RightBrace |
+            Token::This | Token::Null | Token::True | Token::False | Token::Super |
+            Token::Increment | Token::Decrement)
+    }
+}
+
+impl Iterator for Lexer<'_> {
+    type Item = (Token, Location);
+
+    /// Scans the next token. The last token is `Token::Eof`.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let Ok((token, location)) = self.scan() else {
+            self.finished = true;
+            return None;
+        };
+        if token == Token::Eof {
+            self.finished = true;
+        }
+        Some((token, location))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+
+    fn tokens(text: &str) -> Vec<Token> {
+        let compilation_unit = CompilationUnit::new(None, text.into(), &CompilerOptions::new());
+        Lexer::tokenize(&compilation_unit).tokens().iter().map(|token| token.token.clone()).collect()
+    }
+
+    #[test]
+    fn lexing_parser_tests() {
+        let directory = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/parser");
+        for entry in std::fs::read_dir(directory).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_none_or(|extension| extension != "jet") {
+                continue;
+            }
+            let text = std::fs::read_to_string(&path).unwrap();
+            let compilation_unit = CompilationUnit::new(None, text.clone(), &CompilerOptions::new());
+            let (_, parsed) = ParserFacade::parse_program_with_syntax_tokens(&compilation_unit).unwrap();
+            if compilation_unit.invalidated() {
+                continue;
+            }
+            let lexed_compilation_unit = CompilationUnit::new(None, text.clone(), &CompilerOptions::new());
+            let lexed = Lexer::tokenize(&lexed_compilation_unit);
+            assert_eq!(lexed.to_string(), text, "{}", path.display());
+            let kinds = |list: &SyntaxTokenList| list.tokens().iter().map(|token| (token.token.clone(), token.location.first_offset())).collect::<Vec<_>>();
+            assert_eq!(kinds(&lexed), kinds(&parsed), "{}", path.display());
+        }
+    }
+
+    #[test]
+    fn lexing_ambiguous_tokens() {
+        use Token::*;
+        assert_eq!(tokens("x = a /= 2 / b"), [
+            Identifier("x".into()), Assign, Identifier("a".into()), DivideAssign, NumericLiteral("2".into()), Div, Identifier("b".into()), Eof,
+        ]);
+        assert_eq!(tokens("f(/=/, (a) / 2)"), [
+            Identifier("f".into()), LeftParen, RegExpLiteral { body: "=".into(), flags: "".into() }, Comma,
+            LeftParen, Identifier("a".into()), RightParen, Div, NumericLiteral("2".into()), RightParen, Eof,
+        ]);
+        assert_eq!(tokens("a < b"), [Identifier("a".into()), Lt, Identifier("b".into()), Eof]);
+        let compilation_unit = CompilationUnit::new(None, "x = y! / 2".into(), &CompilerOptions::new());
+        let (_, parsed) = ParserFacade::parse_program_with_syntax_tokens(&compilation_unit).unwrap();
+        assert!(compilation_unit.diagnostics().is_empty());
+        assert_eq!(parsed.tokens().iter().map(|token| token.token.clone()).collect::<Vec<_>>(), tokens("x = y! / 2"));
+        assert_eq!(tokens("x = y! / 2"), [
+            Identifier("x".into()), Assign, Identifier("y".into()), Exclamation, Div, NumericLiteral("2".into()), Eof,
+        ]);
+        assert_eq!(tokens("x = !/y/"), [
+            Identifier("x".into()), Assign, Exclamation, RegExpLiteral { body: "y".into(), flags: "".into() }, Eof,
+        ]);
+        assert_eq!(tokens("x = <a k={v}>t{1 / 2}<b/></a> / 2"), [
+            Identifier("x".into()), Assign,
+            Lt, XmlName("a".into()), XmlWhitespace, XmlName("k".into()), Assign, LeftBrace, Identifier("v".into()), RightBrace, Gt,
+            XmlText("t".into()), LeftBrace, NumericLiteral("1".into()), Div, NumericLiteral("2".into()), RightBrace,
+            Lt, XmlName("b".into()), XmlSlashGt,
+            XmlLtSlash, XmlName("a".into()), Gt,
+            Div, NumericLiteral("2".into()), Eof,
+        ]);
+        assert_eq!(tokens("x = <>{y}</>;"), [
+            Identifier("x".into()), Assign, Lt, Gt, LeftBrace, Identifier("y".into()), RightBrace, XmlLtSlash, Gt, Semicolon, Eof,
+        ]);
+    }
+
+    #[test]
+    fn lexing_comments() {
+        let compilation_unit = CompilationUnit::new(None, "a; // c1\n/* c2 */ /r/".into(), &CompilerOptions::new());
+        let list = Lexer::tokenize(&compilation_unit);
+        let tokens = list.tokens();
+        assert_eq!(tokens[1].trailing_trivia.iter().map(|trivia| trivia.kind).collect::<Vec<_>>(), [TriviaKind::Whitespace, TriviaKind::SingleLineComment, TriviaKind::LineTerminator]);
+        assert_eq!(tokens[2].leading_trivia.iter().map(|trivia| trivia.text()).collect::<Vec<_>>(), ["/* c2 */", " "]);
+        assert_eq!(tokens[2].token, Token::RegExpLiteral { body: "r".into(), flags: "".into() });
+        assert_eq!(compilation_unit.comments().len(), 2);
+    }
+}
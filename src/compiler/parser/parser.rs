@@ -8,6 +8,8 @@ pub struct Parser<'input> {
     token: (Token, Location),
     locations: Vec<Location>,
     activations: Vec<ParsingActivation>,
+    /// Scanned tokens, recorded for building a `SyntaxTokenList`.
+    recorded_tokens: Option<Vec<(Token, Location)>>,
 }
 
 impl<'input> Parser<'input> {
@@ -15,10 +17,25 @@ impl<'input> Parser<'input> {
     pub fn new(compilation_unit: &'input Rc<CompilationUnit>) -> Self {
         Self {
             tokenizer: Tokenizer::new(compilation_unit),
-            previous_token: (Token::Eof, Location::with_offset(&compilation_unit, 0)),
-            token: (Token::Eof, Location::with_offset(&compilation_unit, 0)),
+            previous_token: (Token::Eof, Location::with_offset(compilation_unit, 0)),
+            token: (Token::Eof, Location::with_offset(compilation_unit, 0)),
             locations: vec![],
             activations: vec![],
+            recorded_tokens: None,
+        }
+    }
+
+    /// Constructs a parser that parses a region of an already parsed
+    /// compilation unit, starting at `offset`. `text` is the current source text
+    /// of the compilation unit.
+    pub(crate) fn new_at(compilation_unit: &Rc<CompilationUnit>, text: &'input str, offset: usize) -> Self {
+        Self {
+            tokenizer: Tokenizer::new_at(compilation_unit, text, offset),
+            previous_token: (Token::Eof, Location::with_offset(compilation_unit, offset)),
+            token: (Token::Eof, Location::with_offset(compilation_unit, offset)),
+            locations: vec![],
+            activations: vec![],
+            recorded_tokens: None,
         }
     }
 
@@ -26,10 +43,14 @@ impl<'input> Parser<'input> {
         self.tokenizer.compilation_unit()
     }
 
-    fn token_location(&self) -> Location {
+    pub(crate) fn token_location(&self) -> Location {
         self.token.1.clone()
     }
 
+    pub(crate) fn previous_token_location(&self) -> Location {
+        self.previous_token.1.clone()
+    }
+
     fn mark_location(&mut self) {
         self.locations.push(self.token.1.clone());
     }
@@ -43,7 +64,7 @@ impl<'input> Parser<'input> {
     }
 
     fn pop_location(&mut self) -> Location {
-        self.locations.pop().unwrap().combine_with_start_of(self.previous_token.1.clone())
+        self.locations.pop().unwrap().combine_with(self.previous_token.1.clone())
     }
 
     fn add_syntax_error(&self, location: &Location, kind: DiagnosticKind, arguments: Vec<DiagnosticArgument>) {
@@ -56,21 +77,37 @@ impl<'input> Parser<'input> {
     }
     */
 
-    fn next(&mut self) -> Result<(), ParsingFailure> {
+    pub(crate) fn next(&mut self) -> Result<(), ParsingFailure> {
         self.previous_token = self.token.clone();
         self.token = self.tokenizer.scan_ie_div()?;
+        self.record_token();
         Ok(())
     }
 
+    /// Records the current token if tokens are being recorded. A token
+    /// scanned again from an earlier position, such as a regular expression
+    /// initially scanned as a division operator, replaces the tokens it overlaps.
+    fn record_token(&mut self) {
+        if let Some(tokens) = self.recorded_tokens.as_mut() {
+            let first_offset = self.token.1.first_offset();
+            while tokens.last().is_some_and(|(_, location)| location.first_offset() >= first_offset) {
+                tokens.pop();
+            }
+            tokens.push(self.token.clone());
+        }
+    }
+
     fn next_ie_xml_tag(&mut self) -> Result<(), ParsingFailure> {
         self.previous_token = self.token.clone();
         self.token = self.tokenizer.scan_ie_xml_tag()?;
+        self.record_token();
         Ok(())
     }
 
     fn next_ie_xml_content(&mut self) -> Result<(), ParsingFailure> {
         self.previous_token = self.token.clone();
         self.token = self.tokenizer.scan_ie_xml_content()?;
+        self.record_token();
         Ok(())
     }
 
@@ -154,15 +191,22 @@ impl<'input> Parser<'input> {
         }
     }
 
+    /// Expects a token. If the facing token is different, a syntax error is
+    /// reported and tokens are skipped until either the expected token,
+    /// which is consumed, or a synchronization point is reached.
     fn expect(&mut self, token: Token) -> Result<(), ParsingFailure> {
         if self.token.0 != token {
             self.add_syntax_error(&self.token_location(), DiagnosticKind::Expected, diagnostic_arguments![Token(token.clone()), Token(self.token.0.clone())]);
             let expecting_identifier_name = token.is_identifier_name();
-            while self.token.0 != Token::Eof && (if expecting_identifier_name { self.token.0.is_identifier_name() } else { true }) {
-                self.next()?;
+            loop {
                 if self.token.0 == token {
-                    return Ok(());
+                    self.next()?;
+                    break;
+                }
+                if self.at_synchronization_point() || (expecting_identifier_name && !self.token.0.is_identifier_name()) {
+                    break;
                 }
+                self.next()?;
             }
             Ok(())
         } else {
@@ -171,6 +215,52 @@ impl<'input> Parser<'input> {
         }
     }
 
+    /// Indicates whether the facing token is a statement, block or definition
+    /// boundary, at which syntax error recovery stops skipping tokens.
+    fn at_synchronization_point(&self) -> bool {
+        match self.token.0 {
+            Token::Eof | Token::Semicolon | Token::LeftBrace | Token::RightBrace => true,
+            _ => self.previous_token.1.line_break(&self.token.1) && self.peek_directive_start(),
+        }
+    }
+
+    /// Indicates whether the facing token may only start a directive.
+    fn peek_directive_start(&self) -> bool {
+        if self.peek_annotatable_directive_identifier_name() {
+            return true;
+        }
+        matches!(self.token.0,
+            Token::Package | Token::Import | Token::If | Token::Switch | Token::Do |
+            Token::While | Token::For | Token::With | Token::Break | Token::Continue |
+            Token::Return | Token::Throw | Token::Try)
+    }
+
+    /// Reports a missing semicolon between two directives. Unlike `expect()`,
+    /// no tokens are skipped, so that the next directive is parsed
+    /// from the facing token.
+    fn expect_directive_separator(&mut self) {
+        self.add_syntax_error(&self.token_location(), DiagnosticKind::Expected, diagnostic_arguments![Token(Token::Semicolon), Token(self.token.0.clone())]);
+    }
+
+    /// Parses a directive within a list of directives. If the directive
+    /// consumes no tokens, tokens are skipped until the next synchronization
+    /// point, resulting in an invalidated directive.
+    fn parse_directive_in_list(&mut self, context: ParsingDirectiveContext) -> Result<(Rc<Directive>, bool), ParsingFailure> {
+        let start_offset = self.token.1.first_offset;
+        let (directive, semicolon_inserted) = self.parse_directive(context)?;
+        if self.token.1.first_offset != start_offset || self.peek(Token::Eof) {
+            return Ok((directive, semicolon_inserted));
+        }
+        self.mark_location();
+        self.next()?;
+        while !self.at_synchronization_point() {
+            self.next()?;
+        }
+        self.consume(Token::Semicolon)?;
+        let location = self.pop_location();
+        Ok((self.create_invalidated_directive(&location), true))
+    }
+
     fn expect_and_ie_xml_tag(&mut self, token: Token) -> Result<(), ParsingFailure> {
         if self.token.0 != token {
             self.add_syntax_error(&self.token_location(), DiagnosticKind::Expected, diagnostic_arguments![Token(token.clone()), Token(self.token.0.clone())]);
@@ -511,7 +601,7 @@ impl<'input> Parser<'input> {
         // a logical AND, XOR or OR operation.
         if operator == Operator::NullCoalescing {
             if let Expression::Unary(UnaryExpression { expression, operator, .. }) = base.as_ref() {
-                if [Operator::LogicalAnd, Operator::LogicalXor, Operator::LogicalOr].contains(&operator) {
+                if [Operator::LogicalAnd, Operator::LogicalXor, Operator::LogicalOr].contains(operator) {
                     self.add_syntax_error(&expression.location(), DiagnosticKind::IllegalNullishCoalescingLeftOperand, vec![]);
                 }
             }
@@ -716,7 +806,6 @@ impl<'input> Parser<'input> {
             }));
             if self.peek(Token::ColonColon) {
                 self.push_location(&id_location.clone());
-                self.duplicate_location();
                 let ql = self.pop_location();
                 let id = self.finish_qualified_identifier(false, ql, id)?;
                 Ok(Some(Rc::new(Expression::QualifiedIdentifier(id))))
@@ -766,6 +855,7 @@ impl<'input> Parser<'input> {
         } else if self.peek(Token::Div) || self.peek(Token::DivideAssign) {
             self.mark_location();
             self.token = self.tokenizer.scan_regexp_literal(self.token.1.clone(), if self.peek(Token::DivideAssign) { "=".into() } else { "".into() })?;
+            self.record_token();
             let Token::RegExpLiteral { ref body, ref flags } = self.token.0.clone() else {
                 panic!();
             };
@@ -781,7 +871,7 @@ impl<'input> Parser<'input> {
             Ok(Some(Rc::new(Expression::QualifiedIdentifier(id))))
         // Parentheses
         } else if self.peek(Token::LeftParen) {
-            return Ok(Some(self.parse_paren_list_expr_or_qual_id()?));
+            Ok(Some(self.parse_paren_list_expr_or_qual_id()?))
         // `*`
         } else if self.peek(Token::Times) {
             let id_location = self.token_location();
@@ -794,7 +884,6 @@ impl<'input> Parser<'input> {
             }));
             if self.peek(Token::ColonColon) {
                 self.push_location(&id_location.clone());
-                self.duplicate_location();
                 let ql = self.pop_location();
                 let id = self.finish_qualified_identifier(false, ql, id)?;
                 Ok(Some(Rc::new(Expression::QualifiedIdentifier(id))))
@@ -805,6 +894,7 @@ impl<'input> Parser<'input> {
         } else if self.peek(Token::Lt) {
             if let Some(token) = self.tokenizer.scan_xml_markup(self.token_location())? {
                 self.token = token;
+                self.record_token();
             }
             let start = self.token_location();
             if let Token::XmlMarkup(content) = &self.token.0.clone() {
@@ -957,7 +1047,7 @@ impl<'input> Parser<'input> {
                 allow_in,
                 min_precedence: OperatorPrecedence::AssignmentAndOther,
                 ..default()
-            })?.map(|e| FunctionBody::Expression(e))
+            })?.map(FunctionBody::Expression)
         };
 
         // Body is required by function expressions
@@ -981,6 +1071,15 @@ impl<'input> Parser<'input> {
         }))
     }
 
+    /// Parses the block body of a function outside of its definition, returning
+    /// the block and whether it uses `await` and `yield`.
+    pub(crate) fn parse_function_body(&mut self, block_context: ParsingDirectiveContext) -> Result<(Rc<Block>, bool, bool), ParsingFailure> {
+        self.activations.push(ParsingActivation::new());
+        let block = Rc::new(self.parse_block(block_context)?);
+        let activation = self.activations.pop().unwrap();
+        Ok((block, activation.uses_await, activation.uses_yield))
+    }
+
     fn parse_parameter(&mut self) -> Result<Rc<Parameter>, ParsingFailure> {
         self.mark_location();
         let rest = self.consume(Token::Ellipsis)?;
@@ -1190,7 +1289,7 @@ impl<'input> Parser<'input> {
 
             // EmbedExpression
             if self.peek(Token::LeftBrace) && id == "embed" && self.previous_token.1.character_count() == "embed".len() {
-                return Ok(self.finish_embed_expression(id_location)?);
+                return self.finish_embed_expression(id_location);
             }
 
             let id = Rc::new(Expression::QualifiedIdentifier(QualifiedIdentifier {
@@ -1201,7 +1300,6 @@ impl<'input> Parser<'input> {
             }));
             if self.peek(Token::ColonColon) {
                 self.push_location(&id_location.clone());
-                self.duplicate_location();
                 let ql = self.pop_location();
                 let id = self.finish_qualified_identifier(false, ql, id)?;
                 Ok(Rc::new(Expression::QualifiedIdentifier(id)))
@@ -1251,6 +1349,7 @@ impl<'input> Parser<'input> {
         } else if self.peek(Token::Div) || self.peek(Token::DivideAssign) {
             self.mark_location();
             self.token = self.tokenizer.scan_regexp_literal(self.token.1.clone(), if self.peek(Token::DivideAssign) { "=".into() } else { "".into() })?;
+            self.record_token();
             let Token::RegExpLiteral { ref body, ref flags } = self.token.0.clone() else {
                 panic!();
             };
@@ -1266,7 +1365,7 @@ impl<'input> Parser<'input> {
             Ok(Rc::new(Expression::QualifiedIdentifier(id)))
         // Parentheses
         } else if self.peek(Token::LeftParen) {
-            return Ok(self.parse_paren_list_expr_or_qual_id()?);
+            self.parse_paren_list_expr_or_qual_id()
         // `*`
         } else if self.peek(Token::Times) {
             let id_location = self.token_location();
@@ -1279,7 +1378,6 @@ impl<'input> Parser<'input> {
             }));
             if self.peek(Token::ColonColon) {
                 self.push_location(&id_location.clone());
-                self.duplicate_location();
                 let ql = self.pop_location();
                 let id = self.finish_qualified_identifier(false, ql, id)?;
                 Ok(Rc::new(Expression::QualifiedIdentifier(id)))
@@ -1290,6 +1388,7 @@ impl<'input> Parser<'input> {
         } else if self.peek(Token::Lt) {
             if let Some(token) = self.tokenizer.scan_xml_markup(self.token_location())? {
                 self.token = token;
+                self.record_token();
             }
             let start = self.token_location();
             if let Token::XmlMarkup(content) = &self.token.0.clone() {
@@ -1319,10 +1418,10 @@ impl<'input> Parser<'input> {
         let Expression::ObjectInitializer(descriptor) = descriptor.as_ref() else {
             panic!();
         };
-        return Ok(Rc::new(Expression::Embed(EmbedExpression {
+        Ok(Rc::new(Expression::Embed(EmbedExpression {
             location: self.pop_location(),
             description: descriptor.clone(),
-        })));
+        })))
     }
 
     fn parse_array_initializer(&mut self) -> Result<Rc<Expression>, ParsingFailure> {
@@ -1381,10 +1480,10 @@ impl<'input> Parser<'input> {
 
         self.push_location(&start);
         let element = Rc::new(self.parse_xml_element(start, true)?);
-        return Ok(Rc::new(Expression::Xml(XmlExpression {
+        Ok(Rc::new(Expression::Xml(XmlExpression {
             location: self.pop_location(),
             element,
-        })));
+        })))
     }
 
     /// Parses XMLElement starting from its XMLTagContent.
@@ -1406,14 +1505,13 @@ impl<'input> Parser<'input> {
                 self.consume_and_ie_xml_tag(Token::XmlWhitespace)?;
                 self.expect_and_ie_xml_tag(Token::Assign)?;
                 self.consume_and_ie_xml_tag(Token::XmlWhitespace)?;
-                let value: XmlAttributeValue;
-                if self.consume(Token::LeftBrace)? {
+                let value = if self.consume(Token::LeftBrace)? {
                     let expr = self.parse_expression(ParsingExpressionContext { allow_in: true, min_precedence: OperatorPrecedence::AssignmentAndOther, ..default() })?;
                     self.expect_and_ie_xml_tag(Token::RightBrace)?;
-                    value = XmlAttributeValue::Expression(expr);
+                    XmlAttributeValue::Expression(expr)
                 } else {
-                    value = XmlAttributeValue::Value(self.parse_xml_attribute_value()?);
-                }
+                    XmlAttributeValue::Value(self.parse_xml_attribute_value()?)
+                };
                 attributes.push(Rc::new(XmlAttribute {
                     location: self.pop_location(),
                     name, value
@@ -1426,13 +1524,11 @@ impl<'input> Parser<'input> {
         let mut content: Option<Vec<Rc<XmlElementContent>>> = None;
         let mut closing_name: Option<XmlTagName> = None;
 
-        let is_empty;
-
-        if ends_at_ie_div {
-            is_empty = self.consume(Token::XmlSlashGt)?;
+        let is_empty = if ends_at_ie_div {
+            self.consume(Token::XmlSlashGt)?
         } else {
-            is_empty = self.consume_and_ie_xml_content(Token::XmlSlashGt)?;
-        }
+            self.consume_and_ie_xml_content(Token::XmlSlashGt)?
+        };
 
         if !is_empty {
             self.expect_and_ie_xml_content(Token::Gt)?;
@@ -1461,7 +1557,7 @@ impl<'input> Parser<'input> {
         if let Token::XmlAttributeValue(value) = self.token.0.clone() {
             let location = self.token_location();
             self.next_ie_xml_tag()?;
-            return Ok((value, location));
+            Ok((value, location))
         } else {
             self.add_syntax_error(&self.token_location(), DiagnosticKind::ExpectedXmlAttributeValue, diagnostic_arguments![Token(self.token.0.clone())]);
             Ok(("".into(), self.tokenizer.cursor_location()))
@@ -1486,7 +1582,7 @@ impl<'input> Parser<'input> {
         if let Token::XmlName(name) = self.token.0.clone() {
             let name_location = self.token_location();
             self.next_ie_xml_tag()?;
-            return Ok((name, name_location));
+            Ok((name, name_location))
         } else {
             self.add_syntax_error(&self.token_location(), DiagnosticKind::ExpectedXmlName, diagnostic_arguments![Token(self.token.0.clone())]);
             Ok(("".into(), self.tokenizer.cursor_location()))
@@ -1534,10 +1630,10 @@ impl<'input> Parser<'input> {
             return Ok(Rc::new(Expression::QualifiedIdentifier(id)));
         }
         self.push_location(&start);
-        return Ok(Rc::new(Expression::Paren(ParenExpression {
+        Ok(Rc::new(Expression::Paren(ParenExpression {
             location: self.pop_location(),
             expression: left,
-        })));
+        })))
     }
 
     /// Parses either a ParenListExpression, (), or a QualifiedIdentifier
@@ -1804,7 +1900,9 @@ impl<'input> Parser<'input> {
         // Parenthesized
         if self.peek(Token::LeftParen) {
             self.mark_location();
+            self.next()?;
             let expression = self.parse_type_expression()?;
+            self.expect(Token::RightParen)?;
             Ok((Rc::new(Expression::Paren(ParenExpression {
                 location: self.pop_location(),
                 expression,
@@ -1850,9 +1948,9 @@ impl<'input> Parser<'input> {
         } else if self.peek(Token::Times) {
             let location = self.token_location();
             self.next()?;
-            return Ok((Rc::new(Expression::AnyType(AnyTypeExpression {
+            Ok((Rc::new(Expression::AnyType(AnyTypeExpression {
                 location,
-            })), wrap_nullable));
+            })), wrap_nullable))
         // Identifier
         } else {
             let id = self.expect_identifier(false)?;
@@ -1986,9 +2084,17 @@ impl<'input> Parser<'input> {
                 })), semicolon_inserted))
             } else {
                 // SuperStatement
+                let arguments = match arguments {
+                    Some(arguments) => arguments,
+                    None => {
+                        self.expect(Token::LeftParen)?;
+                        semicolon_inserted = self.parse_semicolon()?;
+                        vec![]
+                    },
+                };
                 let node = Rc::new(Directive::SuperStatement(SuperStatement {
                     location: self.pop_location(),
-                    arguments: arguments.unwrap(),
+                    arguments,
                 }));
 
                 // Check whether super statement is allowed here
@@ -2106,7 +2212,6 @@ impl<'input> Parser<'input> {
             }));
             if self.peek(Token::ColonColon) {
                 self.push_location(&id_location.clone());
-                self.duplicate_location();
                 let ql = self.pop_location();
                 let id = self.finish_qualified_identifier(false, ql, id)?;
                 exp = Rc::new(Expression::QualifiedIdentifier(id));
@@ -2136,9 +2241,9 @@ impl<'input> Parser<'input> {
         let mut semicolon_inserted = false;
         while !self.peek(Token::RightBrace) && !self.peek(Token::Eof) {
             if !directives.is_empty() && !semicolon_inserted {
-                self.expect(Token::Semicolon)?;
+                self.expect_directive_separator();
             }
-            let (directive, semicolon_inserted_1) = self.parse_directive(context.clone())?;
+            let (directive, semicolon_inserted_1) = self.parse_directive_in_list(context.clone())?;
             directives.push(directive);
             semicolon_inserted = semicolon_inserted_1;
         }
@@ -2165,7 +2270,7 @@ impl<'input> Parser<'input> {
         let mut alternative = None;
         if self.peek(Token::Else) {
             if !semicolon_inserted_1 {
-                self.expect(Token::Semicolon)?;
+                self.expect_directive_separator();
             }
             self.next()?;
             let (alternative_2, semicolon_inserted_2) = self.parse_substatement(context.clone())?;
@@ -2209,7 +2314,7 @@ impl<'input> Parser<'input> {
         let mut semicolon_inserted = false;
         while !self.peek(Token::RightBrace) {
             if !cases.is_empty() && !semicolon_inserted {
-                self.expect(Token::Semicolon)?;
+                self.expect_directive_separator();
             }
             if !(self.peek(Token::Case) || self.peek(Token::Default)) {
                 break;
@@ -2238,11 +2343,11 @@ impl<'input> Parser<'input> {
             }
             let mut directives = vec![];
             semicolon_inserted = false;
-            while !(self.peek(Token::RightBrace) || self.peek(Token::Case) || self.peek(Token::Default)) {
+            while !(self.peek(Token::RightBrace) || self.peek(Token::Case) || self.peek(Token::Default) || self.peek(Token::Eof)) {
                 if !directives.is_empty() && !semicolon_inserted {
-                    self.expect(Token::Semicolon)?;
+                    self.expect_directive_separator();
                 }
-                let (directive, semicolon_inserted_1) = self.parse_directive(context.clone())?;
+                let (directive, semicolon_inserted_1) = self.parse_directive_in_list(context.clone())?;
                 directives.push(directive);
                 semicolon_inserted = semicolon_inserted_1;
             }
@@ -2312,7 +2417,7 @@ impl<'input> Parser<'input> {
         // Body
         let (body, semicolon_inserted_1) = self.parse_substatement(context)?;
         if !semicolon_inserted_1 {
-            self.expect(Token::Semicolon)?;
+            self.expect_directive_separator();
         }
 
         self.expect(Token::While)?;
@@ -2375,8 +2480,10 @@ impl<'input> Parser<'input> {
             None
         };
 
-        if init_variable.is_some() && self.consume(Token::In)? {
-            return self.parse_for_in_statement_with_left_variable(context, init_variable.unwrap());
+        if let Some(variable) = &init_variable {
+            if self.consume(Token::In)? {
+                return self.parse_for_in_statement_with_left_variable(context, variable.clone());
+            }
         }
 
         let mut init_exp = if init_variable.is_none() && !self.peek(Token::Semicolon) {
@@ -2389,8 +2496,10 @@ impl<'input> Parser<'input> {
             None
         };
 
-        if init_exp.is_some() && self.consume(Token::In)? {
-            return self.parse_for_in_statement_with_left_exp(context, init_exp.unwrap());
+        if let Some(exp) = &init_exp {
+            if self.consume(Token::In)? {
+                return self.parse_for_in_statement_with_left_exp(context, exp.clone());
+            }
         }
 
         if init_exp.is_none() && init_variable.is_none() && !self.peek(Token::Semicolon) {
@@ -2405,11 +2514,7 @@ impl<'input> Parser<'input> {
 
         let init = if let Some(exp) = init_exp.as_ref() {
             Some(ForInitializer::Expression(exp.clone()))
-        } else if let Some(variable) = init_variable.as_ref() {
-            Some(ForInitializer::VariableDefinition(Rc::new(variable.clone())))
-        } else {
-            None
-        };
+        } else { init_variable.as_ref().map(|variable| ForInitializer::VariableDefinition(Rc::new(variable.clone()))) };
 
         self.expect(Token::Semicolon)?;
         let test = if self.peek(Token::Semicolon) {
@@ -2515,14 +2620,13 @@ impl<'input> Parser<'input> {
 
     fn parse_simple_variable_definition(&mut self, allow_in: bool) -> Result<SimpleVariableDefinition, ParsingFailure> {
         self.mark_location();
-        let kind: VariableDefinitionKind;
         let kind_location = self.token_location();
-        if self.consume(Token::Const)? {
-            kind = VariableDefinitionKind::Const;
+        let kind = if self.consume(Token::Const)? {
+            VariableDefinitionKind::Const
         } else {
             self.expect(Token::Var)?;
-            kind = VariableDefinitionKind::Var;
-        }
+            VariableDefinitionKind::Var
+        };
         let mut bindings = vec![Rc::new(self.parse_variable_binding(allow_in)?)];
         while self.consume(Token::Comma)? {
             bindings.push(Rc::new(self.parse_variable_binding(allow_in)?));
@@ -2711,7 +2815,6 @@ impl<'input> Parser<'input> {
             allow_in: true,
             allow_assignment: false,
             min_precedence: OperatorPrecedence::AssignmentAndOther,
-            ..default()
         })?;
 
         let semicolon_inserted = self.parse_semicolon()?;
@@ -2747,17 +2850,26 @@ impl<'input> Parser<'input> {
                         directive_context_keyword: Some(id.clone()),
                     };
                     // self.parse_attribute_identifier_names(&mut context)?;
-                } else {
+                } else if let Some(attribute) = self.keyword_attribute_from_previous_token() {
                     context1 = AnnotatableContext {
                         start_location: id.1.clone(),
                         jetdoc,
-                        attributes: vec![self.keyword_attribute_from_previous_token().unwrap()],
+                        attributes: vec![attribute],
                         context: context.clone(),
                         directive_context_keyword: None,
                     };
                     self.parse_attribute_identifier_names(&mut context1)?;
+                } else if self.previous_token.1.line_break(&self.token.1) {
+                    // An expression statement followed by a definition
+                    return self.parse_statement_starting_with_identifier(context, id);
+                } else {
+                    // An identifier that is not an attribute, such as `x` in
+                    // `x function f() {}`, is invalidated, and the definition
+                    // that follows is parsed as the next directive.
+                    self.add_syntax_error(&id.1, DiagnosticKind::UnexpectedOrInvalidToken, diagnostic_arguments![]);
+                    return Ok((self.create_invalidated_directive(&id.1), true));
                 }
-                return self.parse_annotatable_directive(context1);
+                self.parse_annotatable_directive(context1)
             } else if self.peek(Token::LeftBrace) && &id.0 == "configuration" && id.1.character_count() == "configuration".len() {
                 self.parse_configuration_directive(context, id.1)
             } else {
@@ -2797,7 +2909,10 @@ impl<'input> Parser<'input> {
                             breakable: true,
                             iteration: false,
                         });
-                        let block = self.parse_block_with_metadata(context, Some(metadata))?;
+                        // The block is located from its meta-data.
+                        let start_location = self.pop_location();
+                        let mut block = self.parse_block_with_metadata(context, Some(metadata))?;
+                        block.location = start_location.combine_with(block.location);
                         return Ok((Rc::new(Directive::Block(block)), true));
                     },
                     Ok(None) => {},
@@ -2822,7 +2937,7 @@ impl<'input> Parser<'input> {
                 directive_context_keyword: None,
             };
             self.parse_attribute_identifier_names(&mut context)?;
-            return self.parse_annotatable_directive(context);
+            self.parse_annotatable_directive(context)
         } else if self.peek(Token::Use) {
             self.parse_use_package_directive(context)
         } else {
@@ -2830,17 +2945,40 @@ impl<'input> Parser<'input> {
         }
     }
 
-    fn parse_directives(&mut self, context: ParsingDirectiveContext) -> Result<Vec<Rc<Directive>>, ParsingFailure> {
+    pub(crate) fn parse_directives(&mut self, context: ParsingDirectiveContext) -> Result<Vec<Rc<Directive>>, ParsingFailure> {
         let mut directives = vec![];
         let mut semicolon = false;
         while !self.peek(Token::Eof) {
             if !directives.is_empty() && !semicolon {
-                self.expect(Token::Semicolon)?;
+                self.expect_directive_separator();
+            }
+            let (directive, semicolon_1) = self.parse_directive_in_list(context.clone())?;
+            directives.push(directive);
+            semicolon = semicolon_1;
+        }
+        Ok(directives)
+    }
+
+    /// Parses directives up to the token at `end_offset`, as when reparsing
+    /// a region of a directive list. `followed_by_directive` indicates whether
+    /// a directive follows the region in the list.
+    pub(crate) fn parse_directives_before(&mut self, context: ParsingDirectiveContext, end_offset: usize, followed_by_directive: bool) -> Result<Vec<Rc<Directive>>, ParsingFailure> {
+        let mut directives = vec![];
+        let mut semicolon = false;
+        while !self.peek(Token::Eof) && self.token.1.first_offset() < end_offset {
+            if !directives.is_empty() && !semicolon {
+                self.expect_directive_separator();
             }
-            let (directive, semicolon_1) = self.parse_directive(context.clone())?;
+            let (directive, semicolon_1) = self.parse_directive_in_list(context.clone())?;
             directives.push(directive);
             semicolon = semicolon_1;
         }
+        if self.token.1.first_offset() != end_offset {
+            return Err(ParsingFailure);
+        }
+        if followed_by_directive && !directives.is_empty() && !semicolon {
+            self.expect_directive_separator();
+        }
         Ok(directives)
     }
 
@@ -2910,7 +3048,7 @@ impl<'input> Parser<'input> {
     fn refine_metadata_entries(&self, list: &Vec<Rc<Expression>>) -> Result<Vec<Rc<UnprocessedMetadataEntry>>, MetadataRefineError> {
         let mut r = Vec::<Rc<UnprocessedMetadataEntry>>::new();
         for entry in list {
-            r.push(self.refine_metadata_entry(&entry)?);
+            r.push(self.refine_metadata_entry(entry)?);
         }
         Ok(r)
     }
@@ -2946,7 +3084,7 @@ impl<'input> Parser<'input> {
     fn refine_metadata_value(&self, exp: &Rc<Expression>) -> Result<UnprocessedMetadataValue, MetadataRefineError> {
         match exp.as_ref() {
             Expression::QualifiedIdentifier(_) => {
-                let name = self.refine_metadata_name(&exp)?;
+                let name = self.refine_metadata_name(exp)?;
                 Ok(UnprocessedMetadataValue::IdentifierString(name))
             },
             Expression::StringLiteral(StringLiteral { value, .. }) => Ok(UnprocessedMetadataValue::String((value.clone(), exp.location()))),
@@ -2979,7 +3117,7 @@ impl<'input> Parser<'input> {
         }
     }
 
-    fn refine_metadata_file_value(&self, list: &Vec<Rc<Expression>>, location: &Location) -> Result<UnprocessedMetadataValue, MetadataRefineError> {
+    fn refine_metadata_file_value(&self, list: &[Rc<Expression>], location: &Location) -> Result<UnprocessedMetadataValue, MetadataRefineError> {
         if list.len() != 1 {
             return Err(MetadataRefineError::Syntax);
         }
@@ -3123,7 +3261,7 @@ impl<'input> Parser<'input> {
         let semicolon = self.parse_semicolon()?;
         let location = self.pop_location();
 
-        if !(matches!(context, ParsingDirectiveContext::PackageBlock { .. })) {
+        if !(matches!(context, ParsingDirectiveContext::PackageBlock)) {
             self.add_syntax_error(&location, DiagnosticKind::NotAllowedHere, diagnostic_arguments![String("'use'".into()), Token(self.token.0.clone())]);
         }
 
@@ -3182,7 +3320,7 @@ impl<'input> Parser<'input> {
                 Attribute::Private(_) |
                 Attribute::Protected(_) |
                 Attribute::Internal(_) => {
-                    self.verify_visibility(&a, &context);
+                    self.verify_visibility(a, &context);
                 },
                 _ => {
                     // Unallowed attribute
@@ -3276,11 +3414,10 @@ impl<'input> Parser<'input> {
         }
 
         // Interface methods must not contain any annotations except for meta-data.
-        if !attributes.is_empty() && interface_method {
-            if !attributes.last().unwrap().is_metadata() {
+        if !attributes.is_empty() && interface_method
+            && !attributes.last().unwrap().is_metadata() {
                 self.add_syntax_error(&name.location(), DiagnosticKind::FunctionMustNotContainAnnotations, diagnostic_arguments![]);
             }
-        }
 
         for a in &attributes {
             if a.is_metadata() {
@@ -3318,7 +3455,7 @@ impl<'input> Parser<'input> {
                         // Unallowed visibility in proxy function
                         self.add_syntax_error(&a.location(), DiagnosticKind::UnallowedAttribute, diagnostic_arguments![]);
                     } else {
-                        self.verify_visibility(&a, &context);
+                        self.verify_visibility(a, &context);
                     }
                 },
                 _ => {
@@ -3328,20 +3465,18 @@ impl<'input> Parser<'input> {
             }
         }
 
-        let node: Rc<Directive>;
-
-        if invalidated {
+        let node = if invalidated {
             let loc = &self.pop_location();
-            node = self.create_invalidated_directive(loc);
+            self.create_invalidated_directive(loc)
         } else {
-            node = Rc::new(Directive::FunctionDefinition(FunctionDefinition {
+            Rc::new(Directive::FunctionDefinition(FunctionDefinition {
                 location: self.pop_location(),
                 jetdoc,
                 attributes,
                 name: name.clone(),
                 common,
-            }));
-        }
+            }))
+        };
 
         Ok((node, semicolon))
     }
@@ -3376,7 +3511,7 @@ impl<'input> Parser<'input> {
                 Attribute::Private(_) |
                 Attribute::Protected(_) |
                 Attribute::Internal(_) => {
-                    self.verify_visibility(&a, &context);
+                    self.verify_visibility(a, &context);
                 },
                 _ => {
                     // Unallowed attribute
@@ -3433,7 +3568,7 @@ impl<'input> Parser<'input> {
                 Attribute::Private(_) |
                 Attribute::Protected(_) |
                 Attribute::Internal(_) => {
-                    self.verify_visibility(&a, &context);
+                    self.verify_visibility(a, &context);
                 },
                 _ => {
                     // Unallowed attribute
@@ -3496,7 +3631,7 @@ impl<'input> Parser<'input> {
                 Attribute::Private(_) |
                 Attribute::Protected(_) |
                 Attribute::Internal(_) => {
-                    self.verify_visibility(&a, &context);
+                    self.verify_visibility(a, &context);
                 },
                 _ => {
                     // Unallowed attribute
@@ -3539,7 +3674,7 @@ impl<'input> Parser<'input> {
                 Attribute::Private(_) |
                 Attribute::Protected(_) |
                 Attribute::Internal(_) => {
-                    self.verify_visibility(&a, &context);
+                    self.verify_visibility(a, &context);
                 },
                 _ => {
                     // Unallowed attribute
@@ -3609,9 +3744,19 @@ impl<'input> Parser<'input> {
     fn parse_type_parameter(&mut self) -> Result<Rc<TypeParameter>, ParsingFailure> {
         self.mark_location();
         let name = self.expect_identifier(false)?;
+        let mut extends_clause: Option<Rc<Expression>> = None;
+        if self.consume(Token::Extends)? {
+            extends_clause = Some(self.parse_type_expression()?);
+        }
+        let mut implements_clause: Option<Vec<Rc<Expression>>> = None;
+        if self.consume(Token::Implements)? {
+            implements_clause = Some(self.parse_type_expression_list()?);
+        }
         Ok(Rc::new(TypeParameter {
             location: self.pop_location(),
             name,
+            extends_clause,
+            implements_clause,
         }))
     }
 
@@ -3680,7 +3825,7 @@ impl<'input> Parser<'input> {
             let mut id = id.clone();
             if self.consume(Token::ColonColon)? {
                 let (id_1, _) = self.expect_identifier(true)?;
-                id = id + &"::".to_owned() + &id_1;
+                id = id + "::" + &id_1;
             }
             let id_location = self.pop_location();
             let id = Rc::new(Expression::QualifiedIdentifier(QualifiedIdentifier {
@@ -3791,7 +3936,7 @@ impl<'input> Parser<'input> {
         }
         loop {
             if let Some(a) = self.peek_attribute() {
-                let last_attribute_is_identifier = context.attributes.last().map_or(false, |a| !a.is_metadata());
+                let last_attribute_is_identifier = context.attributes.last().is_some_and(|a| !a.is_metadata());
                 if last_attribute_is_identifier {
                     self.forbid_line_break_before_token();
                 }
@@ -3855,14 +4000,14 @@ impl<'input> Parser<'input> {
 
     pub fn parse_jetdoc(&mut self) -> Result<Option<Rc<JetDoc>>, ParsingFailure> {
         let comments = self.compilation_unit().comments.borrow();
-        let last_comment = comments.last().map(|last_comment| last_comment.clone());
+        let last_comment = comments.last().cloned();
         drop(comments);
         Ok(last_comment.and_then(|comment| {
             if comment.is_jetdoc(&self.token.1) {
                 self.compilation_unit().comments_mut().pop();
                 let location = comment.location();
                 let comment_prefix_length: usize = 3;
-                let location = Location::with_offsets(self.compilation_unit(), location.first_offset + comment_prefix_length, location.last_offset - 2);
+                let location = Location::with_offsets(self.compilation_unit(), location.first_offset() + comment_prefix_length, location.last_offset() - 2);
                 let content = &comment.content.borrow()[1..];
                 let (main_body, tags) = self.parse_jetdoc_content(&location, content);
                 Some(Rc::new(JetDoc {
@@ -3876,7 +4021,7 @@ impl<'input> Parser<'input> {
         }))
     }
 
-    fn parse_jetdoc_content(&mut self, location: &Location, content: &str) -> (Option<(String, Location)>, Vec<(JetDocTag, Location)>) {
+    fn parse_jetdoc_content(&mut self, location: &Location, content: &str) -> JetDocContent {
         let lines = self.split_jetdoc_lines(location, content);
 
         let mut main_body: Option<(String, Location)> = None;
@@ -4029,7 +4174,7 @@ impl<'input> Parser<'input> {
                     if let Some(exp) = ParserFacade::parse_expression(&compilation_unit_2) {
                         tags.push((JetDocTag::EventType(exp), location));
                     } else {
-                        self.add_syntax_error(&tag_location, DiagnosticKind::FailedParsingJetDocTag, diagnostic_arguments![String(tag_name.clone())]);
+                        self.add_syntax_error(tag_location, DiagnosticKind::FailedParsingJetDocTag, diagnostic_arguments![String(tag_name.clone())]);
                     }
                 },
 
@@ -4047,7 +4192,7 @@ impl<'input> Parser<'input> {
 
                     // Content must be non empty
                     if regex_is_match!(r"^\s*$", &path) {
-                        self.add_syntax_error(&tag_location, DiagnosticKind::FailedParsingJetDocTag, diagnostic_arguments![String(tag_name.clone())]);
+                        self.add_syntax_error(tag_location, DiagnosticKind::FailedParsingJetDocTag, diagnostic_arguments![String(tag_name.clone())]);
                     }
 
                     tags.push((JetDocTag::Image { path }, location));
@@ -4060,7 +4205,7 @@ impl<'input> Parser<'input> {
 
                     // Content must be non empty
                     if regex_is_match!(r"^\s*$", &text) {
-                        self.add_syntax_error(&tag_location, DiagnosticKind::FailedParsingJetDocTag, diagnostic_arguments![String(tag_name.clone())]);
+                        self.add_syntax_error(tag_location, DiagnosticKind::FailedParsingJetDocTag, diagnostic_arguments![String(tag_name.clone())]);
                     }
 
                     tags.push((JetDocTag::Internal(text), location));
@@ -4085,7 +4230,7 @@ impl<'input> Parser<'input> {
 
                     // Content must be empty
                     if !regex_is_match!(r"^\s*$", &text) {
-                        self.add_syntax_error(&tag_location, DiagnosticKind::FailedParsingJetDocTag, diagnostic_arguments![String(tag_name.clone())]);
+                        self.add_syntax_error(tag_location, DiagnosticKind::FailedParsingJetDocTag, diagnostic_arguments![String(tag_name.clone())]);
                     }
 
                     tags.push((JetDocTag::Private, location));
@@ -4111,7 +4256,7 @@ impl<'input> Parser<'input> {
                         reference = content;
                         display_text = None;
                     }
-                    if let Some(reference) = self.parse_jetdoc_reference(&reference, &tag_location, &tag_name) {
+                    if let Some(reference) = self.parse_jetdoc_reference(&reference, tag_location, tag_name) {
                         tags.push((JetDocTag::See { reference, display_text }, location));
                     }
                 },
@@ -4134,16 +4279,16 @@ impl<'input> Parser<'input> {
                         if let Some(exp) = ParserFacade::parse_type_expression(&compilation_unit_2) {
                             tags.push((JetDocTag::Throws { class_reference: exp, description }, location));
                         } else {
-                            self.add_syntax_error(&tag_location, DiagnosticKind::FailedParsingJetDocTag, diagnostic_arguments![String(tag_name.clone())]);
+                            self.add_syntax_error(tag_location, DiagnosticKind::FailedParsingJetDocTag, diagnostic_arguments![String(tag_name.clone())]);
                         }
                     } else {
-                        self.add_syntax_error(&tag_location, DiagnosticKind::FailedParsingJetDocTag, diagnostic_arguments![String(tag_name.clone())]);
+                        self.add_syntax_error(tag_location, DiagnosticKind::FailedParsingJetDocTag, diagnostic_arguments![String(tag_name.clone())]);
                     }
                 },
 
                 // Unrecognized tag
                 _ => {
-                    self.add_syntax_error(&tag_location, DiagnosticKind::UnrecognizedJetDocTag, diagnostic_arguments![String(tag_name.clone())]);
+                    self.add_syntax_error(tag_location, DiagnosticKind::UnrecognizedJetDocTag, diagnostic_arguments![String(tag_name.clone())]);
                 },
             }
         } else if !building_content.is_empty() {
@@ -4157,7 +4302,7 @@ impl<'input> Parser<'input> {
     fn parse_jetdoc_reference(&self, reference: &str, tag_location: &Location, tag_name: &str) -> Option<Rc<JetDocReference>> {
         let split: Vec<&str> = reference.split("#").collect();
         if split.len() > 2 {
-            self.add_syntax_error(&tag_location, DiagnosticKind::FailedParsingJetDocTag, diagnostic_arguments![String(tag_name.to_owned())]);
+            self.add_syntax_error(tag_location, DiagnosticKind::FailedParsingJetDocTag, diagnostic_arguments![String(tag_name.to_owned())]);
             return None;
         }
         let mut base: Option<Rc<Expression>> = None;
@@ -4169,20 +4314,23 @@ impl<'input> Parser<'input> {
             if let Some(exp) = ParserFacade::parse_expression(&compilation_unit_2) {
                 base = Some(exp);
             } else {
-                self.add_syntax_error(&tag_location, DiagnosticKind::FailedParsingJetDocTag, diagnostic_arguments![String(tag_name.to_owned())]);
+                self.add_syntax_error(tag_location, DiagnosticKind::FailedParsingJetDocTag, diagnostic_arguments![String(tag_name.to_owned())]);
                 return None;
             }
         }
 
         if base.is_none() && instance_property.is_none() {
-            self.add_syntax_error(&tag_location, DiagnosticKind::FailedParsingJetDocTag, diagnostic_arguments![String(tag_name.to_owned())]);
+            self.add_syntax_error(tag_location, DiagnosticKind::FailedParsingJetDocTag, diagnostic_arguments![String(tag_name.to_owned())]);
             return None;
         }
         Some(Rc::new(JetDocReference { base, instance_property, }))
     }
 }
 
-fn join_jetdoc_content(content: &Vec<(String, Location)>) -> (String, Location) {
+/// The main body and the tags of a JetDoc comment.
+type JetDocContent = (Option<(String, Location)>, Vec<(JetDocTag, Location)>);
+
+fn join_jetdoc_content(content: &[(String, Location)]) -> (String, Location) {
     // Ignore first empty lines
     let mut i = 0usize;
     for content1 in content.iter() {
@@ -4261,6 +4409,9 @@ pub struct ParserFacade;
 
 impl ParserFacade {
     /// Parses `Program` until end-of-file.
+    ///
+    /// Every syntax error is reported to the compilation unit, and the resulting
+    /// program contains invalidated nodes where syntax errors occurred.
     pub fn parse_program(compilation_unit: &Rc<CompilationUnit>) -> Option<Rc<Program>> {
         let mut parser = Parser::new(compilation_unit);
         if parser.next().is_ok() {
@@ -4272,6 +4423,32 @@ impl ParserFacade {
         }
     }
 
+    /// Loads `Program` from a cache if the source text is unchanged since
+    /// it was cached, and otherwise parses it until end-of-file, caching
+    /// the result.
+    pub fn parse_program_with_cache(compilation_unit: &Rc<CompilationUnit>, cache: &AstCache) -> Option<Rc<Program>> {
+        if let Some(program) = cache.load(compilation_unit) {
+            return Some(program);
+        }
+        let program = Self::parse_program(compilation_unit)?;
+        // Failing to write the cache does not affect parsing.
+        let _ = cache.store(compilation_unit, &program);
+        Some(program)
+    }
+
+    /// Parses `Program` until end-of-file, additionally returning the
+    /// lossless list of tokens and trivia of the compilation unit.
+    pub fn parse_program_with_syntax_tokens(compilation_unit: &Rc<CompilationUnit>) -> Option<(Rc<Program>, SyntaxTokenList)> {
+        let mut parser = Parser::new(compilation_unit);
+        parser.recorded_tokens = Some(vec![]);
+        if parser.next().is_ok() {
+            let program = parser.parse_program().ok()?;
+            Some((program, SyntaxTokenList::new(compilation_unit, parser.recorded_tokens.take().unwrap())))
+        } else {
+            None
+        }
+    }
+
     /// Parses `ListExpression^allowIn` and expects end-of-file.
     pub fn parse_expression(compilation_unit: &Rc<CompilationUnit>) -> Option<Rc<Expression>> {
         let mut parser = Parser::new(compilation_unit);
@@ -4312,3 +4489,239 @@ impl ParserFacade {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+
+    fn parse(text: &str) -> (Rc<Program>, Vec<String>) {
+        let compilation_unit = CompilationUnit::new(None, text.into(), &CompilerOptions::new());
+        let program = ParserFacade::parse_program(&compilation_unit).unwrap();
+        compilation_unit.sort_diagnostics();
+        (program, compilation_unit.diagnostics().iter().map(|d| d.format_message_english()).collect())
+    }
+
+    #[test]
+    fn recovering_from_syntax_errors() {
+        let (program, diagnostics) = parse(r#"
+            package p {
+                public function f(): void {
+                    if (x {
+                        g();
+                    }
+                    h(a b);
+                    )
+                    k();
+                }
+                public class C {
+                    var x: Number = ;
+                }
+            }
+            f() g()
+        "#);
+        assert_eq!(diagnostics, vec![
+            "Expected ')' before '{'",
+            "Expected ')' before identifier",
+            "Expected expression before ')'",
+            "Expected expression before ';'",
+            "Expected ';' before identifier",
+        ]);
+        assert_eq!(program.packages.len(), 1);
+        let Directive::FunctionDefinition(f) = program.packages[0].block.directives[0].as_ref() else { panic!() };
+        let FunctionBody::Block(body) = f.common.body.as_ref().unwrap() else { panic!() };
+        assert_eq!(body.directives.len(), 4);
+        assert!(matches!(body.directives[3].as_ref(), Directive::ExpressionStatement(_)));
+        assert!(matches!(program.packages[0].block.directives[1].as_ref(), Directive::ClassDefinition(_)));
+        assert_eq!(program.directives.len(), 2);
+    }
+
+    #[test]
+    fn recovering_from_stray_identifier_before_definition() {
+        let (program, diagnostics) = parse(r#"
+            package p {
+                x function f(): void {}
+                y class C {}
+            }
+            z var v = 10;
+            w
+            function g(): void {}
+        "#);
+        assert_eq!(diagnostics, vec![
+            "Unexpected or invalid token",
+            "Unexpected or invalid token",
+            "Unexpected or invalid token",
+        ]);
+        let directives = &program.packages[0].block.directives;
+        assert_eq!(directives.len(), 4);
+        assert!(matches!(directives[0].as_ref(), Directive::InvalidatedDirective(_)));
+        assert!(matches!(directives[1].as_ref(), Directive::FunctionDefinition(_)));
+        assert!(matches!(directives[2].as_ref(), Directive::InvalidatedDirective(_)));
+        assert!(matches!(directives[3].as_ref(), Directive::ClassDefinition(_)));
+        assert_eq!(program.directives.len(), 4);
+        assert!(matches!(program.directives[0].as_ref(), Directive::InvalidatedDirective(_)));
+        assert!(matches!(program.directives[1].as_ref(), Directive::VariableDefinition(_)));
+        assert!(matches!(program.directives[2].as_ref(), Directive::ExpressionStatement(_)));
+        assert!(matches!(program.directives[3].as_ref(), Directive::FunctionDefinition(_)));
+    }
+
+    #[test]
+    fn recovering_from_super_without_arguments() {
+        let (program, diagnostics) = parse(r#"
+            class C extends B {
+                function C() {
+                    super);
+                    f();
+                }
+            }
+        "#);
+        assert_eq!(diagnostics, vec!["Expected '(' before ')'"]);
+        let Directive::ClassDefinition(c) = program.directives[0].as_ref() else { panic!() };
+        let Directive::FunctionDefinition(constructor) = c.block.directives[0].as_ref() else { panic!() };
+        let FunctionBody::Block(body) = constructor.common.body.as_ref().unwrap() else { panic!() };
+        assert_eq!(body.directives.len(), 2);
+        assert!(matches!(body.directives[0].as_ref(), Directive::SuperStatement(s) if s.arguments.is_empty()));
+    }
+
+    #[test]
+    fn reparsing_incrementally() {
+        let text = "package p {\n    public function f(): void {\n        g();\n    }\n    public class C {\n        function m(): void {\n            a();\n        }\n    }\n}\nh();\n";
+        let compilation_unit = CompilationUnit::new(None, text.into(), &CompilerOptions::new());
+        let program = ParserFacade::parse_program(&compilation_unit).unwrap();
+        let source_of = |location: &Location| compilation_unit.current_text()[location.first_offset()..location.last_offset()].to_owned();
+
+        // Edit within a function body
+        let offset = text.find("g()").unwrap() + 2;
+        let program_1 = compilation_unit.apply_edits(&program, &[TextEdit::new(offset..offset, "xy, 10")]).unwrap();
+        assert!(Rc::ptr_eq(&program.packages[0].block.directives[1], &program_1.packages[0].block.directives[1]));
+        assert!(Rc::ptr_eq(&program.directives[0], &program_1.directives[0]));
+        let Directive::FunctionDefinition(f) = program_1.packages[0].block.directives[0].as_ref() else { panic!() };
+        // The function definition is reused with a reparsed body
+        assert_eq!(f.location.version, 0);
+        let FunctionBody::Block(body) = f.common.body.as_ref().unwrap() else { panic!() };
+        assert_eq!(source_of(&body.directives[0].location()), "g(xy, 10);");
+        assert!(source_of(&program_1.packages[0].block.directives[1].location()).starts_with("public class C"));
+        assert_eq!(source_of(&program_1.directives[0].location()), "h();");
+
+        // A syntax error is reported and then fixed
+        let offset = compilation_unit.current_text().find("a()").unwrap() + 2;
+        let program_2 = compilation_unit.apply_edits(&program_1, &[TextEdit::new(offset..(offset + 1), "")]).unwrap();
+        assert_eq!(compilation_unit.diagnostics().iter().map(|d| d.format_message_english()).collect::<Vec<_>>(), vec!["Expected expression before ';'", "Expected ')' before ';'"]);
+        let program_3 = compilation_unit.apply_edits(&program_2, &[TextEdit::new(offset..offset, ")")]).unwrap();
+        assert!(compilation_unit.diagnostics().is_empty());
+        assert_eq!(compilation_unit.current_text().as_str(), text.replace("g()", "g(xy, 10)"));
+        assert_eq!(compilation_unit.text().as_str(), text);
+
+        // Edit between top-level directives
+        let offset = compilation_unit.current_text().len();
+        let program_4 = compilation_unit.apply_edits(&program_3, &[TextEdit::new(offset..offset, "k();\n")]).unwrap();
+        assert!(Rc::ptr_eq(&program_3.packages[0], &program_4.packages[0]));
+        assert_eq!(program_4.directives.len(), 2);
+        assert_eq!(source_of(&program_4.directives[1].location()), "k();");
+
+        // An edit touching a comment reparses the program
+        let program_5 = compilation_unit.apply_edits(&program_4, &[TextEdit::new(offset..offset, "// Comment\n")]).unwrap();
+        assert!(!Rc::ptr_eq(&program_4.packages[0], &program_5.packages[0]));
+        assert_eq!(source_of(&program_5.directives[1].location()), "k();");
+        assert_eq!(compilation_unit.comments().len(), 1);
+    }
+
+    #[test]
+    fn rejecting_invalid_edits() {
+        let text = "x = \"é\";";
+        let compilation_unit = CompilationUnit::new(None, text.into(), &CompilerOptions::new());
+        let program = ParserFacade::parse_program(&compilation_unit).unwrap();
+        let offset = text.find('é').unwrap();
+        let apply = |edits: &[TextEdit]| compilation_unit.apply_edits(&program, edits).map(|_| ());
+
+        assert_eq!(apply(&[TextEdit::new((offset + 1)..(offset + 2), "e")]), Err(TextEditError::NotCharBoundary(0)));
+        assert_eq!(apply(&[TextEdit::new(offset..offset, "a"), TextEdit::new((offset + 2)..(offset + 3), "")]), Err(TextEditError::NotCharBoundary(1)));
+        assert_eq!(apply(&[TextEdit::new(std::ops::Range { start: 2, end: 1 }, "")]), Err(TextEditError::ReversedRange(0)));
+        assert_eq!(apply(&[TextEdit::new(0..(text.len() + 1), "")]), Err(TextEditError::OutOfBounds(0)));
+        // No edit is applied if any of them is invalid
+        assert_eq!(compilation_unit.current_text().as_str(), text);
+
+        let program = compilation_unit.apply_edits(&program, &[TextEdit::new(offset..(offset + 'é'.len_utf8()), "ü")]).unwrap();
+        assert_eq!(compilation_unit.current_text().as_str(), "x = \"ü\";");
+        let Directive::ExpressionStatement(statement) = program.directives[0].as_ref() else { panic!() };
+        let Expression::Assignment(assignment) = statement.expression.as_ref() else { panic!() };
+        assert!(matches!(assignment.right.as_ref(), Expression::StringLiteral(literal) if literal.value == "ü"));
+    }
+
+    /// Applies edits incrementally and checks that the program, diagnostics
+    /// and comments equal those of parsing the edited text from scratch.
+    fn assert_reparses_as_full_parse(text: &str, edits: &[TextEdit]) {
+        let compilation_unit = CompilationUnit::new(None, text.into(), &CompilerOptions::new());
+        let mut program = ParserFacade::parse_program(&compilation_unit).unwrap();
+        for edit in edits {
+            program = compilation_unit.apply_edits(&program, std::slice::from_ref(edit)).unwrap();
+            let edited_text = compilation_unit.current_text().clone();
+            let expected_compilation_unit = CompilationUnit::new(None, edited_text.clone(), &CompilerOptions::new());
+            let expected_program = ParserFacade::parse_program(&expected_compilation_unit).unwrap();
+            let summary = |compilation_unit: &Rc<CompilationUnit>| {
+                let mut diagnostics: Vec<_> = compilation_unit.diagnostics().iter().map(|d| {
+                    (d.location().first_offset(), d.location().last_offset(), d.format_message_english())
+                }).collect();
+                diagnostics.sort();
+                let comments: Vec<_> = compilation_unit.comments().iter().map(|c| {
+                    (c.location().first_offset(), c.location().last_offset(), c.content())
+                }).collect();
+                (diagnostics, comments)
+            };
+            assert_eq!(AstJson::serialize_program(&program), AstJson::serialize_program(&expected_program), "program after {edit:?} in {edited_text:?}");
+            assert_eq!(summary(&compilation_unit), summary(&expected_compilation_unit), "diagnostics and comments after {edit:?} in {edited_text:?}");
+        }
+    }
+
+    #[test]
+    fn reparsing_incrementally_as_full_parse() {
+        let directory = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/parser");
+        let read = |name: &str| std::fs::read_to_string(directory.join(name)).unwrap();
+
+        // Edits within and around comments and JetDoc comments
+        assert_reparses_as_full_parse(&read("JetDoc.jet"), &[TextEdit::new(191..191, ";")]);
+        assert_reparses_as_full_parse(&read("JetDoc.jet"), &[TextEdit::new(298..299, "")]);
+        assert_reparses_as_full_parse(&read("AnnotatableDirective.jet"), &[TextEdit::new(267..272, "")]);
+
+        let replacements = [";", "x", " ", "\n", "(", ")", "{", "}", "\"", "/", "*", "var y = 0;", "function g() {}"];
+        for entry in std::fs::read_dir(&directory).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_none_or(|extension| extension != "jet") {
+                continue;
+            }
+            let text = std::fs::read_to_string(&path).unwrap();
+            let offsets: Vec<usize> = (0..=text.len()).filter(|&i| text.is_char_boundary(i)).collect();
+            for (i, &offset) in offsets.iter().enumerate() {
+                assert_reparses_as_full_parse(&text, &[TextEdit::new(offset..offset, replacements[i % replacements.len()])]);
+                let length = [1, 5][i % 2];
+                if let Some(&end) = offsets.get(i + length) {
+                    assert_reparses_as_full_parse(&text, &[TextEdit::new(offset..end, ["", "z"][i / 2 % 2])]);
+                }
+            }
+
+            // Typing a statement character by character, then deleting it
+            for offset in offsets.iter().copied().step_by(53) {
+                let typed = "if (a) { b(c, 1) } else d;\n";
+                let mut edits: Vec<TextEdit> = typed.char_indices().map(|(i, ch)| TextEdit::new((offset + i)..(offset + i), &ch.to_string())).collect();
+                edits.extend((0..typed.len()).rev().map(|i| TextEdit::new((offset + i)..(offset + i + 1), "")));
+                assert_reparses_as_full_parse(&text, &edits);
+            }
+        }
+    }
+
+    #[test]
+    fn mapping_nodes_to_source_text() {
+        let text = "// Header\npackage p {\n    /* Leading */ public function f(): void {\n        g( 1 ,2 ); // Trailing\n    }\n}\nx = /re/g;\n";
+        let compilation_unit = CompilationUnit::new(None, text.into(), &CompilerOptions::new());
+        let (program, syntax_tokens) = ParserFacade::parse_program_with_syntax_tokens(&compilation_unit).unwrap();
+        assert_eq!(syntax_tokens.to_string(), text);
+        assert!(syntax_tokens.tokens().iter().any(|t| matches!(t.token, Token::RegExpLiteral { .. })));
+        assert!(matches!(syntax_tokens.tokens().last().unwrap().token, Token::Eof));
+
+        let f = program.packages[0].block.directives[0].location();
+        assert!(syntax_tokens.text(&f).starts_with("public function f"));
+        assert_eq!(syntax_tokens.full_text(&f), "    /* Leading */ public function f(): void {\n        g( 1 ,2 ); // Trailing\n    }\n");
+        let first = &syntax_tokens.tokens_within(&f)[0];
+        assert_eq!(first.leading_trivia.iter().map(|t| t.kind).collect::<Vec<_>>(), vec![TriviaKind::Whitespace, TriviaKind::MultiLineComment, TriviaKind::Whitespace]);
+        assert_eq!(syntax_tokens.tokens()[0].leading_trivia[0].text(), "// Header");
+    }
+}
@@ -1,13 +1,14 @@
 use crate::ns::*;
 
 /// Invalidated identifier name.
-pub static INVALIDATED_IDENTIFIER: &'static str = "\x00\x00\x00\x00\x00";
+pub static INVALIDATED_IDENTIFIER: &str = "\x00\x00\x00\x00\x00";
 
 /// Indicates a fatal syntax error that leads parsing
 /// to finish without a resulting node.
-/// 
-/// This parser is intolerant in general,
-/// thus resulting in a `ParsingFailure` for almost any syntax error.
+///
+/// The parser recovers from syntax errors by skipping tokens up to
+/// a statement, block or definition boundary, leaving invalidated nodes
+/// in place, thus a `ParsingFailure` is not expected in general.
 #[derive(Copy, Clone, Debug)]
 pub struct ParsingFailure;
 
@@ -0,0 +1,191 @@
+use crate::ns::*;
+
+/// Kind of a trivia piece.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TriviaKind {
+    Whitespace,
+    LineTerminator,
+    SingleLineComment,
+    MultiLineComment,
+    /// Characters skipped by the tokenizer due to a syntax error.
+    Skipped,
+}
+
+/// Source text that is not part of a token, such as whitespace and comments.
+#[derive(Clone, Debug)]
+pub struct Trivia {
+    pub kind: TriviaKind,
+    pub location: Location,
+}
+
+impl Trivia {
+    /// The exact source text of the trivia.
+    pub fn text(&self) -> String {
+        self.location.compilation_unit().current_text()[self.location.first_offset()..self.location.last_offset()].to_owned()
+    }
+}
+
+/// A token together with its surrounding trivia.
+///
+/// The trailing trivia of a token extends until the end of its line,
+/// including the line terminator; the remaining trivia preceding
+/// the next token is the leading trivia of the next token.
+#[derive(Clone, Debug)]
+pub struct SyntaxToken {
+    pub token: Token,
+    pub location: Location,
+    pub leading_trivia: Vec<Trivia>,
+    pub trailing_trivia: Vec<Trivia>,
+}
+
+impl SyntaxToken {
+    /// The exact source text of the token, excluding trivia.
+    pub fn text(&self) -> String {
+        self.location.compilation_unit().current_text()[self.location.first_offset()..self.location.last_offset()].to_owned()
+    }
+
+    /// The location of the token including its leading and trailing trivia.
+    pub fn full_location(&self) -> Location {
+        let first = self.leading_trivia.first().map_or(&self.location, |t| &t.location);
+        let last = self.trailing_trivia.last().map_or(&self.location, |t| &t.location);
+        first.combine_with(last.clone())
+    }
+
+    /// The exact source text of the token including its leading and trailing trivia.
+    pub fn full_text(&self) -> String {
+        let location = self.full_location();
+        location.compilation_unit().current_text()[location.first_offset()..location.last_offset()].to_owned()
+    }
+}
+
+/// Lossless list of the tokens of a compilation unit with their trivia,
+/// ending with an end-of-file token.
+///
+/// Concatenating the full text of every token results in the exact source text,
+/// and the source text of any node, with or without its surrounding trivia, can
+/// be obtained from its location. The list reflects the source text at the time
+/// it was built.
+pub struct SyntaxTokenList {
+    compilation_unit: Rc<CompilationUnit>,
+    tokens: Vec<SyntaxToken>,
+}
+
+impl SyntaxTokenList {
+    /// Builds a token list from the tokens scanned from a compilation unit,
+    /// in ascending order of offsets.
+    pub fn new(compilation_unit: &Rc<CompilationUnit>, scanned_tokens: Vec<(Token, Location)>) -> Self {
+        let text = compilation_unit.current_text();
+        let mut scanned_tokens = scanned_tokens;
+        if !matches!(scanned_tokens.last(), Some((Token::Eof, _))) {
+            scanned_tokens.push((Token::Eof, Location::with_offset(compilation_unit, text.len())));
+        }
+        let mut tokens: Vec<SyntaxToken> = vec![];
+        let mut offset = 0;
+        for (token, location) in scanned_tokens {
+            let mut trivia = Self::scan_trivia(compilation_unit, &text, offset, location.first_offset());
+            if let Some(previous) = tokens.last_mut() {
+                let line_end = trivia.iter().position(|t| t.kind == TriviaKind::LineTerminator).map_or(trivia.len(), |i| i + 1);
+                previous.trailing_trivia = trivia.drain(..line_end).collect();
+            }
+            offset = location.last_offset();
+            tokens.push(SyntaxToken {
+                token,
+                location,
+                leading_trivia: trivia,
+                trailing_trivia: vec![],
+            });
+        }
+        Self { compilation_unit: compilation_unit.clone(), tokens }
+    }
+
+    /// Splits the text between two tokens into trivia.
+    fn scan_trivia(compilation_unit: &Rc<CompilationUnit>, text: &str, first_offset: usize, last_offset: usize) -> Vec<Trivia> {
+        let mut trivia = vec![];
+        let mut characters = CharacterReader::from_index(&text[..last_offset], text[..first_offset].chars().count());
+        while characters.has_remaining() {
+            let start = characters.index();
+            let ch = characters.next_or_zero();
+            let kind = if ch == '\r' && characters.peek_or_zero() == '\n' {
+                characters.next();
+                TriviaKind::LineTerminator
+            } else if CharacterValidator::is_line_terminator(ch) {
+                TriviaKind::LineTerminator
+            } else if CharacterValidator::is_whitespace(ch) {
+                while CharacterValidator::is_whitespace(characters.peek_or_zero()) {
+                    characters.next();
+                }
+                TriviaKind::Whitespace
+            } else if ch == '/' && characters.peek_or_zero() == '/' {
+                while characters.has_remaining() && !CharacterValidator::is_line_terminator(characters.peek_or_zero()) {
+                    characters.next();
+                }
+                TriviaKind::SingleLineComment
+            } else if ch == '/' && characters.peek_or_zero() == '*' {
+                characters.next();
+                while characters.has_remaining() && !(characters.peek_or_zero() == '*' && characters.peek_at_or_zero(1) == '/') {
+                    characters.next();
+                }
+                characters.skip_count_in_place(2);
+                TriviaKind::MultiLineComment
+            } else {
+                while characters.has_remaining() && !(CharacterValidator::is_whitespace(characters.peek_or_zero()) || CharacterValidator::is_line_terminator(characters.peek_or_zero()) || characters.peek_or_zero() == '/') {
+                    characters.next();
+                }
+                TriviaKind::Skipped
+            };
+            trivia.push(Trivia {
+                kind,
+                location: Location::with_offsets(compilation_unit, start, characters.index()),
+            });
+        }
+        trivia
+    }
+
+    pub fn compilation_unit(&self) -> Rc<CompilationUnit> {
+        self.compilation_unit.clone()
+    }
+
+    /// The tokens, ending with an end-of-file token.
+    pub fn tokens(&self) -> &[SyntaxToken] {
+        &self.tokens
+    }
+
+    /// The tokens within a location, such as the location of a node.
+    pub fn tokens_within(&self, location: &Location) -> &[SyntaxToken] {
+        let (first_offset, last_offset) = (location.first_offset(), location.last_offset());
+        let start = self.tokens.partition_point(|t| t.location.first_offset() < first_offset);
+        let end = self.tokens.partition_point(|t| t.location.last_offset() <= last_offset && t.location.first_offset() < last_offset).max(start);
+        &self.tokens[start..end]
+    }
+
+    /// The exact source text at a location, excluding surrounding trivia.
+    pub fn text(&self, location: &Location) -> String {
+        self.compilation_unit.current_text()[location.first_offset()..location.last_offset()].to_owned()
+    }
+
+    /// Extends a location, such as the location of a node, to the leading trivia
+    /// of its first token and the trailing trivia of its last token.
+    pub fn full_location(&self, location: &Location) -> Location {
+        let tokens = self.tokens_within(location);
+        let (Some(first), Some(last)) = (tokens.first(), tokens.last()) else {
+            return location.clone();
+        };
+        first.full_location().combine_with(last.full_location())
+    }
+
+    /// The exact source text at a location, including the leading trivia of
+    /// its first token and the trailing trivia of its last token.
+    pub fn full_text(&self, location: &Location) -> String {
+        self.text(&self.full_location(location))
+    }
+}
+
+impl std::fmt::Display for SyntaxTokenList {
+    /// Reconstructs the source text from the tokens and their trivia.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for token in &self.tokens {
+            f.write_str(&token.full_text())?;
+        }
+        Ok(())
+    }
+}
@@ -144,6 +144,7 @@ pub enum Token {
     XmlAttributeValue(String),
 }
 
+#[allow(clippy::to_string_trait_impl, reason = "tokens are described in diagnostics rather than displayed")]
 impl ToString for Token {
     /// Converts the token into a readable string.
     ///
@@ -424,7 +425,7 @@ impl Token {
             Self::Protected => Some(Attribute::Protected(location.clone())),
             Self::Internal => Some(Attribute::Internal(location.clone())),
             Self::Identifier(ref name) => {
-                Attribute::from_identifier_name(name, &location)
+                Attribute::from_identifier_name(name, location)
             },
             _ => None,
         }
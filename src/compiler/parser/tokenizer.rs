@@ -20,6 +20,17 @@ impl<'input> Tokenizer<'input> {
         }
     }
 
+    /// Constructs a tokenizer that scans a region of an already tokenized
+    /// compilation unit, starting at `offset`. `text` is the current source text
+    /// of the compilation unit.
+    pub(crate) fn new_at(compilation_unit: &Rc<CompilationUnit>, text: &'input str, offset: usize) -> Self {
+        Self {
+            compilation_unit: compilation_unit.clone(),
+            line_number: compilation_unit.get_line_number(offset),
+            characters: CharacterReader::from_index(text, text[..offset].chars().count()),
+        }
+    }
+
     pub fn compilation_unit(&self) -> &Rc<CompilationUnit> {
         &self.compilation_unit
     }
@@ -51,43 +62,43 @@ impl<'input> Tokenizer<'input> {
                 // Comma
                 self.characters.next();
                 let location = start.combine_with(self.cursor_location());
-                return Ok((Token::Comma, location));
+                Ok((Token::Comma, location))
             },
             '(' => {
                 // LeftParen
                 self.characters.next();
                 let location = start.combine_with(self.cursor_location());
-                return Ok((Token::LeftParen, location));
+                Ok((Token::LeftParen, location))
             },
             ')' => {
                 // RightParen
                 self.characters.next();
                 let location = start.combine_with(self.cursor_location());
-                return Ok((Token::RightParen, location));
+                Ok((Token::RightParen, location))
             },
             '[' => {
                 // LeftBracket
                 self.characters.next();
                 let location = start.combine_with(self.cursor_location());
-                return Ok((Token::LeftBracket, location));
+                Ok((Token::LeftBracket, location))
             },
             ']' => {
                 // RightBracket
                 self.characters.next();
                 let location = start.combine_with(self.cursor_location());
-                return Ok((Token::RightBracket, location));
+                Ok((Token::RightBracket, location))
             },
             '{' => {
                 // LeftBrace
                 self.characters.next();
                 let location = start.combine_with(self.cursor_location());
-                return Ok((Token::LeftBrace, location));
+                Ok((Token::LeftBrace, location))
             },
             '}' => {
                 // RightBrace
                 self.characters.next();
                 let location = start.combine_with(self.cursor_location());
-                return Ok((Token::RightBrace, location));
+                Ok((Token::RightBrace, location))
             },
             ':' => {
                 self.characters.next();
@@ -99,7 +110,7 @@ impl<'input> Tokenizer<'input> {
                 }
                 // Colon
                 let location = start.combine_with(self.cursor_location());
-                return Ok((Token::Colon, location));
+                Ok((Token::Colon, location))
             },
             '=' => {
                 self.characters.next();
@@ -118,7 +129,7 @@ impl<'input> Tokenizer<'input> {
                 }
                 // Assign
                 let location = start.combine_with(self.cursor_location());
-                return Ok((Token::Assign, location));
+                Ok((Token::Assign, location))
             },
             '!' => {
                 self.characters.next();
@@ -137,7 +148,7 @@ impl<'input> Tokenizer<'input> {
                 }
                 // Exclamation
                 let location = start.combine_with(self.cursor_location());
-                return Ok((Token::Exclamation, location));
+                Ok((Token::Exclamation, location))
             },
             '?' => {
                 self.characters.next();
@@ -162,13 +173,13 @@ impl<'input> Tokenizer<'input> {
                 }
                 // Question
                 let location = start.combine_with(self.cursor_location());
-                return Ok((Token::Question, location));
+                Ok((Token::Question, location))
             },
             ';' => {
                 // Semicolon
                 self.characters.next();
                 let location = start.combine_with(self.cursor_location());
-                return Ok((Token::Semicolon, location));
+                Ok((Token::Semicolon, location))
             },
             '<' => {
                 self.characters.next();
@@ -193,7 +204,7 @@ impl<'input> Tokenizer<'input> {
                 }
                 // Lt
                 let location = start.combine_with(self.cursor_location());
-                return Ok((Token::Lt, location));
+                Ok((Token::Lt, location))
             },
             '>' => {
                 self.characters.next();
@@ -230,7 +241,7 @@ impl<'input> Tokenizer<'input> {
                 }
                 // Gt
                 let location = start.combine_with(self.cursor_location());
-                return Ok((Token::Gt, location));
+                Ok((Token::Gt, location))
             },
             '@' => {
                 // Attribute
@@ -239,7 +250,7 @@ impl<'input> Tokenizer<'input> {
                     return Ok(token);
                 }
                 let location = start.combine_with(self.cursor_location());
-                return Ok((Token::Attribute, location));
+                Ok((Token::Attribute, location))
             },
             '+' => {
                 self.characters.next();
@@ -258,7 +269,7 @@ impl<'input> Tokenizer<'input> {
                 }
                 // Plus
                 let location = start.combine_with(self.cursor_location());
-                return Ok((Token::Plus, location));
+                Ok((Token::Plus, location))
             },
             '-' => {
                 self.characters.next();
@@ -277,7 +288,7 @@ impl<'input> Tokenizer<'input> {
                 }
                 // Minus
                 let location = start.combine_with(self.cursor_location());
-                return Ok((Token::Minus, location));
+                Ok((Token::Minus, location))
             },
             '*' => {
                 self.characters.next();
@@ -302,7 +313,7 @@ impl<'input> Tokenizer<'input> {
                 }
                 // Times
                 let location = start.combine_with(self.cursor_location());
-                return Ok((Token::Times, location));
+                Ok((Token::Times, location))
             },
             '/' => {
                 self.characters.next();
@@ -315,7 +326,7 @@ impl<'input> Tokenizer<'input> {
                 }
                 // Div
                 let location = start.combine_with(self.cursor_location());
-                return Ok((Token::Div, location));
+                Ok((Token::Div, location))
             },
             '%' => {
                 self.characters.next();
@@ -328,7 +339,7 @@ impl<'input> Tokenizer<'input> {
                 }
                 // Remainder
                 let location = start.combine_with(self.cursor_location());
-                return Ok((Token::Remainder, location));
+                Ok((Token::Remainder, location))
             },
             '&' => {
                 self.characters.next();
@@ -353,7 +364,7 @@ impl<'input> Tokenizer<'input> {
                 }
                 // BitwiseAnd
                 let location = start.combine_with(self.cursor_location());
-                return Ok((Token::BitwiseAnd, location));
+                Ok((Token::BitwiseAnd, location))
             },
             '^' => {
                 self.characters.next();
@@ -378,7 +389,7 @@ impl<'input> Tokenizer<'input> {
                 }
                 // BitwiseXor
                 let location = start.combine_with(self.cursor_location());
-                return Ok((Token::BitwiseXor, location));
+                Ok((Token::BitwiseXor, location))
             },
             '|' => {
                 self.characters.next();
@@ -403,22 +414,22 @@ impl<'input> Tokenizer<'input> {
                 }
                 // BitwiseOr
                 let location = start.combine_with(self.cursor_location());
-                return Ok((Token::BitwiseOr, location));
+                Ok((Token::BitwiseOr, location))
             },
             '~' => {
                 // BitwiseNot
                 self.characters.next();
                 let location = start.combine_with(self.cursor_location());
-                return Ok((Token::BitwiseNot, location));
+                Ok((Token::BitwiseNot, location))
             },
             _ => {
                 if self.characters.has_remaining() {
                     self.add_unexpected_error();
                     self.characters.next();
-                    return self.scan_ie_div();
+                    self.scan_ie_div()
                 // Eof
                 } else {
-                    return Ok((Token::Eof, start))
+                    Ok((Token::Eof, start))
                 }
             },
         }
@@ -438,7 +449,7 @@ impl<'input> Tokenizer<'input> {
                 let ch = self.characters.peek_or_zero();
                 if self.characters.reached_end() {
                     self.add_unexpected_error();
-                    return Err(ParsingFailure);
+                    break;
                 } else if CharacterValidator::is_line_terminator(ch) {
                     self.add_unexpected_error();
                     self.consume_line_terminator();
@@ -451,7 +462,7 @@ impl<'input> Tokenizer<'input> {
                 self.consume_line_terminator();
             } else if self.characters.reached_end() {
                 self.add_unexpected_error();
-                return Err(ParsingFailure);
+                break;
             } else {
                 body.push(ch);
                 self.characters.next();
@@ -474,7 +485,7 @@ impl<'input> Tokenizer<'input> {
 
     fn character_ahead_location(&self) -> Location {
         if self.characters.reached_end() {
-            return Location::with_offset(&self.compilation_unit, self.compilation_unit.text().len());
+            return Location::with_offset(&self.compilation_unit, self.characters.index());
         }
         let offset = self.characters.index();
         let mut next_characters = self.characters.clone();
@@ -528,7 +539,7 @@ impl<'input> Tokenizer<'input> {
 
             self.compilation_unit.comments.borrow_mut().push(Rc::new(Comment {
                 multiline: false,
-                content: RefCell::new(self.compilation_unit.text()[(location.first_offset() + 2)..location.last_offset()].to_owned()),
+                content: RefCell::new(self.compilation_unit.current_text()[(location.first_offset() + 2)..location.last_offset()].to_owned()),
                 location: RefCell::new(location),
             }));
 
@@ -555,15 +566,18 @@ impl<'input> Tokenizer<'input> {
                     self.characters.skip_in_place();
                 } else {
                     self.add_unexpected_error();
-                    return Err(ParsingFailure);
+                    break;
                 }
             }
 
             let location = start.combine_with(self.cursor_location());
 
+            // An unterminated comment extends to the end of the text
+            let content_end = if nested == -1 { location.last_offset() - 2 } else { location.last_offset() };
+
             self.compilation_unit.comments.borrow_mut().push(Rc::new(Comment {
                 multiline: true,
-                content: RefCell::new(self.compilation_unit.text()[(location.first_offset() + 2)..(location.last_offset() - 2)].to_owned()),
+                content: RefCell::new(self.compilation_unit.current_text()[(location.first_offset() + 2)..content_end].to_owned()),
                 location: RefCell::new(location),
             }));
 
@@ -667,7 +681,7 @@ impl<'input> Tokenizer<'input> {
         }
         self.characters.next();
         let location = start.combine_with(self.cursor_location());
-        let r = u32::from_str_radix(&self.compilation_unit.text()[(start.first_offset + 2)..(location.last_offset - 1)], 16);
+        let r = u32::from_str_radix(&self.compilation_unit.current_text()[(start.first_offset + 2)..(location.last_offset - 1)], 16);
         let Ok(r) = r else {
             self.compilation_unit.add_diagnostic(Diagnostic::new_syntax_error(&location, DiagnosticKind::UnexpectedOrInvalidToken, vec![]));
             return Ok('_');
@@ -774,7 +788,7 @@ impl<'input> Tokenizer<'input> {
         self.unallow_numeric_suffix();
 
         let location = start.combine_with(self.cursor_location());
-        let string = self.compilation_unit.text()[location.first_offset..location.last_offset].to_owned();
+        let string = self.compilation_unit.current_text()[location.first_offset..location.last_offset].to_owned();
 
         Ok(Some((Token::NumericLiteral(string), location)))
     }
@@ -791,7 +805,7 @@ impl<'input> Tokenizer<'input> {
         self.unallow_numeric_suffix();
 
         let location = start.combine_with(self.cursor_location());
-        let s = self.compilation_unit.text()[location.first_offset..location.last_offset].to_owned();
+        let s = self.compilation_unit.current_text()[location.first_offset..location.last_offset].to_owned();
         Ok(Some((Token::NumericLiteral(s), location)))
     }
 
@@ -807,7 +821,7 @@ impl<'input> Tokenizer<'input> {
         self.unallow_numeric_suffix();
 
         let location = start.combine_with(self.cursor_location());
-        let s = self.compilation_unit.text()[location.first_offset..location.last_offset].to_owned();
+        let s = self.compilation_unit.current_text()[location.first_offset..location.last_offset].to_owned();
         Ok(Some((Token::NumericLiteral(s), location)))
     }
 
@@ -883,7 +897,7 @@ impl<'input> Tokenizer<'input> {
                     self.consume_line_terminator();
                 } else if !self.characters.has_remaining() {
                     self.add_unexpected_error();
-                    return Err(ParsingFailure);
+                    break;
                 } else {
                     value.push(ch);
                     self.characters.next();
@@ -903,7 +917,7 @@ impl<'input> Tokenizer<'input> {
                         self.consume_line_terminator();
                     } else if !self.characters.has_remaining() {
                         self.add_unexpected_error();
-                        return Err(ParsingFailure);
+                        break;
                     } else {
                         value.push(ch);
                         self.characters.next();
@@ -933,7 +947,8 @@ impl<'input> Tokenizer<'input> {
                     self.consume_line_terminator();
                 } else if !self.characters.has_remaining() {
                     self.add_unexpected_error();
-                    return Err(ParsingFailure);
+                    lines.push(builder.clone());
+                    break;
                 } else {
                     builder.push(ch);
                     self.characters.next();
@@ -955,7 +970,8 @@ impl<'input> Tokenizer<'input> {
                         self.consume_line_terminator();
                     } else if !self.characters.has_remaining() {
                         self.add_unexpected_error();
-                        return Err(ParsingFailure);
+                        lines.push(builder.clone());
+                        break;
                     } else {
                         builder.push(ch);
                         self.characters.next();
@@ -993,9 +1009,9 @@ impl<'input> Tokenizer<'input> {
             return Ok(None);
         }
         self.characters.next();
+        // The unexpected end is reported by the enclosing literal
         if !self.characters.has_remaining() {
-            self.add_unexpected_error();
-            return Err(ParsingFailure);
+            return Ok(None);
         }
         if self.consume_line_terminator() {
             return Ok(Some("".into()));
@@ -1068,7 +1084,7 @@ impl<'input> Tokenizer<'input> {
                 self.characters.next();
             }
             let location = start.combine_with(self.cursor_location());
-            let name = self.compilation_unit.text()[location.first_offset..location.last_offset].to_owned();
+            let name = self.compilation_unit.current_text()[location.first_offset..location.last_offset].to_owned();
             return Ok((Token::XmlName(name), location));
         }
 
@@ -1111,7 +1127,8 @@ impl<'input> Tokenizer<'input> {
                             return Ok((Token::XmlSlashGt, location));
                         }
                     }
-                    return Err(ParsingFailure);
+                    let location = start.combine_with(self.cursor_location());
+                    return Ok((Token::XmlSlashGt, location));
                 }
                 self.characters.next();
                 let location = start.combine_with(self.cursor_location());
@@ -1127,12 +1144,12 @@ impl<'input> Tokenizer<'input> {
                         self.characters.next();
                     }
                 }
+                let value = self.compilation_unit.current_text()[(start.first_offset + 1)..self.cursor_location().first_offset].to_owned();
                 if self.characters.reached_end() {
                     self.add_unexpected_error();
-                    return Err(ParsingFailure)
+                } else {
+                    self.characters.next();
                 }
-                let value = self.compilation_unit.text()[(start.first_offset + 1)..self.cursor_location().first_offset].to_owned();
-                self.characters.next();
                 
                 let location = start.combine_with(self.cursor_location());
                 Ok((Token::XmlAttributeValue(value), location))
@@ -1148,7 +1165,7 @@ impl<'input> Tokenizer<'input> {
             _ => {
                 self.add_unexpected_error();
                 if self.characters.reached_end() {
-                    return Err(ParsingFailure);
+                    return Ok((Token::Eof, start));
                 }
                 self.characters.next();
                 self.scan_ie_xml_tag()
@@ -1209,7 +1226,7 @@ impl<'input> Tokenizer<'input> {
                 }
 
                 let location = start.combine_with(self.cursor_location());
-                let content = self.compilation_unit.text()[location.first_offset..location.last_offset].to_owned();
+                let content = self.compilation_unit.current_text()[location.first_offset..location.last_offset].to_owned();
                 Ok((Token::XmlText(content), location))
             },
         }
@@ -1228,14 +1245,14 @@ impl<'input> Tokenizer<'input> {
                     self.consume_line_terminator();
                 } else if self.characters.reached_end() {
                     self.add_unexpected_error();
-                    return Err(ParsingFailure);
+                    break;
                 } else {
                     self.characters.next();
                 }
             }
 
             let location = start.combine_with(self.cursor_location());
-            let content = self.compilation_unit.text()[location.first_offset..location.last_offset].to_owned();
+            let content = self.compilation_unit.current_text()[location.first_offset..location.last_offset].to_owned();
 
             return Ok(Some((Token::XmlMarkup(content), location)));
         }
@@ -1251,14 +1268,14 @@ impl<'input> Tokenizer<'input> {
                     self.consume_line_terminator();
                 } else if self.characters.reached_end() {
                     self.add_unexpected_error();
-                    return Err(ParsingFailure);
+                    break;
                 } else {
                     self.characters.next();
                 }
             }
 
             let location = start.combine_with(self.cursor_location());
-            let content = self.compilation_unit.text()[location.first_offset..location.last_offset].to_owned();
+            let content = self.compilation_unit.current_text()[location.first_offset..location.last_offset].to_owned();
 
             return Ok(Some((Token::XmlMarkup(content), location)));
         }
@@ -1274,14 +1291,14 @@ impl<'input> Tokenizer<'input> {
                     self.consume_line_terminator();
                 } else if self.characters.reached_end() {
                     self.add_unexpected_error();
-                    return Err(ParsingFailure);
+                    break;
                 } else {
                     self.characters.next();
                 }
             }
 
             let location = start.combine_with(self.cursor_location());
-            let content = self.compilation_unit.text()[location.first_offset..location.last_offset].to_owned();
+            let content = self.compilation_unit.current_text()[location.first_offset..location.last_offset].to_owned();
 
             return Ok(Some((Token::XmlMarkup(content), location)));
         }
@@ -1398,4 +1415,19 @@ mod tests {
         assert_eq!(body, "(?:)");
         assert_eq!(flags, "gi");
     }
+
+    #[test]
+    fn tokenize_unterminated_literals() {
+        let source = CompilationUnit::new(None, "\"abc".into(), &CompilerOptions::new());
+        let mut tokenizer = Tokenizer::new(&source);
+        let Ok((Token::StringLiteral(value), _)) = tokenizer.scan_ie_div() else { panic!() };
+        assert_eq!(value, "abc");
+        assert!(matches!(tokenizer.scan_ie_div(), Ok((Token::Eof, _))));
+        assert_eq!(source.diagnostics().len(), 1);
+
+        let source = CompilationUnit::new(None, "/* abc".into(), &CompilerOptions::new());
+        let mut tokenizer = Tokenizer::new(&source);
+        assert!(matches!(tokenizer.scan_ie_div(), Ok((Token::Eof, _))));
+        assert_eq!(source.comments()[0].content(), " abc");
+    }
 }
@@ -0,0 +1,2 @@
+mod semantic_tokens;
+pub use semantic_tokens::*;
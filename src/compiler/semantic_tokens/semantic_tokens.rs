@@ -0,0 +1,552 @@
+use crate::ns::*;
+use bitflags::bitflags;
+
+/// Classifies the identifiers of verified programs for semantic highlighting,
+/// after the semantic tokens of the Language Server Protocol.
+///
+/// Identifiers are classified after the symbols that the verifier assigned
+/// to them in a `TreeSemantics`. Identifiers that were not assigned a symbol,
+/// such as unresolved references, are not classified, and neither are references
+/// to compile-time constants other than enumeration members, which the verifier
+/// replaces by their value.
+///
+/// # Example
+///
+/// ```
+/// use hydroper_jet_compiler::ns::*;
+///
+/// let host = SymbolHost::new("", "");
+/// let compilation_unit = CompilationUnit::new(None, "package p { public class C {} }".into(), &CompilerOptions::new());
+/// let program = ParserFacade::parse_program(&compilation_unit).unwrap();
+/// let mut verifier = Verifier::new(&host);
+/// verifier.verify_programs(vec![program.clone()]);
+///
+/// let tokens = SemanticTokens::classify(&program, verifier.ast_to_symbol(), &host);
+/// assert_eq!(tokens[0].token_type, SemanticTokenType::Package);
+/// assert_eq!(tokens[1].token_type, SemanticTokenType::Class);
+/// assert_eq!(SemanticTokens::encode(&tokens), [0, 8, 1, 10, 1, 0, 17, 1, 1, 1]);
+/// ```
+pub struct SemanticTokens;
+
+impl SemanticTokens {
+    /// Classifies the identifiers of a verified program, returning
+    /// tokens in the order of their locations.
+    pub fn classify(program: &Rc<Program>, semantics: &TreeSemantics, host: &SymbolHost) -> Vec<SemanticToken> {
+        let mut classifier = SemanticTokenClassifier {
+            semantics,
+            host,
+            parameters: HashSet::new(),
+            tokens: vec![],
+        };
+        classifier.visit_program(program);
+        let mut tokens = classifier.tokens;
+        tokens.sort_by_key(|token| token.location.first_offset());
+        tokens
+    }
+
+    /// Encodes tokens into the integer format of the Language Server Protocol.
+    ///
+    /// Each token is encoded as five integers: its line relative to the previous
+    /// token, its start character, relative to the previous token if on the same line,
+    /// its length, the index of its type and its modifier bits. Lines are counted
+    /// from zero and characters are counted in UTF-16 code units.
+    pub fn encode(tokens: &[SemanticToken]) -> Vec<u32> {
+        let mut data = vec![];
+        let mut previous_line = 0;
+        let mut previous_start = 0;
+        for token in tokens {
+            let location = &token.location;
+            let compilation_unit = location.compilation_unit();
+            let text = compilation_unit.current_text();
+            let line = location.first_line_number() - 1;
+            let start = text[location.first_line_offset()..location.first_offset()].encode_utf16().count();
+            let length = text[location.first_offset()..location.last_offset()].encode_utf16().count();
+            let relative_start = if line == previous_line { start - previous_start } else { start };
+            data.extend([(line - previous_line) as u32, relative_start as u32, length as u32, token.token_type as u32, token.modifiers.bits()]);
+            previous_line = line;
+            previous_start = start;
+        }
+        data
+    }
+}
+
+/// A classified identifier.
+#[derive(Clone)]
+pub struct SemanticToken {
+    pub location: Location,
+    pub token_type: SemanticTokenType,
+    pub modifiers: SemanticTokenModifiers,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[repr(u32)]
+pub enum SemanticTokenType {
+    Type = 0,
+    Class = 1,
+    Interface = 2,
+    Enum = 3,
+    EnumMember = 4,
+    Method = 5,
+    /// A function that is not a member of a type.
+    Function = 6,
+    Property = 7,
+    Parameter = 8,
+    /// A variable that is not a member of a type, such as a local variable.
+    Variable = 9,
+    Package = 10,
+    Namespace = 11,
+    ImportMeta = 12,
+}
+
+impl SemanticTokenType {
+    /// Token types in the order of their indices, as listed
+    /// in the legend of a language server.
+    pub const ALL: [SemanticTokenType; 13] = [
+        Self::Type,
+        Self::Class,
+        Self::Interface,
+        Self::Enum,
+        Self::EnumMember,
+        Self::Method,
+        Self::Function,
+        Self::Property,
+        Self::Parameter,
+        Self::Variable,
+        Self::Package,
+        Self::Namespace,
+        Self::ImportMeta,
+    ];
+
+    /// Name of the token type in the legend of a language server.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Type => "type",
+            Self::Class => "class",
+            Self::Interface => "interface",
+            Self::Enum => "enum",
+            Self::EnumMember => "enumMember",
+            Self::Method => "method",
+            Self::Function => "function",
+            Self::Property => "property",
+            Self::Parameter => "parameter",
+            Self::Variable => "variable",
+            Self::Package => "package",
+            Self::Namespace => "namespace",
+            Self::ImportMeta => "importMeta",
+        }
+    }
+}
+
+bitflags! {
+    #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+    pub struct SemanticTokenModifiers: u32 {
+        /// The identifier is the name of a definition.
+        const DECLARATION = 0b00000001;
+        const STATIC = 0b00000010;
+        const READONLY = 0b00000100;
+        /// The definition has a `@deprecated` JetDoc tag.
+        const DEPRECATED = 0b00001000;
+        const ABSTRACT = 0b00010000;
+        const ASYNC = 0b00100000;
+    }
+}
+
+impl SemanticTokenModifiers {
+    /// Modifier names in the order of their bits, as listed
+    /// in the legend of a language server.
+    pub const NAMES: [&'static str; 6] = ["declaration", "static", "readonly", "deprecated", "abstract", "async"];
+}
+
+struct SemanticTokenClassifier<'a> {
+    semantics: &'a TreeSemantics,
+    host: &'a SymbolHost,
+    /// Variables declared by parameters.
+    parameters: HashSet<Symbol>,
+    tokens: Vec<SemanticToken>,
+}
+
+impl SemanticTokenClassifier<'_> {
+    fn push(&mut self, location: &Location, token_type: SemanticTokenType, modifiers: SemanticTokenModifiers) {
+        self.tokens.push(SemanticToken {
+            location: location.clone(),
+            token_type,
+            modifiers,
+        });
+    }
+
+    /// Adds a token for the symbol assigned to an identifier, which is
+    /// either a value, such as a reference to a property, a type resolved
+    /// by a type expression or a variable declared by a destructuring pattern.
+    fn add_symbol(&mut self, location: &Location, symbol: &Symbol) {
+        let mut symbol = symbol.clone();
+        while symbol.is_conversion_value() {
+            symbol = symbol.base();
+        }
+        let symbol = &symbol;
+        if symbol.is_import_meta() || symbol.is_import_meta_env() {
+            self.push(location, SemanticTokenType::ImportMeta, SemanticTokenModifiers::empty());
+        } else if symbol.is_enum_constant() {
+            self.push(location, SemanticTokenType::EnumMember, SemanticTokenModifiers::STATIC | SemanticTokenModifiers::READONLY);
+        } else if symbol.is_type_as_reference_value() {
+            self.add_property(location, &symbol.referenced_type(), SemanticTokenModifiers::empty());
+        } else if symbol.is_static_reference_value() || symbol.is_instance_reference_value() || symbol.is_scope_reference_value() || symbol.is_package_reference_value() {
+            self.add_property(location, &symbol.property(), SemanticTokenModifiers::empty());
+        } else if symbol.is_type() || symbol.is_package() {
+            self.add_property(location, symbol, SemanticTokenModifiers::empty());
+        } else if symbol.is_variable_property() {
+            self.add_property(location, symbol, SemanticTokenModifiers::DECLARATION);
+        }
+    }
+
+    fn add_property(&mut self, location: &Location, property: &Symbol, modifiers: SemanticTokenModifiers) {
+        if let Some((token_type, property_modifiers)) = self.classify_property(property) {
+            self.push(location, token_type, modifiers | property_modifiers);
+        }
+    }
+
+    /// Adds a token for the name of a type definition.
+    fn add_type_definition(&mut self, location: &Location, symbol: Option<Symbol>, token_type: SemanticTokenType) {
+        let modifiers = symbol.and_then(|symbol| self.classify_property(&symbol)).map_or(SemanticTokenModifiers::empty(), |(_, modifiers)| modifiers);
+        self.push(location, token_type, SemanticTokenModifiers::DECLARATION | modifiers);
+    }
+
+    /// Adds tokens for the names of a package, returning the package
+    /// if it exists.
+    fn add_package_name(&mut self, name: &[(String, Location)], modifiers: SemanticTokenModifiers) -> Option<Symbol> {
+        let mut package = Some(self.host.top_level_package());
+        for (name_1, location) in name {
+            package = package.and_then(|package| package.subpackages().get(name_1));
+            let deprecated = package.as_ref().is_some_and(Self::is_deprecated);
+            self.push(location, SemanticTokenType::Package, if deprecated { modifiers | SemanticTokenModifiers::DEPRECATED } else { modifiers });
+        }
+        package
+    }
+
+    /// Adds tokens for the names of a `a.b.c` expression that the verifier
+    /// resolved as a fully qualified package name.
+    fn add_package_prefix(&mut self, base: &Rc<Expression>) {
+        let mut names = vec![];
+        let mut exp = base.clone();
+        loop {
+            if self.semantics.get(&exp).is_some() {
+                return;
+            }
+            match exp.as_ref() {
+                Expression::QualifiedIdentifier(id) => {
+                    let Some(name) = id.to_identifier_name() else {
+                        return;
+                    };
+                    names.insert(0, name);
+                    break;
+                },
+                Expression::Member(me) => {
+                    let Some(name) = me.identifier.to_identifier_name() else {
+                        return;
+                    };
+                    names.insert(0, name);
+                    exp = me.base.clone();
+                },
+                _ => return,
+            }
+        }
+        let mut package = self.host.top_level_package();
+        for (name, _) in &names {
+            let Some(subpackage) = package.subpackages().get(name) else {
+                return;
+            };
+            package = subpackage;
+        }
+        self.add_package_name(&names, SemanticTokenModifiers::empty());
+    }
+
+    fn classify_property(&self, property: &Symbol) -> Option<(SemanticTokenType, SemanticTokenModifiers)> {
+        let mut modifiers = SemanticTokenModifiers::empty();
+        if Self::is_deprecated(property) {
+            modifiers |= SemanticTokenModifiers::DEPRECATED;
+        }
+        let token_type = if property.is_class_type() {
+            modifiers.set(SemanticTokenModifiers::ABSTRACT, property.is_abstract());
+            modifiers.set(SemanticTokenModifiers::STATIC, property.is_static());
+            SemanticTokenType::Class
+        } else if property.is_interface_type() {
+            SemanticTokenType::Interface
+        } else if property.is_enum_type() {
+            SemanticTokenType::Enum
+        } else if property.is_type() || property.is_alias() {
+            SemanticTokenType::Type
+        } else if property.is_package() || property.is_package_set() {
+            SemanticTokenType::Package
+        } else if property.is_method() {
+            modifiers.set(SemanticTokenModifiers::ABSTRACT, property.is_abstract());
+            modifiers.set(SemanticTokenModifiers::ASYNC, property.is_async());
+            modifiers.set(SemanticTokenModifiers::STATIC, self.is_static_member(property));
+            if property.parent().is_some_and(|parent| parent.is_type()) {
+                SemanticTokenType::Method
+            } else {
+                SemanticTokenType::Function
+            }
+        } else if property.is_virtual_property() {
+            modifiers.set(SemanticTokenModifiers::READONLY, property.read_only(self.host));
+            modifiers.set(SemanticTokenModifiers::STATIC, self.is_static_member(property));
+            SemanticTokenType::Property
+        } else if property.is_variable_property() {
+            let is_static = self.is_static_member(property);
+            modifiers.set(SemanticTokenModifiers::READONLY, property.read_only(self.host));
+            modifiers.set(SemanticTokenModifiers::STATIC, is_static);
+            let parent = property.parent();
+            if is_static && parent.as_ref().is_some_and(|parent| parent.is_enum_type()) {
+                SemanticTokenType::EnumMember
+            } else if property.static_type(self.host) == self.host.namespace_type() {
+                SemanticTokenType::Namespace
+            } else if parent.as_ref().is_some_and(|parent| parent.is_type()) {
+                SemanticTokenType::Property
+            } else if self.parameters.contains(property) {
+                SemanticTokenType::Parameter
+            } else {
+                SemanticTokenType::Variable
+            }
+        } else {
+            return None;
+        };
+        Some((token_type, modifiers))
+    }
+
+    /// Indicates whether a property is a static member of a class or enumeration.
+    fn is_static_member(&self, property: &Symbol) -> bool {
+        let Some(parent) = property.parent() else {
+            return false;
+        };
+        (parent.is_class_type() || parent.is_enum_type())
+            && parent.static_properties(self.host).get(&property.name()).is_some_and(|member| &member == property)
+    }
+
+    /// Indicates whether a symbol has a `@deprecated` JetDoc tag.
+    fn is_deprecated(symbol: &Symbol) -> bool {
+        let documented = symbol.is_class_type() || symbol.is_interface_type() || symbol.is_enum_type()
+            || symbol.is_alias() || symbol.is_package() || symbol.is_package_set()
+            || symbol.is_variable_property() || symbol.is_virtual_property() || symbol.is_method();
+        documented && symbol.jetdoc().is_some_and(|jetdoc| {
+            jetdoc.tags.iter().any(|(tag, _)| matches!(tag, JetDocTag::Deprecated { .. }))
+        })
+    }
+
+    /// Collects the variables declared by a parameter pattern.
+    fn declare_parameters(&mut self, pattern: &Rc<Expression>) {
+        match pattern.as_ref() {
+            Expression::QualifiedIdentifier(_) => {
+                if let Some(variable) = self.semantics.get(pattern) {
+                    self.parameters.insert(variable);
+                }
+            },
+            Expression::Unary(e) => {
+                self.declare_parameters(&e.expression);
+            },
+            Expression::ArrayLiteral(al) => {
+                for element in &al.elements {
+                    if let Element::Expression(e) | Element::Rest((e, _)) = element {
+                        self.declare_parameters(e);
+                    }
+                }
+            },
+            Expression::ObjectInitializer(oi) => {
+                for field in &oi.fields {
+                    match field.as_ref() {
+                        InitializerField::Field { value: Some(value), .. } => self.declare_parameters(value),
+                        InitializerField::Rest((e, _)) => self.declare_parameters(e),
+                        _ => {},
+                    }
+                }
+            },
+            _ => {},
+        }
+    }
+}
+
+impl Visitor for SemanticTokenClassifier<'_> {
+    fn visit_package_definition(&mut self, node: &PackageDefinition) {
+        self.add_package_name(&node.name, SemanticTokenModifiers::DECLARATION);
+        self.walk_package_definition(node);
+    }
+
+    fn visit_import_directive(&mut self, node: &ImportDirective) {
+        let package = self.add_package_name(&node.package_name, SemanticTokenModifiers::empty());
+        let property = match (&package, &node.import_specifier) {
+            (Some(package), ImportSpecifier::Identifier((name, location))) => {
+                let property = package.properties(self.host).get(name);
+                if let Some(property) = &property {
+                    self.add_property(location, property, SemanticTokenModifiers::empty());
+                }
+                property
+            },
+            _ => None,
+        };
+        if let Some((_, location)) = &node.alias {
+            match (&node.import_specifier, property) {
+                (ImportSpecifier::Wildcard(_), _) => self.push(location, SemanticTokenType::Package, SemanticTokenModifiers::DECLARATION),
+                (_, Some(property)) => self.add_property(location, &property, SemanticTokenModifiers::DECLARATION),
+                _ => {},
+            }
+        }
+    }
+
+    fn visit_directive(&mut self, node: &Rc<Directive>) {
+        match node.as_ref() {
+            Directive::ClassDefinition(defn) => self.add_type_definition(&defn.name.1, self.semantics.get(node), SemanticTokenType::Class),
+            Directive::EnumDefinition(defn) => self.add_type_definition(&defn.name.1, self.semantics.get(node), SemanticTokenType::Enum),
+            Directive::InterfaceDefinition(defn) => self.add_type_definition(&defn.name.1, self.semantics.get(node), SemanticTokenType::Interface),
+            Directive::TypeDefinition(defn) => self.add_type_definition(&defn.left.1, self.semantics.get(node), SemanticTokenType::Type),
+            _ => {},
+        }
+        self.walk_directive(node);
+    }
+
+    fn visit_function_definition(&mut self, node: &FunctionDefinition) {
+        let function = self.semantics.get(&node.common);
+        let (token_type, mut modifiers) = function.and_then(|function| self.classify_property(&function))
+            .unwrap_or((SemanticTokenType::Function, SemanticTokenModifiers::empty()));
+        if Attribute::find_static(&node.attributes).is_some() {
+            modifiers |= SemanticTokenModifiers::STATIC;
+        }
+        let token_type = match &node.name {
+            FunctionName::Getter(_) | FunctionName::Setter(_) => SemanticTokenType::Property,
+            FunctionName::Constructor(_) => SemanticTokenType::Class,
+            _ => token_type,
+        };
+        self.push(&node.name.location(), token_type, SemanticTokenModifiers::DECLARATION | modifiers);
+        self.walk_function_definition(node);
+    }
+
+    fn visit_parameter(&mut self, node: &Parameter) {
+        self.declare_parameters(&node.destructuring.destructuring);
+        self.walk_parameter(node);
+    }
+
+    fn visit_expression(&mut self, node: &Rc<Expression>) {
+        match node.as_ref() {
+            Expression::QualifiedIdentifier(QualifiedIdentifier { id: QualifiedIdentifierIdentifier::Id((_, location)), .. }) |
+            Expression::Member(MemberExpression { identifier: QualifiedIdentifier { id: QualifiedIdentifierIdentifier::Id((_, location)), .. }, .. }) => {
+                if let Some(symbol) = self.semantics.get(node) {
+                    if let Expression::Member(me) = node.as_ref() {
+                        self.add_package_prefix(&me.base);
+                    }
+                    self.add_symbol(location, &symbol);
+                }
+            },
+            Expression::ImportMeta(e) => {
+                self.push(&e.location, SemanticTokenType::ImportMeta, SemanticTokenModifiers::empty());
+            },
+            _ => {},
+        }
+        self.walk_expression(node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+
+    const JET_LANG: &str = r#"
+        package jet.lang {
+            public class Object {}
+            public class Boolean {}
+            public class Class {}
+            public class Function {}
+            public class Number {}
+            public class String {}
+        }
+    "#;
+
+    fn classify(text: &str) -> Vec<(String, SemanticTokenType, SemanticTokenModifiers)> {
+        let host = SymbolHost::new("", "");
+        let compilation_unit = CompilationUnit::new(None, JET_LANG.to_owned() + text, &CompilerOptions::new());
+        let program = ParserFacade::parse_program(&compilation_unit).unwrap();
+        let mut verifier = Verifier::new(&host);
+        verifier.verify_programs(vec![program.clone()]);
+        assert!(compilation_unit.diagnostics().is_empty(), "{:?}", compilation_unit.diagnostics().iter().map(|d| d.format_message_english()).collect::<Vec<_>>());
+        let text = compilation_unit.current_text();
+        SemanticTokens::classify(&program, verifier.ast_to_symbol(), &host).into_iter()
+            .filter(|token| token.location.first_offset() >= JET_LANG.len())
+            .map(|token| (text[token.location.first_offset()..token.location.last_offset()].to_owned(), token.token_type, token.modifiers))
+            .collect()
+    }
+
+    #[test]
+    fn classifying_identifiers() {
+        use SemanticTokenType::*;
+        let declaration = SemanticTokenModifiers::DECLARATION;
+        let r#static = SemanticTokenModifiers::STATIC;
+        let readonly = SemanticTokenModifiers::READONLY;
+        let deprecated = SemanticTokenModifiers::DEPRECATED;
+        let r#abstract = SemanticTokenModifiers::ABSTRACT;
+        let none = SemanticTokenModifiers::empty();
+        let tokens = classify(r#"
+            package p {
+                /** @deprecated */
+                public abstract class A {
+                    public static var k: Number = 1;
+                    public var x: Number = 0;
+                    public abstract function m(): void;
+                    public static function s(): void {
+                        k = 2;
+                    }
+                }
+                public enum E {
+                    const FOO;
+                }
+                public interface I {}
+                public type T = jet.lang.Number;
+                public function f(a: A, e: E): void {
+                    const y: Number = a.x;
+                    a.m();
+                    import.meta;
+                }
+            }
+        "#);
+        assert_eq!(tokens, vec![
+            ("p".into(), Package, declaration),
+            ("A".into(), Class, declaration | deprecated | r#abstract),
+            ("k".into(), Property, declaration | r#static),
+            ("Number".into(), Class, none),
+            ("x".into(), Property, declaration),
+            ("Number".into(), Class, none),
+            ("m".into(), Method, declaration | r#abstract),
+            ("s".into(), Method, declaration | r#static),
+            ("k".into(), Property, r#static),
+            ("E".into(), Enum, declaration),
+            ("FOO".into(), EnumMember, declaration | r#static | readonly),
+            ("I".into(), Interface, declaration),
+            ("T".into(), Type, declaration),
+            ("jet".into(), Package, none),
+            ("lang".into(), Package, none),
+            ("Number".into(), Class, none),
+            ("f".into(), Function, declaration),
+            ("a".into(), Parameter, declaration),
+            ("A".into(), Class, deprecated | r#abstract),
+            ("e".into(), Parameter, declaration),
+            ("E".into(), Enum, none),
+            ("y".into(), Variable, declaration | readonly),
+            ("Number".into(), Class, none),
+            ("a".into(), Parameter, none),
+            ("x".into(), Property, none),
+            ("a".into(), Parameter, none),
+            ("m".into(), Method, r#abstract),
+            ("import.meta".into(), ImportMeta, none),
+        ]);
+    }
+
+    #[test]
+    fn encoding_tokens() {
+        let compilation_unit = CompilationUnit::new(None, "\"\u{10000}\"; x;\n  y".into(), &CompilerOptions::new());
+        let token = |first_offset, last_offset, token_type, modifiers| SemanticToken {
+            location: Location::with_offsets(&compilation_unit, first_offset, last_offset),
+            token_type,
+            modifiers,
+        };
+        let tokens = [
+            token(8, 9, SemanticTokenType::Variable, SemanticTokenModifiers::empty()),
+            token(13, 14, SemanticTokenType::Parameter, SemanticTokenModifiers::DECLARATION | SemanticTokenModifiers::READONLY),
+        ];
+        assert_eq!(SemanticTokens::encode(&tokens), [0, 6, 1, 9, 0, 1, 2, 1, 8, 5]);
+        assert_eq!(SemanticTokenType::ALL.iter().map(|token_type| *token_type as usize).collect::<Vec<_>>(), (0..SemanticTokenType::ALL.len()).collect::<Vec<_>>());
+    }
+}
@@ -12,8 +12,12 @@ mod abstract_range_number;
 pub use abstract_range_number::*;
 mod type_substitution;
 pub use type_substitution::*;
+mod type_inference;
+pub use type_inference::*;
 mod type_conversion;
 pub use type_conversion::*;
+mod constant_folding;
+pub use constant_folding::*;
 mod property_resolution;
 pub use property_resolution::*;
 mod method_overriding;
@@ -1,5 +1,5 @@
 use crate::ns::*;
-use num_traits::{FromPrimitive, One, Zero};
+use num_traits::{FromPrimitive, One, Signed, ToPrimitive, Zero};
 use num_bigint::BigInt;
 use std::ops::{BitXor, BitOr};
 
@@ -238,7 +238,7 @@ impl AbstractRangeNumber {
                 Self::Single(v) => Self::Long(unsafe { v.to_int_unchecked() }),
                 Self::Number(v) => Self::Long(unsafe { v.to_int_unchecked() }),
                 Self::BigInt(v) => Self::Long(v.try_into().unwrap_or(0)),
-                Self::Long(v) => Self::Long((*v).try_into().unwrap_or(0)),
+                Self::Long(v) => Self::Long(*v),
             }
         } else if target_type == &big_int_type {
             match self {
@@ -275,4 +275,179 @@ impl AbstractRangeNumber {
             _ => false,
         }
     }
+
+    /// Converts a floating point value into a 32-bit signed integer
+    /// with the wrapping semantics of the ECMAScript *ToInt32*() operation.
+    fn float_to_int32(v: f64) -> i32 {
+        if !v.is_finite() {
+            return 0;
+        }
+        v.trunc().rem_euclid(4_294_967_296.0) as u32 as i32
+    }
+
+    /// Performs addition, wrapping around for `Long` and following
+    /// IEEE 754 for floating point types. Returns `None` if the operands
+    /// are of different types.
+    pub fn plus(&self, rhs: &Self) -> Option<Self> {
+        match (self, rhs) {
+            (Self::Single(a), Self::Single(b)) => Some(Self::Single(a + b)),
+            (Self::Number(a), Self::Number(b)) => Some(Self::Number(a + b)),
+            (Self::BigInt(a), Self::BigInt(b)) => Some(Self::BigInt(a + b)),
+            (Self::Long(a), Self::Long(b)) => Some(Self::Long(a.wrapping_add(*b))),
+            _ => None,
+        }
+    }
+
+    pub fn minus(&self, rhs: &Self) -> Option<Self> {
+        match (self, rhs) {
+            (Self::Single(a), Self::Single(b)) => Some(Self::Single(a - b)),
+            (Self::Number(a), Self::Number(b)) => Some(Self::Number(a - b)),
+            (Self::BigInt(a), Self::BigInt(b)) => Some(Self::BigInt(a - b)),
+            (Self::Long(a), Self::Long(b)) => Some(Self::Long(a.wrapping_sub(*b))),
+            _ => None,
+        }
+    }
+
+    pub fn times(&self, rhs: &Self) -> Option<Self> {
+        match (self, rhs) {
+            (Self::Single(a), Self::Single(b)) => Some(Self::Single(a * b)),
+            (Self::Number(a), Self::Number(b)) => Some(Self::Number(a * b)),
+            (Self::BigInt(a), Self::BigInt(b)) => Some(Self::BigInt(a * b)),
+            (Self::Long(a), Self::Long(b)) => Some(Self::Long(a.wrapping_mul(*b))),
+            _ => None,
+        }
+    }
+
+    /// Performs division. Integer division by zero results in `None`,
+    /// as it fails at runtime.
+    pub fn divided_by(&self, rhs: &Self) -> Option<Self> {
+        match (self, rhs) {
+            (Self::Single(a), Self::Single(b)) => Some(Self::Single(a / b)),
+            (Self::Number(a), Self::Number(b)) => Some(Self::Number(a / b)),
+            (Self::BigInt(a), Self::BigInt(b)) => if b.is_zero() { None } else { Some(Self::BigInt(a / b)) },
+            (Self::Long(a), Self::Long(b)) => if *b == 0 { None } else { Some(Self::Long(a.wrapping_div(*b))) },
+            _ => None,
+        }
+    }
+
+    /// Performs remainder. Integer remainder by zero results in `None`,
+    /// as it fails at runtime.
+    pub fn remainder(&self, rhs: &Self) -> Option<Self> {
+        match (self, rhs) {
+            (Self::Single(a), Self::Single(b)) => Some(Self::Single(a % b)),
+            (Self::Number(a), Self::Number(b)) => Some(Self::Number(a % b)),
+            (Self::BigInt(a), Self::BigInt(b)) => if b.is_zero() { None } else { Some(Self::BigInt(a % b)) },
+            (Self::Long(a), Self::Long(b)) => if *b == 0 { None } else { Some(Self::Long(a.wrapping_rem(*b))) },
+            _ => None,
+        }
+    }
+
+    /// Performs exponentiation. Integer exponentiation by a negative
+    /// exponent, or resulting in an excessively large `BigInt`, results in `None`.
+    pub fn power(&self, rhs: &Self) -> Option<Self> {
+        match (self, rhs) {
+            (Self::Single(a), Self::Single(b)) => Some(Self::Single(a.powf(*b))),
+            (Self::Number(a), Self::Number(b)) => Some(Self::Number(a.powf(*b))),
+            (Self::BigInt(a), Self::BigInt(b)) => {
+                let b = b.to_u32()?;
+                if a.bits().saturating_mul(b as u64) > 1 << 20 {
+                    return None;
+                }
+                Some(Self::BigInt(a.pow(b)))
+            },
+            (Self::Long(a), Self::Long(b)) => Some(Self::Long(a.wrapping_pow((*b).try_into().ok()?))),
+            _ => None,
+        }
+    }
+
+    pub fn shift_left(&self, rhs: &Self) -> Option<Self> {
+        match (self, rhs) {
+            (Self::Single(a), Self::Single(b)) => Some(Self::Single(Self::float_to_int32(*a as f64).wrapping_shl(Self::float_to_int32(*b as f64) as u32) as f32)),
+            (Self::Number(a), Self::Number(b)) => Some(Self::Number(Self::float_to_int32(*a).wrapping_shl(Self::float_to_int32(*b) as u32) as f64)),
+            (Self::BigInt(a), Self::BigInt(b)) => {
+                let b = b.to_u32()?;
+                if a.bits().saturating_add(b as u64) > 1 << 20 {
+                    return None;
+                }
+                Some(Self::BigInt(a << b))
+            },
+            (Self::Long(a), Self::Long(b)) => Some(Self::Long(a.wrapping_shl(*b as u32))),
+            _ => None,
+        }
+    }
+
+    /// Performs an arithmetic right shift.
+    pub fn shift_right(&self, rhs: &Self) -> Option<Self> {
+        match (self, rhs) {
+            (Self::Single(a), Self::Single(b)) => Some(Self::Single(Self::float_to_int32(*a as f64).wrapping_shr(Self::float_to_int32(*b as f64) as u32) as f32)),
+            (Self::Number(a), Self::Number(b)) => Some(Self::Number(Self::float_to_int32(*a).wrapping_shr(Self::float_to_int32(*b) as u32) as f64)),
+            (Self::BigInt(a), Self::BigInt(b)) => {
+                if b.is_negative() {
+                    return None;
+                }
+                Some(Self::BigInt(a >> b.to_u64().unwrap_or(u64::MAX)))
+            },
+            (Self::Long(a), Self::Long(b)) => Some(Self::Long(a.wrapping_shr(*b as u32))),
+            _ => None,
+        }
+    }
+
+    /// Performs a logical right shift. `BigInt` has no logical
+    /// right shift and results in `None`.
+    pub fn shift_right_unsigned(&self, rhs: &Self) -> Option<Self> {
+        match (self, rhs) {
+            (Self::Single(a), Self::Single(b)) => Some(Self::Single((Self::float_to_int32(*a as f64) as u32).wrapping_shr(Self::float_to_int32(*b as f64) as u32) as f32)),
+            (Self::Number(a), Self::Number(b)) => Some(Self::Number((Self::float_to_int32(*a) as u32).wrapping_shr(Self::float_to_int32(*b) as u32) as f64)),
+            (Self::Long(a), Self::Long(b)) => Some(Self::Long((*a as u64).wrapping_shr(*b as u32) as i64)),
+            _ => None,
+        }
+    }
+
+    pub fn bitwise_and(&self, rhs: &Self) -> Option<Self> {
+        match (self, rhs) {
+            (Self::Single(a), Self::Single(b)) => Some(Self::Single((Self::float_to_int32(*a as f64) & Self::float_to_int32(*b as f64)) as f32)),
+            (Self::Number(a), Self::Number(b)) => Some(Self::Number((Self::float_to_int32(*a) & Self::float_to_int32(*b)) as f64)),
+            (Self::BigInt(a), Self::BigInt(b)) => Some(Self::BigInt(a & b)),
+            (Self::Long(a), Self::Long(b)) => Some(Self::Long(a & b)),
+            _ => None,
+        }
+    }
+
+    pub fn bitwise_xor(&self, rhs: &Self) -> Option<Self> {
+        match (self, rhs) {
+            (Self::Single(a), Self::Single(b)) => Some(Self::Single((Self::float_to_int32(*a as f64) ^ Self::float_to_int32(*b as f64)) as f32)),
+            (Self::Number(a), Self::Number(b)) => Some(Self::Number((Self::float_to_int32(*a) ^ Self::float_to_int32(*b)) as f64)),
+            (Self::BigInt(a), Self::BigInt(b)) => Some(Self::BigInt(a ^ b)),
+            (Self::Long(a), Self::Long(b)) => Some(Self::Long(a ^ b)),
+            _ => None,
+        }
+    }
+
+    pub fn bitwise_or(&self, rhs: &Self) -> Option<Self> {
+        match (self, rhs) {
+            (Self::Single(a), Self::Single(b)) => Some(Self::Single((Self::float_to_int32(*a as f64) | Self::float_to_int32(*b as f64)) as f32)),
+            (Self::Number(a), Self::Number(b)) => Some(Self::Number((Self::float_to_int32(*a) | Self::float_to_int32(*b)) as f64)),
+            (Self::BigInt(a), Self::BigInt(b)) => Some(Self::BigInt(a | b)),
+            (Self::Long(a), Self::Long(b)) => Some(Self::Long(a | b)),
+            _ => None,
+        }
+    }
+
+    pub fn bitwise_not(&self) -> Self {
+        match self {
+            Self::Single(v) => Self::Single(!Self::float_to_int32(*v as f64) as f32),
+            Self::Number(v) => Self::Number(!Self::float_to_int32(*v) as f64),
+            Self::BigInt(v) => Self::BigInt(!v),
+            Self::Long(v) => Self::Long(!v),
+        }
+    }
+
+    pub fn negate(&self) -> Self {
+        match self {
+            Self::Single(v) => Self::Single(-v),
+            Self::Number(v) => Self::Number(-v),
+            Self::BigInt(v) => Self::BigInt(-v),
+            Self::Long(v) => Self::Long(v.wrapping_neg()),
+        }
+    }
 }
\ No newline at end of file
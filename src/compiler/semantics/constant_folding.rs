@@ -0,0 +1,175 @@
+use crate::ns::*;
+
+/// Evaluates operators over compile-time constants.
+///
+/// Each method returns `None` if the operation cannot be folded, in which
+/// case the expression results in a non-constant value.
+pub struct ConstantFolding<'a>(pub &'a SymbolHost);
+
+impl<'a> ConstantFolding<'a> {
+    pub fn fold_unary(&self, operator: Operator, operand: &Symbol) -> Option<Symbol> {
+        if !operand.is_constant() {
+            return None;
+        }
+        let factory = self.0.factory();
+        let static_type = operand.static_type(self.0);
+        match operator {
+            Operator::LogicalNot if operand.is_boolean_constant() => {
+                Some(factory.create_boolean_constant(!operand.boolean_value(), &static_type))
+            },
+            Operator::Positive if operand.is_number_constant() => {
+                Some(factory.create_number_constant(operand.number_value(), &static_type))
+            },
+            Operator::Negative if operand.is_number_constant() => {
+                Some(factory.create_number_constant(operand.number_value().negate(), &static_type))
+            },
+            Operator::BitwiseNot if operand.is_number_constant() => {
+                Some(factory.create_number_constant(operand.number_value().bitwise_not(), &static_type))
+            },
+            _ => None,
+        }
+    }
+
+    /// Folds a binary operator. For operators other than `??`, the operands
+    /// are expected to be of the same type, as the right operand is limited
+    /// to the type of the left operand.
+    pub fn fold_binary(&self, operator: Operator, left: &Symbol, right: &Symbol) -> Option<Symbol> {
+        if !(left.is_constant() && right.is_constant()) {
+            return None;
+        }
+        let factory = self.0.factory();
+        let left_type = left.static_type(self.0);
+
+        if operator == Operator::NullCoalescing {
+            return if left.is_null_constant() || left.is_undefined_constant() {
+                Some(right.clone())
+            } else if !left_type.is_nullable_type() {
+                Some(left.clone())
+            } else {
+                None
+            };
+        }
+
+        // String concatenation
+        if operator == Operator::Add && left.is_string_constant() {
+            let right = self.constant_to_string(right)?;
+            return Some(factory.create_string_constant(left.string_value() + &right, &self.0.string_type()));
+        }
+
+        if left.is_boolean_constant() && right.is_boolean_constant() {
+            let (a, b) = (left.boolean_value(), right.boolean_value());
+            let v = match operator {
+                Operator::LogicalAnd => a && b,
+                Operator::LogicalXor => a != b,
+                Operator::LogicalOr => a || b,
+                _ => return None,
+            };
+            return Some(factory.create_boolean_constant(v, &left_type));
+        }
+
+        // Bitwise operators over set enumerations
+        if left.is_enum_constant() && right.is_enum_constant() {
+            let (a, b) = (left.number_value(), right.number_value());
+            let v = match operator {
+                Operator::BitwiseAnd => a.bitwise_and(&b)?,
+                Operator::BitwiseXor => a.bitwise_xor(&b)?,
+                Operator::BitwiseOr => a.bitwise_or(&b)?,
+                _ => return None,
+            };
+            if !left_type.is_set_enumeration() {
+                return None;
+            }
+            return Some(factory.create_enum_constant(v, &left_type));
+        }
+
+        if left.is_number_constant() && right.is_number_constant() {
+            let (a, b) = (left.number_value(), right.number_value());
+            let v = match operator {
+                Operator::Add => a.plus(&b)?,
+                Operator::Subtract => a.minus(&b)?,
+                Operator::Multiply => a.times(&b)?,
+                Operator::Divide => a.divided_by(&b)?,
+                Operator::Remainder => a.remainder(&b)?,
+                Operator::Power => a.power(&b)?,
+                Operator::ShiftLeft => a.shift_left(&b)?,
+                Operator::ShiftRight => a.shift_right(&b)?,
+                Operator::ShiftRightUnsigned => a.shift_right_unsigned(&b)?,
+                Operator::BitwiseAnd => a.bitwise_and(&b)?,
+                Operator::BitwiseXor => a.bitwise_xor(&b)?,
+                Operator::BitwiseOr => a.bitwise_or(&b)?,
+                _ => return None,
+            };
+            return Some(factory.create_number_constant(v, &left_type));
+        }
+
+        None
+    }
+
+    /// Converts a constant into a string as done by string concatenation.
+    /// Floating point numbers are converted only where their
+    /// decimal representation is unambiguous.
+    pub fn constant_to_string(&self, value: &Symbol) -> Option<String> {
+        if value.is_string_constant() {
+            return Some(value.string_value());
+        }
+        if value.is_char_constant() {
+            return Some(value.char_value().to_string());
+        }
+        if value.is_boolean_constant() {
+            return Some(value.boolean_value().to_string());
+        }
+        if value.is_null_constant() {
+            return Some("null".into());
+        }
+        if value.is_undefined_constant() {
+            return Some("undefined".into());
+        }
+        if value.is_number_constant() {
+            return match value.number_value() {
+                AbstractRangeNumber::Number(v) => Self::float_to_string(v),
+                AbstractRangeNumber::Single(v) => Self::float_to_string(v as f64),
+                AbstractRangeNumber::BigInt(v) => Some(v.to_string()),
+                AbstractRangeNumber::Long(v) => Some(v.to_string()),
+            };
+        }
+        None
+    }
+
+    fn float_to_string(v: f64) -> Option<String> {
+        if v.is_nan() {
+            Some("NaN".into())
+        } else if v.is_infinite() {
+            Some(if v > 0.0 { "Infinity" } else { "-Infinity" }.into())
+        } else if v == 0.0 {
+            Some("0".into())
+        } else if (1e-6..1e21).contains(&v.abs()) {
+            Some(v.to_string())
+        } else {
+            None
+        }
+    }
+
+    /// Indicates whether two constants of the same type are equal.
+    /// Returns `None` if the constants cannot be compared.
+    pub fn constants_equal(&self, left: &Symbol, right: &Symbol) -> Option<bool> {
+        if !(left.is_constant() && right.is_constant()) {
+            return None;
+        }
+        if left.is_null_constant() || left.is_undefined_constant() || right.is_null_constant() || right.is_undefined_constant() {
+            return Some(left.is_null_constant() == right.is_null_constant() && left.is_undefined_constant() == right.is_undefined_constant());
+        }
+        if left.is_string_constant() && right.is_string_constant() {
+            return Some(left.string_value() == right.string_value());
+        }
+        if left.is_char_constant() && right.is_char_constant() {
+            return Some(left.char_value() == right.char_value());
+        }
+        if left.is_boolean_constant() && right.is_boolean_constant() {
+            return Some(left.boolean_value() == right.boolean_value());
+        }
+        if (left.is_number_constant() && right.is_number_constant()) || (left.is_enum_constant() && right.is_enum_constant()) {
+            return Some(left.number_value() == right.number_value());
+        }
+        None
+    }
+}
@@ -16,13 +16,15 @@ impl<'a> InterfaceImplementations<'a> {
             interface.throw_if_unresolved().map_err(|_| DeferVerificationError)?;
 
             for (name, item) in interface.prototype(self.0).borrow().iter() {
-                let implementor_item = implementor.prototype(self.0).get(name);
-
-                if implementor_item.is_some() && implementor_item.clone().unwrap().visibility() != expected_visibility {
-                    log.push(InterfaceImplementationLog::WrongVisibility { name: name.clone(), expected_visibility });
+                // Directives not allowed in interfaces are reported by the
+                // parser, but may still define other properties.
+                if !(item.is_method() || item.is_virtual_property()) {
+                    continue;
                 }
 
-                if implementor_item.is_none() {
+                let implementor_item = implementor.prototype(self.0).get(name);
+
+                let Some(implementor_item) = implementor_item else {
                     if item.is_virtual_property() {
                         if item.getter(self.0).is_some() && !item.getter(self.0).unwrap().is_optional_interface_method() {
                             log.push(InterfaceImplementationLog::UnimplementedGetter { name: name.clone() });
@@ -33,9 +35,15 @@ impl<'a> InterfaceImplementations<'a> {
                     } else if !item.is_optional_interface_method() {
                         log.push(InterfaceImplementationLog::UnimplementedMethod { name: name.clone() });
                     }
+                    continue;
+                };
+
+                if implementor_item.visibility() != expected_visibility {
+                    log.push(InterfaceImplementationLog::WrongVisibility { name: name.clone(), expected_visibility });
+                }
+
                 // Verify accessors
-                } else if item.is_virtual_property() {
-                    let implementor_item = implementor_item.unwrap();
+                if item.is_virtual_property() {
                     if !implementor_item.is_virtual_property() {
                         log.push(InterfaceImplementationLog::PropertyMustBeVirtualProperty { name: name.clone() });
                     } else {
@@ -75,7 +83,6 @@ impl<'a> InterfaceImplementations<'a> {
                     }
                 // Verify regular method
                 } else {
-                    let implementor_item = implementor_item.unwrap();
                     if !implementor_item.is_method() {
                         log.push(InterfaceImplementationLog::PropertyMustBeMethod { name: name.clone() });
                     }
@@ -18,7 +18,7 @@ impl SemanticPropertyKey {
             },
             Self::Number(d) => {
                 let number_type = host.number_type();
-                host.factory().create_number_constant(AbstractRangeNumber::Number(d.clone()), &number_type)
+                host.factory().create_number_constant(AbstractRangeNumber::Number(*d), &number_type)
             },
             Self::Value(s) => s.clone(),
         }
@@ -91,19 +91,55 @@ impl<'a> PropertyResolution<'a> {
         let string_key = key.string_value();
         let number_key = key.number_value();
 
-        // 3. If base is a value whose type is * or if key is not a String or Number constant
+        // 3. If base is the import.meta symbol
+        if base.is_import_meta() {
+            // Key must be a String constant
+            let Some(key) = string_key else {
+                return Ok(None);
+            };
+
+            match key.as_ref() {
+                "env" => {
+                    return Ok(Some(self.0.import_meta_env()));
+                },
+                "output" => {
+                    return Ok(Some(self.0.factory().create_import_meta_output_value()));
+                },
+                _ => {
+                    return Ok(None);
+                },
+            }
+        }
+
+        // 4. If base is the import.meta.env symbol
+        if base.is_import_meta_env() {
+            // Key must be a String constant
+            let Some(key) = string_key else {
+                return Ok(None);
+            };
+
+            let ev_dict = self.0.preload_environment_variables();
+            if let Some(ev) = ev_dict.get(&key) {
+                let string_type = self.0.string_type();
+                return Ok(Some(self.0.factory().create_string_constant(ev.clone(), &string_type)));
+            } else {
+                return Ok(None);
+            }
+        }
+
+        // 5. If base is a value whose type is * or if key is not a String or Number constant
         //     1. Return DynamicReferenceValue(base, qual, key)
         if (base.is_value() && base.static_type(self.0) == self.0.any_type()) || !(string_key.is_some() || number_key.is_some()) {
             let k = key.symbol(self.0);
             return Ok(Some(self.0.factory().create_dynamic_reference_value(base, qual, &k, disamb)));
         }
 
-        // 4. Return undefined if qual is not undefined.
+        // 6. Return undefined if qual is not undefined.
         if qual.is_some() {
             return Ok(None);
         }
 
-        // 5. If base is a class or enum
+        // 7. If base is a class or enum
         if base.is_class_type() || base.is_enum_type() {
             // Key must be a String constant
             let Some(key) = string_key else {
@@ -125,11 +161,11 @@ impl<'a> PropertyResolution<'a> {
             return Ok(None);
         }
 
-        // 6. If base is a value
+        // 8. If base is a value
         if base.is_value() {
             let base_type = base.static_type(self.0);
 
-            // 6.1. Return undefined if the type of base is void or a nullable type.
+            // 8.1. Return undefined if the type of base is void or a nullable type.
             if base_type == self.0.void_type() {
                 return Err(PropertyResolutionError::VoidBase);
             }
@@ -139,7 +175,7 @@ impl<'a> PropertyResolution<'a> {
                 });
             }
 
-            // 6.2. If key is a String constant and disambiguation is one of { default, fixed }
+            // 8.2. If key is a String constant and disambiguation is one of { default, fixed }
             if [PropertyDisambiguation::Default, PropertyDisambiguation::Fixed].contains(&disamb) {
                 if let Some(key) = string_key {
                     if base_type.is_class_type() || base_type.is_enum_type() {
@@ -152,11 +188,13 @@ impl<'a> PropertyResolution<'a> {
                                 // Throw if unresolved
                                 prop.property_static_type(self.0).throw_if_unresolved().map_err(|_| PropertyResolutionError::DeferVerification)?;
 
-                                return Ok(Some(self.0.factory().create_instance_reference_value(&base, &prop)));
+                                return Ok(Some(self.0.factory().create_instance_reference_value(base, &prop)));
                             }
                         }
                     } else if base_type.is_interface_type() {
-                        for itrfc in base_type.all_ascending_types(self.0).iter().rev() {
+                        // Search the interface itself, then the interfaces it extends
+                        let interfaces = std::iter::once(base_type.clone()).chain(base_type.all_ascending_types(self.0).into_iter().rev());
+                        for itrfc in interfaces {
                             // Throw if unresolved
                             itrfc.throw_if_unresolved().map_err(|_| PropertyResolutionError::DeferVerification)?;
 
@@ -165,15 +203,24 @@ impl<'a> PropertyResolution<'a> {
                                 // Throw if unresolved
                                 prop.property_static_type(self.0).throw_if_unresolved().map_err(|_| PropertyResolutionError::DeferVerification)?;
 
-                                return Ok(Some(self.0.factory().create_instance_reference_value(&base, &prop)));
+                                return Ok(Some(self.0.factory().create_instance_reference_value(base, &prop)));
+                            }
+                        }
+                    } else if base_type.is_type_parameter_type() {
+                        // Resolve through the bounds of the type parameter
+                        for bound in base_type.type_parameter_bounds().iter() {
+                            let bound_value = self.0.factory().create_value(&bound);
+                            let r = self.resolve_property_with_disambiguation(&bound_value, None, SemanticPropertyKey::String(key.clone()), PropertyDisambiguation::Fixed)?;
+                            if let Some(r) = r.filter(|r| r.is_instance_reference_value()) {
+                                return Ok(Some(self.0.factory().create_instance_reference_value(base, &r.property())));
                             }
                         }
                     }
                 }
             }
 
-            // 6.3. If disambiguation is one of { default, dynamic }
-            // 6.3.1 For each descending type in the type hierarchy of base
+            // 8.3. If disambiguation is one of { default, dynamic }
+            // 8.3.1 For each descending type in the type hierarchy of base
             if [PropertyDisambiguation::Default, PropertyDisambiguation::Dynamic].contains(&disamb)
             && (base_type.is_class_type() || base_type.is_enum_type()) {
                 let proxy = base_type.find_proxy(ProxyKind::GetProperty, self.0).map_err(|_| PropertyResolutionError::DeferVerification)?;
@@ -186,24 +233,24 @@ impl<'a> PropertyResolution<'a> {
 
                     let proxy_key_type = proxy_signature.parameters().get(0).unwrap().static_type.clone();
                     if key_type.is_equals_or_subtype_of(&proxy_key_type, self.0) {
-                        return Ok(Some(self.0.factory().create_proxy_reference_value(&base, &proxy)));
+                        return Ok(Some(self.0.factory().create_proxy_reference_value(base, &proxy)));
                     }
                 }
             }
 
-            // 6.4. If key is a Number constant value and base is of a tuple type
-            if number_key.is_some() && base_type.is_tuple_type() {
-                let index: usize = unsafe { number_key.unwrap().to_int_unchecked() };
+            // 8.4. If key is a Number constant value and base is of a tuple type
+            if let Some(number_key) = number_key.filter(|_| base_type.is_tuple_type()) {
+                let index: usize = unsafe { number_key.to_int_unchecked() };
                 if index >= base_type.element_types().length() {
                     return Ok(None);
                 }
-                return Ok(Some(self.0.factory().create_tuple_reference_value(&base, index)));
+                return Ok(Some(self.0.factory().create_tuple_reference_value(base, index)));
             }
 
             return Ok(None);
         }
 
-        // 7. If base is a package
+        // 9. If base is a package
         if base.is_package() {
             // Key must be a String constant
             let Some(key) = string_key else {
@@ -223,10 +270,12 @@ impl<'a> PropertyResolution<'a> {
                     return Ok(r);
                 }
             }
-            return Ok(None);
+
+            // Subpackages are resolved last, allowing fully qualified names.
+            return Ok(base.subpackages().get(&key));
         }
 
-        // 8. If base is a package set
+        // 10. If base is a package set
         if base.is_package_set() {
             // Key must be a String constant
             let Some(key) = string_key else {
@@ -242,44 +291,8 @@ impl<'a> PropertyResolution<'a> {
             return Ok(None);
         }
 
-        // 9. If base is the import.meta symbol
-        if base.is_import_meta() {
-            // Key must be a String constant
-            let Some(key) = string_key else {
-                return Ok(None);
-            };
-
-            match key.as_ref() {
-                "env" => {
-                    return Ok(Some(self.0.import_meta_env()));
-                },
-                "output" => {
-                    return Ok(Some(self.0.factory().create_import_meta_output_value()));
-                },
-                _ => {
-                    return Ok(None);
-                },
-            }
-        }
-
-        // 10. If base is the import.meta.env symbol
-        if base.is_import_meta_env() {
-            // Key must be a String constant
-            let Some(key) = string_key else {
-                return Ok(None);
-            };
-
-            let ev_dict = self.0.preload_environment_variables();
-            if let Some(ev) = ev_dict.get(&key) {
-                let string_type = self.0.string_type();
-                return Ok(Some(self.0.factory().create_string_constant(ev.clone(), &string_type)));
-            } else {
-                return Ok(None);
-            }
-        }
-
         // 11. Return undefined
-        return Ok(None);
+        Ok(None)
     }
 
     pub fn resolve_scope_property(&mut self, base: &Symbol, qual: Option<Symbol>, key: SemanticPropertyKey, disamb: PropertyDisambiguation) -> Result<Option<Symbol>, PropertyResolutionError> {
@@ -321,16 +334,16 @@ impl<'a> PropertyResolution<'a> {
             r = Some(r.unwrap().resolve_alias().wrap_property_reference(self.0));
         }
 
-        // 6. If base is an activation scope and base[[This]] is not undefined
-        if base.is_activation_scope() && base.this().is_some() {
+        // 6. If r is undefined, base is an activation scope and base[[This]] is not undefined
+        if r.is_none() && base.is_activation_scope() && base.this().is_some() {
             r = self.resolve_property_with_disambiguation(&base.this().unwrap(), qual.clone(), key.clone(), disamb)?;
             if r.is_some() {
                 return Ok(r);
             }
         }
 
-        // 7. If base is a class scope or enum scope
-        if base.is_class_scope() || base.is_enum_scope() {
+        // 7. If r is undefined and base is a class scope or enum scope
+        if r.is_none() && (base.is_class_scope() || base.is_enum_scope()) {
             r = self.resolve_property_with_disambiguation(&base.class(), qual.clone(), key.clone(), disamb)?;
         }
 
@@ -340,10 +353,12 @@ impl<'a> PropertyResolution<'a> {
         // 9. If base is a package scope
         if base.is_package_scope() {
             amb = self.resolve_property_with_disambiguation(&base.package(), qual.clone(), key.clone(), disamb)?;
-            if r.is_some() {
-                return Err(PropertyResolutionError::AmbiguousReference { name: string_key.clone().unwrap() });
+            if amb.is_some() {
+                if r.is_some() {
+                    return Err(PropertyResolutionError::AmbiguousReference { name: string_key.clone().unwrap() });
+                }
+                r = amb;
             }
-            r = amb;
         }
 
         // 10. If qual is undefined and key is a String constant
@@ -359,21 +374,31 @@ impl<'a> PropertyResolution<'a> {
                 }
                 r = amb;
             }
+
+            // Package aliases
+            let p = base.package_aliases().get(&string_key.clone().unwrap());
+            if let Some(p) = p {
+                if r.is_some() {
+                    return Err(PropertyResolutionError::AmbiguousReference { name: string_key.clone().unwrap() });
+                }
+                r = Some(p);
+            }
         }
 
         // 11. For each op in base[[OpenPackages]]
-        for p in base.packages().iter() {
+        for p in base.open_packages().iter() {
             amb = self.resolve_property_with_disambiguation(&p, qual.clone(), key.clone(), disamb)?;
-            if r.is_some() {
-                return Err(PropertyResolutionError::AmbiguousReference { name: string_key.clone().unwrap() });
+            if amb.is_some() {
+                if r.is_some() {
+                    return Err(PropertyResolutionError::AmbiguousReference { name: string_key.clone().unwrap() });
+                }
+                r = amb;
             }
-            r = amb;
         }
 
         // 12. If r is undefined and base[[Parent]] is not undefined
-        let parent_scope = base.parent();
-        if r.is_none() && parent_scope.is_some() {
-            return self.resolve_scope_property(&parent_scope.unwrap(), qual, key, disamb);
+        if let (None, Some(parent_scope)) = (&r, base.parent()) {
+            return self.resolve_scope_property(&parent_scope, qual, key, disamb);
         }
 
         // 13. Return r
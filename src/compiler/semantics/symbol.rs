@@ -70,10 +70,7 @@ impl Symbol {
     }
 
     pub fn is_origin_class_type(&self) -> bool {
-        match self.0.upgrade().unwrap().as_ref() {
-            SymbolKind::Type(TypeKind::ClassType(_)) => true,
-            _ => false,
-        }
+        matches!(self.0.upgrade().unwrap().as_ref(), SymbolKind::Type(TypeKind::ClassType(_)))
     }
 
     pub fn is_enum_type(&self) -> bool {
@@ -89,10 +86,7 @@ impl Symbol {
     }
 
     pub fn is_origin_interface_type(&self) -> bool {
-        match self.0.upgrade().unwrap().as_ref() {
-            SymbolKind::Type(TypeKind::InterfaceType(_)) => true,
-            _ => false,
-        }
+        matches!(self.0.upgrade().unwrap().as_ref(), SymbolKind::Type(TypeKind::InterfaceType(_)))
     }
 
     pub fn is_function_type(&self) -> bool {
@@ -444,6 +438,11 @@ impl Symbol {
         let mut r: Vec<String> = vec![];
         let mut p = Some(self.clone());
         while let Some(p1) = p {
+            // Properties declared outside of packages, such as at the
+            // top level of a program, are named after the scope.
+            if p1.is_scope() {
+                break;
+            }
             let name = p1.name();
             if !name.is_empty() {
                 r.insert(0, name);
@@ -751,34 +750,34 @@ impl Symbol {
         let symbol = self.0.upgrade().unwrap();
         match symbol.as_ref() {
             SymbolKind::Type(TypeKind::ClassType(data)) => {
-                data.parent.replace(value.map(|v| v.clone()));
+                data.parent.replace(value.cloned());
             },
             SymbolKind::Type(TypeKind::EnumType(data)) => {
-                data.parent.replace(value.map(|v| v.clone()));
+                data.parent.replace(value.cloned());
             },
             SymbolKind::Type(TypeKind::InterfaceType(data)) => {
-                data.parent.replace(value.map(|v| v.clone()));
+                data.parent.replace(value.cloned());
             },
             SymbolKind::Alias(data) => {
-                data.parent.replace(value.map(|v| v.clone()));
+                data.parent.replace(value.cloned());
             },
             SymbolKind::Package(data) => {
-                data.parent.replace(value.map(|v| v.clone()));
+                data.parent.replace(value.cloned());
             },
             SymbolKind::PackageSet(data) => {
-                data.parent.replace(value.map(|v| v.clone()));
+                data.parent.replace(value.cloned());
             },
             SymbolKind::VariableProperty(data) => {
-                data.parent.replace(value.map(|v| v.clone()));
+                data.parent.replace(value.cloned());
             },
             SymbolKind::VirtualProperty(data) => {
-                data.parent.replace(value.map(|v| v.clone()));
+                data.parent.replace(value.cloned());
             },
             SymbolKind::Function(data) => {
-                data.parent.replace(value.map(|v| v.clone()));
+                data.parent.replace(value.cloned());
             },
             SymbolKind::Scope(data, _) => {
-                data.parent.replace(value.map(|v| v.clone()));
+                data.parent.replace(value.cloned());
             },
             _ => panic!(),
         }
@@ -796,9 +795,7 @@ impl Symbol {
                     return Some(r.clone());
                 }
                 let r = data.origin.extends_class(host);
-                if r.is_none() {
-                    return None;
-                }
+                r.as_ref()?;
                 let r = r.unwrap();
                 if r.is_unresolved() {
                     return Some(r.clone());
@@ -815,7 +812,7 @@ impl Symbol {
         let symbol = self.0.upgrade().unwrap();
         match symbol.as_ref() {
             SymbolKind::Type(TypeKind::ClassType(data)) => {
-                data.extends_class.replace(value.map(|c| c.clone()));
+                data.extends_class.replace(value.cloned());
             },
             _ => panic!(),
         }
@@ -843,7 +840,7 @@ impl Symbol {
         match symbol.as_ref() {
             SymbolKind::Type(TypeKind::EnumType(data)) => {
                 let EnumTypeData { ref numeric_type, .. } = data.as_ref();
-                numeric_type.replace(value.map(|t| t.clone()));
+                numeric_type.replace(value.cloned());
             },
             _ => panic!(),
         }
@@ -858,14 +855,24 @@ impl Symbol {
         }
     }
 
+    /// Types that a type parameter is bounded by, as in
+    /// `T extends C implements I`.
+    pub fn type_parameter_bounds(&self) -> SharedArray<Symbol> {
+        let symbol = self.0.upgrade().unwrap();
+        match symbol.as_ref() {
+            SymbolKind::Type(TypeKind::TypeParameterType(data)) => data.bounds.clone(),
+            _ => panic!(),
+        }
+    }
+
     pub fn set_type_parameters(&self, value: Option<&SharedArray<Symbol>>) {
         let symbol = self.0.upgrade().unwrap();
         match symbol.as_ref() {
             SymbolKind::Type(TypeKind::ClassType(data)) => {
-                data.type_parameters.replace(value.map(|p| p.clone()));
+                data.type_parameters.replace(value.cloned());
             },
             SymbolKind::Type(TypeKind::InterfaceType(data)) => {
-                data.type_parameters.replace(value.map(|p| p.clone()));
+                data.type_parameters.replace(value.cloned());
             },
             _ => panic!(),
         }
@@ -888,7 +895,7 @@ impl Symbol {
                     return r.clone();
                 }
                 let r: SharedMap<String, Symbol> = data.origin.static_properties(host).borrow().iter().map(|(name, p)| {
-                    let p = TypeSubstitution(host).execute(&p, &data.origin.type_parameters().unwrap(), &data.substitute_types);
+                    let p = TypeSubstitution(host).execute(p, &data.origin.type_parameters().unwrap(), &data.substitute_types);
                     (name.clone(), p)
                 }).collect();
                 static_properties.replace(Some(r.clone()));
@@ -919,7 +926,7 @@ impl Symbol {
                     return r.clone();
                 }
                 let r: SharedMap<String, Symbol> = data.origin.prototype(host).borrow().iter().map(|(name, p)| {
-                    let p = TypeSubstitution(host).execute(&p, &data.origin.type_parameters().unwrap(), &data.substitute_types);
+                    let p = TypeSubstitution(host).execute(p, &data.origin.type_parameters().unwrap(), &data.substitute_types);
                     (name.clone(), p)
                 }).collect();
                 prototype.replace(Some(r.clone()));
@@ -946,7 +953,7 @@ impl Symbol {
                     return r.clone();
                 }
                 let r: SharedMap<ProxyKind, Symbol> = data.origin.proxies(host).borrow().iter().map(|(kind, p)| {
-                    let p = TypeSubstitution(host).execute(&p, &data.origin.type_parameters().unwrap(), &data.substitute_types);
+                    let p = TypeSubstitution(host).execute(p, &data.origin.type_parameters().unwrap(), &data.substitute_types);
                     (*kind, p)
                 }).collect();
                 proxies.replace(Some(r.clone()));
@@ -1007,9 +1014,7 @@ impl Symbol {
                     return Some(r.clone());
                 }
                 let r = data.origin.constructor_method(host);
-                if r.is_none() {
-                    return None;
-                }
+                r.as_ref()?;
                 let r = r.unwrap();
                 let r = TypeSubstitution(host).execute(&r, &data.origin.type_parameters().unwrap(), &data.substitute_types);
                 data.constructor_method.replace(Some(r.clone()));
@@ -1024,7 +1029,7 @@ impl Symbol {
         match symbol.as_ref() {
             SymbolKind::Type(TypeKind::ClassType(data)) => {
                 let ClassTypeData { ref constructor_method, .. } = data.as_ref();
-                constructor_method.replace(value.map(|f| f.clone()));
+                constructor_method.replace(value.cloned());
             },
             _ => panic!(),
         }
@@ -1288,7 +1293,7 @@ impl Symbol {
         let symbol = self.0.upgrade().unwrap();
         match symbol.as_ref() {
             SymbolKind::Type(TypeKind::AnyType) |
-            SymbolKind::Type(TypeKind::VoidType) => { return true; },
+            SymbolKind::Type(TypeKind::VoidType) => { true},
             _ => {
                 if self.is_type() {
                     return false;
@@ -1303,7 +1308,7 @@ impl Symbol {
         let symbol = self.0.upgrade().unwrap();
         match symbol.as_ref() {
             SymbolKind::Type(TypeKind::AnyType) |
-            SymbolKind::Type(TypeKind::NullableType(_)) => { return true; },
+            SymbolKind::Type(TypeKind::NullableType(_)) => { true},
             _ => {
                 if self.is_type() {
                     return false;
@@ -1375,7 +1380,7 @@ impl Symbol {
         if self.is_alias() {
             return self.alias_of().resolve_alias();
         }
-        return self.clone();
+        self.clone()
     }
 
     pub fn resolve_property(&self, qual: Option<Symbol>, key: SemanticPropertyKey, host: &SymbolHost) -> Result<Option<Symbol>, PropertyResolutionError> {
@@ -1523,7 +1528,7 @@ impl Symbol {
                                 if r.is_err() {
                                     return true;
                                 }
-                                r.unwrap()
+                                !r.unwrap()
                             },
                             ReferenceValueKind::Tuple { .. } => false,
                             ReferenceValueKind::Scope { property, .. } => property.read_only(host),
@@ -1548,6 +1553,7 @@ impl Symbol {
         }
     }
 
+    #[allow(clippy::only_used_in_recursion, reason = "property accessors take the host uniformly")]
     pub fn write_only(&self, host: &SymbolHost) -> bool {
         let symbol = self.0.upgrade().unwrap();
         match symbol.as_ref() {
@@ -1569,7 +1575,7 @@ impl Symbol {
                             ReferenceValueKind::Package { property, .. } => property.write_only(host),
                         }
                     }
-                    _ => true,
+                    _ => false,
                 }
             },
             _ => false,
@@ -1644,7 +1650,7 @@ impl Symbol {
         let symbol = self.0.upgrade().unwrap();
         match symbol.as_ref() {
             SymbolKind::VariableProperty(data) => {
-                data.constant_initializer.replace(value.map(|v| v.clone()));
+                data.constant_initializer.replace(value.cloned());
             },
             _ => panic!(),
         }
@@ -1684,7 +1690,7 @@ impl Symbol {
         let symbol = self.0.upgrade().unwrap();
         match symbol.as_ref() {
             SymbolKind::VirtualProperty(data) => {
-                data.getter.replace(value.map(|v| v.clone()));
+                data.getter.replace(value.cloned());
             },
             _ => panic!(),
         }
@@ -1714,7 +1720,7 @@ impl Symbol {
         let symbol = self.0.upgrade().unwrap();
         match symbol.as_ref() {
             SymbolKind::VirtualProperty(data) => {
-                data.setter.replace(value.map(|v| v.clone()));
+                data.setter.replace(value.cloned());
             },
             _ => panic!(),
         }
@@ -1734,7 +1740,7 @@ impl Symbol {
                 if r.is_unresolved() {
                     return r.clone();
                 }
-                let r = TypeSubstitution(host).execute(&r, &data.indirect_substitute_types, &data.indirect_substitute_types);
+                let r = TypeSubstitution(host).execute(&r, &data.indirect_type_parameters, &data.indirect_substitute_types);
                 data.signature.replace(Some(r.clone()));
                 r
             },
@@ -1762,9 +1768,7 @@ impl Symbol {
                     return Some(r.clone());
                 }
                 let r = data.origin.of_virtual_property(host);
-                if r.is_none() {
-                    return None;
-                }
+                r.as_ref()?;
                 let r = TypeSubstitution(host).execute(&r.unwrap(), &data.indirect_substitute_types, &data.indirect_substitute_types);
                 data.of_virtual_property.replace(Some(r.clone()));
                 Some(r)
@@ -1777,7 +1781,7 @@ impl Symbol {
         let symbol = self.0.upgrade().unwrap();
         match symbol.as_ref() {
             SymbolKind::Function(data) => {
-                data.of_virtual_property.replace(value.map(|v| v.clone()));
+                data.of_virtual_property.replace(value.cloned());
             },
             _ => panic!(),
         }
@@ -1810,9 +1814,7 @@ impl Symbol {
                     return Some(r.clone());
                 }
                 let r = data.origin.overrides_method(host);
-                if r.is_none() {
-                    return None;
-                }
+                r.as_ref()?;
                 let r = TypeSubstitution(host).execute(&r.unwrap(), &data.indirect_substitute_types, &data.indirect_substitute_types);
                 data.overrides_method.replace(Some(r.clone()));
                 Some(r)
@@ -1858,6 +1860,16 @@ impl Symbol {
         }
     }
 
+    /// Types that variables are narrowed to within this scope,
+    /// such as after a `x != null` or `x is T` test.
+    pub fn narrowed_types(&self) -> SharedMap<Symbol, Symbol> {
+        let symbol = self.0.upgrade().unwrap();
+        match symbol.as_ref() {
+            SymbolKind::Scope(data, _) => data.narrowed_types.clone(),
+            _ => panic!(),
+        }
+    }
+
     pub fn local_variable_scope_count(&self) -> usize {
         let symbol = self.0.upgrade().unwrap();
         match symbol.as_ref() {
@@ -1904,7 +1916,7 @@ impl Symbol {
         let symbol = self.0.upgrade().unwrap();
         match symbol.as_ref() {
             SymbolKind::Scope(_, Some(ScopeKind::Activation(data))) => {
-                data.this.replace(value.map(|v| v.clone()));
+                data.this.replace(value.cloned());
             },
             _ => panic!(),
         }
@@ -1953,7 +1965,7 @@ impl Symbol {
         match symbol.as_ref() {
             SymbolKind::Value(_, Some(data)) => {
                 match data.as_ref() {
-                    ValueKind::Constant(ConstantKind::Char(v)) => v.clone(),
+                    ValueKind::Constant(ConstantKind::Char(v)) => *v,
                     _ => panic!(),
                 }
             },
@@ -1966,7 +1978,7 @@ impl Symbol {
         match symbol.as_ref() {
             SymbolKind::Value(_, Some(data)) => {
                 match data.as_ref() {
-                    ValueKind::Constant(ConstantKind::Boolean(v)) => v.clone(),
+                    ValueKind::Constant(ConstantKind::Boolean(v)) => *v,
                     _ => panic!(),
                 }
             },
@@ -1993,7 +2005,7 @@ impl Symbol {
         match symbol.as_ref() {
             SymbolKind::Value(_, Some(data)) => {
                 match data.as_ref() {
-                    ValueKind::Conversion(data) => data.relationship.clone(),
+                    ValueKind::Conversion(data) => data.relationship,
                     _ => panic!(),
                 }
             },
@@ -2006,7 +2018,7 @@ impl Symbol {
         match symbol.as_ref() {
             SymbolKind::Value(_, Some(data)) => {
                 match data.as_ref() {
-                    ValueKind::Conversion(data) => data.optional.clone(),
+                    ValueKind::Conversion(data) => data.optional,
                     _ => panic!(),
                 }
             },
@@ -2133,7 +2145,7 @@ impl Symbol {
                 match data.as_ref() {
                     ValueKind::Reference(data) => {
                         match data.as_ref() {
-                            ReferenceValueKind::Tuple { index, .. } => index.clone(),
+                            ReferenceValueKind::Tuple { index, .. } => *index,
                             _ => panic!(),
                         }
                     },
@@ -2147,11 +2159,7 @@ impl Symbol {
     pub fn property_is_visible(&self, scope: &Symbol, host: &SymbolHost) -> bool {
         let mut prop = self.clone();
         if prop.is_value() {
-            if prop.is_static_reference_value() {
-                prop = prop.property();
-            } else if prop.is_instance_reference_value() {
-                prop = prop.property();
-            } else if prop.is_package_reference_value() {
+            if prop.is_static_reference_value() || prop.is_instance_reference_value() || prop.is_package_reference_value() {
                 prop = prop.property();
             } else {
                 return true;
@@ -2179,7 +2187,7 @@ impl Symbol {
                         return true;
                     }
                 }
-                return false;
+                false
             },
             Visibility::Private => {
                 let mut t: Option<Symbol> = None;
@@ -2200,7 +2208,7 @@ impl Symbol {
                         return true;
                     }
                 }
-                return false;
+                false
             },
             Visibility::Protected => {
                 let mut t: Option<Symbol> = None;
@@ -2221,7 +2229,7 @@ impl Symbol {
                         return true;
                     }
                 }
-                return false;
+                false
             },
         }
     }
@@ -2230,6 +2238,9 @@ impl Symbol {
         possibly_subtype.is_subtype_of(self, host)
     }
 
+    /// Type arguments are invariant: a type substitution is only a subtype
+    /// of the types it inherits, never of the same origin substituted with
+    /// other arguments.
     pub fn is_subtype_of(&self, possibly_ascending_type: &Symbol, host: &SymbolHost) -> bool {
         possibly_ascending_type.is_any_type() || self.all_ascending_types(host).contains(possibly_ascending_type)
     }
@@ -2274,8 +2285,10 @@ impl Symbol {
             return vec![host.function_type()];
         } else if self.is_tuple_type() {
             return vec![host.object_type()];
+        } else if self.is_type_parameter_type() {
+            return self.type_parameter_bounds().iter().collect();
         }
-        return vec![];
+        vec![]
     }
 
     pub fn expect_type(&self) -> Result<Symbol, ExpectedTypeError> {
@@ -2316,7 +2329,16 @@ impl Symbol {
     }
 
     /// The internal *PropertyStaticType*() function.
+    ///
+    /// Aliases are resolved first. `Unresolved` and package symbols
+    /// result into themselves.
     pub fn property_static_type(&self, host: &SymbolHost) -> Symbol {
+        if self.is_alias() {
+            return self.resolve_alias().property_static_type(host);
+        }
+        if self.is_unresolved() || self.is_package() {
+            return self.clone();
+        }
         if self.is_variable_property() || self.is_virtual_property() {
             return self.static_type(host);
         }
@@ -2324,7 +2346,7 @@ impl Symbol {
             return self.signature(host);
         }
         assert!(self.is_type());
-        return host.class_type();
+        host.class_type()
     }
 
     /// Throws `DeferVerificationError` error if the symbol is `Unresolved`.
@@ -2353,31 +2375,28 @@ impl Symbol {
 
     /// The internal *WrapPropertyReference*() function.
     pub fn wrap_property_reference(&self, host: &SymbolHost) -> Symbol {
-        if self.is_type() && (self.is_void_type() || self.is_any_type() || self.is_function_type() || self.is_tuple_type() || self.is_nullable_type()) {
-            return host.factory().create_type_as_reference_value(&self);
+        if self.is_package() {
+            return self.clone();
+        }
+        if self.is_type() && (self.is_void_type() || self.is_any_type() || self.is_function_type() || self.is_tuple_type() || self.is_nullable_type() || self.is_type_parameter_type() || self.is_type_after_explicit_type_substitution()) {
+            return host.factory().create_type_as_reference_value(self);
         }
         let parent = self.parent().unwrap();
         if parent.is_class_type() || parent.is_enum_type() {
-            return host.factory().create_static_reference_value(&parent, &self);
+            return host.factory().create_static_reference_value(&parent, self);
         }
         if parent.is_package() {
-            return host.factory().create_package_reference_value(&parent, &self);
+            return host.factory().create_package_reference_value(&parent, self);
         }
-        assert!(self.is_scope());
-        return host.factory().create_scope_reference_value(&parent, &self);
+        assert!(parent.is_scope());
+        host.factory().create_scope_reference_value(&parent, self)
     }
 
     pub fn is_floating_point_type_of_wider_range_than(&self, other: &Symbol, host: &SymbolHost) -> bool {
         let number_type = host.number_type();
         let single_type = host.single_type();
 
-        if self == &number_type {
-            other == &single_type
-        } else if self == &single_type {
-            false
-        } else {
-            false
-        }
+        self == &number_type && other == &single_type
     }
 
     pub fn is_integer_type_of_wider_range_than(&self, other: &Symbol, host: &SymbolHost) -> bool {
@@ -2411,6 +2430,21 @@ impl Symbol {
         }
     }
 
+    /// If a type is or implements `Iterator.<T>`, returns `T`, either as
+    /// an origin type parameter or as a substitute type.
+    pub fn iterator_item_type(&self, host: &SymbolHost) -> Result<Option<Symbol>, DeferVerificationError> {
+        let iterator_type = host.iterator_type();
+        iterator_type.throw_if_unresolved()?;
+        for t in std::iter::once(self.clone()).chain(self.all_ascending_types(host)) {
+            if t == iterator_type {
+                return Ok(Some(iterator_type.type_parameters().unwrap().get(0).unwrap()));
+            } else if t.type_after_substitution_has_origin(&iterator_type) {
+                return Ok(Some(t.substitute_types().get(0).unwrap()));
+            }
+        }
+        Ok(None)
+    }
+
     /// If a type is `Map.<K, V>`, returns (`K`, `V`), either as origin type parameters
     /// or as substitute types.
     pub fn map_key_value_types(&self, host: &SymbolHost) -> Result<Option<(Symbol, Symbol)>, DeferVerificationError> {
@@ -2420,7 +2454,7 @@ impl Symbol {
             let params = map_type.type_parameters().unwrap();
             Ok(Some((params.get(0).unwrap(), params.get(1).unwrap())))
         } else if self.type_after_substitution_has_origin(&map_type) {
-            let sub = map_type.substitute_types();
+            let sub = self.substitute_types();
             Ok(Some((sub.get(0).unwrap(), sub.get(1).unwrap())))
         } else {
             Ok(None)
@@ -2484,12 +2518,7 @@ impl Symbol {
     }
 
     pub fn find_activation(&self) -> Option<Symbol> {
-        for scope in self.descending_scope_hierarchy() {
-            if scope.is_activation_scope() {
-                return Some(scope);
-            }
-        }
-        return None
+        self.descending_scope_hierarchy().find(|scope| scope.is_activation_scope())
     }
 
     pub fn property_has_capture(&self, property: &Symbol) -> bool {
@@ -2510,7 +2539,8 @@ impl Symbol {
         let symbol = self.0.upgrade().unwrap();
         match symbol.as_ref() {
             SymbolKind::Scope(_, Some(ScopeKind::Activation(data))) => {
-                if let Some(set) = data.property_has_capture.borrow_mut().as_mut() {
+                let mut property_has_capture = data.property_has_capture.borrow_mut();
+                if let Some(set) = property_has_capture.as_mut() {
                     if value {
                         if !set.includes(property) {
                             set.push(property.clone());
@@ -2522,7 +2552,7 @@ impl Symbol {
                         }
                     }
                 } else if value {
-                    data.property_has_capture.replace(Some(shared_array![property.clone()]));
+                    *property_has_capture = Some(shared_array![property.clone()]);
                 }
             },
             _ => panic!(),
@@ -2535,7 +2565,7 @@ impl Symbol {
     ///
     /// `check_property_has_capture` performs the following action:
     /// 
-    /// ```
+    /// ```ignore
     /// let current_scope = self;
     /// if reference.is_scope_reference_value() && reference.base().find_activation().unwrap() != current_scope.find_activation().unwrap() {
     ///     reference.base().find_activation().unwrap().set_property_has_capture(reference.property(), true);
@@ -2549,8 +2579,14 @@ impl Symbol {
     /// ```
     pub fn check_property_has_capture(&self, reference: &Symbol) {
         let current_scope = self;
-        if reference.is_scope_reference_value() && reference.base().find_activation().unwrap() != current_scope.find_activation().unwrap() {
-            reference.base().find_activation().unwrap().set_property_has_capture(&reference.property(), true);
+        if !reference.is_scope_reference_value() {
+            return;
+        }
+        let (Some(base_activation), Some(current_activation)) = (reference.base().find_activation(), current_scope.find_activation()) else {
+            return;
+        };
+        if base_activation != current_activation {
+            base_activation.set_property_has_capture(&reference.property(), true);
         }
     }
 
@@ -2603,6 +2639,7 @@ impl Symbol {
     }
 }
 
+#[allow(clippy::to_string_trait_impl, reason = "symbols are described by their fully qualified names")]
 impl ToString for Symbol {
     fn to_string(&self) -> String {
         let symbol = self.0.upgrade().unwrap();
@@ -2807,6 +2844,7 @@ pub(crate) struct TupleTypeData {
 
 pub(crate) struct TypeParameterTypeData {
     pub name: String,
+    pub bounds: SharedArray<Symbol>,
 }
 
 pub(crate) struct TypeAfterExplicitTypeSubstitutionData {
@@ -2941,6 +2979,7 @@ pub(crate) struct ScopeData {
     pub open_packages: SharedArray<Symbol>,
     pub package_aliases: SharedMap<String, Symbol>,
     pub local_variable_scope_count: Cell<usize>,
+    pub narrowed_types: SharedMap<Symbol, Symbol>,
 }
 
 pub(crate) struct ActivationScopeData {
@@ -4446,4 +4485,14 @@ mod tests {
         let p = host.factory().create_package(["y", "n"]);
         println!("{}", p.fully_qualified_name());
     }
+
+    #[test]
+    fn naming_properties_outside_of_packages() {
+        let host = SymbolHost::new("", "");
+        let scope = host.factory().create_scope();
+        let class = host.factory().create_class_type("C".into());
+        class.set_parent(Some(&scope));
+        assert_eq!(class.fully_qualified_name(), "C");
+        assert_eq!(class.to_string(), "C");
+    }
 }
\ No newline at end of file
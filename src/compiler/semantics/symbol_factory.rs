@@ -85,10 +85,10 @@ impl<'a> SymbolFactory<'a> {
             if result_type != ft.result_type() {
                 continue 'ft;
             }
-            let mut parameters_1 = parameters.iter();
+            let parameters_1 = parameters.iter();
             let parameters_2 = ft.parameters();
             let mut parameters_2 = parameters_2.iter();
-            while let Some(param_1) = parameters_1.next() {
+            for param_1 in parameters_1 {
                 let param_2 = parameters_2.next().unwrap();
                 if !(param_1.kind == param_2.kind && param_1.name == param_2.name && param_1.static_type == param_2.static_type) {
                     continue 'ft;
@@ -118,10 +118,10 @@ impl<'a> SymbolFactory<'a> {
             tuple_types.insert(element_count, vec![]);
         }
         'tt: for tt in collection.unwrap() {
-            let mut element_types_1 = element_types.iter();
+            let element_types_1 = element_types.iter();
             let element_types_2 = tt.element_types();
             let mut element_types_2 = element_types_2.iter();
-            while let Some(e_1) = element_types_1.next() {
+            for e_1 in element_types_1 {
                 let e_2 = element_types_2.next().unwrap();
                 if e_1 != &e_2 {
                     continue 'tt;
@@ -158,6 +158,7 @@ impl<'a> SymbolFactory<'a> {
     pub fn create_type_parameter_type(&self, name: String) -> Symbol {
         Symbol(self.host.arena.allocate(SymbolKind::Type(TypeKind::TypeParameterType(Rc::new(TypeParameterTypeData {
             name,
+            bounds: SharedArray::new(),
         })))))
     }
 
@@ -170,17 +171,17 @@ impl<'a> SymbolFactory<'a> {
 
         let mut taets_list = self.host.taets.borrow_mut();
 
-        let mut list = taets_list.get(&origin);
+        let mut list = taets_list.get(origin);
         let empty_list = vec![];
         if list.is_none() {
             list = Some(&empty_list);
             taets_list.insert(origin.clone(), vec![]);
         }
         'taets: for taets in list.unwrap() {
-            let mut substitute_types_1 = substitute_types.iter();
+            let substitute_types_1 = substitute_types.iter();
             let substitute_types_2 = taets.substitute_types();
             let mut substitute_types_2 = substitute_types_2.iter();
-            while let Some(substitute_type_1) = substitute_types_1.next() {
+            for substitute_type_1 in substitute_types_1 {
                 let substitute_type_2 = substitute_types_2.next().unwrap();
                 if substitute_type_1 != substitute_type_2 {
                     continue 'taets;
@@ -202,8 +203,9 @@ impl<'a> SymbolFactory<'a> {
             list_of_to_proxies: RefCell::new(None),
         })))));
 
-        let list = taets_list.get_mut(&origin).unwrap();
+        let list = taets_list.get_mut(origin).unwrap();
         list.push(taets.clone());
+        self.host.unchecked_taets.borrow_mut().push(taets.clone());
 
         taets
     }
@@ -282,26 +284,12 @@ impl<'a> SymbolFactory<'a> {
         assert_eq!(indirect_type_parameters.length(), indirect_substitute_types.length());
 
         let mut vapaits_list = self.host.vapaits.borrow_mut();
-
-        let mut base_list = vapaits_list.get_mut(origin);
-        let mut empty_base_list = HashMap::<SharedArray<Symbol>, Vec<Symbol>>::new();
-        if base_list.is_none() {
-            base_list = Some(&mut empty_base_list);
-            vapaits_list.insert(origin.clone(), HashMap::new());
-        }
-        let base_list = base_list.unwrap();
-
-        let mut list = base_list.get(indirect_type_parameters);
-        let empty_list = vec![];
-        if list.is_none() {
-            list = Some(&empty_list);
-            base_list.insert(indirect_type_parameters.clone(), vec![]);
-        }
-        'vapaits: for vapaits in list.unwrap() {
-            let mut substitute_types_1 = indirect_substitute_types.iter();
+        let list = vapaits_list.entry(origin.clone()).or_default().entry(indirect_type_parameters.clone()).or_default();
+        'vapaits: for vapaits in list.iter() {
+            let substitute_types_1 = indirect_substitute_types.iter();
             let substitute_types_2 = vapaits.indirect_substitute_types();
             let mut substitute_types_2 = substitute_types_2.iter();
-            while let Some(substitute_type_1) = substitute_types_1.next() {
+            for substitute_type_1 in substitute_types_1 {
                 let substitute_type_2 = substitute_types_2.next().unwrap();
                 if substitute_type_1 != substitute_type_2 {
                     continue 'vapaits;
@@ -317,7 +305,6 @@ impl<'a> SymbolFactory<'a> {
             static_type: RefCell::new(None),
         }))));
 
-        let list = vapaits_list.get_mut(origin).unwrap().get_mut(&indirect_type_parameters).unwrap();
         list.push(vapaits.clone());
 
         vapaits
@@ -341,26 +328,12 @@ impl<'a> SymbolFactory<'a> {
         assert_eq!(indirect_type_parameters.length(), indirect_substitute_types.length());
 
         let mut vipaits_list = self.host.vipaits.borrow_mut();
-
-        let mut base_list = vipaits_list.get_mut(origin);
-        let mut empty_base_list = HashMap::<SharedArray<Symbol>, Vec<Symbol>>::new();
-        if base_list.is_none() {
-            base_list = Some(&mut empty_base_list);
-            vipaits_list.insert(origin.clone(), HashMap::new());
-        }
-        let base_list = base_list.unwrap();
-
-        let mut list = base_list.get(indirect_type_parameters);
-        let empty_list = vec![];
-        if list.is_none() {
-            list = Some(&empty_list);
-            base_list.insert(indirect_type_parameters.clone(), vec![]);
-        }
-        'vipaits: for vipaits in list.unwrap() {
-            let mut substitute_types_1 = indirect_substitute_types.iter();
+        let list = vipaits_list.entry(origin.clone()).or_default().entry(indirect_type_parameters.clone()).or_default();
+        'vipaits: for vipaits in list.iter() {
+            let substitute_types_1 = indirect_substitute_types.iter();
             let substitute_types_2 = vipaits.indirect_substitute_types();
             let mut substitute_types_2 = substitute_types_2.iter();
-            while let Some(substitute_type_1) = substitute_types_1.next() {
+            for substitute_type_1 in substitute_types_1 {
                 let substitute_type_2 = substitute_types_2.next().unwrap();
                 if substitute_type_1 != substitute_type_2 {
                     continue 'vipaits;
@@ -378,7 +351,6 @@ impl<'a> SymbolFactory<'a> {
             setter: RefCell::new(None),
         }))));
 
-        let list = vipaits_list.get_mut(origin).unwrap().get_mut(&indirect_type_parameters).unwrap();
         list.push(vipaits.clone());
 
         vipaits
@@ -406,26 +378,12 @@ impl<'a> SymbolFactory<'a> {
         assert_eq!(indirect_type_parameters.length(), indirect_substitute_types.length());
 
         let mut faits_list = self.host.faits.borrow_mut();
-
-        let mut base_list = faits_list.get_mut(origin);
-        let mut empty_base_list = HashMap::<SharedArray<Symbol>, Vec<Symbol>>::new();
-        if base_list.is_none() {
-            base_list = Some(&mut empty_base_list);
-            faits_list.insert(origin.clone(), HashMap::new());
-        }
-        let base_list = base_list.unwrap();
-
-        let mut list = base_list.get(indirect_type_parameters);
-        let empty_list = vec![];
-        if list.is_none() {
-            list = Some(&empty_list);
-            base_list.insert(indirect_type_parameters.clone(), vec![]);
-        }
-        'faits: for faits in list.unwrap() {
-            let mut substitute_types_1 = indirect_substitute_types.iter();
+        let list = faits_list.entry(origin.clone()).or_default().entry(indirect_type_parameters.clone()).or_default();
+        'faits: for faits in list.iter() {
+            let substitute_types_1 = indirect_substitute_types.iter();
             let substitute_types_2 = faits.indirect_substitute_types();
             let mut substitute_types_2 = substitute_types_2.iter();
-            while let Some(substitute_type_1) = substitute_types_1.next() {
+            for substitute_type_1 in substitute_types_1 {
                 let substitute_type_2 = substitute_types_2.next().unwrap();
                 if substitute_type_1 != substitute_type_2 {
                     continue 'faits;
@@ -445,7 +403,6 @@ impl<'a> SymbolFactory<'a> {
             is_overriding: Cell::new(origin.is_overriding()),
         }))));
 
-        let list = faits_list.get_mut(origin).unwrap().get_mut(&indirect_type_parameters).unwrap();
         list.push(faits.clone());
 
         faits
@@ -459,6 +416,7 @@ impl<'a> SymbolFactory<'a> {
             open_packages: SharedArray::new(),
             package_aliases: SharedMap::new(),
             local_variable_scope_count: Cell::new(0),
+            narrowed_types: SharedMap::new(),
         }), None)))
     }
 
@@ -470,6 +428,7 @@ impl<'a> SymbolFactory<'a> {
             open_packages: SharedArray::new(),
             package_aliases: SharedMap::new(),
             local_variable_scope_count: Cell::new(0),
+            narrowed_types: SharedMap::new(),
         }), Some(ScopeKind::With {
             object: object.clone(),
         }))))
@@ -483,6 +442,7 @@ impl<'a> SymbolFactory<'a> {
             open_packages: SharedArray::new(),
             package_aliases: SharedMap::new(),
             local_variable_scope_count: Cell::new(0),
+            narrowed_types: SharedMap::new(),
         }), Some(ScopeKind::FilterOperator {
             base: base.clone(),
         }))))
@@ -496,6 +456,7 @@ impl<'a> SymbolFactory<'a> {
             open_packages: SharedArray::new(),
             package_aliases: SharedMap::new(),
             local_variable_scope_count: Cell::new(0),
+            narrowed_types: SharedMap::new(),
         }), Some(ScopeKind::Activation(Rc::new(ActivationScopeData {
             function: function.clone(),
             this: RefCell::new(None),
@@ -511,6 +472,7 @@ impl<'a> SymbolFactory<'a> {
             open_packages: SharedArray::new(),
             package_aliases: SharedMap::new(),
             local_variable_scope_count: Cell::new(0),
+            narrowed_types: SharedMap::new(),
         }), Some(ScopeKind::Class {
             class: class.clone(),
         }))))
@@ -524,6 +486,7 @@ impl<'a> SymbolFactory<'a> {
             open_packages: SharedArray::new(),
             package_aliases: SharedMap::new(),
             local_variable_scope_count: Cell::new(0),
+            narrowed_types: SharedMap::new(),
         }), Some(ScopeKind::Enum {
             class: class.clone(),
         }))))
@@ -537,6 +500,7 @@ impl<'a> SymbolFactory<'a> {
             open_packages: SharedArray::new(),
             package_aliases: SharedMap::new(),
             local_variable_scope_count: Cell::new(0),
+            narrowed_types: SharedMap::new(),
         }), Some(ScopeKind::Interface {
             interface: interface.clone(),
         }))))
@@ -550,6 +514,7 @@ impl<'a> SymbolFactory<'a> {
             open_packages: SharedArray::new(),
             package_aliases: SharedMap::new(),
             local_variable_scope_count: Cell::new(0),
+            narrowed_types: SharedMap::new(),
         }), Some(ScopeKind::Package {
             package: package.clone(),
         }))))
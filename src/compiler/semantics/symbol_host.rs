@@ -1,5 +1,8 @@
 use crate::ns::*;
 
+/// Substituted symbols by their origin and their type arguments.
+pub(crate) type SubstitutionCache = HashMap<Symbol, HashMap<SharedArray<Symbol>, Vec<Symbol>>>;
+
 pub struct SymbolHost {
     pub(crate) arena: Arena<SymbolKind>,
     pub(crate) unresolved: Symbol,
@@ -19,12 +22,15 @@ pub struct SymbolHost {
 
     /// Types after explicit type substitution.
     pub(crate) taets: RefCell<HashMap<Symbol, Vec<Symbol>>>,
+    /// Types after explicit type substitution whose type arguments
+    /// have not yet been checked against the bounds of their type parameters.
+    pub(crate) unchecked_taets: RefCell<Vec<Symbol>>,
     /// Variable properties after indirect type substitution.
-    pub(crate) vapaits: RefCell<HashMap<Symbol, HashMap<SharedArray<Symbol>, Vec<Symbol>>>>,
+    pub(crate) vapaits: RefCell<SubstitutionCache>,
     /// Virtual properties after indirect type substitution.
-    pub(crate) vipaits: RefCell<HashMap<Symbol, HashMap<SharedArray<Symbol>, Vec<Symbol>>>>,
+    pub(crate) vipaits: RefCell<SubstitutionCache>,
     /// Functions after explicit or indirect type substitution.
-    pub(crate) faits: RefCell<HashMap<Symbol, HashMap<SharedArray<Symbol>, Vec<Symbol>>>>,
+    pub(crate) faits: RefCell<SubstitutionCache>,
 
     pub(crate) top_level_package: Symbol,
     pub(crate) jet_lang_package: RefCell<Option<Symbol>>,
@@ -96,6 +102,7 @@ impl SymbolHost {
             tuple_types: RefCell::new(HashMap::new()),
             nullable_types: RefCell::new(HashMap::new()),
             taets: RefCell::new(HashMap::new()),
+            unchecked_taets: RefCell::new(vec![]),
             vapaits: RefCell::new(HashMap::new()),
             vipaits: RefCell::new(HashMap::new()),
             faits: RefCell::new(HashMap::new()),
@@ -132,7 +139,7 @@ impl SymbolHost {
         })
     }
 
-    pub fn factory(&self) -> SymbolFactory {
+    pub fn factory(&self) -> SymbolFactory<'_> {
         SymbolFactory { host: self }
     }
 
@@ -524,6 +531,7 @@ impl SymbolHost {
         let r = self.factory().create_scope();
         r.open_packages().push(self.top_level_package());
         r.open_packages().push(self.jet_lang_package());
+        self.root_scope.replace(Some(r.clone()));
         r
     }
 
@@ -532,11 +540,9 @@ impl SymbolHost {
             return env.clone();
         }
         let mut r = HashMap::<String, String>::new();
-        if let Ok(iterator) = dotenvy::from_path_iter(&self.jetdependencies_project_directory) {
-            for item in iterator {
-                if let Ok((key, value)) = item {
-                    r.insert(key, value);
-                }
+        if let Ok(iterator) = dotenvy::from_path_iter(std::path::Path::new(&self.jetdependencies_project_directory).join(".env")) {
+            for (key, value) in iterator.flatten() {
+                r.insert(key, value);
             }
         }
         let r = Rc::new(r);
@@ -13,6 +13,9 @@ pub enum TypeConversionRelationship {
     FromNonNullableToNullable,
     FromInterfaceToObject,
     FromNullableInterfaceToNullableObject,
+    /// Results from flow-sensitive narrowing of a variable, such as
+    /// from `?T` to `T` after a null check.
+    Narrowing,
 
     // Explicit
     ThroughToProxy,
@@ -221,7 +224,7 @@ impl<'a> TypeConversions<'a> {
                     continue;
                 }
                 let result_type = signature.result_type();
-                if result_type.is_equals_or_subtype_of(&target_type, self.0) {
+                if result_type.is_equals_or_subtype_of(target_type, self.0) {
                     return Ok(Some(self.0.factory().create_conversion_value(value, TypeConversionRelationship::ThroughToProxy, optional, target_type)));
                 }
             }
@@ -313,11 +316,11 @@ impl<'a> TypeConversions<'a> {
             return Ok(Some(self.0.factory().create_conversion_value(value, TypeConversionRelationship::FromTypeParameter, optional, target_type)));
         }
 
-        let from_promise_result_type = from_type.promise_result_type(&self.0)?;
+        let from_promise_result_type = from_type.promise_result_type(self.0)?;
         if let Some(from_promise_result_type) = from_promise_result_type {
-            let to_promise_result_type = target_type.promise_result_type(&self.0)?;
+            let to_promise_result_type = target_type.promise_result_type(self.0)?;
             if let Some(to_promise_result_type) = to_promise_result_type {
-                if from_promise_result_type == self.0.any_type() && to_promise_result_type.is_subtype_of(&from_promise_result_type, &self.0) {
+                if from_promise_result_type == self.0.any_type() && to_promise_result_type.is_subtype_of(&from_promise_result_type, self.0) {
                     return Ok(Some(self.0.factory().create_conversion_value(value, TypeConversionRelationship::FromAnyPromiseToSubtypePromise, optional, target_type)));
                 }
             }
@@ -0,0 +1,72 @@
+use crate::ns::*;
+
+/// Type argument inference.
+///
+/// Infers the substitute types of a list of type parameters by matching
+/// types that mention such type parameters, such as the parameter types of
+/// a constructor, against actual types, such as the static types
+/// of the call arguments.
+pub struct TypeInference<'a>(pub &'a SymbolHost);
+
+impl<'a> TypeInference<'a> {
+    /// Matches `pattern` against `actual`, assigning type parameters
+    /// that have not yet been inferred. The first match of a type parameter wins.
+    pub fn infer(&self, pattern: &Symbol, actual: &Symbol, type_parameters: &SharedArray<Symbol>, inferred: &mut [Option<Symbol>]) {
+        if pattern.is_unresolved() || actual.is_unresolved() {
+            return;
+        }
+        if pattern.is_type_parameter_type() {
+            if let Some(i) = type_parameters.index_of(pattern) {
+                if inferred[i].is_none() {
+                    inferred[i] = Some(actual.clone());
+                }
+            }
+        } else if pattern.is_nullable_type() {
+            self.infer(&pattern.base(), &actual.non_null_type(), type_parameters, inferred);
+        } else if pattern.is_function_type() && actual.is_function_type() {
+            for (p1, p2) in pattern.parameters().iter().zip(actual.parameters().iter()) {
+                self.infer(&p1.static_type, &p2.static_type, type_parameters, inferred);
+            }
+            self.infer(&pattern.result_type(), &actual.result_type(), type_parameters, inferred);
+        } else if pattern.is_tuple_type() && actual.is_tuple_type() {
+            for (t1, t2) in pattern.element_types().iter().zip(actual.element_types().iter()) {
+                self.infer(&t1, &t2, type_parameters, inferred);
+            }
+        } else if pattern.is_type_after_explicit_type_substitution() {
+            // Match against the actual type or the ascending type
+            // that is a substitution of the same origin.
+            let origin = pattern.origin();
+            let actual = actual.non_null_type();
+            let actual = if actual.is_type_after_explicit_type_substitution() && actual.origin() == origin {
+                Some(actual)
+            } else {
+                actual.all_ascending_types(self.0).into_iter().find(|t| t.is_type_after_explicit_type_substitution() && t.origin() == origin)
+            };
+            if let Some(actual) = actual {
+                for (t1, t2) in pattern.substitute_types().iter().zip(actual.substitute_types().iter()) {
+                    self.infer(&t1, &t2, type_parameters, inferred);
+                }
+            }
+        }
+    }
+
+    /// Indicates whether a type mentions any of the given type parameters.
+    pub fn mentions_type_parameters(&self, t: &Symbol, type_parameters: &SharedArray<Symbol>) -> bool {
+        if t.is_unresolved() {
+            false
+        } else if t.is_type_parameter_type() {
+            type_parameters.index_of(t).is_some()
+        } else if t.is_nullable_type() {
+            self.mentions_type_parameters(&t.base(), type_parameters)
+        } else if t.is_function_type() {
+            t.parameters().iter().any(|p| self.mentions_type_parameters(&p.static_type, type_parameters))
+                || self.mentions_type_parameters(&t.result_type(), type_parameters)
+        } else if t.is_tuple_type() {
+            t.element_types().iter().any(|t| self.mentions_type_parameters(&t, type_parameters))
+        } else if t.is_type_after_explicit_type_substitution() {
+            t.substitute_types().iter().any(|t| self.mentions_type_parameters(&t, type_parameters))
+        } else {
+            false
+        }
+    }
+}
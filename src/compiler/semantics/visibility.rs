@@ -6,13 +6,13 @@ pub enum Visibility {
     Internal,
 }
 
-impl ToString for Visibility {
-    fn to_string(&self) -> String {
-        match self {
-            Self::Public => "public".into(),
-            Self::Private => "private".into(),
-            Self::Protected => "protected".into(),
-            Self::Internal => "internal".into(),
-        }
+impl std::fmt::Display for Visibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Public => "public",
+            Self::Private => "private",
+            Self::Protected => "protected",
+            Self::Internal => "internal",
+        })
     }
 }
\ No newline at end of file
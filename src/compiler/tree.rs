@@ -140,6 +140,18 @@ pub use package_definition::*;
 mod program;
 pub use program::*;
 
+// Traversal
+mod visitor;
+pub use visitor::*;
+mod folder;
+pub use folder::*;
+
+// Serialization
+mod ast_json;
+pub use ast_json::*;
+mod ast_cache;
+pub use ast_cache::*;
+
 // Helpers
 #[path = "./tree_semantics.rs"]
 mod tree_semantics;
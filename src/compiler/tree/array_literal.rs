@@ -33,12 +33,11 @@ impl ArrayLiteral {
             }
         } else if context_type_non_null.is_tuple_type() {
             let mut elision_found = false;
-            let mut i: usize = 0;
             let tuple_type = context_type_non_null.clone();
             if self.elements.len() > tuple_type.element_types().length() {
                 verifier.add_syntax_error(&self.location, DiagnosticKind::ArrayLiteralExceedingTupleElements, diagnostic_arguments![Symbol(tuple_type.clone())]);
             }
-            for elem in &self.elements {
+            for (i, elem) in self.elements.iter().enumerate() {
                 match elem {
                     Element::Elision => {
                         elision_found = true;
@@ -56,7 +55,6 @@ impl ArrayLiteral {
                         }
                     },
                 }
-                i += 1;
             }
             if elision_found {
                 verifier.add_syntax_error(&self.location, DiagnosticKind::ArrayLiteralMustNotContainElision, diagnostic_arguments![]);
@@ -105,7 +103,7 @@ impl ArrayLiteral {
                             elision_found = true;
                         },
                         Element::Rest((exp, _)) => {
-                            ArrayLiteral::verify_rest(exp, verifier, &element_type);
+                            ArrayLiteral::verify_rest(exp, verifier, &element_type)?;
                         },
                         Element::Expression(exp) => {
                             verifier.limit_expression_type(exp, &element_type)?;
@@ -11,4 +11,74 @@ pub struct AssignmentExpression {
     /// and there is no compound assignment, it is a destructuring pattern.
     pub left: Rc<Expression>,
     pub right: Rc<Expression>,
-}
\ No newline at end of file
+}
+
+impl AssignmentExpression {
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier) -> Result<Option<Symbol>, DeferVerificationError> {
+        // Destructuring assignment
+        if self.compound.is_none() && matches!(self.left.as_ref(), Expression::ArrayLiteral(_) | Expression::ObjectInitializer(_)) {
+            let right = verifier.verify_expression(&self.right, &default())?;
+            Self::verify_destructuring(verifier, &self.left)?;
+            return Ok(right);
+        }
+
+        let Some(left) = verifier.verify_expression(&self.left, &ExpressionVerifyContext {
+            mode: VerifyMode::Write,
+            ..default()
+        })? else {
+            verifier.verify_expression(&self.right, &default())?;
+            return Ok(None);
+        };
+        let left_type = left.static_type(&verifier.host);
+        let right = verifier.limit_expression_type(&self.right, &left_type)?;
+        Ok(right.map(|_| verifier.host.factory().create_value(&left_type)))
+    }
+
+    /// Verifies the assignment targets of a destructuring pattern.
+    fn verify_destructuring(verifier: &mut VerifierVerifier, pattern: &Rc<Expression>) -> Result<(), DeferVerificationError> {
+        match pattern.as_ref() {
+            Expression::ArrayLiteral(al) => {
+                for element in &al.elements {
+                    match element {
+                        Element::Expression(exp) | Element::Rest((exp, _)) => {
+                            Self::verify_destructuring(verifier, exp)?;
+                        },
+                        Element::Elision => {},
+                    }
+                }
+            },
+            Expression::ObjectInitializer(oi) => {
+                for field in &oi.fields {
+                    match field.as_ref() {
+                        InitializerField::Field { value: Some(value), .. } => {
+                            Self::verify_destructuring(verifier, value)?;
+                        },
+                        InitializerField::Field { value: None, .. } => {
+                            let Some(name) = field.shorthand() else {
+                                continue;
+                            };
+                            let scope = verifier.scope.clone();
+                            let r = verifier.resolve_property_as_exp(&scope, None, SemanticPropertyKey::String(name.0.clone()), PropertyDisambiguation::Default, &name.1, &default())?;
+                            if r.map(|r| r.read_only(&verifier.host)).unwrap_or(false) {
+                                verifier.add_verify_error(&name.1, DiagnosticKind::ReferenceIsReadOnly, diagnostic_arguments![]);
+                            }
+                        },
+                        InitializerField::Rest((exp, _)) => {
+                            Self::verify_destructuring(verifier, exp)?;
+                        },
+                    }
+                }
+            },
+            Expression::Unary(ue) if ue.operator == Operator::NonNull => {
+                Self::verify_destructuring(verifier, &ue.expression)?;
+            },
+            _ => {
+                verifier.verify_expression(pattern, &ExpressionVerifyContext {
+                    mode: VerifyMode::Write,
+                    ..default()
+                })?;
+            },
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,242 @@
+use crate::ns::*;
+use bincode::Options;
+use serde::{Serialize, Deserialize};
+use std::path::{Path, PathBuf};
+
+/// Directory of binary encoded programs, used for reloading
+/// unchanged sources without parsing them.
+///
+/// An entry contains a program and the comments of its compilation unit,
+/// and is keyed by the hash of the source text (`CompilationUnit::text_hash`),
+/// the hash of the compiler options (`CompilerOptions::fingerprint`), the
+/// versions of the binary encoding and of the tree (`AstJson::VERSION`)
+/// and the compiler version. Corrupt and stale entries are ignored.
+///
+/// Only sources that parse without diagnostics are cached, since
+/// an entry does not restore diagnostics.
+///
+/// # Example
+///
+/// ```no_run
+/// use hydroper_jet_compiler::ns::*;
+///
+/// let cache = AstCache::new("target/jet-ast-cache");
+/// let compilation_unit = CompilationUnit::new(None, "x = 10".into(), &CompilerOptions::new());
+/// let program = ParserFacade::parse_program_with_cache(&compilation_unit, &cache).unwrap();
+/// ```
+pub struct AstCache {
+    directory: PathBuf,
+}
+
+impl AstCache {
+    /// Version of the compiler whose entries are accepted.
+    pub const COMPILER_VERSION: &'static str = env!("CARGO_PKG_VERSION");
+
+    const MAGIC: [u8; 4] = *b"JAST";
+
+    /// Version of the binary encoding.
+    const FORMAT_VERSION: u32 = 2;
+
+    pub fn new(directory: impl AsRef<Path>) -> Self {
+        Self {
+            directory: directory.as_ref().to_owned(),
+        }
+    }
+
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Path of the entry for a compilation unit.
+    pub fn entry_path(&self, compilation_unit: &Rc<CompilationUnit>) -> PathBuf {
+        self.directory.join(format!(
+            "{:016x}-{:016x}-{}.{}-{}.jetast",
+            compilation_unit.text_hash(),
+            compilation_unit.compiler_options().fingerprint(),
+            Self::FORMAT_VERSION,
+            AstJson::VERSION,
+            Self::COMPILER_VERSION,
+        ))
+    }
+
+    /// Loads the cached program of a compilation unit, adding its
+    /// comments to the compilation unit. Returns `None` if there is
+    /// no entry or if the entry is corrupt or stale.
+    pub fn load(&self, compilation_unit: &Rc<CompilationUnit>) -> Option<Rc<Program>> {
+        let bytes = std::fs::read(self.entry_path(compilation_unit)).ok()?;
+        Self::decode(&bytes, compilation_unit)
+    }
+
+    /// Stores the program of a compilation unit along with its comments.
+    /// Does nothing if the compilation unit has diagnostics.
+    pub fn store(&self, compilation_unit: &Rc<CompilationUnit>, program: &Rc<Program>) -> std::io::Result<()> {
+        if compilation_unit.error_count() != 0 || compilation_unit.warning_count() != 0 {
+            return Ok(());
+        }
+        std::fs::create_dir_all(&self.directory)?;
+        // Write to a temporary file first so that concurrent builds
+        // never observe a partially written entry.
+        let path = self.entry_path(compilation_unit);
+        let temporary_path = path.with_extension(format!("jetast.{}", std::process::id()));
+        std::fs::write(&temporary_path, Self::encode(program))?;
+        std::fs::rename(&temporary_path, &path)
+    }
+
+    /// Encodes a program along with the comments of its compilation unit.
+    pub fn encode(program: &Rc<Program>) -> Vec<u8> {
+        let compilation_unit = program.location.compilation_unit();
+        let header = EntryHeader {
+            magic: Self::MAGIC,
+            format_version: Self::FORMAT_VERSION,
+            tree_version: AstJson::VERSION,
+            compiler_version: Self::COMPILER_VERSION.into(),
+            options_hash: compilation_unit.compiler_options().fingerprint(),
+            text_hash: compilation_unit.text_hash(),
+            text_length: compilation_unit.current_text().len(),
+        };
+        let comments: Vec<EntryComment> = compilation_unit.comments().iter().map(|comment| EntryComment {
+            multiline: comment.multiline(),
+            content: comment.content(),
+            location: comment.location(),
+        }).collect();
+        let mut bytes = Self::options().serialize(&header).unwrap();
+        Location::with_serialization_context(&compilation_unit, || {
+            Self::options().serialize_into(&mut bytes, &(program, comments)).unwrap();
+        });
+        bytes
+    }
+
+    /// Decodes a program encoded by `encode()` into a compilation unit,
+    /// adding the encoded comments to the compilation unit. Returns `None`
+    /// if the bytes are corrupt or were not encoded for the source text and
+    /// compiler options of the compilation unit by this compiler version.
+    pub fn decode(bytes: &[u8], compilation_unit: &Rc<CompilationUnit>) -> Option<Rc<Program>> {
+        let mut reader = bytes;
+        let header: EntryHeader = Self::options().with_limit(bytes.len() as u64).deserialize_from(&mut reader).ok()?;
+        if header.magic != Self::MAGIC
+            || header.format_version != Self::FORMAT_VERSION
+            || header.tree_version != AstJson::VERSION
+            || header.compiler_version != Self::COMPILER_VERSION
+            || header.options_hash != compilation_unit.compiler_options().fingerprint()
+            || header.text_hash != compilation_unit.text_hash()
+            || header.text_length != compilation_unit.current_text().len() {
+            return None;
+        }
+        let (program, comments): (Rc<Program>, Vec<EntryComment>) = Location::with_serialization_context(compilation_unit, || {
+            Self::options().with_limit(reader.len() as u64).reject_trailing_bytes().deserialize(reader)
+        }).ok()?;
+        compilation_unit.comments_mut().extend(comments.into_iter().map(|comment| {
+            Rc::new(Comment::new(comment.multiline, comment.content, comment.location))
+        }));
+        Some(program)
+    }
+
+    fn options() -> impl Options {
+        bincode::DefaultOptions::new()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct EntryHeader {
+    magic: [u8; 4],
+    format_version: u32,
+    tree_version: u32,
+    compiler_version: String,
+    options_hash: u64,
+    text_hash: u64,
+    text_length: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+struct EntryComment {
+    multiline: bool,
+    content: String,
+    location: Location,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+
+    #[test]
+    fn caching_parser_tests() {
+        let directory = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/parser");
+        for entry in std::fs::read_dir(directory).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_none_or(|extension| extension != "jet") {
+                continue;
+            }
+            let text = std::fs::read_to_string(&path).unwrap();
+            let compilation_unit = CompilationUnit::new(None, text.clone(), &CompilerOptions::new());
+            let program = ParserFacade::parse_program(&compilation_unit).unwrap();
+            let bytes = AstCache::encode(&program);
+
+            let other_compilation_unit = CompilationUnit::new(None, text, &CompilerOptions::new());
+            let loaded = AstCache::decode(&bytes, &other_compilation_unit).unwrap_or_else(|| panic!("{}", path.display()));
+            assert_eq!(AstJson::serialize_program(&loaded).split_once("\"program\"").unwrap().1, AstJson::serialize_program(&program).split_once("\"program\"").unwrap().1, "{}", path.display());
+            let comments = |compilation_unit: &Rc<CompilationUnit>| compilation_unit.comments().iter().map(|comment| {
+                (comment.multiline(), comment.content(), comment.location().first_offset(), comment.location().last_offset())
+            }).collect::<Vec<_>>();
+            assert_eq!(comments(&other_compilation_unit), comments(&compilation_unit), "{}", path.display());
+        }
+    }
+
+    #[test]
+    fn decoding_programs_without_directives_and_recovered_programs() {
+        for text in ["", " ", "\n", "// c", "/** c */", "f(", "class C { function }", "package p { x. }"] {
+            let compilation_unit = CompilationUnit::new(None, text.into(), &CompilerOptions::new());
+            let program = ParserFacade::parse_program(&compilation_unit).unwrap();
+            let bytes = AstCache::encode(&program);
+
+            let other_compilation_unit = CompilationUnit::new(None, text.into(), &CompilerOptions::new());
+            let loaded = AstCache::decode(&bytes, &other_compilation_unit).unwrap_or_else(|| panic!("{text:?}"));
+            assert_eq!(AstJson::serialize_program(&loaded), AstJson::serialize_program(&program), "{text:?}");
+            assert_eq!(other_compilation_unit.comments().len(), compilation_unit.comments().len(), "{text:?}");
+        }
+    }
+
+    #[test]
+    fn ignoring_corrupt_and_stale_entries() {
+        let directory = std::env::temp_dir().join(format!("jet-ast-cache-test-{}", std::process::id()));
+        let cache = AstCache::new(&directory);
+        let text = "// Comment\nf(x)";
+
+        let compilation_unit = CompilationUnit::new(None, text.into(), &CompilerOptions::new());
+        assert!(cache.load(&compilation_unit).is_none());
+        let parsed = ParserFacade::parse_program_with_cache(&compilation_unit, &cache).unwrap();
+        assert!(cache.entry_path(&compilation_unit).exists());
+
+        let compilation_unit = CompilationUnit::new(None, text.into(), &CompilerOptions::new());
+        let loaded = cache.load(&compilation_unit).unwrap();
+        assert_eq!(serde_json::to_string(&loaded).unwrap(), serde_json::to_string(&parsed).unwrap());
+        assert_eq!(compilation_unit.comments().len(), 1);
+
+        // A different source text is not served from the entry.
+        let bytes = std::fs::read(cache.entry_path(&compilation_unit)).unwrap();
+        let changed = CompilationUnit::new(None, "// Comment\nf(y)".into(), &CompilerOptions::new());
+        assert!(AstCache::decode(&bytes, &changed).is_none());
+
+        // Corrupt entries are ignored and replaced.
+        std::fs::write(cache.entry_path(&compilation_unit), &bytes[..bytes.len() / 2]).unwrap();
+        let compilation_unit = CompilationUnit::new(None, text.into(), &CompilerOptions::new());
+        assert!(cache.load(&compilation_unit).is_none());
+        ParserFacade::parse_program_with_cache(&compilation_unit, &cache).unwrap();
+        assert_eq!(compilation_unit.comments().len(), 1);
+        assert_eq!(std::fs::read(cache.entry_path(&compilation_unit)).unwrap(), bytes);
+
+        // Entries are not served under other compiler options.
+        let mut options = CompilerOptions::default();
+        options.apply_arguments(&["--dynamic".into()]).unwrap();
+        let dynamic = CompilationUnit::new(None, text.into(), &Rc::new(options));
+        assert_ne!(cache.entry_path(&dynamic), cache.entry_path(&compilation_unit));
+        assert!(AstCache::decode(&bytes, &dynamic).is_none());
+        assert!(cache.load(&dynamic).is_none());
+
+        // Sources with syntax errors are not cached.
+        let invalid = CompilationUnit::new(None, "f(".into(), &CompilerOptions::new());
+        ParserFacade::parse_program_with_cache(&invalid, &cache);
+        assert!(!cache.entry_path(&invalid).exists());
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
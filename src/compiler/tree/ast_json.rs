@@ -0,0 +1,216 @@
+use crate::ns::*;
+use serde::{Serialize, Deserialize};
+
+/// Reads and writes programs in the AST JSON format.
+///
+/// An AST JSON document consists of a header identifying the compilation
+/// unit of the program and the program itself, whose locations
+/// are written as byte offsets into the source text of that compilation unit.
+/// The format is described in `docs/ast-json.md`.
+///
+/// Outside of this API, locations are written as empty objects and
+/// read as empty locations.
+///
+/// # Example
+///
+/// ```
+/// use hydroper_jet_compiler::ns::*;
+///
+/// let compilation_unit = CompilationUnit::new(None, "x = 10".into(), &CompilerOptions::new());
+/// let program = ParserFacade::parse_program(&compilation_unit).unwrap();
+/// let json = AstJson::serialize_program(&program);
+///
+/// let other_compilation_unit = CompilationUnit::new(None, "x = 10".into(), &CompilerOptions::new());
+/// let loaded = AstJson::deserialize_program(&json, &other_compilation_unit).unwrap();
+/// assert_eq!(loaded.location.last_offset(), 6);
+/// ```
+pub struct AstJson;
+
+impl AstJson {
+    /// Value of the `format` field of an AST JSON document.
+    pub const FORMAT: &'static str = "jet-ast";
+
+    /// Current version of the AST JSON format.
+    pub const VERSION: u32 = 1;
+
+    /// Writes a program into an AST JSON document.
+    pub fn serialize_program(program: &Rc<Program>) -> String {
+        let compilation_unit = program.location.compilation_unit();
+        let document = SerializedDocument {
+            format: Self::FORMAT.into(),
+            version: Self::VERSION,
+            compilation_unit: AstJsonCompilationUnit::of(&compilation_unit),
+            program: program.clone(),
+        };
+        Location::with_serialization_context(&compilation_unit, || serde_json::to_string_pretty(&document).unwrap())
+    }
+
+    /// Reads a program from an AST JSON document, attaching its locations
+    /// to the given compilation unit.
+    ///
+    /// The compilation unit must have the source text the document was written for.
+    pub fn deserialize_program(json: &str, compilation_unit: &Rc<CompilationUnit>) -> Result<Rc<Program>, AstJsonError> {
+        let document: DeserializedDocument = serde_json::from_str(json).map_err(|error| AstJsonError::Malformed(error.to_string()))?;
+        if document.format != Self::FORMAT {
+            return Err(AstJsonError::Malformed(format!("unknown format \"{}\"", document.format)));
+        }
+        if document.version != Self::VERSION {
+            return Err(AstJsonError::UnsupportedVersion(document.version));
+        }
+        let expected = AstJsonCompilationUnit::of(compilation_unit);
+        if document.compilation_unit.id != expected.id || document.compilation_unit.text_length != expected.text_length {
+            return Err(AstJsonError::CompilationUnitMismatch {
+                expected: expected.id,
+                found: document.compilation_unit.id,
+            });
+        }
+        Location::with_serialization_context(compilation_unit, || {
+            Program::deserialize(document.program).map(Rc::new).map_err(|error| AstJsonError::Malformed(error.to_string()))
+        })
+    }
+}
+
+/// Header identifying the compilation unit of an AST JSON document.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct AstJsonCompilationUnit {
+    /// Identifier derived from the source text of the compilation unit,
+    /// as 16 hexadecimal digits of `CompilationUnit::text_hash`.
+    pub id: String,
+    pub file_path: Option<String>,
+    /// Length of the source text in bytes.
+    pub text_length: usize,
+}
+
+impl AstJsonCompilationUnit {
+    pub fn of(compilation_unit: &Rc<CompilationUnit>) -> Self {
+        Self {
+            id: format!("{:016x}", compilation_unit.text_hash()),
+            file_path: compilation_unit.file_path(),
+            text_length: compilation_unit.current_text().len(),
+        }
+    }
+}
+
+/// Error reading an AST JSON document.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum AstJsonError {
+    /// The document is not valid JSON, does not follow the schema
+    /// or contains offsets out of the bounds of the source text.
+    Malformed(String),
+    UnsupportedVersion(u32),
+    /// The document was written for a different source text.
+    CompilationUnitMismatch {
+        expected: String,
+        found: String,
+    },
+}
+
+#[derive(Serialize)]
+struct SerializedDocument {
+    format: String,
+    version: u32,
+    compilation_unit: AstJsonCompilationUnit,
+    program: Rc<Program>,
+}
+
+#[derive(Deserialize)]
+struct DeserializedDocument {
+    format: String,
+    version: u32,
+    compilation_unit: AstJsonCompilationUnit,
+    program: serde_json::Value,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+
+    #[test]
+    fn round_tripping_parser_tests() {
+        let directory = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/parser");
+        for entry in std::fs::read_dir(directory).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_none_or(|extension| extension != "jet") {
+                continue;
+            }
+            let text = std::fs::read_to_string(&path).unwrap();
+            let file_path = format!("tests/parser/{}", path.file_name().unwrap().to_string_lossy());
+            let compilation_unit = CompilationUnit::new(Some(file_path.clone()), text.clone(), &CompilerOptions::new());
+            let program = ParserFacade::parse_program(&compilation_unit).unwrap();
+            let json = AstJson::serialize_program(&program);
+
+            // The `.ast.json` files are written by the parser test program
+            // and must be regenerated when the tree changes.
+            if let Ok(expected) = std::fs::read_to_string(path.with_extension("ast.json")) {
+                assert_eq!(json, expected, "{} is outdated", path.with_extension("ast.json").display());
+            }
+
+            let other_compilation_unit = CompilationUnit::new(Some(file_path), text, &CompilerOptions::new());
+            let loaded = AstJson::deserialize_program(&json, &other_compilation_unit).unwrap_or_else(|error| panic!("{}: {error:?}", path.display()));
+            assert!(Rc::ptr_eq(&loaded.location.compilation_unit(), &other_compilation_unit));
+            assert_eq!(AstJson::serialize_program(&loaded), json, "{}", path.display());
+        }
+    }
+
+    fn assert_round_trips(text: &str) {
+        let compilation_unit = CompilationUnit::new(None, text.into(), &CompilerOptions::new());
+        let program = ParserFacade::parse_program(&compilation_unit).unwrap();
+        let json = AstJson::serialize_program(&program);
+
+        let other_compilation_unit = CompilationUnit::new(None, text.into(), &CompilerOptions::new());
+        let loaded = AstJson::deserialize_program(&json, &other_compilation_unit).unwrap_or_else(|error| panic!("{text:?}: {error:?}"));
+        assert_eq!(AstJson::serialize_program(&loaded), json, "{text:?}");
+    }
+
+    #[test]
+    fn round_tripping_programs_without_directives() {
+        for text in ["", " ", "\n", "// c", "/* c */\n", "/** c */"] {
+            assert_round_trips(text);
+
+            // The program is located at the end of the source text.
+            let compilation_unit = CompilationUnit::new(None, text.into(), &CompilerOptions::new());
+            let program = ParserFacade::parse_program(&compilation_unit).unwrap();
+            assert_eq!((program.location.first_offset(), program.location.last_offset()), (text.len(), text.len()), "{text:?}");
+        }
+    }
+
+    #[test]
+    fn round_tripping_recovered_programs() {
+        for text in ["f(", "x = ;", "class C { function }", "package p { x. }", "if (", "[", "a b c", "var x: = 1", "(x => )"] {
+            assert_round_trips(text);
+        }
+        // Remove every line of the parser tests in turn, which leaves
+        // unbalanced brackets and incomplete directives behind.
+        let directory = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/parser");
+        for entry in std::fs::read_dir(directory).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_none_or(|extension| extension != "jet") {
+                continue;
+            }
+            let text = std::fs::read_to_string(&path).unwrap();
+            let lines: Vec<&str> = text.split_inclusive('\n').collect();
+            for i in 0..lines.len() {
+                assert_round_trips(&[&lines[..i], &lines[i + 1..]].concat().concat());
+            }
+        }
+    }
+
+    #[test]
+    fn rejecting_documents() {
+        let compilation_unit = CompilationUnit::new(None, "f(x)".into(), &CompilerOptions::new());
+        let program = ParserFacade::parse_program(&compilation_unit).unwrap();
+        let json = AstJson::serialize_program(&program);
+
+        let other_compilation_unit = CompilationUnit::new(None, "f(y)".into(), &CompilerOptions::new());
+        assert!(matches!(AstJson::deserialize_program(&json, &other_compilation_unit), Err(AstJsonError::CompilationUnitMismatch { .. })));
+
+        let future = json.replacen("\"version\": 1", "\"version\": 2", 1);
+        assert!(matches!(AstJson::deserialize_program(&future, &compilation_unit), Err(AstJsonError::UnsupportedVersion(2))));
+
+        let out_of_bounds = json.replacen("\"last_offset\": 4", "\"last_offset\": 40", 1);
+        assert!(matches!(AstJson::deserialize_program(&out_of_bounds, &compilation_unit), Err(AstJsonError::Malformed(_))));
+
+        // Outside of the AST JSON API, locations are not written.
+        assert!(serde_json::to_string(&program).unwrap().starts_with("{\"location\":{},"));
+    }
+}
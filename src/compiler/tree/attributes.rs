@@ -24,6 +24,7 @@ pub struct UnprocessedMetadata {
 }
 
 impl UnprocessedMetadata {
+    #[allow(dead_code)]
     pub(crate) fn process(&self, verifier: &mut VerifierVerifier) -> Rc<Metadata> {
         let mut entries = Vec::<Rc<MetadataEntry>>::new();
         if let Some(u_entries) = self.entries.as_ref() {
@@ -49,6 +50,7 @@ pub struct UnprocessedMetadataEntry {
 }
 
 impl UnprocessedMetadataEntry {
+    #[allow(dead_code)]
     pub(crate) fn process(&self, verifier: &mut VerifierVerifier) -> Result<Rc<MetadataEntry>, ()> {
         let value = self.value.process(verifier);
         let Ok(value) = value else {
@@ -88,6 +90,7 @@ impl UnprocessedMetadataValue {
         }
     }
 
+    #[allow(dead_code)]
     pub(crate) fn process(&self, verifier: &mut VerifierVerifier) -> Result<Rc<MetadataValue>, ()> {
         match self {
             Self::IdentifierString((v, _)) => Ok(Rc::new(MetadataValue::String(v.clone()))),
@@ -107,7 +110,7 @@ impl UnprocessedMetadataValue {
                 if let Ok(v) = v {
                     Ok(Rc::new(MetadataValue::Number(v)))
                 } else {
-                    verifier.add_verify_error(&l, DiagnosticKind::FailedParsingNumericLiteral, diagnostic_arguments![]);
+                    verifier.add_verify_error(l, DiagnosticKind::FailedParsingNumericLiteral, diagnostic_arguments![]);
                     Err(())
                 }
             },
@@ -120,7 +123,7 @@ impl UnprocessedMetadataValue {
                 if *output {
                     file_path = FlexPath::from_n_native([verifier.host.jetdependencies_output_directory().as_ref(), file_path.as_ref()]).to_string_with_flex_separator();
                 } else {
-                    file_path = FlexPath::new_native(&self.location().compilation_unit().file_path().unwrap_or(String::new())).resolve("..").resolve(&file_path).to_string_with_flex_separator();
+                    file_path = FlexPath::new_native(&self.location().compilation_unit().file_path().unwrap_or_default()).resolve("..").resolve(&file_path).to_string_with_flex_separator();
                 }
 
                 // Read file
@@ -130,7 +133,7 @@ impl UnprocessedMetadataValue {
                         data,
                     }))
                 } else {
-                    verifier.add_verify_error(&location, DiagnosticKind::FailedLoadingMetadataFile, diagnostic_arguments![String(file_path)]);
+                    verifier.add_verify_error(location, DiagnosticKind::FailedLoadingMetadataFile, diagnostic_arguments![String(file_path)]);
                     Err(())
                 }
             },
@@ -197,7 +200,7 @@ impl Attribute {
     pub fn remove_metadata(list: &mut Vec<Attribute>, metadata: &Rc<UnprocessedMetadata>) {
         for i in 0..list.len() {
             if let Attribute::Metadata(metadata_1) = &list[i] {
-                if Rc::ptr_eq(&metadata_1, metadata) {
+                if Rc::ptr_eq(metadata_1, metadata) {
                     list.remove(i);
                     break;
                 }
@@ -208,25 +211,22 @@ impl Attribute {
     pub fn find_metadata(list: &Vec<Attribute>) -> Vec<Rc<UnprocessedMetadata>> {
         let mut r = vec![];
         for a in list {
-            match &a {
-                Self::Metadata(e) => {
-                    r.push(e.clone());
-                },
-                _ => {},
+            if let Self::Metadata(e) = &a {
+                r.push(e.clone());
             }
         }
         r
     }
-    pub fn find_public(list: &Vec<Attribute>) -> Option<Location> { for a in list { match &a { Self::Public(l) => return Some(l.clone()), _ => {} } }; None }
-    pub fn find_private(list: &Vec<Attribute>) -> Option<Location> { for a in list { match &a { Self::Private(l) => return Some(l.clone()), _ => {} } }; None }
-    pub fn find_protected(list: &Vec<Attribute>) -> Option<Location> { for a in list { match &a { Self::Protected(l) => return Some(l.clone()), _ => {} } }; None }
-    pub fn find_internal(list: &Vec<Attribute>) -> Option<Location> { for a in list { match &a { Self::Internal(l) => return Some(l.clone()), _ => {} } }; None }
-    pub fn find_proxy(list: &Vec<Attribute>) -> Option<Location> { for a in list { match &a { Self::Proxy(l) => return Some(l.clone()), _ => {} } }; None }
-    pub fn find_final(list: &Vec<Attribute>) -> Option<Location> { for a in list { match &a { Self::Final(l) => return Some(l.clone()), _ => {} } }; None }
-    pub fn find_native(list: &Vec<Attribute>) -> Option<Location> { for a in list { match &a { Self::Native(l) => return Some(l.clone()), _ => {} } }; None }
-    pub fn find_static(list: &Vec<Attribute>) -> Option<Location> { for a in list { match &a { Self::Static(l) => return Some(l.clone()), _ => {} } }; None }
-    pub fn find_abstract(list: &Vec<Attribute>) -> Option<Location> { for a in list { match &a { Self::Abstract(l) => return Some(l.clone()), _ => {} } }; None }
-    pub fn find_override(list: &Vec<Attribute>) -> Option<Location> { for a in list { match &a { Self::Override(l) => return Some(l.clone()), _ => {} } }; None }
+    pub fn find_public(list: &Vec<Attribute>) -> Option<Location> { for a in list { if let Self::Public(l) = &a { return Some(l.clone()) } }; None }
+    pub fn find_private(list: &Vec<Attribute>) -> Option<Location> { for a in list { if let Self::Private(l) = &a { return Some(l.clone()) } }; None }
+    pub fn find_protected(list: &Vec<Attribute>) -> Option<Location> { for a in list { if let Self::Protected(l) = &a { return Some(l.clone()) } }; None }
+    pub fn find_internal(list: &Vec<Attribute>) -> Option<Location> { for a in list { if let Self::Internal(l) = &a { return Some(l.clone()) } }; None }
+    pub fn find_proxy(list: &Vec<Attribute>) -> Option<Location> { for a in list { if let Self::Proxy(l) = &a { return Some(l.clone()) } }; None }
+    pub fn find_final(list: &Vec<Attribute>) -> Option<Location> { for a in list { if let Self::Final(l) = &a { return Some(l.clone()) } }; None }
+    pub fn find_native(list: &Vec<Attribute>) -> Option<Location> { for a in list { if let Self::Native(l) = &a { return Some(l.clone()) } }; None }
+    pub fn find_static(list: &Vec<Attribute>) -> Option<Location> { for a in list { if let Self::Static(l) = &a { return Some(l.clone()) } }; None }
+    pub fn find_abstract(list: &Vec<Attribute>) -> Option<Location> { for a in list { if let Self::Abstract(l) = &a { return Some(l.clone()) } }; None }
+    pub fn find_override(list: &Vec<Attribute>) -> Option<Location> { for a in list { if let Self::Override(l) = &a { return Some(l.clone()) } }; None }
 
     pub fn has(list: &Vec<Attribute>, attribute: &Attribute) -> bool {
         match attribute {
@@ -270,7 +270,7 @@ impl Attribute {
         if location.character_count() != name.chars().count() {
             return None;
         }
-        match name.as_ref() {
+        match name {
             "proxy" => Some(Attribute::Proxy(location.clone())),
             "final" => Some(Attribute::Final(location.clone())),
             "native" => Some(Attribute::Native(location.clone())),
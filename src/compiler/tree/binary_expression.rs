@@ -7,4 +7,122 @@ pub struct BinaryExpression {
     pub operator: Operator,
     pub left: Rc<Expression>,
     pub right: Rc<Expression>,
-}
\ No newline at end of file
+}
+
+impl BinaryExpression {
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier, exp: &Rc<Expression>, context: &ExpressionVerifyContext) -> Result<Option<Symbol>, DeferVerificationError> {
+        let host = verifier.host.clone();
+        match self.operator {
+            Operator::Is | Operator::IsNot => {
+                let left = verifier.verify_expression(&self.left, &default())?;
+                let right = verifier.verify_type_expression(&self.right)?;
+                if left.is_none() || right.is_none() {
+                    return Ok(None);
+                }
+                Ok(Some(host.factory().create_value(&host.boolean_type())))
+            },
+            Operator::As => {
+                let left = verifier.verify_expression(&self.left, &default())?;
+                let right = verifier.verify_type_expression(&self.right)?;
+                let (Some(_), Some(right)) = (left, right) else {
+                    return Ok(None);
+                };
+                Ok(Some(host.factory().create_value(&host.factory().create_nullable_type(&right))))
+            },
+            Operator::In | Operator::NotIn => {
+                let left = verifier.verify_expression(&self.left, &default())?;
+                let right = verifier.verify_expression(&self.right, &default())?;
+                let (Some(_), Some(right)) = (left, right) else {
+                    return Ok(None);
+                };
+                if let Some(proxy) = verifier.find_operator_proxy(&right.static_type(&host), self.operator)? {
+                    return verifier.verify_proxy_call(exp, &proxy, &[&self.left]);
+                }
+                Ok(Some(host.factory().create_value(&host.boolean_type())))
+            },
+            Operator::Lt | Operator::Gt | Operator::Le | Operator::Ge |
+            Operator::Equals | Operator::NotEquals | Operator::StrictEquals | Operator::StrictNotEquals => {
+                let Some(left) = verifier.verify_expression(&self.left, &default())? else {
+                    verifier.verify_expression(&self.right, &default())?;
+                    return Ok(None);
+                };
+                let right = verifier.verify_expression(&self.right, &ExpressionVerifyContext {
+                    context_type: Some(left.static_type(&host)),
+                    ..default()
+                })?;
+                Ok(right.map(|_| host.factory().create_value(&host.boolean_type())))
+            },
+            Operator::LogicalAnd | Operator::LogicalXor | Operator::LogicalOr => {
+                let Some(left) = verifier.verify_expression(&self.left, &ExpressionVerifyContext {
+                    context_type: context.context_type.clone(),
+                    ..default()
+                })? else {
+                    verifier.verify_expression(&self.right, &default())?;
+                    return Ok(None);
+                };
+                let left_type = left.static_type(&host);
+
+                // The right operand of `&&` is evaluated only when the left
+                // operand is true, and that of `||` only when it is false.
+                let narrowings = match self.operator {
+                    Operator::LogicalAnd => verifier.condition_narrowings(&self.left, true),
+                    Operator::LogicalOr => verifier.condition_narrowings(&self.left, false),
+                    _ => vec![],
+                };
+                let right = verifier.with_narrowings(narrowings, |verifier| {
+                    verifier.verify_expression(&self.right, &ExpressionVerifyContext {
+                        context_type: Some(left_type.clone()),
+                        ..default()
+                    })
+                })?;
+                Ok(right.map(|right| self.fold(&host, &left, &right).unwrap_or_else(|| host.factory().create_value(&left_type))))
+            },
+            Operator::NullCoalescing => {
+                let Some(left) = verifier.verify_expression(&self.left, &ExpressionVerifyContext {
+                    context_type: context.context_type.clone(),
+                    ..default()
+                })? else {
+                    verifier.verify_expression(&self.right, &default())?;
+                    return Ok(None);
+                };
+                let left_type = left.static_type(&host).non_null_type();
+                let right = verifier.limit_expression_type(&self.right, &left_type)?;
+                Ok(right.map(|right| self.fold(&host, &left, &right).unwrap_or_else(|| host.factory().create_value(&left_type))))
+            },
+            _ => {
+                let Some(left) = verifier.verify_expression(&self.left, &ExpressionVerifyContext {
+                    context_type: context.context_type.clone(),
+                    ..default()
+                })? else {
+                    verifier.verify_expression(&self.right, &default())?;
+                    return Ok(None);
+                };
+                let left_type = left.static_type(&host);
+
+                // Operator overloaded by a proxy of the left operand's type
+                if let Some(proxy) = verifier.find_operator_proxy(&left_type, self.operator)? {
+                    return verifier.verify_proxy_call(exp, &proxy, &[&self.right]);
+                }
+
+                // String concatenation
+                if self.operator == Operator::Add && left_type.non_null_type() == host.string_type() {
+                    let right = verifier.verify_expression(&self.right, &default())?;
+                    return Ok(right.map(|right| self.fold(&host, &left, &right).unwrap_or_else(|| host.factory().create_value(&host.string_type()))));
+                }
+
+                if left_type.is_any_type() {
+                    let right = verifier.verify_expression(&self.right, &default())?;
+                    return Ok(right.map(|_| host.factory().create_value(&left_type)));
+                }
+
+                let right = verifier.limit_expression_type(&self.right, &left_type)?;
+                Ok(right.map(|right| self.fold(&host, &left, &right).unwrap_or_else(|| host.factory().create_value(&left_type))))
+            },
+        }
+    }
+
+    /// Results into a constant if both operands are constants.
+    fn fold(&self, host: &SymbolHost, left: &Symbol, right: &Symbol) -> Option<Symbol> {
+        ConstantFolding(host).fold_binary(self.operator, left, right)
+    }
+}
@@ -11,4 +11,21 @@ pub struct Block {
     /// Block meta-data for block statements.
     pub metadata: Option<Vec<Attribute>>,
     pub directives: Vec<Rc<Directive>>,
-}
\ No newline at end of file
+}
+
+impl Block {
+    /// Verifies a block statement. The block directive is
+    /// assigned the scope of the block.
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier, directive: &Rc<Directive>) {
+        let scope = if let Some(scope) = verifier.ast_to_symbol.get(directive) {
+            scope
+        } else {
+            let scope = verifier.host.factory().create_scope();
+            verifier.ast_to_symbol.set(directive, Some(scope.clone()));
+            scope
+        };
+        verifier.enter_scope(&scope);
+        verifier.verify_nested_directives(&self.directives);
+        verifier.exit_scope();
+    }
+}
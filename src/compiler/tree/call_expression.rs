@@ -6,4 +6,95 @@ pub struct CallExpression {
     pub location: Location,
     pub base: Rc<Expression>,
     pub arguments: Vec<Rc<Expression>>,
-}
\ No newline at end of file
+}
+
+impl CallExpression {
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier, context: &ExpressionVerifyContext) -> Result<Option<Symbol>, DeferVerificationError> {
+        let host = verifier.host.clone();
+        let Some(base) = verifier.verify_expression(&self.base, &default())? else {
+            for argument in &self.arguments {
+                verifier.verify_expression(argument, &default())?;
+            }
+            return Ok(None);
+        };
+
+        // Explicit type conversion
+        if let Ok(target_type) = base.expect_type() {
+            if self.arguments.len() != 1 {
+                for argument in &self.arguments {
+                    verifier.verify_expression(argument, &default())?;
+                }
+                return Ok(Some(host.factory().create_value(&target_type)));
+            }
+            let argument = &self.arguments[0];
+            let Some(v) = verifier.verify_expression(argument, &default())? else {
+                return Ok(None);
+            };
+            let got_type = v.static_type(&host);
+            let r = TypeConversions(&host).explicit_conversion(&v, &target_type, false)?;
+            if r.is_none() {
+                verifier.add_verify_error(&argument.location(), DiagnosticKind::IncompatibleTypes, diagnostic_arguments![Symbol(target_type), Symbol(got_type)]);
+            }
+            return Ok(r);
+        }
+
+        let base_type = base.static_type(&host);
+        if base_type.is_function_type() {
+            if let Some(class) = Self::uninstantiated_generic_class(&host, &base, &base_type) {
+                return self.verify_generic_static_call(verifier, &class, &base_type, context);
+            }
+            verifier.verify_arguments(&self.location, &base_type, &self.arguments)?;
+            return Ok(Some(host.factory().create_value(&base_type.result_type())));
+        }
+
+        for argument in &self.arguments {
+            verifier.verify_expression(argument, &default())?;
+        }
+        if base_type.is_any_type() || base_type == host.function_type() {
+            return Ok(Some(host.factory().create_value(&host.any_type())));
+        }
+        verifier.add_verify_error(&self.base.location(), DiagnosticKind::NotCallable, diagnostic_arguments![Symbol(base_type)]);
+        Ok(None)
+    }
+
+    /// Returns the generic class of a static method referenced without
+    /// type arguments, as in `Map.of(k, v)`, if its signature mentions
+    /// the type parameters of that class.
+    fn uninstantiated_generic_class(host: &SymbolHost, base: &Symbol, signature: &Symbol) -> Option<Symbol> {
+        if !base.is_static_reference_value() {
+            return None;
+        }
+        let class = base.base();
+        if !class.is_origin_class_type() {
+            return None;
+        }
+        let type_parameters = class.type_parameters().filter(|p| p.length() != 0)?;
+        TypeInference(host).mentions_type_parameters(signature, &type_parameters).then_some(class)
+    }
+
+    /// Verifies a call to a static method of a generic class, inferring
+    /// the type arguments of the class from the context type and from
+    /// the call arguments.
+    fn verify_generic_static_call(&self, verifier: &mut VerifierVerifier, class: &Symbol, signature: &Symbol, context: &ExpressionVerifyContext) -> Result<Option<Symbol>, DeferVerificationError> {
+        let host = verifier.host.clone();
+        let type_parameters = class.type_parameters().unwrap();
+        let mut inferred: Vec<Option<Symbol>> = vec![None; type_parameters.length()];
+
+        // Context type, such as `const m: Map.<String, Number> = Map.empty()`
+        if let Some(context_type) = context.context_type.as_ref() {
+            TypeInference(&host).infer(&signature.result_type(), context_type, &type_parameters, &mut inferred);
+        }
+
+        let Some(substitute_types) = verifier.infer_type_arguments_from_arguments(&self.location, Some(signature), &self.arguments, &type_parameters, inferred)? else {
+            for argument in &self.arguments {
+                verifier.verify_expression(argument, &default())?;
+            }
+            return Ok(None);
+        };
+        let instantiation = host.factory().create_type_after_explicit_type_substitution(class, &substitute_types);
+        verifier.check_type_arguments_later(&self.location, &instantiation);
+        let signature = TypeSubstitution(&host).execute(signature, &type_parameters, &substitute_types);
+        verifier.verify_arguments(&self.location, &signature, &self.arguments)?;
+        Ok(Some(host.factory().create_value(&signature.result_type())))
+    }
+}
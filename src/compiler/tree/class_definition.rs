@@ -12,4 +12,206 @@ pub struct ClassDefinition {
     pub extends_clause: Option<Rc<Expression>>,
     pub implements_clause: Option<Vec<Rc<Expression>>>,
     pub block: Rc<Block>,
-}
\ No newline at end of file
+}
+
+impl ClassDefinition {
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier, directive: &Rc<Directive>, phase: VerifierPhase) -> Result<(), DeferVerificationError> {
+        if phase == VerifierPhase::Declarations && !verifier.ast_to_symbol.has(directive) {
+            self.declare(verifier, directive);
+        }
+        let Some(class) = verifier.ast_to_symbol.get(directive) else {
+            return Ok(());
+        };
+        let scope = verifier.ast_to_symbol.get(&self.block).unwrap();
+
+        if phase == VerifierPhase::Heritage {
+            verifier.enter_scope(&scope);
+            let r = self.verify_heritage(verifier, &class);
+            verifier.exit_scope();
+            r?;
+        }
+
+        verifier.enter_scope(&scope);
+        verifier.verify_directives(&self.block.directives, phase);
+        verifier.exit_scope();
+
+        if phase == VerifierPhase::Signatures {
+            self.verify_implementation(verifier, &class)?;
+        }
+        Ok(())
+    }
+
+    fn declare(&self, verifier: &mut VerifierVerifier, directive: &Rc<Directive>) {
+        let (properties, parent) = verifier.definition_target(true);
+        let name = self.name.0.clone();
+        if let Some(existing) = properties.get(&name) {
+            verifier.add_duplicate_definition_error(&self.name.1, &name, Some(&existing));
+            verifier.ast_to_symbol.set(directive, None);
+            return;
+        }
+        let class = verifier.host.factory().create_class_type(name.clone());
+        class.set_parent(Some(&parent));
+        verifier.definition_locations.insert(class.clone(), self.name.1.clone());
+        class.set_visibility(Attribute::visibility(&self.attributes, false));
+        class.set_jetdoc(self.jetdoc.clone());
+        class.set_allow_literal(self.allow_literal);
+        class.set_is_abstract(Attribute::find_abstract(&self.attributes).is_some());
+        class.set_is_final(Attribute::find_final(&self.attributes).is_some());
+        class.set_is_static(Attribute::find_static(&self.attributes).is_some());
+        properties.clone().set(name, class.clone());
+        verifier.ast_to_symbol.set(directive, Some(class.clone()));
+
+        let scope = verifier.host.factory().create_class_scope(&class);
+        scope.set_parent(Some(&verifier.scope));
+        if let Some(type_parameters) = self.type_parameters.as_ref() {
+            class.set_type_parameters(Some(&verifier.declare_type_parameters(type_parameters, &scope)));
+        }
+        verifier.ast_to_symbol.set(&self.block, Some(scope));
+    }
+
+    /// Resolves the `extends` and `implements` clauses
+    /// and the type parameter bounds.
+    fn verify_heritage(&self, verifier: &mut VerifierVerifier, class: &Symbol) -> Result<(), DeferVerificationError> {
+        let host = verifier.host.clone();
+
+        // Resolve every type before reporting anything, as the
+        // verification may be deferred.
+        let scope = verifier.scope.clone();
+        let bounds = verifier.resolve_type_parameter_bounds(self.type_parameters.as_deref().unwrap_or_default(), &scope)?;
+        let extends_type = if let Some(exp) = self.extends_clause.as_ref() {
+            verifier.verify_type_expression(exp)?
+        } else {
+            None
+        };
+        let mut implements_types: Vec<(Symbol, Location)> = vec![];
+        for exp in self.implements_clause.iter().flatten() {
+            if let Some(t) = verifier.verify_type_expression(exp)? {
+                implements_types.push((t, exp.location()));
+            }
+        }
+        let object_type = host.object_type();
+        object_type.throw_if_unresolved()?;
+
+        verifier.set_type_parameter_bounds(bounds);
+
+        let mut extends_class = if &object_type == class { None } else { Some(object_type) };
+        if let Some(t) = extends_type {
+            let location = self.extends_clause.as_ref().unwrap().location();
+            if !t.is_class_type() {
+                verifier.add_verify_error(&location, DiagnosticKind::NotAClass, diagnostic_arguments![Symbol(t)]);
+            } else if t.is_final() {
+                verifier.add_verify_error(&location, DiagnosticKind::ExtendingFinalClass, diagnostic_arguments![Symbol(t)]);
+            } else if verifier.inherits_from(&t, class) {
+                verifier.add_verify_error(&location, DiagnosticKind::CircularInheritance, diagnostic_arguments![Symbol(class.clone())]);
+            } else {
+                extends_class = Some(t);
+            }
+        }
+        class.set_extends_class(extends_class.as_ref());
+
+        let mut implements = class.implements(&host);
+        for (t, location) in implements_types {
+            if !t.is_interface_type() {
+                verifier.add_verify_error(&location, DiagnosticKind::NotAnInterface, diagnostic_arguments![Symbol(t)]);
+            } else if !implements.includes(&t) {
+                implements.push(t);
+            }
+        }
+        Ok(())
+    }
+
+    /// Verifies method overriding, abstract methods and interface
+    /// implementations once member signatures are resolved.
+    fn verify_implementation(&self, verifier: &mut VerifierVerifier, class: &Symbol) -> Result<(), DeferVerificationError> {
+        let host = verifier.host.clone();
+
+        // Collect results before reporting anything, as the
+        // verification may be deferred.
+        let abstract_methods = if class.is_abstract() {
+            vec![]
+        } else {
+            MethodOverriding(&host).abstract_methods_not_overriden(class)?
+        };
+        let mut implementation_logs: Vec<(Symbol, Vec<InterfaceImplementationLog>)> = vec![];
+        for interface in class.implements(&host).iter() {
+            let log = InterfaceImplementations(&host).verify(class, &interface)?;
+            implementation_logs.push((interface, log));
+        }
+        let mut overriding_errors: Vec<(Symbol, MethodOverridingError)> = vec![];
+        for method in Self::overriding_methods(class, &host) {
+            if method.overrides_method(&host).is_some() {
+                continue;
+            }
+            match MethodOverriding(&host).override_method(&method) {
+                Ok(_) => {},
+                Err(MethodOverridingError::DeferVerification) => {
+                    return Err(DeferVerificationError);
+                },
+                Err(e) => {
+                    overriding_errors.push((method, e));
+                },
+            }
+        }
+
+        for prop in abstract_methods {
+            verifier.add_verify_error(&self.name.1, DiagnosticKind::AbstractMethodMustBeOverriden, diagnostic_arguments![String(prop.name())]);
+        }
+        for (interface, log) in implementation_logs {
+            for item in log {
+                let (kind, arguments) = match item {
+                    InterfaceImplementationLog::UnimplementedMethod { name } => (DiagnosticKind::UnimplementedMethod, diagnostic_arguments![String(name), Symbol(interface.clone())]),
+                    InterfaceImplementationLog::UnimplementedGetter { name } => (DiagnosticKind::UnimplementedGetter, diagnostic_arguments![String(name), Symbol(interface.clone())]),
+                    InterfaceImplementationLog::UnimplementedSetter { name } => (DiagnosticKind::UnimplementedSetter, diagnostic_arguments![String(name), Symbol(interface.clone())]),
+                    InterfaceImplementationLog::PropertyMustBeMethod { name } => (DiagnosticKind::PropertyMustBeMethod, diagnostic_arguments![String(name)]),
+                    InterfaceImplementationLog::PropertyMustBeVirtualProperty { name } => (DiagnosticKind::PropertyMustBeVirtualProperty, diagnostic_arguments![String(name)]),
+                    InterfaceImplementationLog::WrongMethodSignature { name, expected_signature } => (DiagnosticKind::WrongMethodSignature, diagnostic_arguments![String(name), Symbol(expected_signature)]),
+                    InterfaceImplementationLog::WrongGetterSignature { name, expected_signature } => (DiagnosticKind::WrongGetterSignature, diagnostic_arguments![String(name), Symbol(expected_signature)]),
+                    InterfaceImplementationLog::WrongSetterSignature { name, expected_signature } => (DiagnosticKind::WrongSetterSignature, diagnostic_arguments![String(name), Symbol(expected_signature)]),
+                    InterfaceImplementationLog::WrongVisibility { name, expected_visibility } => (DiagnosticKind::WrongVisibility, diagnostic_arguments![String(name), String(expected_visibility.to_string())]),
+                };
+                verifier.add_verify_error(&self.name.1, kind, arguments);
+            }
+        }
+        for (method, error) in overriding_errors {
+            let location = self.member_location(verifier, &method);
+            match error {
+                MethodOverridingError::MustOverrideAMethod => {
+                    verifier.add_verify_error(&location, DiagnosticKind::MustOverrideAMethod, diagnostic_arguments![String(method.name())]);
+                },
+                MethodOverridingError::IncompatibleSignature { expected_signature, actual_signature } => {
+                    verifier.add_verify_error(&location, DiagnosticKind::IncompatibleOverride, diagnostic_arguments![Symbol(expected_signature), Symbol(actual_signature)]);
+                },
+                MethodOverridingError::OverridingFinalMethod => {
+                    verifier.add_verify_error(&location, DiagnosticKind::OverridingFinalMethod, diagnostic_arguments![String(method.name())]);
+                },
+                MethodOverridingError::DeferVerification => {},
+            }
+        }
+        Ok(())
+    }
+
+    /// Instance methods, getters and setters that use the `override` attribute.
+    fn overriding_methods(class: &Symbol, host: &SymbolHost) -> Vec<Symbol> {
+        let mut r = vec![];
+        for prop in class.prototype(host).borrow().values() {
+            if prop.is_virtual_property() {
+                r.extend(prop.getter(host).into_iter().chain(prop.setter(host)).filter(|f| f.is_overriding()));
+            } else if prop.is_method() && prop.is_overriding() {
+                r.push(prop.clone());
+            }
+        }
+        r
+    }
+
+    /// Location of the name of a method defined in the class block.
+    fn member_location(&self, verifier: &VerifierVerifier, method: &Symbol) -> Location {
+        for directive in &self.block.directives {
+            if let Directive::FunctionDefinition(defn) = directive.as_ref() {
+                if verifier.ast_to_symbol.get(&defn.common).as_ref() == Some(method) {
+                    return defn.name.location();
+                }
+            }
+        }
+        self.name.1.clone()
+    }
+}
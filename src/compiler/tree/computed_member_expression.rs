@@ -6,4 +6,38 @@ pub struct ComputedMemberExpression {
     pub location: Location,
     pub base: Rc<Expression>,
     pub key: Rc<Expression>,
-}
\ No newline at end of file
+}
+
+impl ComputedMemberExpression {
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier, exp: &Rc<Expression>, context: &ExpressionVerifyContext) -> Result<Option<Symbol>, DeferVerificationError> {
+        let base = verifier.verify_expression(&self.base, &default())?;
+        let key = verifier.verify_expression(&self.key, &default())?;
+        let (Some(base), Some(key)) = (base, key) else {
+            return Ok(None);
+        };
+        let r = verifier.resolve_property_as_exp(&base, None, SemanticPropertyKey::Value(key), PropertyDisambiguation::Default, &self.key.location(), context)?;
+
+        // Record the proxy called according to how the reference is used.
+        // The key is checked against the proxy's key type, which may differ
+        // from that of `getProperty()`.
+        if let Some(r) = r.as_ref().filter(|r| r.is_proxy_reference_value()) {
+            let base_type = base.static_type(&verifier.host);
+            let proxy = match context.mode {
+                VerifyMode::Read => Some(r.proxy()),
+                VerifyMode::Write => base_type.find_proxy(ProxyKind::SetProperty, &verifier.host)?,
+                VerifyMode::Delete => base_type.find_proxy(ProxyKind::DeleteProperty, &verifier.host)?,
+            };
+            if let Some(proxy) = proxy {
+                let signature = proxy.signature(&verifier.host);
+                signature.throw_if_unresolved()?;
+                if let Some(parameter) = signature.parameters().get(0) {
+                    if verifier.limit_expression_type(&self.key, &parameter.static_type)?.is_none() {
+                        return Ok(None);
+                    }
+                }
+                verifier.ast_to_symbol.set_proxy_call(exp, Some(proxy));
+            }
+        }
+        Ok(r)
+    }
+}
@@ -7,4 +7,25 @@ pub struct ConditionalExpression {
     pub test: Rc<Expression>,
     pub consequent: Rc<Expression>,
     pub alternative: Rc<Expression>,
-}
\ No newline at end of file
+}
+
+impl ConditionalExpression {
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier, context: &ExpressionVerifyContext) -> Result<Option<Symbol>, DeferVerificationError> {
+        verifier.verify_expression(&self.test, &default())?;
+        let consequent_narrowings = verifier.condition_narrowings(&self.test, true);
+        let alternative_narrowings = verifier.condition_narrowings(&self.test, false);
+        let consequent = verifier.with_narrowings(consequent_narrowings, |verifier| {
+            verifier.verify_expression(&self.consequent, &ExpressionVerifyContext {
+                context_type: context.context_type.clone(),
+                ..default()
+            })
+        })?;
+        let Some(consequent) = consequent else {
+            verifier.with_narrowings(alternative_narrowings, |verifier| verifier.verify_expression(&self.alternative, &default()))?;
+            return Ok(None);
+        };
+        let consequent_type = consequent.static_type(&verifier.host);
+        let alternative = verifier.with_narrowings(alternative_narrowings, |verifier| verifier.limit_expression_type(&self.alternative, &consequent_type))?;
+        Ok(alternative.map(|_| verifier.host.factory().create_value(&consequent_type)))
+    }
+}
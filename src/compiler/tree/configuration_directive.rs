@@ -1,8 +1,188 @@
 use crate::ns::*;
 use serde::{Serialize, Deserialize};
 
+/// The `configuration` directive, which selects directives
+/// at compile time.
+///
+/// Conditions test configuration constants, which are looked up
+/// in the `CompilerOptions` of the compilation unit and then in
+/// the environment variables exposed by `import.meta.env`:
+///
+/// ```jet
+/// configuration {
+///     if (CONFIG::DEBUG) {
+///         trace("Debugging");
+///     } else if (platform="ios") {
+///         trace("Releasing for iOS");
+///     }
+/// }
+/// ```
+///
+/// A constant whose value is `true` or `false` is a Boolean; any other value
+/// is a String, which may only be compared with `=` and `!=`.
 #[derive(Clone, Serialize, Deserialize)]
 pub struct ConfigurationDirective {
     pub location: Location,
     pub directive: Rc<Directive>,
-}
\ No newline at end of file
+}
+
+impl ConfigurationDirective {
+    /// Verifies the directives of the selected branch as if they appeared
+    /// in place of the configuration directive. Dropped branches
+    /// are not verified.
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier, directive: &Rc<Directive>, phase: VerifierPhase) -> Result<(), DeferVerificationError> {
+        let branch = if let Some(branch) = verifier.ast_to_symbol.configuration_branch(directive) {
+            branch
+        } else {
+            let branch = Self::select_branch(verifier, &self.directive);
+            verifier.ast_to_symbol.set_configuration_branch(directive, branch.clone());
+            branch
+        };
+        if let Some(Directive::Block(block)) = branch.as_deref() {
+            verifier.verify_directives(&block.directives, phase);
+        }
+        Ok(())
+    }
+
+    fn select_branch(verifier: &mut VerifierVerifier, directive: &Rc<Directive>) -> Option<Rc<Directive>> {
+        let Directive::IfStatement(stmt) = directive.as_ref() else {
+            return Some(directive.clone());
+        };
+        let test = Self::evaluate_boolean(verifier, &stmt.test)?;
+        if test {
+            Some(stmt.consequent.clone())
+        } else {
+            stmt.alternative.as_ref().and_then(|alternative| Self::select_branch(verifier, alternative))
+        }
+    }
+
+    /// Evaluates a condition that must result in a Boolean. Returns `None`
+    /// after reporting a diagnostic if evaluation fails.
+    fn evaluate_boolean(verifier: &mut VerifierVerifier, exp: &Rc<Expression>) -> Option<bool> {
+        let value = Self::evaluate(verifier, exp)?;
+        if !value.is_boolean_constant() {
+            verifier.add_verify_error(&exp.location(), DiagnosticKind::ConfigurationConditionMustBeConstant, diagnostic_arguments![]);
+            return None;
+        }
+        Some(value.boolean_value())
+    }
+
+    fn evaluate(verifier: &mut VerifierVerifier, exp: &Rc<Expression>) -> Option<Symbol> {
+        let host = verifier.host.clone();
+        let folding = ConstantFolding(&host);
+        match exp.as_ref() {
+            Expression::QualifiedIdentifier(QualifiedIdentifier { id: QualifiedIdentifierIdentifier::Id((name, _)), .. }) => {
+                let value = exp.location().compilation_unit().compiler_options().constants.get(name).cloned()
+                    .or_else(|| host.preload_environment_variables().get(name).cloned());
+                let Some(value) = value else {
+                    verifier.add_verify_error(&exp.location(), DiagnosticKind::UndefinedConfigurationConstant, diagnostic_arguments![String(name.clone())]);
+                    return None;
+                };
+                Some(match value.as_ref() {
+                    "true" => host.factory().create_boolean_constant(true, &host.boolean_type()),
+                    "false" => host.factory().create_boolean_constant(false, &host.boolean_type()),
+                    _ => host.factory().create_string_constant(value, &host.string_type()),
+                })
+            },
+            Expression::StringLiteral(literal) => {
+                Some(host.factory().create_string_constant(literal.value.clone(), &host.string_type()))
+            },
+            Expression::Paren(paren) => Self::evaluate(verifier, &paren.expression),
+            Expression::Unary(UnaryExpression { operator: Operator::LogicalNot, expression, .. }) => {
+                let operand = Self::evaluate_boolean(verifier, expression)?;
+                let operand = host.factory().create_boolean_constant(operand, &host.boolean_type());
+                folding.fold_unary(Operator::LogicalNot, &operand)
+            },
+            Expression::Binary(BinaryExpression { operator: operator @ (Operator::LogicalAnd | Operator::LogicalOr), left, right, .. }) => {
+                let left = Self::evaluate_boolean(verifier, left);
+                let right = Self::evaluate_boolean(verifier, right);
+                let (left, right) = (left?, right?);
+                let left = host.factory().create_boolean_constant(left, &host.boolean_type());
+                let right = host.factory().create_boolean_constant(right, &host.boolean_type());
+                folding.fold_binary(*operator, &left, &right)
+            },
+            Expression::Binary(BinaryExpression { operator: operator @ (Operator::Equals | Operator::NotEquals), left, right, .. }) => {
+                let left = Self::evaluate(verifier, left);
+                let right = Self::evaluate(verifier, right);
+                // Constants are compared by their string representation,
+                // as in `CONFIG::DEBUG=true`.
+                let left = folding.constant_to_string(&left?)?;
+                let right = folding.constant_to_string(&right?)?;
+                let equal = (left == right) == (*operator == Operator::Equals);
+                Some(host.factory().create_boolean_constant(equal, &host.boolean_type()))
+            },
+            // The parser has already reported the invalidated expression.
+            Expression::Invalidated(_) => None,
+            _ => {
+                verifier.add_verify_error(&exp.location(), DiagnosticKind::ConfigurationConditionMustBeConstant, diagnostic_arguments![]);
+                None
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+
+    const JET_LANG: &str = "package jet.lang { public class Object {} public class Boolean {} public class Class {} public class Function {} public class Number {} public class String {} }\n";
+
+    fn verify(text: &str, constants: &[(&str, &str)], host: &Rc<SymbolHost>) -> Vec<String> {
+        let mut options = CompilerOptions::default();
+        options.constants.extend(constants.iter().map(|(name, value)| (name.to_string(), value.to_string())));
+        let compilation_unit = CompilationUnit::new(None, JET_LANG.to_owned() + text, &Rc::new(options));
+        let program = ParserFacade::parse_program(&compilation_unit).unwrap();
+        Verifier::new(host).verify_programs(vec![program]);
+        compilation_unit.sort_diagnostics();
+        compilation_unit.diagnostics().iter().map(|d| d.format_english()).collect()
+    }
+
+    #[test]
+    fn selecting_branches() {
+        let text = r#"package p {
+            configuration {
+                if (CONFIG::DEBUG && !(platform="ios")) {
+                    public function f(): Number { return 0 }
+                } else {
+                    public function f(): String { return "" }
+                    public function g(): void { undefinedInReleaseBranch }
+                }
+            }
+            public var v: Number = f();
+        }"#;
+        let host = SymbolHost::new("", "");
+        let diagnostics = verify(text, &[("CONFIG::DEBUG", "true"), ("platform", "android")], &host);
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+        assert!(host.top_level_package().subpackages().get(&"p".into()).unwrap().properties(&host).get(&"g".into()).is_none());
+
+        let host = SymbolHost::new("", "");
+        let diagnostics = verify(text, &[("CONFIG::DEBUG", "true"), ("platform", "ios")], &host);
+        assert_eq!(diagnostics, vec![
+            "8:49: Verify error #1089: Access of undefined property 'undefinedInReleaseBranch'".to_owned(),
+            "11:36: Verify error #1064: Incompatible types: expected 'jet.lang.Number', got 'jet.lang.String'".to_owned(),
+        ]);
+    }
+
+    #[test]
+    fn reporting_invalid_conditions() {
+        let text = "function f(): void { configuration { if (UNDEFINED || platform) { trace() } } }";
+        let host = SymbolHost::new("", "");
+        let diagnostics = verify(text, &[("platform", "ios")], &host);
+        assert_eq!(diagnostics, vec![
+            "2:42: Verify error #1128: Undefined configuration constant 'UNDEFINED'".to_owned(),
+            "2:55: Verify error #1129: Configuration condition must be a constant Boolean".to_owned(),
+        ]);
+    }
+
+    #[test]
+    fn reading_environment_variables() {
+        let directory = std::env::temp_dir().join(format!("jet-configuration-test-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(directory.join(".env"), "PLATFORM=ios\n").unwrap();
+        let host = SymbolHost::new("", &directory.to_string_lossy());
+        let text = "function f(): void { var p: String = import.meta.env.PLATFORM; import.meta.env.MISSING; configuration { if (PLATFORM=ios) { return } else { undefined1 } } }";
+        let diagnostics = verify(text, &[], &host);
+        std::fs::remove_dir_all(&directory).unwrap();
+        assert_eq!(diagnostics, vec!["2:80: Verify error #1089: Access of undefined property 'MISSING'".to_owned()]);
+    }
+}
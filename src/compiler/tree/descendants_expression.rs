@@ -6,4 +6,15 @@ pub struct DescendantsExpression {
     pub location: Location,
     pub base: Rc<Expression>,
     pub identifier: QualifiedIdentifier,
-}
\ No newline at end of file
+}
+
+impl DescendantsExpression {
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier) -> Result<Option<Symbol>, DeferVerificationError> {
+        let base = verifier.verify_expression(&self.base, &default())?;
+        let qn = self.identifier.verify(verifier)?;
+        if base.is_none() || qn.is_none() {
+            return Ok(None);
+        }
+        Ok(Some(verifier.host.factory().create_value(&verifier.host.xml_list_type())))
+    }
+}
@@ -6,4 +6,171 @@ pub struct TypedDestructuring {
     pub location: Location,
     pub destructuring: Rc<Expression>,
     pub type_annotation: Option<Rc<Expression>>,
-}
\ No newline at end of file
+}
+
+impl TypedDestructuring {
+    /// Declares the variables of a destructuring pattern into `properties`,
+    /// initially with an `Unresolved` static type.
+    ///
+    /// Identifier patterns are assigned their variable property. Patterns that
+    /// are already assigned a symbol or `None` are skipped.
+    pub(crate) fn declare(&self, verifier: &mut VerifierVerifier, read_only: bool, properties: &SharedMap<String, Symbol>, parent: &Symbol, visibility: Visibility, jetdoc: Option<Rc<JetDoc>>) {
+        Self::declare_pattern(&self.destructuring, verifier, read_only, properties, parent, visibility, &jetdoc);
+    }
+
+    fn declare_pattern(pattern: &Rc<Expression>, verifier: &mut VerifierVerifier, read_only: bool, properties: &SharedMap<String, Symbol>, parent: &Symbol, visibility: Visibility, jetdoc: &Option<Rc<JetDoc>>) {
+        match pattern.as_ref() {
+            Expression::QualifiedIdentifier(id) => {
+                if verifier.ast_to_symbol.has(pattern) {
+                    return;
+                }
+                let Some((name, location)) = id.to_identifier_name() else {
+                    verifier.ast_to_symbol.set(pattern, None);
+                    return;
+                };
+                let variable = Self::declare_variable(verifier, name, &location, read_only, properties, parent, visibility, jetdoc);
+                verifier.ast_to_symbol.set(pattern, variable);
+            },
+            Expression::Unary(e) if e.operator == Operator::NonNull => {
+                Self::declare_pattern(&e.expression, verifier, read_only, properties, parent, visibility, jetdoc);
+            },
+            Expression::ArrayLiteral(al) => {
+                for element in &al.elements {
+                    match element {
+                        Element::Elision => {},
+                        Element::Expression(e) | Element::Rest((e, _)) => {
+                            Self::declare_pattern(e, verifier, read_only, properties, parent, visibility, jetdoc);
+                        },
+                    }
+                }
+            },
+            Expression::ObjectInitializer(oi) => {
+                for field in &oi.fields {
+                    match field.as_ref() {
+                        InitializerField::Field { value: Some(value), .. } => {
+                            Self::declare_pattern(value, verifier, read_only, properties, parent, visibility, jetdoc);
+                        },
+                        InitializerField::Field { name, value: None, .. } => {
+                            if let Some(name) = name.0.id() {
+                                if properties.get(&name).is_none() {
+                                    Self::declare_variable(verifier, name, &field.location(), read_only, properties, parent, visibility, jetdoc);
+                                }
+                            }
+                        },
+                        InitializerField::Rest((e, _)) => {
+                            Self::declare_pattern(e, verifier, read_only, properties, parent, visibility, jetdoc);
+                        },
+                    }
+                }
+            },
+            _ => {},
+        }
+    }
+
+    /// Declares a variable, reporting a duplicate definition if the name
+    /// is already defined. Variables in an activation may be shadowed.
+    #[allow(clippy::too_many_arguments)]
+    fn declare_variable(verifier: &mut VerifierVerifier, name: String, location: &Location, read_only: bool, properties: &SharedMap<String, Symbol>, parent: &Symbol, visibility: Visibility, jetdoc: &Option<Rc<JetDoc>>) -> Option<Symbol> {
+        if let Some(existing) = properties.get(&name) {
+            if !(parent.is_activation_scope() && existing.is_variable_property()) {
+                verifier.add_duplicate_definition_error(location, &name, Some(&existing));
+                return None;
+            }
+        }
+        let variable = verifier.host.factory().create_variable_property(name.clone(), read_only, &verifier.host.unresolved());
+        variable.set_parent(Some(parent));
+        verifier.definition_locations.insert(variable.clone(), location.clone());
+        variable.set_visibility(visibility);
+        if jetdoc.is_some() {
+            variable.set_jetdoc(jetdoc.clone());
+        }
+        properties.clone().set(name, variable.clone());
+        Some(variable)
+    }
+
+    /// Assigns a static type to the variables declared by the destructuring pattern.
+    pub(crate) fn verify_type(&self, verifier: &mut VerifierVerifier, static_type: &Symbol, properties: &SharedMap<String, Symbol>) -> Result<(), DeferVerificationError> {
+        Self::verify_pattern_type(&self.destructuring, verifier, static_type, properties)
+    }
+
+    fn verify_pattern_type(pattern: &Rc<Expression>, verifier: &mut VerifierVerifier, static_type: &Symbol, properties: &SharedMap<String, Symbol>) -> Result<(), DeferVerificationError> {
+        match pattern.as_ref() {
+            Expression::QualifiedIdentifier(_) => {
+                if let Some(variable) = verifier.ast_to_symbol.get(pattern) {
+                    if variable.static_type(&verifier.host).is_unresolved() {
+                        variable.set_static_type(static_type);
+                    }
+                }
+            },
+            Expression::Unary(e) if e.operator == Operator::NonNull => {
+                Self::verify_pattern_type(&e.expression, verifier, &static_type.non_null_type(), properties)?;
+            },
+            Expression::ArrayLiteral(al) => {
+                let array_element_type = static_type.array_element_type(&verifier.host)?;
+                for (i, element) in al.elements.iter().enumerate() {
+                    match element {
+                        Element::Elision => {},
+                        Element::Expression(e) => {
+                            let element_type = if static_type.is_tuple_type() {
+                                static_type.element_types().get(i).unwrap_or(verifier.host.any_type())
+                            } else {
+                                array_element_type.clone().unwrap_or(verifier.host.any_type())
+                            };
+                            Self::verify_pattern_type(e, verifier, &element_type, properties)?;
+                        },
+                        Element::Rest((e, _)) => {
+                            let rest_type = if array_element_type.is_some() {
+                                static_type.clone()
+                            } else {
+                                let t = verifier.host.array_type_of_any();
+                                t.throw_if_unresolved()?;
+                                t
+                            };
+                            Self::verify_pattern_type(e, verifier, &rest_type, properties)?;
+                        },
+                    }
+                }
+            },
+            Expression::ObjectInitializer(oi) => {
+                for field in &oi.fields {
+                    match field.as_ref() {
+                        InitializerField::Field { name, value, non_null } => {
+                            let mut field_type = Self::field_type(verifier, static_type, &name.0)?;
+                            if *non_null {
+                                field_type = field_type.non_null_type();
+                            }
+                            if let Some(value) = value {
+                                Self::verify_pattern_type(value, verifier, &field_type, properties)?;
+                            } else if let Some(variable) = name.0.id().and_then(|name| properties.get(&name)) {
+                                if variable.is_variable_property() && variable.static_type(&verifier.host).is_unresolved() {
+                                    variable.set_static_type(&field_type);
+                                }
+                            }
+                        },
+                        InitializerField::Rest((e, _)) => {
+                            Self::verify_pattern_type(e, verifier, static_type, properties)?;
+                        },
+                    }
+                }
+            },
+            _ => {},
+        }
+        Ok(())
+    }
+
+    /// Determines the static type of a field from an object destructuring pattern.
+    fn field_type(verifier: &mut VerifierVerifier, static_type: &Symbol, name: &FieldName) -> Result<Symbol, DeferVerificationError> {
+        if let Some((_, v)) = static_type.map_key_value_types(&verifier.host)? {
+            return Ok(v);
+        }
+        let Some(name) = name.id() else {
+            return Ok(verifier.host.any_type());
+        };
+        let base = verifier.host.factory().create_value(static_type);
+        match base.resolve_property(None, SemanticPropertyKey::String(name), &verifier.host) {
+            Ok(Some(r)) => Ok(r.static_type(&verifier.host)),
+            Err(PropertyResolutionError::DeferVerification) => Err(DeferVerificationError),
+            _ => Ok(verifier.host.any_type()),
+        }
+    }
+}
@@ -82,15 +82,13 @@ impl EmbedExpression {
         let value = value.as_ref().unwrap();
         match value.as_ref() {
             Expression::StringLiteral(StringLiteral { value, .. }) => {
-                Some(FlexPath::new_native(&self.location.compilation_unit().file_path().unwrap_or(String::new())).resolve("..").resolve(value).to_string_with_flex_separator())
+                Some(FlexPath::new_native(&self.location.compilation_unit().file_path().unwrap_or_default()).resolve("..").resolve(value).to_string_with_flex_separator())
             },
             Expression::Binary(BinaryExpression { left, operator, right, .. }) => {
                 if *operator != Operator::Add {
                     return None;
                 }
-                let Some(left_id) = left.to_identifier_name_or_asterisk() else {
-                    return None;
-                };
+                let left_id = left.to_identifier_name_or_asterisk()?;
                 if left_id.0 != "output" {
                     return None;
                 }
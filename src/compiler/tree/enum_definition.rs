@@ -10,4 +10,185 @@ pub struct EnumDefinition {
     pub name: (String, Location),
     pub as_clause: Option<Rc<Expression>>,
     pub block: Rc<Block>,
-}
\ No newline at end of file
+}
+
+impl EnumDefinition {
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier, directive: &Rc<Directive>, phase: VerifierPhase) -> Result<(), DeferVerificationError> {
+        if phase == VerifierPhase::Declarations && !verifier.ast_to_symbol.has(directive) {
+            self.declare(verifier, directive);
+        }
+        let Some(enum_type) = verifier.ast_to_symbol.get(directive) else {
+            return Ok(());
+        };
+        let scope = verifier.ast_to_symbol.get(&self.block).unwrap();
+
+        if phase == VerifierPhase::Heritage && enum_type.enumeration_numeric_type().is_none() {
+            verifier.enter_scope(&scope);
+            let r = self.verify_numeric_type(verifier, &enum_type);
+            verifier.exit_scope();
+            r?;
+        }
+
+        verifier.enter_scope(&scope);
+        verifier.verify_directives(&self.block.directives, phase);
+        let r = if phase == VerifierPhase::Signatures {
+            self.assign_members(verifier, &enum_type)
+        } else {
+            Ok(())
+        };
+        verifier.exit_scope();
+        r
+    }
+
+    fn declare(&self, verifier: &mut VerifierVerifier, directive: &Rc<Directive>) {
+        let (properties, parent) = verifier.definition_target(true);
+        let name = self.name.0.clone();
+        if let Some(existing) = properties.get(&name) {
+            verifier.add_duplicate_definition_error(&self.name.1, &name, Some(&existing));
+            verifier.ast_to_symbol.set(directive, None);
+            return;
+        }
+        let enum_type = verifier.host.factory().create_enum_type(name.clone(), self.is_set);
+        enum_type.set_parent(Some(&parent));
+        verifier.definition_locations.insert(enum_type.clone(), self.name.1.clone());
+        enum_type.set_visibility(Attribute::visibility(&self.attributes, false));
+        enum_type.set_jetdoc(self.jetdoc.clone());
+        properties.clone().set(name, enum_type.clone());
+        verifier.ast_to_symbol.set(directive, Some(enum_type.clone()));
+
+        let scope = verifier.host.factory().create_enum_scope(&enum_type);
+        scope.set_parent(Some(&verifier.scope));
+        verifier.ast_to_symbol.set(&self.block, Some(scope));
+    }
+
+    /// Resolves the numeric type given by the `as` clause,
+    /// defaulting to `Number`.
+    fn verify_numeric_type(&self, verifier: &mut VerifierVerifier, enum_type: &Symbol) -> Result<(), DeferVerificationError> {
+        let host = verifier.host.clone();
+        let t = if let Some(exp) = self.as_clause.as_ref() {
+            verifier.verify_type_expression(exp)?
+        } else {
+            None
+        };
+        let number_type = host.number_type();
+        number_type.throw_if_unresolved()?;
+
+        let mut numeric_type = number_type;
+        if let Some(t) = t {
+            if host.is_numeric_type(&t) {
+                numeric_type = t;
+            } else {
+                verifier.add_verify_error(&self.as_clause.as_ref().unwrap().location(), DiagnosticKind::EnumNumericTypeMustBeNumeric, diagnostic_arguments![Symbol(t)]);
+            }
+        }
+        enum_type.set_enumeration_numeric_type(Some(&numeric_type));
+        Ok(())
+    }
+
+    /// Assigns the string and numeric values of the enumeration members,
+    /// in the order they are defined.
+    fn assign_members(&self, verifier: &mut VerifierVerifier, enum_type: &Symbol) -> Result<(), DeferVerificationError> {
+        let host = verifier.host.clone();
+        let Some(numeric_type) = enum_type.enumeration_numeric_type() else {
+            return Ok(());
+        };
+
+        // Evaluate every member before reporting anything, as the
+        // verification may be deferred.
+        type MemberValue = Result<(String, Option<AbstractRangeNumber>), Location>;
+        let mut values: Vec<(Symbol, Location, MemberValue)> = vec![];
+        for directive in &self.block.directives {
+            let Directive::VariableDefinition(defn) = directive.as_ref() else {
+                continue;
+            };
+            if Attribute::find_static(&defn.attributes).is_some() {
+                continue;
+            }
+            for binding in &defn.bindings {
+                let Some(variable) = verifier.ast_to_symbol.get(&binding.destructuring.destructuring) else {
+                    continue;
+                };
+                let value = Self::member_value(verifier, binding, &variable.name(), &numeric_type)?;
+                values.push((variable, binding.location(), value));
+            }
+        }
+
+        let mut members = enum_type.enumeration_members();
+        let mut counter = if self.is_set {
+            AbstractRangeNumber::one(&numeric_type, &host)
+        } else {
+            AbstractRangeNumber::zero(&numeric_type, &host)
+        };
+        for (variable, location, value) in values {
+            let (string, number) = match value {
+                Ok(value) => value,
+                Err(location) => {
+                    verifier.add_verify_error(&location, DiagnosticKind::MalformedEnumMember, diagnostic_arguments![]);
+                    continue;
+                },
+            };
+            let number = number.unwrap_or(counter.clone());
+            counter = if self.is_set { number.multiply_per_two() } else { number.increase_by_one() };
+
+            if members.get(&string).is_some() {
+                verifier.add_verify_error(&location, DiagnosticKind::DuplicateDefinition, diagnostic_arguments![String(string)]);
+                continue;
+            }
+            let constant = host.factory().create_enum_constant(number.clone(), enum_type);
+            variable.set_static_type(enum_type);
+            variable.set_constant_initializer(Some(&constant));
+            members.set(string, number);
+        }
+        Ok(())
+    }
+
+    /// Returns the string and, if explicitly given, the numeric value
+    /// of an enumeration member, or the location of a malformed initializer.
+    fn member_value(verifier: &mut VerifierVerifier, binding: &Rc<VariableBinding>, name: &str, numeric_type: &Symbol) -> Result<Result<(String, Option<AbstractRangeNumber>), Location>, DeferVerificationError> {
+        let Some(init) = binding.initializer.as_ref() else {
+            return Ok(Ok((Self::screaming_snake_case_to_camel_case(name), None)));
+        };
+        match init.as_ref() {
+            Expression::StringLiteral(literal) => Ok(Ok((literal.value.clone(), None))),
+            Expression::ArrayLiteral(literal) if literal.elements.len() == 2 => {
+                let (Element::Expression(string_exp), Element::Expression(number_exp)) = (&literal.elements[0], &literal.elements[1]) else {
+                    return Ok(Err(init.location()));
+                };
+                let Expression::StringLiteral(string) = string_exp.as_ref() else {
+                    return Ok(Err(string_exp.location()));
+                };
+                let number = Self::member_number(verifier, number_exp, numeric_type)?;
+                Ok(number.map(|number| (string.value.clone(), Some(number))))
+            },
+            _ => {
+                let number = Self::member_number(verifier, init, numeric_type)?;
+                Ok(number.map(|number| (Self::screaming_snake_case_to_camel_case(name), Some(number))))
+            },
+        }
+    }
+
+    fn member_number(verifier: &mut VerifierVerifier, exp: &Rc<Expression>, numeric_type: &Symbol) -> Result<Result<AbstractRangeNumber, Location>, DeferVerificationError> {
+        match verifier.limit_expression_type(exp, numeric_type)? {
+            Some(v) if v.is_number_constant() => Ok(Ok(v.number_value())),
+            _ => Ok(Err(exp.location())),
+        }
+    }
+
+    /// Converts a name such as `FOO_BAR` into `fooBar`.
+    fn screaming_snake_case_to_camel_case(name: &str) -> String {
+        let mut r = String::new();
+        for (i, part) in name.split('_').filter(|part| !part.is_empty()).enumerate() {
+            let part = part.to_lowercase();
+            if i == 0 {
+                r.push_str(&part);
+            } else {
+                let mut chars = part.chars();
+                if let Some(ch) = chars.next() {
+                    r.extend(ch.to_uppercase());
+                    r.push_str(chars.as_str());
+                }
+            }
+        }
+        r
+    }
+}
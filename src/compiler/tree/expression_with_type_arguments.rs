@@ -7,4 +7,46 @@ pub struct ExpressionWithTypeArguments {
     pub location: Location,
     pub base: Rc<Expression>,
     pub arguments: Vec<Rc<Expression>>,
-}
\ No newline at end of file
+}
+
+impl ExpressionWithTypeArguments {
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier) -> Result<Option<Symbol>, DeferVerificationError> {
+        let base = verifier.verify_expression(&self.base, &ExpressionVerifyContext {
+            followed_by_type_arguments: true,
+            ..default()
+        })?;
+        let mut arguments: Vec<Symbol> = vec![];
+        let mut failed = false;
+        for argument in &self.arguments {
+            if let Some(t) = verifier.verify_type_expression(argument)? {
+                arguments.push(t);
+            } else {
+                failed = true;
+            }
+        }
+        let Some(base) = base else {
+            return Ok(None);
+        };
+        let Ok(base_type) = base.expect_type() else {
+            verifier.add_verify_error(&self.base.location(), DiagnosticKind::MustResolveToType, diagnostic_arguments![]);
+            return Ok(None);
+        };
+        // Only classes and interfaces have type parameters, which
+        // are no longer present once type arguments are applied.
+        let type_parameter_count = if base_type.is_origin_class_type() || base_type.is_origin_interface_type() {
+            base_type.type_parameters().map(|list| list.length()).unwrap_or(0)
+        } else {
+            0
+        };
+        if type_parameter_count != arguments.len() && !failed {
+            verifier.add_verify_error(&self.location, DiagnosticKind::WrongNumberOfTypeArguments, diagnostic_arguments![String(type_parameter_count.to_string())]);
+            return Ok(None);
+        }
+        if failed || type_parameter_count == 0 {
+            return Ok(None);
+        }
+        let t = verifier.host.factory().create_type_after_explicit_type_substitution(&base_type, &SharedArray::from(arguments));
+        verifier.check_type_arguments_later(&self.location, &t);
+        Ok(Some(verifier.host.factory().create_type_as_reference_value(&t)))
+    }
+}
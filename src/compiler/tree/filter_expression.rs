@@ -7,4 +7,19 @@ pub struct FilterExpression {
     pub location: Location,
     pub base: Rc<Expression>,
     pub test: Rc<Expression>,
-}
\ No newline at end of file
+}
+
+impl FilterExpression {
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier) -> Result<Option<Symbol>, DeferVerificationError> {
+        let Some(base) = verifier.verify_expression(&self.base, &default())? else {
+            return Ok(None);
+        };
+        let scope = verifier.host.factory().create_filter_operator_scope(&base);
+        scope.set_parent(Some(&verifier.scope));
+        verifier.enter_scope(&scope);
+        let r = verifier.verify_expression(&self.test, &default());
+        verifier.exit_scope();
+        r?;
+        Ok(Some(verifier.host.factory().create_value(&verifier.host.xml_list_type())))
+    }
+}
@@ -0,0 +1,1204 @@
+use crate::ns::*;
+
+/// Transforming traversal of the tree.
+///
+/// Each `fold_` method receives a node and returns its replacement. By default
+/// it rebuilds the node through the corresponding `walk_` method from
+/// its folded children, keeping every location and every other field.
+/// An implementor overrides the `fold_` methods of the nodes it transforms,
+/// calling the `walk_` method where it wants the children folded as well.
+///
+/// `fold_expression` and `fold_directive` may replace a node by a node
+/// of a different variant.
+///
+/// # Example
+///
+/// ```
+/// use hydroper_jet_compiler::ns::*;
+///
+/// struct Renamer;
+///
+/// impl Folder for Renamer {
+///     fn fold_qualified_identifier(&mut self, node: &QualifiedIdentifier) -> QualifiedIdentifier {
+///         let node = self.walk_qualified_identifier(node);
+///         match &node.id {
+///             QualifiedIdentifierIdentifier::Id((name, location)) if name == "x" => QualifiedIdentifier {
+///                 id: QualifiedIdentifierIdentifier::Id(("y".into(), location.clone())),
+///                 ..node
+///             },
+///             _ => node,
+///         }
+///     }
+/// }
+///
+/// let compilation_unit = CompilationUnit::new(None, "f(x)".into(), &CompilerOptions::new());
+/// let program = ParserFacade::parse_program(&compilation_unit).unwrap();
+/// let program = Renamer.fold_program(&program);
+/// assert_eq!(Formatter::new(FormatterOptions::default()).format_program(&Rc::new(program)), "f(y);\n");
+/// ```
+pub trait Folder {
+    // Programs and definitions
+
+    fn fold_program(&mut self, node: &Program) -> Program {
+        self.walk_program(node)
+    }
+
+    fn walk_program(&mut self, node: &Program) -> Program {
+        Program {
+            location: node.location.clone(),
+            packages: node.packages.iter().map(|package| Rc::new(self.fold_package_definition(package))).collect(),
+            directives: self.fold_directives(&node.directives),
+        }
+    }
+
+    fn fold_package_definition(&mut self, node: &PackageDefinition) -> PackageDefinition {
+        self.walk_package_definition(node)
+    }
+
+    fn walk_package_definition(&mut self, node: &PackageDefinition) -> PackageDefinition {
+        PackageDefinition {
+            location: node.location.clone(),
+            jetdoc: self.fold_optional_jetdoc(&node.jetdoc),
+            name: node.name.clone(),
+            block: Rc::new(self.fold_block(&node.block)),
+        }
+    }
+
+    /// Folds a list of directives.
+    fn fold_directives(&mut self, list: &[Rc<Directive>]) -> Vec<Rc<Directive>> {
+        list.iter().map(|directive| self.fold_directive(directive)).collect()
+    }
+
+    fn fold_directive(&mut self, node: &Rc<Directive>) -> Rc<Directive> {
+        self.walk_directive(node)
+    }
+
+    fn walk_directive(&mut self, node: &Rc<Directive>) -> Rc<Directive> {
+        Rc::new(match node.as_ref() {
+            Directive::EmptyStatement(d) => Directive::EmptyStatement(self.fold_empty_statement(d)),
+            Directive::ExpressionStatement(d) => Directive::ExpressionStatement(self.fold_expression_statement(d)),
+            Directive::SuperStatement(d) => Directive::SuperStatement(self.fold_super_statement(d)),
+            Directive::Block(d) => Directive::Block(self.fold_block(d)),
+            Directive::LabeledStatement(d) => Directive::LabeledStatement(self.fold_labeled_statement(d)),
+            Directive::IfStatement(d) => Directive::IfStatement(self.fold_if_statement(d)),
+            Directive::SwitchStatement(d) => Directive::SwitchStatement(self.fold_switch_statement(d)),
+            Directive::SwitchTypeStatement(d) => Directive::SwitchTypeStatement(self.fold_switch_type_statement(d)),
+            Directive::DoStatement(d) => Directive::DoStatement(self.fold_do_statement(d)),
+            Directive::WhileStatement(d) => Directive::WhileStatement(self.fold_while_statement(d)),
+            Directive::ForStatement(d) => Directive::ForStatement(self.fold_for_statement(d)),
+            Directive::ForInStatement(d) => Directive::ForInStatement(self.fold_for_in_statement(d)),
+            Directive::BreakStatement(d) => Directive::BreakStatement(self.fold_break_statement(d)),
+            Directive::ContinueStatement(d) => Directive::ContinueStatement(self.fold_continue_statement(d)),
+            Directive::WithStatement(d) => Directive::WithStatement(self.fold_with_statement(d)),
+            Directive::ReturnStatement(d) => Directive::ReturnStatement(self.fold_return_statement(d)),
+            Directive::ThrowStatement(d) => Directive::ThrowStatement(self.fold_throw_statement(d)),
+            Directive::DefaultXmlNamespaceStatement(d) => Directive::DefaultXmlNamespaceStatement(self.fold_default_xml_namespace_statement(d)),
+            Directive::TryStatement(d) => Directive::TryStatement(self.fold_try_statement(d)),
+            Directive::InvalidatedDirective(d) => Directive::InvalidatedDirective(self.fold_invalidated_directive(d)),
+            Directive::ConfigurationDirective(d) => Directive::ConfigurationDirective(self.fold_configuration_directive(d)),
+            Directive::ImportDirective(d) => Directive::ImportDirective(self.fold_import_directive(d)),
+            Directive::UsePackageDirective(d) => Directive::UsePackageDirective(self.fold_use_package_directive(d)),
+            Directive::VariableDefinition(d) => Directive::VariableDefinition(self.fold_variable_definition(d)),
+            Directive::FunctionDefinition(d) => Directive::FunctionDefinition(self.fold_function_definition(d)),
+            Directive::ClassDefinition(d) => Directive::ClassDefinition(self.fold_class_definition(d)),
+            Directive::EnumDefinition(d) => Directive::EnumDefinition(self.fold_enum_definition(d)),
+            Directive::InterfaceDefinition(d) => Directive::InterfaceDefinition(self.fold_interface_definition(d)),
+            Directive::TypeDefinition(d) => Directive::TypeDefinition(self.fold_type_definition(d)),
+        })
+    }
+
+    fn fold_variable_definition(&mut self, node: &VariableDefinition) -> VariableDefinition {
+        self.walk_variable_definition(node)
+    }
+
+    fn walk_variable_definition(&mut self, node: &VariableDefinition) -> VariableDefinition {
+        VariableDefinition {
+            location: node.location.clone(),
+            jetdoc: self.fold_optional_jetdoc(&node.jetdoc),
+            attributes: self.fold_attributes(&node.attributes),
+            kind: node.kind.clone(),
+            bindings: node.bindings.iter().map(|binding| Rc::new(self.fold_variable_binding(binding))).collect(),
+        }
+    }
+
+    fn fold_simple_variable_definition(&mut self, node: &SimpleVariableDefinition) -> SimpleVariableDefinition {
+        self.walk_simple_variable_definition(node)
+    }
+
+    fn walk_simple_variable_definition(&mut self, node: &SimpleVariableDefinition) -> SimpleVariableDefinition {
+        SimpleVariableDefinition {
+            location: node.location.clone(),
+            kind: node.kind.clone(),
+            bindings: node.bindings.iter().map(|binding| Rc::new(self.fold_variable_binding(binding))).collect(),
+        }
+    }
+
+    fn fold_variable_binding(&mut self, node: &VariableBinding) -> VariableBinding {
+        self.walk_variable_binding(node)
+    }
+
+    fn walk_variable_binding(&mut self, node: &VariableBinding) -> VariableBinding {
+        VariableBinding {
+            destructuring: self.fold_typed_destructuring(&node.destructuring),
+            initializer: self.fold_optional_expression(&node.initializer),
+        }
+    }
+
+    fn fold_function_definition(&mut self, node: &FunctionDefinition) -> FunctionDefinition {
+        self.walk_function_definition(node)
+    }
+
+    fn walk_function_definition(&mut self, node: &FunctionDefinition) -> FunctionDefinition {
+        FunctionDefinition {
+            location: node.location.clone(),
+            jetdoc: self.fold_optional_jetdoc(&node.jetdoc),
+            attributes: self.fold_attributes(&node.attributes),
+            name: node.name.clone(),
+            common: Rc::new(self.fold_function_common(&node.common)),
+        }
+    }
+
+    fn fold_function_common(&mut self, node: &FunctionCommon) -> FunctionCommon {
+        self.walk_function_common(node)
+    }
+
+    fn walk_function_common(&mut self, node: &FunctionCommon) -> FunctionCommon {
+        FunctionCommon {
+            location: node.location.clone(),
+            contains_yield: node.contains_yield,
+            contains_await: node.contains_await,
+            signature: self.fold_function_signature(&node.signature),
+            body: node.body.as_ref().map(|body| match body {
+                FunctionBody::Expression(body) => FunctionBody::Expression(self.fold_expression(body)),
+                FunctionBody::Block(body) => FunctionBody::Block(Rc::new(self.fold_block(body))),
+            }),
+        }
+    }
+
+    fn fold_function_signature(&mut self, node: &FunctionSignature) -> FunctionSignature {
+        self.walk_function_signature(node)
+    }
+
+    fn walk_function_signature(&mut self, node: &FunctionSignature) -> FunctionSignature {
+        FunctionSignature {
+            location: node.location.clone(),
+            parameters: node.parameters.iter().map(|parameter| Rc::new(self.fold_parameter(parameter))).collect(),
+            result_type: self.fold_optional_expression(&node.result_type),
+        }
+    }
+
+    fn fold_parameter(&mut self, node: &Parameter) -> Parameter {
+        self.walk_parameter(node)
+    }
+
+    fn walk_parameter(&mut self, node: &Parameter) -> Parameter {
+        Parameter {
+            location: node.location.clone(),
+            kind: node.kind,
+            destructuring: self.fold_typed_destructuring(&node.destructuring),
+            default_value: self.fold_optional_expression(&node.default_value),
+        }
+    }
+
+    fn fold_class_definition(&mut self, node: &ClassDefinition) -> ClassDefinition {
+        self.walk_class_definition(node)
+    }
+
+    fn walk_class_definition(&mut self, node: &ClassDefinition) -> ClassDefinition {
+        ClassDefinition {
+            location: node.location.clone(),
+            jetdoc: self.fold_optional_jetdoc(&node.jetdoc),
+            attributes: self.fold_attributes(&node.attributes),
+            allow_literal: node.allow_literal,
+            name: node.name.clone(),
+            type_parameters: self.fold_optional_type_parameters(&node.type_parameters),
+            extends_clause: self.fold_optional_expression(&node.extends_clause),
+            implements_clause: self.fold_optional_expressions(&node.implements_clause),
+            block: Rc::new(self.fold_block(&node.block)),
+        }
+    }
+
+    fn fold_enum_definition(&mut self, node: &EnumDefinition) -> EnumDefinition {
+        self.walk_enum_definition(node)
+    }
+
+    fn walk_enum_definition(&mut self, node: &EnumDefinition) -> EnumDefinition {
+        EnumDefinition {
+            location: node.location.clone(),
+            jetdoc: self.fold_optional_jetdoc(&node.jetdoc),
+            attributes: self.fold_attributes(&node.attributes),
+            is_set: node.is_set,
+            name: node.name.clone(),
+            as_clause: self.fold_optional_expression(&node.as_clause),
+            block: Rc::new(self.fold_block(&node.block)),
+        }
+    }
+
+    fn fold_interface_definition(&mut self, node: &InterfaceDefinition) -> InterfaceDefinition {
+        self.walk_interface_definition(node)
+    }
+
+    fn walk_interface_definition(&mut self, node: &InterfaceDefinition) -> InterfaceDefinition {
+        InterfaceDefinition {
+            location: node.location.clone(),
+            jetdoc: self.fold_optional_jetdoc(&node.jetdoc),
+            attributes: self.fold_attributes(&node.attributes),
+            name: node.name.clone(),
+            type_parameters: self.fold_optional_type_parameters(&node.type_parameters),
+            extends_clause: self.fold_optional_expressions(&node.extends_clause),
+            block: Rc::new(self.fold_block(&node.block)),
+        }
+    }
+
+    fn fold_type_definition(&mut self, node: &TypeDefinition) -> TypeDefinition {
+        self.walk_type_definition(node)
+    }
+
+    fn walk_type_definition(&mut self, node: &TypeDefinition) -> TypeDefinition {
+        TypeDefinition {
+            location: node.location.clone(),
+            jetdoc: self.fold_optional_jetdoc(&node.jetdoc),
+            attributes: self.fold_attributes(&node.attributes),
+            left: node.left.clone(),
+            right: self.fold_expression(&node.right),
+        }
+    }
+
+    fn fold_optional_type_parameters(&mut self, list: &Option<Vec<Rc<TypeParameter>>>) -> Option<Vec<Rc<TypeParameter>>> {
+        list.as_ref().map(|list| list.iter().map(|type_parameter| Rc::new(self.fold_type_parameter(type_parameter))).collect())
+    }
+
+    fn fold_type_parameter(&mut self, node: &TypeParameter) -> TypeParameter {
+        self.walk_type_parameter(node)
+    }
+
+    fn walk_type_parameter(&mut self, node: &TypeParameter) -> TypeParameter {
+        TypeParameter {
+            location: node.location.clone(),
+            name: node.name.clone(),
+            extends_clause: self.fold_optional_expression(&node.extends_clause),
+            implements_clause: self.fold_optional_expressions(&node.implements_clause),
+        }
+    }
+
+    // Attributes and documentation
+
+    fn fold_attributes(&mut self, list: &[Attribute]) -> Vec<Attribute> {
+        list.iter().map(|attribute| self.fold_attribute(attribute)).collect()
+    }
+
+    fn fold_attribute(&mut self, node: &Attribute) -> Attribute {
+        self.walk_attribute(node)
+    }
+
+    fn walk_attribute(&mut self, node: &Attribute) -> Attribute {
+        match node {
+            Attribute::Metadata(metadata) => Attribute::Metadata(self.fold_metadata(metadata)),
+            _ => node.clone(),
+        }
+    }
+
+    fn fold_metadata(&mut self, node: &Rc<UnprocessedMetadata>) -> Rc<UnprocessedMetadata> {
+        node.clone()
+    }
+
+    fn fold_optional_jetdoc(&mut self, node: &Option<Rc<JetDoc>>) -> Option<Rc<JetDoc>> {
+        node.as_ref().map(|jetdoc| Rc::new(self.fold_jetdoc(jetdoc)))
+    }
+
+    fn fold_jetdoc(&mut self, node: &JetDoc) -> JetDoc {
+        self.walk_jetdoc(node)
+    }
+
+    fn walk_jetdoc(&mut self, node: &JetDoc) -> JetDoc {
+        JetDoc {
+            location: node.location.clone(),
+            main_body: node.main_body.clone(),
+            tags: node.tags.iter().map(|(tag, location)| (self.fold_jetdoc_tag(tag, location), location.clone())).collect(),
+        }
+    }
+
+    fn fold_jetdoc_tag(&mut self, node: &JetDocTag, _location: &Location) -> JetDocTag {
+        self.walk_jetdoc_tag(node)
+    }
+
+    fn walk_jetdoc_tag(&mut self, node: &JetDocTag) -> JetDocTag {
+        match node {
+            JetDocTag::EventType(expression) => JetDocTag::EventType(self.fold_expression(expression)),
+            JetDocTag::See { reference, display_text } => JetDocTag::See {
+                reference: Rc::new(JetDocReference {
+                    base: self.fold_optional_expression(&reference.base),
+                    instance_property: reference.instance_property.clone(),
+                }),
+                display_text: display_text.clone(),
+            },
+            JetDocTag::Throws { class_reference, description } => JetDocTag::Throws {
+                class_reference: self.fold_expression(class_reference),
+                description: description.clone(),
+            },
+            _ => node.clone(),
+        }
+    }
+
+    // Statements
+
+    fn fold_empty_statement(&mut self, node: &EmptyStatement) -> EmptyStatement {
+        node.clone()
+    }
+
+    fn fold_expression_statement(&mut self, node: &ExpressionStatement) -> ExpressionStatement {
+        self.walk_expression_statement(node)
+    }
+
+    fn walk_expression_statement(&mut self, node: &ExpressionStatement) -> ExpressionStatement {
+        ExpressionStatement {
+            location: node.location.clone(),
+            expression: self.fold_expression(&node.expression),
+        }
+    }
+
+    fn fold_super_statement(&mut self, node: &SuperStatement) -> SuperStatement {
+        self.walk_super_statement(node)
+    }
+
+    fn walk_super_statement(&mut self, node: &SuperStatement) -> SuperStatement {
+        SuperStatement {
+            location: node.location.clone(),
+            arguments: self.fold_expressions(&node.arguments),
+        }
+    }
+
+    fn fold_block(&mut self, node: &Block) -> Block {
+        self.walk_block(node)
+    }
+
+    fn walk_block(&mut self, node: &Block) -> Block {
+        Block {
+            location: node.location.clone(),
+            metadata: node.metadata.as_ref().map(|metadata| self.fold_attributes(metadata)),
+            directives: self.fold_directives(&node.directives),
+        }
+    }
+
+    fn fold_labeled_statement(&mut self, node: &LabeledStatement) -> LabeledStatement {
+        self.walk_labeled_statement(node)
+    }
+
+    fn walk_labeled_statement(&mut self, node: &LabeledStatement) -> LabeledStatement {
+        LabeledStatement {
+            location: node.location.clone(),
+            label: node.label.clone(),
+            substatement: self.fold_directive(&node.substatement),
+        }
+    }
+
+    fn fold_if_statement(&mut self, node: &IfStatement) -> IfStatement {
+        self.walk_if_statement(node)
+    }
+
+    fn walk_if_statement(&mut self, node: &IfStatement) -> IfStatement {
+        IfStatement {
+            location: node.location.clone(),
+            test: self.fold_expression(&node.test),
+            consequent: self.fold_directive(&node.consequent),
+            alternative: node.alternative.as_ref().map(|alternative| self.fold_directive(alternative)),
+        }
+    }
+
+    fn fold_switch_statement(&mut self, node: &SwitchStatement) -> SwitchStatement {
+        self.walk_switch_statement(node)
+    }
+
+    fn walk_switch_statement(&mut self, node: &SwitchStatement) -> SwitchStatement {
+        SwitchStatement {
+            location: node.location.clone(),
+            discriminant: self.fold_expression(&node.discriminant),
+            cases: node.cases.iter().map(|case| self.fold_case(case)).collect(),
+        }
+    }
+
+    fn fold_case(&mut self, node: &Case) -> Case {
+        self.walk_case(node)
+    }
+
+    fn walk_case(&mut self, node: &Case) -> Case {
+        Case {
+            location: node.location.clone(),
+            labels: node.labels.iter().map(|label| match label {
+                CaseLabel::Case((expression, location)) => CaseLabel::Case((self.fold_expression(expression), location.clone())),
+                CaseLabel::Default(location) => CaseLabel::Default(location.clone()),
+            }).collect(),
+            directives: self.fold_directives(&node.directives),
+        }
+    }
+
+    fn fold_switch_type_statement(&mut self, node: &SwitchTypeStatement) -> SwitchTypeStatement {
+        self.walk_switch_type_statement(node)
+    }
+
+    fn walk_switch_type_statement(&mut self, node: &SwitchTypeStatement) -> SwitchTypeStatement {
+        SwitchTypeStatement {
+            location: node.location.clone(),
+            discriminant: self.fold_expression(&node.discriminant),
+            cases: node.cases.iter().map(|case| self.fold_type_case(case)).collect(),
+        }
+    }
+
+    fn fold_type_case(&mut self, node: &TypeCase) -> TypeCase {
+        self.walk_type_case(node)
+    }
+
+    fn walk_type_case(&mut self, node: &TypeCase) -> TypeCase {
+        TypeCase {
+            location: node.location.clone(),
+            parameter: node.parameter.as_ref().map(|parameter| self.fold_typed_destructuring(parameter)),
+            block: Rc::new(self.fold_block(&node.block)),
+        }
+    }
+
+    fn fold_do_statement(&mut self, node: &DoStatement) -> DoStatement {
+        self.walk_do_statement(node)
+    }
+
+    fn walk_do_statement(&mut self, node: &DoStatement) -> DoStatement {
+        DoStatement {
+            location: node.location.clone(),
+            body: self.fold_directive(&node.body),
+            test: self.fold_expression(&node.test),
+        }
+    }
+
+    fn fold_while_statement(&mut self, node: &WhileStatement) -> WhileStatement {
+        self.walk_while_statement(node)
+    }
+
+    fn walk_while_statement(&mut self, node: &WhileStatement) -> WhileStatement {
+        WhileStatement {
+            location: node.location.clone(),
+            test: self.fold_expression(&node.test),
+            body: self.fold_directive(&node.body),
+        }
+    }
+
+    fn fold_for_statement(&mut self, node: &ForStatement) -> ForStatement {
+        self.walk_for_statement(node)
+    }
+
+    fn walk_for_statement(&mut self, node: &ForStatement) -> ForStatement {
+        ForStatement {
+            location: node.location.clone(),
+            init: node.init.as_ref().map(|init| match init {
+                ForInitializer::Expression(init) => ForInitializer::Expression(self.fold_expression(init)),
+                ForInitializer::VariableDefinition(init) => ForInitializer::VariableDefinition(Rc::new(self.fold_simple_variable_definition(init))),
+            }),
+            test: self.fold_optional_expression(&node.test),
+            update: self.fold_optional_expression(&node.update),
+            body: self.fold_directive(&node.body),
+        }
+    }
+
+    fn fold_for_in_statement(&mut self, node: &ForInStatement) -> ForInStatement {
+        self.walk_for_in_statement(node)
+    }
+
+    fn walk_for_in_statement(&mut self, node: &ForInStatement) -> ForInStatement {
+        ForInStatement {
+            location: node.location.clone(),
+            each: node.each,
+            left: match &node.left {
+                ForInBinding::Expression(left) => ForInBinding::Expression(self.fold_expression(left)),
+                ForInBinding::VariableDefinition(left) => ForInBinding::VariableDefinition(Rc::new(self.fold_simple_variable_definition(left))),
+            },
+            right: self.fold_expression(&node.right),
+            body: self.fold_directive(&node.body),
+        }
+    }
+
+    fn fold_break_statement(&mut self, node: &BreakStatement) -> BreakStatement {
+        node.clone()
+    }
+
+    fn fold_continue_statement(&mut self, node: &ContinueStatement) -> ContinueStatement {
+        node.clone()
+    }
+
+    fn fold_with_statement(&mut self, node: &WithStatement) -> WithStatement {
+        self.walk_with_statement(node)
+    }
+
+    fn walk_with_statement(&mut self, node: &WithStatement) -> WithStatement {
+        WithStatement {
+            location: node.location.clone(),
+            object: self.fold_expression(&node.object),
+            body: self.fold_directive(&node.body),
+        }
+    }
+
+    fn fold_return_statement(&mut self, node: &ReturnStatement) -> ReturnStatement {
+        self.walk_return_statement(node)
+    }
+
+    fn walk_return_statement(&mut self, node: &ReturnStatement) -> ReturnStatement {
+        ReturnStatement {
+            location: node.location.clone(),
+            expression: self.fold_optional_expression(&node.expression),
+        }
+    }
+
+    fn fold_throw_statement(&mut self, node: &ThrowStatement) -> ThrowStatement {
+        self.walk_throw_statement(node)
+    }
+
+    fn walk_throw_statement(&mut self, node: &ThrowStatement) -> ThrowStatement {
+        ThrowStatement {
+            location: node.location.clone(),
+            expression: self.fold_expression(&node.expression),
+        }
+    }
+
+    fn fold_default_xml_namespace_statement(&mut self, node: &DefaultXmlNamespaceStatement) -> DefaultXmlNamespaceStatement {
+        self.walk_default_xml_namespace_statement(node)
+    }
+
+    fn walk_default_xml_namespace_statement(&mut self, node: &DefaultXmlNamespaceStatement) -> DefaultXmlNamespaceStatement {
+        DefaultXmlNamespaceStatement {
+            location: node.location.clone(),
+            right: self.fold_expression(&node.right),
+        }
+    }
+
+    fn fold_try_statement(&mut self, node: &TryStatement) -> TryStatement {
+        self.walk_try_statement(node)
+    }
+
+    fn walk_try_statement(&mut self, node: &TryStatement) -> TryStatement {
+        TryStatement {
+            location: node.location.clone(),
+            block: Rc::new(self.fold_block(&node.block)),
+            catch_clauses: node.catch_clauses.iter().map(|catch_clause| self.fold_catch_clause(catch_clause)).collect(),
+            finally_clause: node.finally_clause.as_ref().map(|finally_clause| FinallyClause {
+                location: finally_clause.location.clone(),
+                block: Rc::new(self.fold_block(&finally_clause.block)),
+            }),
+        }
+    }
+
+    fn fold_catch_clause(&mut self, node: &CatchClause) -> CatchClause {
+        self.walk_catch_clause(node)
+    }
+
+    fn walk_catch_clause(&mut self, node: &CatchClause) -> CatchClause {
+        CatchClause {
+            location: node.location.clone(),
+            parameter: self.fold_typed_destructuring(&node.parameter),
+            block: Rc::new(self.fold_block(&node.block)),
+        }
+    }
+
+    fn fold_invalidated_directive(&mut self, node: &InvalidatedDirective) -> InvalidatedDirective {
+        node.clone()
+    }
+
+    fn fold_configuration_directive(&mut self, node: &ConfigurationDirective) -> ConfigurationDirective {
+        self.walk_configuration_directive(node)
+    }
+
+    fn walk_configuration_directive(&mut self, node: &ConfigurationDirective) -> ConfigurationDirective {
+        ConfigurationDirective {
+            location: node.location.clone(),
+            directive: self.fold_directive(&node.directive),
+        }
+    }
+
+    fn fold_import_directive(&mut self, node: &ImportDirective) -> ImportDirective {
+        node.clone()
+    }
+
+    fn fold_use_package_directive(&mut self, node: &UsePackageDirective) -> UsePackageDirective {
+        node.clone()
+    }
+
+    // Destructuring
+
+    fn fold_typed_destructuring(&mut self, node: &TypedDestructuring) -> TypedDestructuring {
+        self.walk_typed_destructuring(node)
+    }
+
+    fn walk_typed_destructuring(&mut self, node: &TypedDestructuring) -> TypedDestructuring {
+        TypedDestructuring {
+            location: node.location.clone(),
+            destructuring: self.fold_expression(&node.destructuring),
+            type_annotation: self.fold_optional_expression(&node.type_annotation),
+        }
+    }
+
+    // Expressions
+
+    /// Folds a list of expressions.
+    fn fold_expressions(&mut self, list: &[Rc<Expression>]) -> Vec<Rc<Expression>> {
+        list.iter().map(|expression| self.fold_expression(expression)).collect()
+    }
+
+    fn fold_optional_expression(&mut self, node: &Option<Rc<Expression>>) -> Option<Rc<Expression>> {
+        node.as_ref().map(|expression| self.fold_expression(expression))
+    }
+
+    fn fold_optional_expressions(&mut self, list: &Option<Vec<Rc<Expression>>>) -> Option<Vec<Rc<Expression>>> {
+        list.as_ref().map(|list| self.fold_expressions(list))
+    }
+
+    fn fold_expression(&mut self, node: &Rc<Expression>) -> Rc<Expression> {
+        self.walk_expression(node)
+    }
+
+    fn walk_expression(&mut self, node: &Rc<Expression>) -> Rc<Expression> {
+        Rc::new(match node.as_ref() {
+            Expression::QualifiedIdentifier(e) => Expression::QualifiedIdentifier(self.fold_qualified_identifier(e)),
+            Expression::Embed(e) => Expression::Embed(self.fold_embed_expression(e)),
+            Expression::Paren(e) => Expression::Paren(self.fold_paren_expression(e)),
+            Expression::NullLiteral(e) => Expression::NullLiteral(self.fold_null_literal(e)),
+            Expression::BooleanLiteral(e) => Expression::BooleanLiteral(self.fold_boolean_literal(e)),
+            Expression::NumericLiteral(e) => Expression::NumericLiteral(self.fold_numeric_literal(e)),
+            Expression::StringLiteral(e) => Expression::StringLiteral(self.fold_string_literal(e)),
+            Expression::ThisLiteral(e) => Expression::ThisLiteral(self.fold_this_literal(e)),
+            Expression::RegExpLiteral(e) => Expression::RegExpLiteral(self.fold_regexp_literal(e)),
+            Expression::Xml(e) => Expression::Xml(self.fold_xml_expression(e)),
+            Expression::XmlMarkup(e) => Expression::XmlMarkup(self.fold_xml_markup_expression(e)),
+            Expression::XmlList(e) => Expression::XmlList(self.fold_xml_list_expression(e)),
+            Expression::ArrayLiteral(e) => Expression::ArrayLiteral(self.fold_array_literal(e)),
+            Expression::ObjectInitializer(e) => Expression::ObjectInitializer(self.fold_object_initializer(e)),
+            Expression::Function(e) => Expression::Function(self.fold_function_expression(e)),
+            Expression::ImportMeta(e) => Expression::ImportMeta(self.fold_import_meta(e)),
+            Expression::New(e) => Expression::New(self.fold_new_expression(e)),
+            Expression::Member(e) => Expression::Member(self.fold_member_expression(e)),
+            Expression::ComputedMember(e) => Expression::ComputedMember(self.fold_computed_member_expression(e)),
+            Expression::Descendants(e) => Expression::Descendants(self.fold_descendants_expression(e)),
+            Expression::Filter(e) => Expression::Filter(self.fold_filter_expression(e)),
+            Expression::Super(e) => Expression::Super(self.fold_super_expression(e)),
+            Expression::Call(e) => Expression::Call(self.fold_call_expression(e)),
+            Expression::WithTypeArguments(e) => Expression::WithTypeArguments(self.fold_expression_with_type_arguments(e)),
+            Expression::Unary(e) => Expression::Unary(self.fold_unary_expression(e)),
+            Expression::OptionalChaining(e) => Expression::OptionalChaining(self.fold_optional_chaining_expression(e)),
+            Expression::OptionalChainingPlaceholder(e) => Expression::OptionalChainingPlaceholder(self.fold_optional_chaining_placeholder(e)),
+            Expression::Binary(e) => Expression::Binary(self.fold_binary_expression(e)),
+            Expression::Conditional(e) => Expression::Conditional(self.fold_conditional_expression(e)),
+            Expression::Assignment(e) => Expression::Assignment(self.fold_assignment_expression(e)),
+            Expression::Sequence(e) => Expression::Sequence(self.fold_sequence_expression(e)),
+            Expression::NullableType(e) => Expression::NullableType(self.fold_nullable_type_expression(e)),
+            Expression::NonNullableType(e) => Expression::NonNullableType(self.fold_non_nullable_type_expression(e)),
+            Expression::AnyType(e) => Expression::AnyType(self.fold_any_type_expression(e)),
+            Expression::VoidType(e) => Expression::VoidType(self.fold_void_type_expression(e)),
+            Expression::ArrayType(e) => Expression::ArrayType(self.fold_array_type_expression(e)),
+            Expression::TupleType(e) => Expression::TupleType(self.fold_tuple_type_expression(e)),
+            Expression::FunctionType(e) => Expression::FunctionType(self.fold_function_type_expression(e)),
+            Expression::Invalidated(e) => Expression::Invalidated(self.fold_invalidated_expression(e)),
+        })
+    }
+
+    fn fold_qualified_identifier(&mut self, node: &QualifiedIdentifier) -> QualifiedIdentifier {
+        self.walk_qualified_identifier(node)
+    }
+
+    fn walk_qualified_identifier(&mut self, node: &QualifiedIdentifier) -> QualifiedIdentifier {
+        QualifiedIdentifier {
+            location: node.location.clone(),
+            attribute: node.attribute,
+            qualifier: self.fold_optional_expression(&node.qualifier),
+            id: match &node.id {
+                QualifiedIdentifierIdentifier::Id(id) => QualifiedIdentifierIdentifier::Id(id.clone()),
+                QualifiedIdentifierIdentifier::Brackets(key) => QualifiedIdentifierIdentifier::Brackets(self.fold_expression(key)),
+            },
+        }
+    }
+
+    fn fold_embed_expression(&mut self, node: &EmbedExpression) -> EmbedExpression {
+        self.walk_embed_expression(node)
+    }
+
+    fn walk_embed_expression(&mut self, node: &EmbedExpression) -> EmbedExpression {
+        EmbedExpression {
+            location: node.location.clone(),
+            description: self.fold_object_initializer(&node.description),
+        }
+    }
+
+    fn fold_paren_expression(&mut self, node: &ParenExpression) -> ParenExpression {
+        self.walk_paren_expression(node)
+    }
+
+    fn walk_paren_expression(&mut self, node: &ParenExpression) -> ParenExpression {
+        ParenExpression {
+            location: node.location.clone(),
+            expression: self.fold_expression(&node.expression),
+        }
+    }
+
+    fn fold_null_literal(&mut self, node: &NullLiteral) -> NullLiteral {
+        node.clone()
+    }
+
+    fn fold_boolean_literal(&mut self, node: &BooleanLiteral) -> BooleanLiteral {
+        node.clone()
+    }
+
+    fn fold_numeric_literal(&mut self, node: &NumericLiteral) -> NumericLiteral {
+        node.clone()
+    }
+
+    fn fold_string_literal(&mut self, node: &StringLiteral) -> StringLiteral {
+        node.clone()
+    }
+
+    fn fold_this_literal(&mut self, node: &ThisLiteral) -> ThisLiteral {
+        node.clone()
+    }
+
+    fn fold_regexp_literal(&mut self, node: &RegExpLiteral) -> RegExpLiteral {
+        node.clone()
+    }
+
+    fn fold_xml_expression(&mut self, node: &XmlExpression) -> XmlExpression {
+        self.walk_xml_expression(node)
+    }
+
+    fn walk_xml_expression(&mut self, node: &XmlExpression) -> XmlExpression {
+        XmlExpression {
+            location: node.location.clone(),
+            element: Rc::new(self.fold_xml_element(&node.element)),
+        }
+    }
+
+    fn fold_xml_markup_expression(&mut self, node: &XmlMarkupExpression) -> XmlMarkupExpression {
+        node.clone()
+    }
+
+    fn fold_xml_list_expression(&mut self, node: &XmlListExpression) -> XmlListExpression {
+        self.walk_xml_list_expression(node)
+    }
+
+    fn walk_xml_list_expression(&mut self, node: &XmlListExpression) -> XmlListExpression {
+        XmlListExpression {
+            location: node.location.clone(),
+            content: node.content.iter().map(|content| Rc::new(self.fold_xml_element_content(content))).collect(),
+        }
+    }
+
+    fn fold_xml_element(&mut self, node: &XmlElement) -> XmlElement {
+        self.walk_xml_element(node)
+    }
+
+    fn walk_xml_element(&mut self, node: &XmlElement) -> XmlElement {
+        XmlElement {
+            location: node.location.clone(),
+            name: self.fold_xml_tag_name(&node.name),
+            attributes: node.attributes.iter().map(|attribute| Rc::new(XmlAttribute {
+                location: attribute.location.clone(),
+                name: attribute.name.clone(),
+                value: match &attribute.value {
+                    XmlAttributeValue::Value(value) => XmlAttributeValue::Value(value.clone()),
+                    XmlAttributeValue::Expression(value) => XmlAttributeValue::Expression(self.fold_expression(value)),
+                },
+            })).collect(),
+            attribute_expression: self.fold_optional_expression(&node.attribute_expression),
+            content: node.content.as_ref().map(|content| content.iter().map(|content| Rc::new(self.fold_xml_element_content(content))).collect()),
+            closing_name: node.closing_name.as_ref().map(|name| self.fold_xml_tag_name(name)),
+        }
+    }
+
+    fn fold_xml_tag_name(&mut self, node: &XmlTagName) -> XmlTagName {
+        match node {
+            XmlTagName::Name(name) => XmlTagName::Name(name.clone()),
+            XmlTagName::Expression(name) => XmlTagName::Expression(self.fold_expression(name)),
+        }
+    }
+
+    fn fold_xml_element_content(&mut self, node: &XmlElementContent) -> XmlElementContent {
+        self.walk_xml_element_content(node)
+    }
+
+    fn walk_xml_element_content(&mut self, node: &XmlElementContent) -> XmlElementContent {
+        match node {
+            XmlElementContent::XmlElement(element) => XmlElementContent::XmlElement(Rc::new(self.fold_xml_element(element))),
+            XmlElementContent::Expression(expression) => XmlElementContent::Expression(self.fold_expression(expression)),
+            XmlElementContent::XmlText(_) | XmlElementContent::XmlMarkup(_) => node.clone(),
+        }
+    }
+
+    fn fold_array_literal(&mut self, node: &ArrayLiteral) -> ArrayLiteral {
+        self.walk_array_literal(node)
+    }
+
+    fn walk_array_literal(&mut self, node: &ArrayLiteral) -> ArrayLiteral {
+        ArrayLiteral {
+            location: node.location.clone(),
+            elements: node.elements.iter().map(|element| match element {
+                Element::Elision => Element::Elision,
+                Element::Expression(expression) => Element::Expression(self.fold_expression(expression)),
+                Element::Rest((expression, location)) => Element::Rest((self.fold_expression(expression), location.clone())),
+            }).collect(),
+        }
+    }
+
+    fn fold_object_initializer(&mut self, node: &ObjectInitializer) -> ObjectInitializer {
+        self.walk_object_initializer(node)
+    }
+
+    fn walk_object_initializer(&mut self, node: &ObjectInitializer) -> ObjectInitializer {
+        ObjectInitializer {
+            location: node.location.clone(),
+            fields: node.fields.iter().map(|field| Rc::new(self.fold_initializer_field(field))).collect(),
+        }
+    }
+
+    fn fold_initializer_field(&mut self, node: &InitializerField) -> InitializerField {
+        self.walk_initializer_field(node)
+    }
+
+    fn walk_initializer_field(&mut self, node: &InitializerField) -> InitializerField {
+        match node {
+            InitializerField::Field { name, non_null, value } => InitializerField::Field {
+                name: (match &name.0 {
+                    FieldName::Identifier(name) => FieldName::Identifier(name.clone()),
+                    FieldName::Brackets(key) => FieldName::Brackets(self.fold_expression(key)),
+                    FieldName::StringLiteral(key) => FieldName::StringLiteral(self.fold_expression(key)),
+                    FieldName::NumericLiteral(key) => FieldName::NumericLiteral(self.fold_expression(key)),
+                }, name.1.clone()),
+                non_null: *non_null,
+                value: self.fold_optional_expression(value),
+            },
+            InitializerField::Rest((expression, location)) => InitializerField::Rest((self.fold_expression(expression), location.clone())),
+        }
+    }
+
+    fn fold_function_expression(&mut self, node: &FunctionExpression) -> FunctionExpression {
+        self.walk_function_expression(node)
+    }
+
+    fn walk_function_expression(&mut self, node: &FunctionExpression) -> FunctionExpression {
+        FunctionExpression {
+            location: node.location.clone(),
+            name: node.name.clone(),
+            common: Rc::new(self.fold_function_common(&node.common)),
+        }
+    }
+
+    fn fold_import_meta(&mut self, node: &ImportMeta) -> ImportMeta {
+        node.clone()
+    }
+
+    fn fold_new_expression(&mut self, node: &NewExpression) -> NewExpression {
+        self.walk_new_expression(node)
+    }
+
+    fn walk_new_expression(&mut self, node: &NewExpression) -> NewExpression {
+        NewExpression {
+            location: node.location.clone(),
+            base: self.fold_expression(&node.base),
+            arguments: self.fold_optional_expressions(&node.arguments),
+        }
+    }
+
+    fn fold_member_expression(&mut self, node: &MemberExpression) -> MemberExpression {
+        self.walk_member_expression(node)
+    }
+
+    fn walk_member_expression(&mut self, node: &MemberExpression) -> MemberExpression {
+        MemberExpression {
+            location: node.location.clone(),
+            base: self.fold_expression(&node.base),
+            identifier: self.fold_qualified_identifier(&node.identifier),
+        }
+    }
+
+    fn fold_computed_member_expression(&mut self, node: &ComputedMemberExpression) -> ComputedMemberExpression {
+        self.walk_computed_member_expression(node)
+    }
+
+    fn walk_computed_member_expression(&mut self, node: &ComputedMemberExpression) -> ComputedMemberExpression {
+        ComputedMemberExpression {
+            location: node.location.clone(),
+            base: self.fold_expression(&node.base),
+            key: self.fold_expression(&node.key),
+        }
+    }
+
+    fn fold_descendants_expression(&mut self, node: &DescendantsExpression) -> DescendantsExpression {
+        self.walk_descendants_expression(node)
+    }
+
+    fn walk_descendants_expression(&mut self, node: &DescendantsExpression) -> DescendantsExpression {
+        DescendantsExpression {
+            location: node.location.clone(),
+            base: self.fold_expression(&node.base),
+            identifier: self.fold_qualified_identifier(&node.identifier),
+        }
+    }
+
+    fn fold_filter_expression(&mut self, node: &FilterExpression) -> FilterExpression {
+        self.walk_filter_expression(node)
+    }
+
+    fn walk_filter_expression(&mut self, node: &FilterExpression) -> FilterExpression {
+        FilterExpression {
+            location: node.location.clone(),
+            base: self.fold_expression(&node.base),
+            test: self.fold_expression(&node.test),
+        }
+    }
+
+    fn fold_super_expression(&mut self, node: &SuperExpression) -> SuperExpression {
+        self.walk_super_expression(node)
+    }
+
+    fn walk_super_expression(&mut self, node: &SuperExpression) -> SuperExpression {
+        SuperExpression {
+            location: node.location.clone(),
+            object: self.fold_optional_expressions(&node.object),
+        }
+    }
+
+    fn fold_call_expression(&mut self, node: &CallExpression) -> CallExpression {
+        self.walk_call_expression(node)
+    }
+
+    fn walk_call_expression(&mut self, node: &CallExpression) -> CallExpression {
+        CallExpression {
+            location: node.location.clone(),
+            base: self.fold_expression(&node.base),
+            arguments: self.fold_expressions(&node.arguments),
+        }
+    }
+
+    fn fold_expression_with_type_arguments(&mut self, node: &ExpressionWithTypeArguments) -> ExpressionWithTypeArguments {
+        self.walk_expression_with_type_arguments(node)
+    }
+
+    fn walk_expression_with_type_arguments(&mut self, node: &ExpressionWithTypeArguments) -> ExpressionWithTypeArguments {
+        ExpressionWithTypeArguments {
+            location: node.location.clone(),
+            base: self.fold_expression(&node.base),
+            arguments: self.fold_expressions(&node.arguments),
+        }
+    }
+
+    fn fold_unary_expression(&mut self, node: &UnaryExpression) -> UnaryExpression {
+        self.walk_unary_expression(node)
+    }
+
+    fn walk_unary_expression(&mut self, node: &UnaryExpression) -> UnaryExpression {
+        UnaryExpression {
+            location: node.location.clone(),
+            operator: node.operator,
+            expression: self.fold_expression(&node.expression),
+        }
+    }
+
+    fn fold_optional_chaining_expression(&mut self, node: &OptionalChainingExpression) -> OptionalChainingExpression {
+        self.walk_optional_chaining_expression(node)
+    }
+
+    fn walk_optional_chaining_expression(&mut self, node: &OptionalChainingExpression) -> OptionalChainingExpression {
+        OptionalChainingExpression {
+            location: node.location.clone(),
+            base: self.fold_expression(&node.base),
+            expression: self.fold_expression(&node.expression),
+        }
+    }
+
+    fn fold_optional_chaining_placeholder(&mut self, node: &OptionalChainingPlaceholder) -> OptionalChainingPlaceholder {
+        node.clone()
+    }
+
+    fn fold_binary_expression(&mut self, node: &BinaryExpression) -> BinaryExpression {
+        self.walk_binary_expression(node)
+    }
+
+    fn walk_binary_expression(&mut self, node: &BinaryExpression) -> BinaryExpression {
+        BinaryExpression {
+            location: node.location.clone(),
+            operator: node.operator,
+            left: self.fold_expression(&node.left),
+            right: self.fold_expression(&node.right),
+        }
+    }
+
+    fn fold_conditional_expression(&mut self, node: &ConditionalExpression) -> ConditionalExpression {
+        self.walk_conditional_expression(node)
+    }
+
+    fn walk_conditional_expression(&mut self, node: &ConditionalExpression) -> ConditionalExpression {
+        ConditionalExpression {
+            location: node.location.clone(),
+            test: self.fold_expression(&node.test),
+            consequent: self.fold_expression(&node.consequent),
+            alternative: self.fold_expression(&node.alternative),
+        }
+    }
+
+    fn fold_assignment_expression(&mut self, node: &AssignmentExpression) -> AssignmentExpression {
+        self.walk_assignment_expression(node)
+    }
+
+    fn walk_assignment_expression(&mut self, node: &AssignmentExpression) -> AssignmentExpression {
+        AssignmentExpression {
+            location: node.location.clone(),
+            compound: node.compound,
+            left: self.fold_expression(&node.left),
+            right: self.fold_expression(&node.right),
+        }
+    }
+
+    fn fold_sequence_expression(&mut self, node: &SequenceExpression) -> SequenceExpression {
+        self.walk_sequence_expression(node)
+    }
+
+    fn walk_sequence_expression(&mut self, node: &SequenceExpression) -> SequenceExpression {
+        SequenceExpression {
+            location: node.location.clone(),
+            left: self.fold_expression(&node.left),
+            right: self.fold_expression(&node.right),
+        }
+    }
+
+    fn fold_nullable_type_expression(&mut self, node: &NullableTypeExpression) -> NullableTypeExpression {
+        self.walk_nullable_type_expression(node)
+    }
+
+    fn walk_nullable_type_expression(&mut self, node: &NullableTypeExpression) -> NullableTypeExpression {
+        NullableTypeExpression {
+            location: node.location.clone(),
+            base: self.fold_expression(&node.base),
+        }
+    }
+
+    fn fold_non_nullable_type_expression(&mut self, node: &NonNullableTypeExpression) -> NonNullableTypeExpression {
+        self.walk_non_nullable_type_expression(node)
+    }
+
+    fn walk_non_nullable_type_expression(&mut self, node: &NonNullableTypeExpression) -> NonNullableTypeExpression {
+        NonNullableTypeExpression {
+            location: node.location.clone(),
+            base: self.fold_expression(&node.base),
+        }
+    }
+
+    fn fold_any_type_expression(&mut self, node: &AnyTypeExpression) -> AnyTypeExpression {
+        node.clone()
+    }
+
+    fn fold_void_type_expression(&mut self, node: &VoidTypeExpression) -> VoidTypeExpression {
+        node.clone()
+    }
+
+    fn fold_array_type_expression(&mut self, node: &ArrayTypeExpression) -> ArrayTypeExpression {
+        self.walk_array_type_expression(node)
+    }
+
+    fn walk_array_type_expression(&mut self, node: &ArrayTypeExpression) -> ArrayTypeExpression {
+        ArrayTypeExpression {
+            location: node.location.clone(),
+            expression: self.fold_expression(&node.expression),
+        }
+    }
+
+    fn fold_tuple_type_expression(&mut self, node: &TupleTypeExpression) -> TupleTypeExpression {
+        self.walk_tuple_type_expression(node)
+    }
+
+    fn walk_tuple_type_expression(&mut self, node: &TupleTypeExpression) -> TupleTypeExpression {
+        TupleTypeExpression {
+            location: node.location.clone(),
+            expressions: self.fold_expressions(&node.expressions),
+        }
+    }
+
+    fn fold_function_type_expression(&mut self, node: &FunctionTypeExpression) -> FunctionTypeExpression {
+        self.walk_function_type_expression(node)
+    }
+
+    fn walk_function_type_expression(&mut self, node: &FunctionTypeExpression) -> FunctionTypeExpression {
+        FunctionTypeExpression {
+            location: node.location.clone(),
+            signature: self.fold_function_signature(&node.signature),
+        }
+    }
+
+    fn fold_invalidated_expression(&mut self, node: &InvalidatedExpression) -> InvalidatedExpression {
+        node.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+
+    struct IdentityFolder;
+
+    impl Folder for IdentityFolder {}
+
+    #[derive(Default)]
+    struct IdentifierCollector(Vec<String>);
+
+    impl Visitor for IdentifierCollector {
+        fn visit_qualified_identifier(&mut self, node: &QualifiedIdentifier) {
+            if let QualifiedIdentifierIdentifier::Id((name, _)) = &node.id {
+                self.0.push(name.clone());
+            }
+            self.walk_qualified_identifier(node);
+        }
+    }
+
+    #[test]
+    fn folding_parser_tests() {
+        let directory = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/parser");
+        for entry in std::fs::read_dir(directory).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_none_or(|extension| extension != "jet") {
+                continue;
+            }
+            let compilation_unit = CompilationUnit::new(None, std::fs::read_to_string(&path).unwrap(), &CompilerOptions::new());
+            let program = ParserFacade::parse_program(&compilation_unit).unwrap();
+            let folded = IdentityFolder.fold_program(&program);
+            assert_eq!(serde_json::to_string(program.as_ref()).unwrap(), serde_json::to_string(&folded).unwrap(), "{}", path.display());
+        }
+    }
+
+    #[test]
+    fn visiting_every_node() {
+        let compilation_unit = CompilationUnit::new(None, r#"
+            package p {
+                /**
+                 * @throws E1
+                 * @see T1#m
+                 */
+                [M(k = "v")]
+                public class C.<T2 extends T3> extends T4 implements T5 {
+                    function f({ a: [b] }: T6 = v1, ...c: [T7]): ?function(T8): * {
+                        for each (var d: T9 in v2) {
+                            switch type (v3) {
+                                case (e: T10) {
+                                    v4 = <x a={v5}>{v6}</x>;
+                                }
+                            }
+                        }
+                        try {} catch (g: T11) {}
+                        return { [v7]: v8, ...v9 };
+                    }
+                }
+            }
+        "#.into(), &CompilerOptions::new());
+        let program = ParserFacade::parse_program(&compilation_unit).unwrap();
+        assert!(!compilation_unit.invalidated());
+        let mut collector = IdentifierCollector::default();
+        collector.visit_program(&program);
+        let mut names = collector.0;
+        names.sort();
+        let mut expected = vec![
+            "E1", "T1", "T3", "T4", "T5", "T6", "T7", "T8", "T9", "T10", "T11",
+            "b", "c", "d", "e", "g",
+            "v1", "v2", "v3", "v4", "v5", "v6", "v7", "v8", "v9",
+        ];
+        expected.sort();
+        assert_eq!(names, expected);
+    }
+}
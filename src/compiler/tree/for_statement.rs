@@ -29,4 +29,139 @@ pub struct ForInStatement {
 pub enum ForInBinding {
     Expression(Rc<Expression>),
     VariableDefinition(Rc<SimpleVariableDefinition>),
-}
\ No newline at end of file
+}
+impl ForStatement {
+    /// Verifies the statement. The directive is assigned the
+    /// scope of the statement.
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier, directive: &Rc<Directive>) -> Result<(), DeferVerificationError> {
+        let scope = if let Some(scope) = verifier.ast_to_symbol.get(directive) {
+            scope
+        } else {
+            let scope = verifier.host.factory().create_scope();
+            verifier.ast_to_symbol.set(directive, Some(scope.clone()));
+            scope
+        };
+        verifier.enter_scope(&scope);
+        let r: Result<(), DeferVerificationError> = try {
+            match self.init.as_ref() {
+                Some(ForInitializer::Expression(exp)) => {
+                    verifier.verify_expression(exp, &default())?;
+                },
+                Some(ForInitializer::VariableDefinition(defn)) => {
+                    defn.verify(verifier)?;
+                },
+                None => {},
+            }
+            if let Some(test) = self.test.as_ref() {
+                verifier.verify_condition(test)?;
+            }
+            if let Some(update) = self.update.as_ref() {
+                verifier.verify_expression(update, &default())?;
+            }
+        };
+        if r.is_ok() {
+            let narrowings = self.test.as_ref().map(|test| verifier.condition_narrowings(test, true)).unwrap_or_default();
+            verifier.with_narrowings(narrowings, |verifier| verifier.verify_substatement(&self.body));
+        }
+        verifier.exit_scope();
+        r
+    }
+}
+
+impl ForInStatement {
+    /// Verifies the statement. The directive is assigned the
+    /// scope of the statement.
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier, directive: &Rc<Directive>) -> Result<(), DeferVerificationError> {
+        let scope = if let Some(scope) = verifier.ast_to_symbol.get(directive) {
+            scope
+        } else {
+            let scope = verifier.host.factory().create_scope();
+            verifier.ast_to_symbol.set(directive, Some(scope.clone()));
+            scope
+        };
+        verifier.enter_scope(&scope);
+        let r = self.verify_binding(verifier);
+        if r.is_ok() {
+            verifier.verify_substatement(&self.body);
+        }
+        verifier.exit_scope();
+        r
+    }
+
+    fn verify_binding(&self, verifier: &mut VerifierVerifier) -> Result<(), DeferVerificationError> {
+        let Some(iterable) = verifier.verify_expression(&self.right, &default())? else {
+            return Ok(());
+        };
+
+        // Resolve the binding before reporting anything, as the
+        // verification may be deferred.
+        let mut annotated_types: Vec<Option<Symbol>> = vec![];
+        match &self.left {
+            ForInBinding::Expression(exp) => {
+                verifier.verify_expression(exp, &ExpressionVerifyContext {
+                    mode: VerifyMode::Write,
+                    ..default()
+                })?;
+            },
+            ForInBinding::VariableDefinition(defn) => {
+                for binding in &defn.bindings {
+                    let t = if let Some(type_annotation) = binding.destructuring.type_annotation.as_ref() {
+                        Some(verifier.verify_type_expression(type_annotation)?.unwrap_or(verifier.host.any_type()))
+                    } else {
+                        None
+                    };
+                    annotated_types.push(t);
+                }
+            },
+        }
+        let item_type = self.item_type(verifier, &iterable)?.unwrap_or(verifier.host.any_type());
+
+        if let ForInBinding::VariableDefinition(defn) = &self.left {
+            let read_only = defn.kind.0 == VariableDefinitionKind::Const;
+            let (properties, parent) = verifier.definition_target(false);
+            if !verifier.ast_to_symbol.has(defn) {
+                let symbol = verifier.host.factory().create_variable_definition_directive();
+                verifier.ast_to_symbol.set(defn, Some(symbol));
+            }
+            for binding in &defn.bindings {
+                binding.destructuring.declare(verifier, read_only, &properties, &parent, Visibility::Internal, None);
+            }
+            for (binding, t) in defn.bindings.iter().zip(annotated_types) {
+                binding.destructuring.verify_type(verifier, &t.unwrap_or(item_type.clone()), &properties)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Determines the type of the items iterated by the statement, either
+    /// from the `keys()` or `values()` proxies of the iterable, or from the
+    /// iterable being an `Iterator`. Reports an error and returns `None`
+    /// if the iterable cannot be iterated.
+    fn item_type(&self, verifier: &mut VerifierVerifier, iterable: &Symbol) -> Result<Option<Symbol>, DeferVerificationError> {
+        let host = verifier.host.clone();
+        let iterable_type = iterable.static_type(&host).non_null_type();
+        if iterable_type.is_any_type() {
+            return Ok(Some(host.any_type()));
+        }
+
+        let proxy = if iterable_type.is_class_type() || iterable_type.is_enum_type() {
+            iterable_type.find_proxy(if self.each { ProxyKind::Values } else { ProxyKind::Keys }, &host)?
+        } else {
+            None
+        };
+        if let Some(proxy) = proxy {
+            let proxy_signature = proxy.signature(&host);
+            proxy_signature.throw_if_unresolved()?;
+            if let Some(item_type) = proxy_signature.result_type().iterator_item_type(&host)? {
+                return Ok(Some(item_type));
+            }
+        } else if self.each {
+            if let Some(item_type) = iterable_type.iterator_item_type(&host)? {
+                return Ok(Some(item_type));
+            }
+        }
+
+        verifier.add_verify_error(&self.right.location(), DiagnosticKind::CannotIterateType, diagnostic_arguments![Symbol(iterable_type)]);
+        Ok(None)
+    }
+}
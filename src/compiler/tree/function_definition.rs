@@ -114,6 +114,24 @@ impl TryFrom<ProxyKind> for Operator {
     }
 }
 
+impl ProxyKind {
+    /// Proxies that overload an operator.
+    pub const OPERATORS: [ProxyKind; 16] = [
+        Self::Positive, Self::Negate, Self::BitwiseNot,
+        Self::Add, Self::Subtract, Self::Multiply, Self::Divide, Self::Remainder, Self::Power,
+        Self::BitwiseAnd, Self::BitwiseXor, Self::BitwiseOr,
+        Self::ShiftLeft, Self::ShiftRight, Self::ShiftRightUnsigned,
+        Self::Has,
+    ];
+
+    /// Returns the proxy that overloads an operator. The `not in` operator
+    /// is overloaded by the same proxy as the `in` operator.
+    pub fn from_operator(operator: Operator) -> Option<Self> {
+        let operator = if operator == Operator::NotIn { Operator::In } else { operator };
+        Self::OPERATORS.into_iter().find(|kind| Operator::try_from(*kind) == Ok(operator))
+    }
+}
+
 impl FromStr for ProxyKind {
     type Err = ();
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -158,6 +176,121 @@ pub struct FunctionCommon {
     pub body: Option<FunctionBody>,
 }
 
+impl FunctionDefinition {
+    /// Creates the function and assigns it to the current scope
+    /// according to its name, returning `None` on a duplicate definition.
+    fn declare(&self, verifier: &mut VerifierVerifier) -> Option<Symbol> {
+        let host = verifier.host.clone();
+        let is_static = Attribute::find_static(&self.attributes).is_some();
+        let (properties, parent) = verifier.definition_target(is_static);
+        let at_interface_block = parent.is_interface_type();
+        let (name, location) = self.name_identifier();
+
+        // Constructors are only allowed within classes and
+        // proxies only within classes and enums.
+        match &self.name {
+            FunctionName::Constructor(_) if !parent.is_class_type() => return None,
+            FunctionName::Proxy(..) if !(parent.is_class_type() || parent.is_enum_type()) => return None,
+            _ => {},
+        }
+
+        let function = host.factory().create_function(name.clone(), &host.unresolved());
+        function.set_parent(Some(&parent));
+        function.set_visibility(Attribute::visibility(&self.attributes, at_interface_block));
+        if self.jetdoc.is_some() {
+            function.set_jetdoc(self.jetdoc.clone());
+        }
+        function.set_is_native(Attribute::find_native(&self.attributes).is_some());
+        function.set_is_async(self.common.contains_await);
+        function.set_is_generator(self.common.contains_yield);
+        function.set_is_abstract(Attribute::find_abstract(&self.attributes).is_some());
+        function.set_is_final(Attribute::find_final(&self.attributes).is_some());
+        function.set_is_overriding(Attribute::find_override(&self.attributes).is_some());
+        if at_interface_block {
+            function.set_is_optional_interface_method(self.common.body.is_some());
+        }
+
+        // The definition that conflicts with the function, if any.
+        let existing = match &self.name {
+            FunctionName::Identifier(_) => {
+                if let Some(existing) = properties.get(&name) {
+                    Some(existing)
+                } else {
+                    properties.clone().set(name.clone(), function.clone());
+                    None
+                }
+            },
+            FunctionName::Getter(_) | FunctionName::Setter(_) => {
+                let existing = properties.get(&name);
+                let virtual_property = if let Some(p) = existing.clone() {
+                    p.is_virtual_property().then_some(p)
+                } else {
+                    let p = host.factory().create_virtual_property(name.clone());
+                    p.set_parent(Some(&parent));
+                    verifier.definition_locations.insert(p.clone(), location.clone());
+                    p.set_visibility(function.visibility());
+                    p.set_jetdoc(self.jetdoc.clone());
+                    properties.clone().set(name.clone(), p.clone());
+                    Some(p)
+                };
+                match virtual_property {
+                    Some(p) if self.is_getter() && p.getter(&host).is_none() => {
+                        p.set_getter(Some(&function));
+                        function.set_of_virtual_property(Some(&p));
+                        None
+                    },
+                    Some(p) if self.is_setter() && p.setter(&host).is_none() => {
+                        p.set_setter(Some(&function));
+                        function.set_of_virtual_property(Some(&p));
+                        None
+                    },
+                    Some(p) if self.is_getter() => p.getter(&host),
+                    Some(p) => p.setter(&host),
+                    None => existing,
+                }
+            },
+            FunctionName::Constructor(_) => {
+                if let Some(existing) = parent.constructor_method(&host) {
+                    Some(existing)
+                } else {
+                    function.set_is_constructor(true);
+                    parent.set_constructor_method(Some(&function));
+                    None
+                }
+            },
+            FunctionName::Proxy(ProxyKind::To, _) => {
+                parent.list_of_to_proxies(&host).push(function.clone());
+                None
+            },
+            FunctionName::Proxy(kind, _) => {
+                let mut proxies = parent.proxies(&host);
+                if let Some(existing) = proxies.get(kind) {
+                    Some(existing)
+                } else {
+                    proxies.set(*kind, function.clone());
+                    None
+                }
+            },
+        };
+        if let Some(existing) = existing {
+            verifier.add_duplicate_definition_error(&location, &name, Some(&existing));
+            return None;
+        }
+        verifier.definition_locations.insert(function.clone(), location);
+
+        let has_body = !(function.is_native() || function.is_abstract() || (at_interface_block && self.common.body.is_none()));
+        if has_body {
+            let activation = host.factory().create_activation_scope(&function);
+            activation.set_parent(Some(&verifier.scope));
+            if !is_static && (parent.is_class_type() || parent.is_enum_type() || at_interface_block) {
+                activation.set_this(Some(&host.factory().create_this_value(&parent)));
+            }
+            function.set_activation_scope(Some(activation));
+        }
+        Some(function)
+    }
+}
+
 impl FunctionCommon {
     pub(crate) fn has_block_body(&self) -> bool {
         if let Some(ref body) = self.body { matches!(body, FunctionBody::Block(_)) } else { false }
@@ -197,4 +330,116 @@ impl ParameterKind {
 pub enum FunctionBody {
     Expression(Rc<Expression>),
     Block(Rc<Block>),
-}
\ No newline at end of file
+}
+impl FunctionDefinition {
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier, phase: VerifierPhase) -> Result<(), DeferVerificationError> {
+        match phase {
+            VerifierPhase::Declarations => {
+                if !verifier.ast_to_symbol.has(&self.common) {
+                    let function = self.declare(verifier);
+                    verifier.ast_to_symbol.set(&self.common, function);
+                }
+            },
+            VerifierPhase::Signatures => {
+                let Some(function) = verifier.ast_to_symbol.get(&self.common) else {
+                    return Ok(());
+                };
+                if function.signature(&verifier.host).is_unresolved() {
+                    self.common.verify_signature(verifier, &function)?;
+                }
+            },
+            VerifierPhase::Bodies => {
+                let Some(function) = verifier.ast_to_symbol.get(&self.common) else {
+                    return Ok(());
+                };
+                if let Some(activation) = function.activation_scope() {
+                    if self.common.body.is_some() {
+                        verifier.deferred_function_commons.push((VerifierPhase::Bodies as usize, activation, self.common.clone()));
+                    }
+                }
+            },
+            _ => {},
+        }
+        Ok(())
+    }
+}
+
+impl FunctionCommon {
+    /// Resolves the signature of a function and declares its
+    /// parameters in the function's activation scope.
+    pub(crate) fn verify_signature(&self, verifier: &mut VerifierVerifier, function: &Symbol) -> Result<(), DeferVerificationError> {
+        self.verify_signature_with_inference(verifier, function, None)
+    }
+
+    /// Resolves the signature of a function, taking parameter and result
+    /// types that are not annotated from `inferred_signature`, if any.
+    pub(crate) fn verify_signature_with_inference(&self, verifier: &mut VerifierVerifier, function: &Symbol, inferred_signature: Option<&Symbol>) -> Result<(), DeferVerificationError> {
+        let host = verifier.host.clone();
+        let inferred_parameters = inferred_signature.map(|s| s.parameters());
+        let mut parameters: Vec<Rc<ParameterOfFunctionType>> = vec![];
+        for (i, param) in self.signature.parameters.iter().enumerate() {
+            let inferred_parameter = inferred_parameters.as_ref().and_then(|p| p.get(i)).filter(|p| p.kind == param.kind);
+            let static_type = if let Some(type_annotation) = param.destructuring.type_annotation.as_ref() {
+                verifier.verify_type_expression(type_annotation)?.unwrap_or(host.any_type())
+            } else if let Some(inferred_parameter) = inferred_parameter {
+                inferred_parameter.static_type.clone()
+            } else if param.kind == ParameterKind::Rest {
+                let t = host.array_type_of_any();
+                t.throw_if_unresolved()?;
+                t
+            } else {
+                host.any_type()
+            };
+            let name = param.destructuring.destructuring.to_identifier_name().map(|name| name.0).unwrap_or("_".into());
+            parameters.push(Rc::new(ParameterOfFunctionType {
+                kind: param.kind,
+                name,
+                static_type,
+            }));
+        }
+        let result_type = if let Some(result_type) = self.signature.result_type.as_ref() {
+            verifier.verify_type_expression(result_type)?.unwrap_or(host.any_type())
+        } else if let Some(inferred_signature) = inferred_signature {
+            inferred_signature.result_type()
+        } else {
+            host.any_type()
+        };
+        if let Some(activation) = function.activation_scope() {
+            let properties = activation.properties(&host);
+            for (param, param_1) in self.signature.parameters.iter().zip(parameters.iter()) {
+                param.destructuring.declare(verifier, false, &properties, &activation, Visibility::Internal, None);
+                param.destructuring.verify_type(verifier, &param_1.static_type, &properties)?;
+            }
+        }
+        let signature = host.factory().create_function_type(parameters, result_type);
+        function.set_signature(&signature);
+        Ok(())
+    }
+
+    /// Verifies the body of a function. The current scope is
+    /// expected to be the function's activation scope.
+    pub(crate) fn verify_body(&self, verifier: &mut VerifierVerifier) -> Result<(), DeferVerificationError> {
+        let host = verifier.host.clone();
+        let signature = verifier.scope.function().signature(&host);
+        for (param, param_1) in self.signature.parameters.iter().zip(signature.parameters().iter()) {
+            if let Some(default_value) = param.default_value.as_ref() {
+                verifier.limit_expression_type(default_value, &param_1.static_type)?;
+            }
+        }
+        match self.body.as_ref() {
+            Some(FunctionBody::Block(block)) => {
+                verifier.verify_nested_directives(&block.directives);
+            },
+            Some(FunctionBody::Expression(exp)) => {
+                let result_type = signature.result_type();
+                if result_type.is_void_type() || result_type.is_any_type() {
+                    verifier.verify_expression(exp, &default())?;
+                } else {
+                    verifier.limit_expression_type(exp, &result_type)?;
+                }
+            },
+            None => {},
+        }
+        Ok(())
+    }
+}
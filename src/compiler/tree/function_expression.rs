@@ -6,4 +6,44 @@ pub struct FunctionExpression {
     pub location: Location,
     pub name: Option<(String, Location)>,
     pub common: Rc<FunctionCommon>,
-}
\ No newline at end of file
+}
+impl FunctionExpression {
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier, context: &ExpressionVerifyContext) -> Result<Option<Symbol>, DeferVerificationError> {
+        let host = verifier.host.clone();
+        let function = if let Some(function) = verifier.ast_to_symbol.get(&self.common) {
+            function
+        } else {
+            let name = self.name.as_ref().map(|name| name.0.clone()).unwrap_or_default();
+            let function = host.factory().create_function(name, &host.unresolved());
+            function.set_is_async(self.common.contains_await);
+            function.set_is_generator(self.common.contains_yield);
+            let activation = host.factory().create_activation_scope(&function);
+            activation.set_parent(Some(&verifier.scope));
+            function.set_activation_scope(Some(activation));
+            verifier.ast_to_symbol.set(&self.common, Some(function.clone()));
+            function
+        };
+        let activation = function.activation_scope().unwrap();
+        if function.signature(&host).is_unresolved() {
+            // Parameter and result types that are not annotated are
+            // inferred from a function type expected by the context.
+            let inferred_signature = context.context_type.as_ref().filter(|t| t.is_function_type());
+            self.common.verify_signature_with_inference(verifier, &function, inferred_signature)?;
+
+            // A named function expression may refer to itself.
+            if let Some((name, _)) = self.name.as_ref() {
+                let mut properties = activation.properties(&host);
+                if properties.get(name).is_none() {
+                    let variable = host.factory().create_variable_property(name.clone(), true, &function.signature(&host));
+                    variable.set_parent(Some(&activation));
+                    properties.set(name.clone(), variable);
+                }
+            }
+
+            if self.common.body.is_some() {
+                verifier.deferred_function_commons.push((VerifierPhase::Bodies as usize, activation.clone(), self.common.clone()));
+            }
+        }
+        Ok(Some(host.factory().create_function_value(&activation)))
+    }
+}
@@ -7,4 +7,17 @@ pub struct IfStatement {
     pub test: Rc<Expression>,
     pub consequent: Rc<Directive>,
     pub alternative: Option<Rc<Directive>>,
-}
\ No newline at end of file
+}
+
+impl IfStatement {
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier) -> Result<(), DeferVerificationError> {
+        verifier.verify_condition(&self.test)?;
+        let narrowings = verifier.condition_narrowings(&self.test, true);
+        verifier.with_narrowings(narrowings, |verifier| verifier.verify_substatement(&self.consequent));
+        if let Some(alternative) = self.alternative.as_ref() {
+            let narrowings = verifier.condition_narrowings(&self.test, false);
+            verifier.with_narrowings(narrowings, |verifier| verifier.verify_substatement(alternative));
+        }
+        Ok(())
+    }
+}
@@ -22,4 +22,31 @@ impl ImportSpecifier {
             Self::Identifier((_, l)) => l.clone(),
         }
     }
-}
\ No newline at end of file
+}
+impl ImportDirective {
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier) {
+        let package_name: Vec<String> = self.package_name.iter().map(|name| name.0.clone()).collect();
+        let Some(package) = verifier.lookup_package(&package_name) else {
+            let location = self.package_name.first().unwrap().1.combine_with(self.package_name.last().unwrap().1.clone());
+            verifier.add_verify_error(&location, DiagnosticKind::UndefinedPackage, diagnostic_arguments![String(package_name.join("."))]);
+            return;
+        };
+        match &self.import_specifier {
+            ImportSpecifier::Wildcard(_) => {
+                if let Some(alias) = self.alias.as_ref() {
+                    verifier.scope.package_aliases().set(alias.0.clone(), package);
+                } else if !verifier.scope.open_packages().includes(&package) {
+                    verifier.scope.open_packages().push(package);
+                }
+            },
+            ImportSpecifier::Identifier((name, location)) => {
+                let Some(property) = package.properties(&verifier.host).get(name) else {
+                    verifier.add_verify_error(location, DiagnosticKind::UndefinedProperty, diagnostic_arguments![String(name.clone())]);
+                    return;
+                };
+                let alias = self.alias.as_ref().map(|alias| alias.0.clone()).unwrap_or(name.clone());
+                verifier.scope.imports().set(alias, property);
+            },
+        }
+    }
+}
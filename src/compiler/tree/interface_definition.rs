@@ -10,4 +10,79 @@ pub struct InterfaceDefinition {
     pub type_parameters: Option<Vec<Rc<TypeParameter>>>,
     pub extends_clause: Option<Vec<Rc<Expression>>>,
     pub block: Rc<Block>,
-}
\ No newline at end of file
+}
+impl InterfaceDefinition {
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier, directive: &Rc<Directive>, phase: VerifierPhase) -> Result<(), DeferVerificationError> {
+        if phase == VerifierPhase::Declarations && !verifier.ast_to_symbol.has(directive) {
+            self.declare(verifier, directive);
+        }
+        let Some(interface) = verifier.ast_to_symbol.get(directive) else {
+            return Ok(());
+        };
+        let scope = verifier.ast_to_symbol.get(&self.block).unwrap();
+
+        if phase == VerifierPhase::Heritage {
+            verifier.enter_scope(&scope);
+            let r = self.verify_heritage(verifier, &interface);
+            verifier.exit_scope();
+            r?;
+        }
+
+        verifier.enter_scope(&scope);
+        verifier.verify_directives(&self.block.directives, phase);
+        verifier.exit_scope();
+        Ok(())
+    }
+
+    fn declare(&self, verifier: &mut VerifierVerifier, directive: &Rc<Directive>) {
+        let (properties, parent) = verifier.definition_target(true);
+        let name = self.name.0.clone();
+        if let Some(existing) = properties.get(&name) {
+            verifier.add_duplicate_definition_error(&self.name.1, &name, Some(&existing));
+            verifier.ast_to_symbol.set(directive, None);
+            return;
+        }
+        let interface = verifier.host.factory().create_interface_type(name.clone());
+        interface.set_parent(Some(&parent));
+        verifier.definition_locations.insert(interface.clone(), self.name.1.clone());
+        interface.set_visibility(Attribute::visibility(&self.attributes, false));
+        interface.set_jetdoc(self.jetdoc.clone());
+        properties.clone().set(name, interface.clone());
+        verifier.ast_to_symbol.set(directive, Some(interface.clone()));
+
+        let scope = verifier.host.factory().create_interface_scope(&interface);
+        scope.set_parent(Some(&verifier.scope));
+        if let Some(type_parameters) = self.type_parameters.as_ref() {
+            interface.set_type_parameters(Some(&verifier.declare_type_parameters(type_parameters, &scope)));
+        }
+        verifier.ast_to_symbol.set(&self.block, Some(scope));
+    }
+
+    /// Resolves the `extends` clause and the type parameter bounds.
+    fn verify_heritage(&self, verifier: &mut VerifierVerifier, interface: &Symbol) -> Result<(), DeferVerificationError> {
+        // Resolve every type before reporting anything, as the
+        // verification may be deferred.
+        let scope = verifier.scope.clone();
+        let bounds = verifier.resolve_type_parameter_bounds(self.type_parameters.as_deref().unwrap_or_default(), &scope)?;
+        let mut extends_types: Vec<(Symbol, Location)> = vec![];
+        for exp in self.extends_clause.iter().flatten() {
+            if let Some(t) = verifier.verify_type_expression(exp)? {
+                extends_types.push((t, exp.location()));
+            }
+        }
+
+        verifier.set_type_parameter_bounds(bounds);
+
+        let mut extends_interfaces = interface.extends_interfaces(&verifier.host);
+        for (t, location) in extends_types {
+            if !t.is_interface_type() {
+                verifier.add_verify_error(&location, DiagnosticKind::NotAnInterface, diagnostic_arguments![Symbol(t)]);
+            } else if verifier.inherits_from(&t, interface) {
+                verifier.add_verify_error(&location, DiagnosticKind::CircularInheritance, diagnostic_arguments![Symbol(interface.clone())]);
+            } else if !extends_interfaces.includes(&t) {
+                extends_interfaces.push(t);
+            }
+        }
+        Ok(())
+    }
+}
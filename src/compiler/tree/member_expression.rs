@@ -6,4 +6,52 @@ pub struct MemberExpression {
     pub location: Location,
     pub base: Rc<Expression>,
     pub identifier: QualifiedIdentifier,
-}
\ No newline at end of file
+}
+
+impl MemberExpression {
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier, context: &ExpressionVerifyContext) -> Result<Option<Symbol>, DeferVerificationError> {
+        // Fully qualified package name
+        if let Some(package) = Self::package_name(&self.base).and_then(|name| verifier.lookup_package(&name)) {
+            let Some(name) = self.identifier.to_identifier_name() else {
+                return self.verify_with_base(verifier, &package, context);
+            };
+            if let Some(subpackage) = package.subpackages().get(&name.0) {
+                if package.properties(&verifier.host).get(&name.0).is_none() {
+                    return Ok(Some(subpackage));
+                }
+            }
+            return self.verify_with_base(verifier, &package, context);
+        }
+
+        // A class referenced by name is the base of its static properties,
+        // including a generic class without type arguments, as in `Map.of(k, v)`.
+        let Some(base) = verifier.verify_expression(&self.base, &ExpressionVerifyContext {
+            followed_by_type_arguments: true,
+            ..default()
+        })? else {
+            return Ok(None);
+        };
+        let base = base.expect_type().ok().filter(|t| t.is_class_type() || t.is_enum_type()).unwrap_or(base);
+        self.verify_with_base(verifier, &base, context)
+    }
+
+    fn verify_with_base(&self, verifier: &mut VerifierVerifier, base: &Symbol, context: &ExpressionVerifyContext) -> Result<Option<Symbol>, DeferVerificationError> {
+        let Some((qual, key, disamb)) = self.identifier.verify(verifier)? else {
+            return Ok(None);
+        };
+        verifier.resolve_property_as_exp(base, qual, key, disamb, &self.identifier.location, context)
+    }
+
+    /// Returns the names of a `a.b.c` expression, where `a` is a plain identifier.
+    fn package_name(exp: &Rc<Expression>) -> Option<Vec<String>> {
+        match exp.as_ref() {
+            Expression::QualifiedIdentifier(id) => Some(vec![id.to_identifier_name()?.0]),
+            Expression::Member(me) => {
+                let mut r = Self::package_name(&me.base)?;
+                r.push(me.identifier.to_identifier_name()?.0);
+                Some(r)
+            },
+            _ => None,
+        }
+    }
+}
@@ -6,4 +6,104 @@ pub struct NewExpression {
     pub location: Location,
     pub base: Rc<Expression>,
     pub arguments: Option<Vec<Rc<Expression>>>,
-}
\ No newline at end of file
+}
+
+impl NewExpression {
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier, context: &ExpressionVerifyContext) -> Result<Option<Symbol>, DeferVerificationError> {
+        let host = verifier.host.clone();
+        let arguments = self.arguments.as_deref().unwrap_or_default();
+        // Type arguments may be omitted, in which case they are inferred.
+        let t = verifier.verify_type_expression_with_context(&self.base, &ExpressionVerifyContext {
+            followed_by_type_arguments: true,
+            ..default()
+        })?;
+        let Some(mut t) = t.clone().filter(|t| t.is_class_type()) else {
+            for argument in arguments {
+                verifier.verify_expression(argument, &default())?;
+            }
+            return Ok(t.map(|t| host.factory().create_value(&t)));
+        };
+
+        // A generic class instantiated without type arguments
+        // has its type arguments inferred.
+        if !t.is_type_after_explicit_type_substitution() && t.type_parameters().is_some_and(|p| p.length() != 0) {
+            let Some(substituted_type) = self.infer_type_arguments(verifier, &t, context)? else {
+                for argument in arguments {
+                    verifier.verify_expression(argument, &default())?;
+                }
+                return Ok(None);
+            };
+            t = substituted_type;
+        }
+
+        // Arguments are checked against the nearest constructor
+        // in the class hierarchy. A class hierarchy without constructors
+        // takes no arguments.
+        let mut constructor = None;
+        for class in t.descending_class_hierarchy(&host).collect::<Vec<_>>() {
+            class.throw_if_unresolved()?;
+            constructor = class.constructor_method(&host);
+            if constructor.is_some() {
+                break;
+            }
+        }
+        let signature = if let Some(constructor) = constructor {
+            let signature = constructor.signature(&host);
+            signature.throw_if_unresolved()?;
+            signature
+        } else {
+            host.factory().create_function_type(vec![], host.void_type())
+        };
+        verifier.verify_arguments(&self.location, &signature, arguments)?;
+        Ok(Some(host.factory().create_value(&t)))
+    }
+
+    /// Infers the type arguments of a generic class from the context type
+    /// and from the arguments passed to its constructor, returning
+    /// the class after substitution of the inferred types.
+    fn infer_type_arguments(&self, verifier: &mut VerifierVerifier, class: &Symbol, context: &ExpressionVerifyContext) -> Result<Option<Symbol>, DeferVerificationError> {
+        let host = verifier.host.clone();
+        let type_parameters = class.type_parameters().unwrap();
+        let mut inferred: Vec<Option<Symbol>> = vec![None; type_parameters.length()];
+
+        // Context type, such as `const b: Box.<Number> = new Box(...)`
+        if let Some(context_type) = context.context_type.as_ref().map(|t| t.non_null_type()) {
+            if context_type.is_type_after_explicit_type_substitution() {
+                let origin = context_type.origin();
+                let pattern = if origin == *class {
+                    Some(host.factory().create_type_after_explicit_type_substitution(class, &type_parameters))
+                } else {
+                    class.all_ascending_types(&host).into_iter().find(|t| t.is_type_after_explicit_type_substitution() && t.origin() == origin)
+                };
+                if let Some(pattern) = pattern {
+                    TypeInference(&host).infer(&pattern, &context_type, &type_parameters, &mut inferred);
+                }
+            }
+        }
+
+        // Constructor arguments
+        let mut constructor = None;
+        for class in class.descending_class_hierarchy(&host).collect::<Vec<_>>() {
+            class.throw_if_unresolved()?;
+            constructor = class.constructor_method(&host);
+            if constructor.is_some() {
+                break;
+            }
+        }
+        let signature = match constructor {
+            Some(constructor) => {
+                let signature = constructor.signature(&host);
+                signature.throw_if_unresolved()?;
+                Some(signature)
+            },
+            None => None,
+        };
+        let arguments = self.arguments.as_deref().unwrap_or_default();
+        let Some(substitute_types) = verifier.infer_type_arguments_from_arguments(&self.location, signature.as_ref(), arguments, &type_parameters, inferred)? else {
+            return Ok(None);
+        };
+        let t = host.factory().create_type_after_explicit_type_substitution(class, &substitute_types);
+        verifier.check_type_arguments_later(&self.location, &t);
+        Ok(Some(t))
+    }
+}
@@ -18,4 +18,22 @@ pub struct OptionalChainingExpression {
 #[derive(Clone, Serialize, Deserialize)]
 pub struct OptionalChainingPlaceholder {
     pub location: Location,
-}
\ No newline at end of file
+}
+
+impl OptionalChainingExpression {
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier, context: &ExpressionVerifyContext) -> Result<Option<Symbol>, DeferVerificationError> {
+        let host = verifier.host.clone();
+        let Some(base) = verifier.verify_expression(&self.base, &default())? else {
+            return Ok(None);
+        };
+        let base = host.factory().create_value(&base.static_type(&host).non_null_type());
+        verifier.optional_chaining_bases.push(base);
+        let r = verifier.verify_expression(&self.expression, context);
+        verifier.optional_chaining_bases.pop();
+        let Some(r) = r? else {
+            return Ok(None);
+        };
+        let t = host.factory().create_nullable_type(&r.static_type(&host));
+        Ok(Some(host.factory().create_value(&t)))
+    }
+}
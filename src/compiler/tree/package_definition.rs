@@ -7,4 +7,24 @@ pub struct PackageDefinition {
     pub jetdoc: Option<Rc<JetDoc>>,
     pub name: Vec<(String, Location)>,
     pub block: Rc<Block>,
-}
\ No newline at end of file
+}
+
+impl PackageDefinition {
+    pub(crate) fn verify(self: &Rc<Self>, verifier: &mut VerifierVerifier, phase: VerifierPhase) {
+        let scope = if let Some(scope) = verifier.ast_to_symbol.get(self) {
+            scope
+        } else {
+            let package = verifier.host.factory().create_package(self.name.iter().map(|name| name.0.as_str()));
+            if self.jetdoc.is_some() {
+                package.set_jetdoc(self.jetdoc.clone());
+            }
+            let scope = verifier.host.factory().create_package_scope(&package);
+            verifier.ast_to_symbol.set(self, Some(scope.clone()));
+            scope
+        };
+
+        verifier.enter_scope(&scope);
+        verifier.verify_directives(&self.block.directives, phase);
+        verifier.exit_scope();
+    }
+}
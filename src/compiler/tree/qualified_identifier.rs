@@ -73,53 +73,8 @@ impl QualifiedIdentifier {
             return Ok(None);
         }
         let (qual, key, disamb) = qn.unwrap();
-        let r = verifier.scope.resolve_property_with_disambiguation(qual, key.clone(), &verifier.host, disamb);
-        if r.is_err() {
-            match r.unwrap_err() {
-                PropertyResolutionError::AmbiguousReference { name } => {
-                    verifier.add_verify_error(&self.location, DiagnosticKind::AmbiguousReference, diagnostic_arguments![String(name.clone())]);
-                    return Ok(None);
-                },
-                PropertyResolutionError::DeferVerification => {
-                    return Err(DeferVerificationError);
-                },
-                PropertyResolutionError::VoidBase => {
-                    verifier.add_verify_error(&self.location, DiagnosticKind::AccessingPropertyOfVoidBase, diagnostic_arguments![]);
-                    return Ok(None);
-                },
-                PropertyResolutionError::NullableBase { nullable_type } => {
-                    verifier.add_verify_error(&self.location, DiagnosticKind::AccessingPropertyOfNullableBase, diagnostic_arguments![Symbol(nullable_type)]);
-                    return Ok(None);
-                },
-            }
-        }
-        let r = r.unwrap();
-        if r.is_none() {
-            verifier.add_verify_error(&self.location, DiagnosticKind::UndefinedProperty, diagnostic_arguments![String(key.string_value().unwrap_or(key.number_value().unwrap().to_string()))]);
-            return Ok(None);
-        }
-        let r = r.unwrap();
-
-        if !r.property_is_visible(&verifier.scope, &verifier.host) {
-            verifier.add_verify_error(&self.location, DiagnosticKind::InaccessibleProperty, diagnostic_arguments![String(key.string_value().unwrap())]);
-        }
-
-        if r.is_reference_value() && (r.is_static_reference_value() || r.is_instance_reference_value() || r.is_scope_reference_value() || r.is_package_reference_value()) {
-            let p = r.property();
-
-            // Require type arguments
-            if (p.is_origin_class_type() || p.is_origin_interface_type()) && p.type_parameters().is_some() && !context.followed_by_type_arguments {
-                verifier.add_verify_error(&self.location, DiagnosticKind::ParameterizedTypeMustBeArgumented, diagnostic_arguments![]);
-            }
-
-            // Compile-time constant
-            if p.is_origin_variable_property() && p.read_only(&verifier.host) && p.constant_initializer().is_some() {
-                let r = p.constant_initializer().unwrap();
-                return Ok(Some(r));
-            }
-        }
-
-        Ok(Some(r))
+        let scope = verifier.scope.clone();
+        verifier.resolve_property_as_exp(&scope, qual, key, disamb, &self.location, context)
     }
 
     pub fn to_identifier_name_or_asterisk(&self) -> Option<(String, Location)> {
@@ -5,4 +5,59 @@ use serde::{Serialize, Deserialize};
 pub struct ReturnStatement {
     pub location: Location,
     pub expression: Option<Rc<Expression>>,
-}
\ No newline at end of file
+}
+
+impl ReturnStatement {
+    /// Verifies the returned value against the result type of
+    /// the enclosing function.
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier) -> Result<(), DeferVerificationError> {
+        let host = verifier.host.clone();
+        let Some(activation) = verifier.scope.find_activation() else {
+            return Ok(());
+        };
+
+        // Generators return an iterator, thus their returned value
+        // is not checked against the result type.
+        let Some(result_type) = Self::expected_result_type(&activation.function(), &host)? else {
+            if let Some(exp) = self.expression.as_ref() {
+                verifier.verify_expression(exp, &default())?;
+            }
+            return Ok(());
+        };
+
+        if let Some(exp) = self.expression.as_ref() {
+            if result_type.is_void_type() {
+                if let Some(v) = verifier.verify_expression(exp, &default())? {
+                    verifier.add_verify_error(&exp.location(), DiagnosticKind::IncompatibleTypes, diagnostic_arguments![Symbol(result_type), Symbol(v.static_type(&host))]);
+                }
+            } else {
+                verifier.limit_expression_type(exp, &result_type)?;
+            }
+        } else if !(result_type.is_void_type() || result_type.is_any_type()) {
+            verifier.add_verify_error(&self.location, DiagnosticKind::ReturnValueExpected, diagnostic_arguments![]);
+        }
+        Ok(())
+    }
+
+    /// Returns the type of the values returned by a function: the result
+    /// type, or `T` for an asynchronous function resulting in `Promise.<T>`.
+    /// Returns `None` for generators.
+    pub(crate) fn expected_result_type(function: &Symbol, host: &SymbolHost) -> Result<Option<Symbol>, DeferVerificationError> {
+        if function.is_generator() {
+            return Ok(None);
+        }
+        let signature = function.signature(host);
+        signature.throw_if_unresolved()?;
+        let result_type = signature.result_type();
+        if !function.is_async() {
+            return Ok(Some(result_type));
+        }
+        let promise_type = host.promise_type();
+        promise_type.throw_if_unresolved()?;
+        if result_type.type_after_substitution_has_origin(&promise_type) {
+            Ok(Some(result_type.substitute_types().get(0).unwrap()))
+        } else {
+            Ok(Some(host.any_type()))
+        }
+    }
+}
@@ -8,11 +8,29 @@ use serde::{Serialize, Deserialize};
 /// following property operator is limited to access a property from the base class
 /// or invoke a method of the base class.
 /// 
-/// ```
+/// ```ignore
 /// super.f()
 /// ```
 #[derive(Clone, Serialize, Deserialize)]
 pub struct SuperExpression {
     pub location: Location,
     pub object: Option<Vec<Rc<Expression>>>,
-}
\ No newline at end of file
+}
+
+impl SuperExpression {
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier) -> Result<Option<Symbol>, DeferVerificationError> {
+        for exp in self.object.iter().flatten() {
+            verifier.verify_expression(exp, &default())?;
+        }
+        let this = verifier.scope.find_activation().and_then(|activation| activation.this());
+        // Only classes and enums have a base class.
+        let this_type = this.map(|this| this.static_type(&verifier.host)).filter(|t| t.is_class_type() || t.is_enum_type());
+        let super_class = this_type.and_then(|t| t.extends_class(&verifier.host));
+        let Some(super_class) = super_class else {
+            verifier.add_verify_error(&self.location, DiagnosticKind::IllegalSuperExpression, diagnostic_arguments![]);
+            return Ok(None);
+        };
+        super_class.throw_if_unresolved()?;
+        Ok(Some(verifier.host.factory().create_this_value(&super_class)))
+    }
+}
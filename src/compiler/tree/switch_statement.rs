@@ -43,4 +43,87 @@ pub struct TypeCase {
     /// Case parameter. If `None`, designates a `default {}` case.
     pub parameter: Option<TypedDestructuring>,
     pub block: Rc<Block>,
-}
\ No newline at end of file
+}
+
+impl SwitchStatement {
+    /// Verifies the statement. The directive is assigned the
+    /// scope shared by the cases.
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier, directive: &Rc<Directive>) -> Result<(), DeferVerificationError> {
+        let Some(discriminant) = verifier.verify_expression(&self.discriminant, &default())? else {
+            return Ok(());
+        };
+        let discriminant_type = discriminant.static_type(&verifier.host);
+        let mut labels: Vec<(Symbol, Location)> = vec![];
+        for case in &self.cases {
+            for label in &case.labels {
+                if let CaseLabel::Case((exp, _)) = label {
+                    if let Some(v) = verifier.limit_expression_type(exp, &discriminant_type)? {
+                        labels.push((v, exp.location()));
+                    }
+                }
+            }
+        }
+
+        // Report constant labels that repeat a previous label.
+        let folding = ConstantFolding(&verifier.host);
+        let duplicates: Vec<Location> = labels.iter().enumerate().filter(|(i, (v, _))| {
+            labels[..*i].iter().any(|(v1, _)| folding.constants_equal(v1, v) == Some(true))
+        }).map(|(_, (_, location))| location.clone()).collect();
+        for location in duplicates {
+            verifier.add_verify_error(&location, DiagnosticKind::DuplicateCaseLabel, diagnostic_arguments![]);
+        }
+
+        let scope = if let Some(scope) = verifier.ast_to_symbol.get(directive) {
+            scope
+        } else {
+            let scope = verifier.host.factory().create_scope();
+            verifier.ast_to_symbol.set(directive, Some(scope.clone()));
+            scope
+        };
+        let directives: Vec<Rc<Directive>> = self.cases.iter().flat_map(|case| case.directives.iter().cloned()).collect();
+        verifier.enter_scope(&scope);
+        verifier.verify_nested_directives(&directives);
+        verifier.exit_scope();
+        Ok(())
+    }
+}
+
+impl SwitchTypeStatement {
+    /// Verifies the statement. The block of each case is assigned a
+    /// scope declaring the case parameter, narrowed to the case type.
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier) -> Result<(), DeferVerificationError> {
+        if verifier.verify_expression(&self.discriminant, &default())?.is_none() {
+            return Ok(());
+        }
+
+        // Resolve the case types before verifying anything, as
+        // the verification may be deferred.
+        let mut case_types: Vec<Option<Symbol>> = vec![];
+        for case in &self.cases {
+            let t = if let Some(type_annotation) = case.parameter.as_ref().and_then(|p| p.type_annotation.as_ref()) {
+                verifier.verify_type_expression(type_annotation)?
+            } else {
+                None
+            };
+            case_types.push(t);
+        }
+
+        for (case, case_type) in self.cases.iter().zip(case_types) {
+            let scope = verifier.block_scope(&case.block);
+            if let Some(parameter) = case.parameter.as_ref() {
+                let properties = scope.properties(&verifier.host);
+                let case_type = case_type.unwrap_or(verifier.host.any_type());
+                parameter.declare(verifier, false, &properties, &scope, Visibility::Internal, None);
+                parameter.verify_type(verifier, &case_type, &properties)?;
+            }
+        }
+
+        for case in &self.cases {
+            let scope = verifier.block_scope(&case.block);
+            verifier.enter_scope(&scope);
+            verifier.verify_nested_directives(&case.block.directives);
+            verifier.exit_scope();
+        }
+        Ok(())
+    }
+}
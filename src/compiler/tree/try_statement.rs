@@ -20,4 +20,52 @@ pub struct CatchClause {
 pub struct FinallyClause {
     pub location: Location,
     pub block: Rc<Block>,
-}
\ No newline at end of file
+}
+
+impl TryStatement {
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier) -> Result<(), DeferVerificationError> {
+        // Resolve the catch parameter types before verifying
+        // anything, as the verification may be deferred.
+        let mut catch_types: Vec<Symbol> = vec![];
+        for catch_clause in &self.catch_clauses {
+            let t = if let Some(type_annotation) = catch_clause.parameter.type_annotation.as_ref() {
+                verifier.verify_type_expression(type_annotation)?
+            } else {
+                None
+            };
+            catch_types.push(t.unwrap_or(verifier.host.any_type()));
+        }
+
+        for (catch_clause, catch_type) in self.catch_clauses.iter().zip(catch_types) {
+            let scope = verifier.block_scope(&catch_clause.block);
+            if catch_clause.parameter.type_annotation.is_none() && !verifier.ast_to_symbol.has(&catch_clause.parameter.destructuring)
+                && catch_clause.parameter.location.compilation_unit().compiler_options().is_strict() {
+                verifier.add_verify_error(&catch_clause.parameter.location, DiagnosticKind::CatchParameterMustBeTyped, diagnostic_arguments![]);
+            }
+            let properties = scope.properties(&verifier.host);
+            catch_clause.parameter.declare(verifier, false, &properties, &scope, Visibility::Internal, None);
+            catch_clause.parameter.verify_type(verifier, &catch_type, &properties)?;
+        }
+
+        Self::verify_block(verifier, &self.block);
+
+        for catch_clause in &self.catch_clauses {
+            let scope = verifier.block_scope(&catch_clause.block);
+            verifier.enter_scope(&scope);
+            verifier.verify_nested_directives(&catch_clause.block.directives);
+            verifier.exit_scope();
+        }
+
+        if let Some(finally_clause) = self.finally_clause.as_ref() {
+            Self::verify_block(verifier, &finally_clause.block);
+        }
+        Ok(())
+    }
+
+    fn verify_block(verifier: &mut VerifierVerifier, block: &Rc<Block>) {
+        let scope = verifier.block_scope(block);
+        verifier.enter_scope(&scope);
+        verifier.verify_nested_directives(&block.directives);
+        verifier.exit_scope();
+    }
+}
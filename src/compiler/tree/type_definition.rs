@@ -8,4 +8,43 @@ pub struct TypeDefinition {
     pub attributes: Vec<Attribute>,
     pub left: (String, Location),
     pub right: Rc<Expression>,
-}
\ No newline at end of file
+}
+impl TypeDefinition {
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier, directive: &Rc<Directive>, phase: VerifierPhase) -> Result<(), DeferVerificationError> {
+        match phase {
+            VerifierPhase::Declarations => {
+                if verifier.ast_to_symbol.has(directive) {
+                    return Ok(());
+                }
+                let (properties, parent) = verifier.definition_target(true);
+                let name = self.left.0.clone();
+                if let Some(existing) = properties.get(&name) {
+                    verifier.add_duplicate_definition_error(&self.left.1, &name, Some(&existing));
+                    verifier.ast_to_symbol.set(directive, None);
+                    return Ok(());
+                }
+                let alias = verifier.host.factory().create_alias(name.clone(), &verifier.host.unresolved());
+                alias.set_parent(Some(&parent));
+                verifier.definition_locations.insert(alias.clone(), self.left.1.clone());
+                alias.set_visibility(Attribute::visibility(&self.attributes, false));
+                if self.jetdoc.is_some() {
+                    alias.set_jetdoc(self.jetdoc.clone());
+                }
+                properties.clone().set(name, alias.clone());
+                verifier.ast_to_symbol.set(directive, Some(alias));
+            },
+            VerifierPhase::Heritage => {
+                let Some(alias) = verifier.ast_to_symbol.get(directive) else {
+                    return Ok(());
+                };
+                if !alias.alias_of().is_unresolved() {
+                    return Ok(());
+                }
+                let t = verifier.verify_type_expression(&self.right)?;
+                alias.set_alias_of(&t.unwrap_or(verifier.host.any_type()));
+            },
+            _ => {},
+        }
+        Ok(())
+    }
+}
@@ -40,4 +40,86 @@ pub struct TupleTypeExpression {
 pub struct FunctionTypeExpression {
     pub location: Location,
     pub signature: FunctionSignature,
-}
\ No newline at end of file
+}
+
+impl NullableTypeExpression {
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier) -> Result<Option<Symbol>, DeferVerificationError> {
+        let t = verifier.verify_type_expression(&self.base)?;
+        Ok(t.map(|t| verifier.host.factory().create_nullable_type(&t)))
+    }
+}
+
+impl NonNullableTypeExpression {
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier) -> Result<Option<Symbol>, DeferVerificationError> {
+        let t = verifier.verify_type_expression(&self.base)?;
+        Ok(t.map(|t| t.non_null_type()))
+    }
+}
+
+impl ArrayTypeExpression {
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier) -> Result<Option<Symbol>, DeferVerificationError> {
+        let array_type = verifier.host.array_type();
+        array_type.throw_if_unresolved()?;
+        let t = verifier.verify_type_expression(&self.expression)?;
+        Ok(t.map(|t| verifier.host.factory().create_type_after_explicit_type_substitution(&array_type, &shared_array![t])))
+    }
+}
+
+impl TupleTypeExpression {
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier) -> Result<Option<Symbol>, DeferVerificationError> {
+        let mut element_types: Vec<Symbol> = vec![];
+        let mut failed = false;
+        for exp in &self.expressions {
+            if let Some(t) = verifier.verify_type_expression(exp)? {
+                element_types.push(t);
+            } else {
+                failed = true;
+            }
+        }
+        if failed {
+            return Ok(None);
+        }
+        Ok(Some(verifier.host.factory().create_tuple_type(element_types)))
+    }
+}
+
+impl FunctionTypeExpression {
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier) -> Result<Option<Symbol>, DeferVerificationError> {
+        let host = verifier.host.clone();
+        let mut parameters: Vec<Rc<ParameterOfFunctionType>> = vec![];
+        let mut failed = false;
+        for param in &self.signature.parameters {
+            let static_type = if let Some(type_annotation) = param.destructuring.type_annotation.as_ref() {
+                verifier.verify_type_expression(type_annotation)?
+            } else if param.kind == ParameterKind::Rest {
+                let t = host.array_type_of_any();
+                t.throw_if_unresolved()?;
+                Some(t)
+            } else {
+                Some(host.any_type())
+            };
+            let Some(static_type) = static_type else {
+                failed = true;
+                continue;
+            };
+            let name = param.destructuring.destructuring.to_identifier_name().map(|name| name.0).unwrap_or("_".into());
+            parameters.push(Rc::new(ParameterOfFunctionType {
+                kind: param.kind,
+                name,
+                static_type,
+            }));
+        }
+        let result_type = if let Some(result_type) = self.signature.result_type.as_ref() {
+            verifier.verify_type_expression(result_type)?
+        } else {
+            Some(host.any_type())
+        };
+        let Some(result_type) = result_type else {
+            return Ok(None);
+        };
+        if failed {
+            return Ok(None);
+        }
+        Ok(Some(host.factory().create_function_type(parameters, result_type)))
+    }
+}
@@ -1,9 +1,11 @@
 use crate::ns::*;
 use serde::{Serialize, Deserialize};
 
-/// A type parameter as in `function f.<T>(): void {}`.
+/// A type parameter as in `class C.<T extends B> {}`.
 #[derive(Clone, Serialize, Deserialize)]
 pub struct TypeParameter {
     pub location: Location,
     pub name: (String, Location),
+    pub extends_clause: Option<Rc<Expression>>,
+    pub implements_clause: Option<Vec<Rc<Expression>>>,
 }
\ No newline at end of file
@@ -6,4 +6,93 @@ pub struct UnaryExpression {
     pub location: Location,
     pub operator: Operator,
     pub expression: Rc<Expression>,
-}
\ No newline at end of file
+}
+
+impl UnaryExpression {
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier, exp: &Rc<Expression>, context: &ExpressionVerifyContext) -> Result<Option<Symbol>, DeferVerificationError> {
+        let host = verifier.host.clone();
+        match self.operator {
+            Operator::Delete => {
+                let v = verifier.verify_expression(&self.expression, &ExpressionVerifyContext {
+                    mode: VerifyMode::Delete,
+                    ..default()
+                })?;
+                Ok(v.map(|_| host.factory().create_value(&host.boolean_type())))
+            },
+            Operator::Void => {
+                verifier.verify_expression(&self.expression, &default())?;
+                Ok(Some(host.factory().create_undefined_constant(&host.any_type())))
+            },
+            Operator::Typeof => {
+                let v = verifier.verify_expression(&self.expression, &default())?;
+                Ok(v.map(|_| host.factory().create_value(&host.string_type())))
+            },
+            Operator::LogicalNot => {
+                let v = verifier.verify_expression(&self.expression, &default())?;
+                Ok(v.map(|v| ConstantFolding(&host).fold_unary(self.operator, &v).unwrap_or_else(|| host.factory().create_value(&host.boolean_type()))))
+            },
+            Operator::Await => {
+                let Some(v) = verifier.verify_expression(&self.expression, &default())? else {
+                    return Ok(None);
+                };
+                let t = v.static_type(&host);
+                let t = t.promise_result_type(&host)?.unwrap_or(t);
+                Ok(Some(host.factory().create_value(&t)))
+            },
+            Operator::NonNull => {
+                let v = verifier.verify_expression(&self.expression, &default())?;
+                Ok(v.map(|v| host.factory().create_value(&v.static_type(&host).non_null_type())))
+            },
+            Operator::Negative => {
+                let v = verifier.verify_expression(&self.expression, &ExpressionVerifyContext {
+                    context_type: context.context_type.clone(),
+                    preceded_by_negative: true,
+                    ..default()
+                })?;
+
+                // Numeric literals are negated by themselves.
+                if Self::is_numeric_literal(&self.expression) {
+                    return Ok(v);
+                }
+                let Some(v) = v else {
+                    return Ok(None);
+                };
+                if let Some(proxy) = verifier.find_operator_proxy(&v.static_type(&host), self.operator)? {
+                    return verifier.verify_proxy_call(exp, &proxy, &[]);
+                }
+                Ok(Some(ConstantFolding(&host).fold_unary(self.operator, &v).unwrap_or_else(|| host.factory().create_value(&v.static_type(&host)))))
+            },
+            Operator::Positive | Operator::BitwiseNot => {
+                let Some(v) = verifier.verify_expression(&self.expression, &ExpressionVerifyContext {
+                    context_type: context.context_type.clone(),
+                    ..default()
+                })? else {
+                    return Ok(None);
+                };
+                if let Some(proxy) = verifier.find_operator_proxy(&v.static_type(&host), self.operator)? {
+                    return verifier.verify_proxy_call(exp, &proxy, &[]);
+                }
+                Ok(Some(ConstantFolding(&host).fold_unary(self.operator, &v).unwrap_or_else(|| host.factory().create_value(&v.static_type(&host)))))
+            },
+            Operator::PreIncrement | Operator::PreDecrement | Operator::PostIncrement | Operator::PostDecrement => {
+                let v = verifier.verify_expression(&self.expression, &ExpressionVerifyContext {
+                    mode: VerifyMode::Write,
+                    ..default()
+                })?;
+                Ok(v.map(|v| host.factory().create_value(&v.static_type(&host))))
+            },
+            _ => {
+                let v = verifier.verify_expression(&self.expression, &default())?;
+                Ok(v.map(|_| host.factory().create_value(&host.any_type())))
+            },
+        }
+    }
+
+    fn is_numeric_literal(exp: &Rc<Expression>) -> bool {
+        match exp.as_ref() {
+            Expression::NumericLiteral(_) => true,
+            Expression::Paren(paren_exp) => Self::is_numeric_literal(&paren_exp.expression),
+            _ => false,
+        }
+    }
+}
@@ -7,4 +7,41 @@ pub struct UsePackageDirective {
     pub alias: Option<(String, Location)>,
     pub package_name: Vec<(String, Location)>,
     pub import_specifier: ImportSpecifier,
-}
\ No newline at end of file
+}
+impl UsePackageDirective {
+    /// Verifies the directive, re-exporting properties from another
+    /// package in the enclosing package.
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier) {
+        if !verifier.scope.is_package_scope() {
+            return;
+        }
+        let enclosing_package = verifier.scope.package();
+        let package_name: Vec<String> = self.package_name.iter().map(|name| name.0.clone()).collect();
+        let Some(package) = verifier.lookup_package(&package_name) else {
+            let location = self.package_name.first().unwrap().1.combine_with(self.package_name.last().unwrap().1.clone());
+            verifier.add_verify_error(&location, DiagnosticKind::UndefinedPackage, diagnostic_arguments![String(package_name.join("."))]);
+            return;
+        };
+        match &self.import_specifier {
+            ImportSpecifier::Wildcard(_) => {
+                if !enclosing_package.use_packages().includes(&package) {
+                    enclosing_package.use_packages().push(package);
+                }
+            },
+            ImportSpecifier::Identifier((name, location)) => {
+                let Some(property) = package.properties(&verifier.host).get(name) else {
+                    verifier.add_verify_error(location, DiagnosticKind::UndefinedProperty, diagnostic_arguments![String(name.clone())]);
+                    return;
+                };
+                let alias_name = self.alias.as_ref().map(|alias| alias.0.clone()).unwrap_or(name.clone());
+                if enclosing_package.properties(&verifier.host).get(&alias_name).is_some() {
+                    return;
+                }
+                let alias = verifier.host.factory().create_alias(alias_name.clone(), &property);
+                alias.set_parent(Some(&enclosing_package));
+                alias.set_visibility(Visibility::Public);
+                enclosing_package.properties(&verifier.host).set(alias_name, alias);
+            },
+        }
+    }
+}
@@ -33,4 +33,119 @@ impl VariableBinding {
     pub fn location(&self) -> Location {
         self.initializer.as_ref().map_or(self.destructuring.location.clone(), |init| self.destructuring.location.combine_with(init.location()))
     }
-}
\ No newline at end of file
+}
+impl VariableDefinition {
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier, directive: &Rc<Directive>, phase: VerifierPhase) -> Result<(), DeferVerificationError> {
+        let read_only = self.kind.0 == VariableDefinitionKind::Const;
+        let is_static = Attribute::find_static(&self.attributes).is_some();
+
+        // Enumeration members are declared as static constants whose
+        // type and value are assigned by the enumeration definition.
+        let is_enum_member = !is_static && verifier.scope.is_enum_scope();
+
+        let (properties, parent) = verifier.definition_target(is_static || is_enum_member);
+        match phase {
+            VerifierPhase::Declarations => {
+                if !verifier.ast_to_symbol.has(directive) {
+                    let symbol = verifier.host.factory().create_variable_definition_directive();
+                    verifier.ast_to_symbol.set(directive, Some(symbol));
+                }
+                let visibility = if is_enum_member {
+                    Visibility::Public
+                } else {
+                    Attribute::visibility(&self.attributes, parent.is_interface_type())
+                };
+                for binding in &self.bindings {
+                    binding.destructuring.declare(verifier, read_only, &properties, &parent, visibility, self.jetdoc.clone());
+                }
+            },
+            VerifierPhase::Signatures if !is_enum_member => {
+                for binding in &self.bindings {
+                    binding.verify_type(verifier, read_only, &properties)?;
+                }
+            },
+            VerifierPhase::Bodies if !is_enum_member => {
+                for binding in &self.bindings {
+                    binding.verify_initializer(verifier, read_only)?;
+                }
+            },
+            _ => {},
+        }
+        Ok(())
+    }
+}
+
+impl SimpleVariableDefinition {
+    /// Verifies the definition through all phases, declaring
+    /// variables in the current scope.
+    pub(crate) fn verify(self: &Rc<Self>, verifier: &mut VerifierVerifier) -> Result<(), DeferVerificationError> {
+        let read_only = self.kind.0 == VariableDefinitionKind::Const;
+        let (properties, parent) = verifier.definition_target(false);
+        if !verifier.ast_to_symbol.has(self) {
+            let symbol = verifier.host.factory().create_variable_definition_directive();
+            verifier.ast_to_symbol.set(self, Some(symbol));
+        }
+        for binding in &self.bindings {
+            binding.destructuring.declare(verifier, read_only, &properties, &parent, Visibility::Internal, None);
+        }
+        for binding in &self.bindings {
+            binding.verify_type(verifier, read_only, &properties)?;
+        }
+        for binding in &self.bindings {
+            binding.verify_initializer(verifier, read_only)?;
+        }
+        Ok(())
+    }
+}
+
+impl VariableBinding {
+    /// Assigns the static type of the declared variables, either from
+    /// the type annotation or from the initializer.
+    pub(crate) fn verify_type(&self, verifier: &mut VerifierVerifier, read_only: bool, properties: &SharedMap<String, Symbol>) -> Result<(), DeferVerificationError> {
+        let static_type = if let Some(type_annotation) = self.destructuring.type_annotation.as_ref() {
+            verifier.verify_type_expression(type_annotation)?.unwrap_or(verifier.host.any_type())
+        } else if let Some(initializer) = self.initializer.as_ref() {
+            let v = verifier.verify_expression(initializer, &default())?;
+            if let Some(v) = v.as_ref() {
+                self.assign_constant_initializer(verifier, read_only, v);
+            }
+            v.map(|v| v.static_type(&verifier.host)).unwrap_or(verifier.host.any_type())
+        } else {
+            verifier.host.any_type()
+        };
+        self.destructuring.verify_type(verifier, &static_type, properties)
+    }
+
+    pub(crate) fn verify_initializer(&self, verifier: &mut VerifierVerifier, read_only: bool) -> Result<(), DeferVerificationError> {
+        let Some(initializer) = self.initializer.as_ref() else {
+            return Ok(());
+        };
+        let static_type = if let Some(type_annotation) = self.destructuring.type_annotation.as_ref() {
+            verifier.verify_type_expression(type_annotation)?
+        } else {
+            None
+        };
+        let v = if let Some(static_type) = static_type {
+            verifier.limit_expression_type(initializer, &static_type)?
+        } else {
+            verifier.verify_expression(initializer, &default())?
+        };
+
+        if let Some(v) = v.as_ref() {
+            self.assign_constant_initializer(verifier, read_only, v);
+        }
+        Ok(())
+    }
+
+    /// Assigns a compile-time constant to a `const` variable.
+    fn assign_constant_initializer(&self, verifier: &mut VerifierVerifier, read_only: bool, value: &Symbol) {
+        if !(read_only && value.is_constant()) {
+            return;
+        }
+        if let Some(variable) = verifier.ast_to_symbol.get(&self.destructuring.destructuring) {
+            if variable.is_variable_property() && variable.constant_initializer().is_none() {
+                variable.set_constant_initializer(Some(value));
+            }
+        }
+    }
+}
@@ -0,0 +1,935 @@
+use crate::ns::*;
+
+/// Read-only traversal of the tree.
+///
+/// Each `visit_` method is called for a node of the corresponding type and
+/// by default walks into the children of the node through the corresponding
+/// `walk_` method. An implementor overrides the `visit_` methods of the nodes
+/// it is interested in, calling the `walk_` method to keep traversing
+/// into the children of the node.
+///
+/// # Example
+///
+/// ```
+/// use hydroper_jet_compiler::ns::*;
+///
+/// struct CallCounter(usize);
+///
+/// impl Visitor for CallCounter {
+///     fn visit_call_expression(&mut self, node: &CallExpression) {
+///         self.0 += 1;
+///         self.walk_call_expression(node);
+///     }
+/// }
+///
+/// let compilation_unit = CompilationUnit::new(None, "f(g(), function() { h() })".into(), &CompilerOptions::new());
+/// let program = ParserFacade::parse_program(&compilation_unit).unwrap();
+/// let mut counter = CallCounter(0);
+/// counter.visit_program(&program);
+/// assert_eq!(counter.0, 3);
+/// ```
+pub trait Visitor {
+    // Programs and definitions
+
+    fn visit_program(&mut self, node: &Program) {
+        self.walk_program(node);
+    }
+
+    fn walk_program(&mut self, node: &Program) {
+        for package in &node.packages {
+            self.visit_package_definition(package);
+        }
+        for directive in &node.directives {
+            self.visit_directive(directive);
+        }
+    }
+
+    fn visit_package_definition(&mut self, node: &PackageDefinition) {
+        self.walk_package_definition(node);
+    }
+
+    fn walk_package_definition(&mut self, node: &PackageDefinition) {
+        if let Some(jetdoc) = &node.jetdoc {
+            self.visit_jetdoc(jetdoc);
+        }
+        self.visit_block(&node.block);
+    }
+
+    fn visit_directive(&mut self, node: &Rc<Directive>) {
+        self.walk_directive(node);
+    }
+
+    fn walk_directive(&mut self, node: &Rc<Directive>) {
+        match node.as_ref() {
+            Directive::EmptyStatement(d) => self.visit_empty_statement(d),
+            Directive::ExpressionStatement(d) => self.visit_expression_statement(d),
+            Directive::SuperStatement(d) => self.visit_super_statement(d),
+            Directive::Block(d) => self.visit_block(d),
+            Directive::LabeledStatement(d) => self.visit_labeled_statement(d),
+            Directive::IfStatement(d) => self.visit_if_statement(d),
+            Directive::SwitchStatement(d) => self.visit_switch_statement(d),
+            Directive::SwitchTypeStatement(d) => self.visit_switch_type_statement(d),
+            Directive::DoStatement(d) => self.visit_do_statement(d),
+            Directive::WhileStatement(d) => self.visit_while_statement(d),
+            Directive::ForStatement(d) => self.visit_for_statement(d),
+            Directive::ForInStatement(d) => self.visit_for_in_statement(d),
+            Directive::BreakStatement(d) => self.visit_break_statement(d),
+            Directive::ContinueStatement(d) => self.visit_continue_statement(d),
+            Directive::WithStatement(d) => self.visit_with_statement(d),
+            Directive::ReturnStatement(d) => self.visit_return_statement(d),
+            Directive::ThrowStatement(d) => self.visit_throw_statement(d),
+            Directive::DefaultXmlNamespaceStatement(d) => self.visit_default_xml_namespace_statement(d),
+            Directive::TryStatement(d) => self.visit_try_statement(d),
+            Directive::InvalidatedDirective(d) => self.visit_invalidated_directive(d),
+            Directive::ConfigurationDirective(d) => self.visit_configuration_directive(d),
+            Directive::ImportDirective(d) => self.visit_import_directive(d),
+            Directive::UsePackageDirective(d) => self.visit_use_package_directive(d),
+            Directive::VariableDefinition(d) => self.visit_variable_definition(d),
+            Directive::FunctionDefinition(d) => self.visit_function_definition(d),
+            Directive::ClassDefinition(d) => self.visit_class_definition(d),
+            Directive::EnumDefinition(d) => self.visit_enum_definition(d),
+            Directive::InterfaceDefinition(d) => self.visit_interface_definition(d),
+            Directive::TypeDefinition(d) => self.visit_type_definition(d),
+        }
+    }
+
+    fn visit_variable_definition(&mut self, node: &VariableDefinition) {
+        self.walk_variable_definition(node);
+    }
+
+    fn walk_variable_definition(&mut self, node: &VariableDefinition) {
+        if let Some(jetdoc) = &node.jetdoc {
+            self.visit_jetdoc(jetdoc);
+        }
+        for attribute in &node.attributes {
+            self.visit_attribute(attribute);
+        }
+        for binding in &node.bindings {
+            self.visit_variable_binding(binding);
+        }
+    }
+
+    fn visit_simple_variable_definition(&mut self, node: &SimpleVariableDefinition) {
+        self.walk_simple_variable_definition(node);
+    }
+
+    fn walk_simple_variable_definition(&mut self, node: &SimpleVariableDefinition) {
+        for binding in &node.bindings {
+            self.visit_variable_binding(binding);
+        }
+    }
+
+    fn visit_variable_binding(&mut self, node: &VariableBinding) {
+        self.walk_variable_binding(node);
+    }
+
+    fn walk_variable_binding(&mut self, node: &VariableBinding) {
+        self.visit_typed_destructuring(&node.destructuring);
+        if let Some(initializer) = &node.initializer {
+            self.visit_expression(initializer);
+        }
+    }
+
+    fn visit_function_definition(&mut self, node: &FunctionDefinition) {
+        self.walk_function_definition(node);
+    }
+
+    fn walk_function_definition(&mut self, node: &FunctionDefinition) {
+        if let Some(jetdoc) = &node.jetdoc {
+            self.visit_jetdoc(jetdoc);
+        }
+        for attribute in &node.attributes {
+            self.visit_attribute(attribute);
+        }
+        self.visit_function_common(&node.common);
+    }
+
+    fn visit_function_common(&mut self, node: &FunctionCommon) {
+        self.walk_function_common(node);
+    }
+
+    fn walk_function_common(&mut self, node: &FunctionCommon) {
+        self.visit_function_signature(&node.signature);
+        match &node.body {
+            Some(FunctionBody::Expression(body)) => self.visit_expression(body),
+            Some(FunctionBody::Block(body)) => self.visit_block(body),
+            None => {},
+        }
+    }
+
+    fn visit_function_signature(&mut self, node: &FunctionSignature) {
+        self.walk_function_signature(node);
+    }
+
+    fn walk_function_signature(&mut self, node: &FunctionSignature) {
+        for parameter in &node.parameters {
+            self.visit_parameter(parameter);
+        }
+        if let Some(result_type) = &node.result_type {
+            self.visit_expression(result_type);
+        }
+    }
+
+    fn visit_parameter(&mut self, node: &Parameter) {
+        self.walk_parameter(node);
+    }
+
+    fn walk_parameter(&mut self, node: &Parameter) {
+        self.visit_typed_destructuring(&node.destructuring);
+        if let Some(default_value) = &node.default_value {
+            self.visit_expression(default_value);
+        }
+    }
+
+    fn visit_class_definition(&mut self, node: &ClassDefinition) {
+        self.walk_class_definition(node);
+    }
+
+    fn walk_class_definition(&mut self, node: &ClassDefinition) {
+        if let Some(jetdoc) = &node.jetdoc {
+            self.visit_jetdoc(jetdoc);
+        }
+        for attribute in &node.attributes {
+            self.visit_attribute(attribute);
+        }
+        for type_parameter in node.type_parameters.iter().flatten() {
+            self.visit_type_parameter(type_parameter);
+        }
+        if let Some(extends_clause) = &node.extends_clause {
+            self.visit_expression(extends_clause);
+        }
+        for implemented in node.implements_clause.iter().flatten() {
+            self.visit_expression(implemented);
+        }
+        self.visit_block(&node.block);
+    }
+
+    fn visit_enum_definition(&mut self, node: &EnumDefinition) {
+        self.walk_enum_definition(node);
+    }
+
+    fn walk_enum_definition(&mut self, node: &EnumDefinition) {
+        if let Some(jetdoc) = &node.jetdoc {
+            self.visit_jetdoc(jetdoc);
+        }
+        for attribute in &node.attributes {
+            self.visit_attribute(attribute);
+        }
+        if let Some(as_clause) = &node.as_clause {
+            self.visit_expression(as_clause);
+        }
+        self.visit_block(&node.block);
+    }
+
+    fn visit_interface_definition(&mut self, node: &InterfaceDefinition) {
+        self.walk_interface_definition(node);
+    }
+
+    fn walk_interface_definition(&mut self, node: &InterfaceDefinition) {
+        if let Some(jetdoc) = &node.jetdoc {
+            self.visit_jetdoc(jetdoc);
+        }
+        for attribute in &node.attributes {
+            self.visit_attribute(attribute);
+        }
+        for type_parameter in node.type_parameters.iter().flatten() {
+            self.visit_type_parameter(type_parameter);
+        }
+        for extended in node.extends_clause.iter().flatten() {
+            self.visit_expression(extended);
+        }
+        self.visit_block(&node.block);
+    }
+
+    fn visit_type_definition(&mut self, node: &TypeDefinition) {
+        self.walk_type_definition(node);
+    }
+
+    fn walk_type_definition(&mut self, node: &TypeDefinition) {
+        if let Some(jetdoc) = &node.jetdoc {
+            self.visit_jetdoc(jetdoc);
+        }
+        for attribute in &node.attributes {
+            self.visit_attribute(attribute);
+        }
+        self.visit_expression(&node.right);
+    }
+
+    fn visit_type_parameter(&mut self, node: &TypeParameter) {
+        self.walk_type_parameter(node);
+    }
+
+    fn walk_type_parameter(&mut self, node: &TypeParameter) {
+        if let Some(extends_clause) = &node.extends_clause {
+            self.visit_expression(extends_clause);
+        }
+        for implemented in node.implements_clause.iter().flatten() {
+            self.visit_expression(implemented);
+        }
+    }
+
+    // Attributes and documentation
+
+    fn visit_attribute(&mut self, node: &Attribute) {
+        self.walk_attribute(node);
+    }
+
+    fn walk_attribute(&mut self, node: &Attribute) {
+        if let Attribute::Metadata(metadata) = node {
+            self.visit_metadata(metadata);
+        }
+    }
+
+    fn visit_metadata(&mut self, _node: &UnprocessedMetadata) {}
+
+    fn visit_jetdoc(&mut self, node: &JetDoc) {
+        self.walk_jetdoc(node);
+    }
+
+    fn walk_jetdoc(&mut self, node: &JetDoc) {
+        for (tag, location) in &node.tags {
+            self.visit_jetdoc_tag(tag, location);
+        }
+    }
+
+    fn visit_jetdoc_tag(&mut self, node: &JetDocTag, _location: &Location) {
+        self.walk_jetdoc_tag(node);
+    }
+
+    fn walk_jetdoc_tag(&mut self, node: &JetDocTag) {
+        match node {
+            JetDocTag::EventType(expression) => self.visit_expression(expression),
+            JetDocTag::See { reference, .. } => {
+                if let Some(base) = &reference.base {
+                    self.visit_expression(base);
+                }
+            },
+            JetDocTag::Throws { class_reference, .. } => self.visit_expression(class_reference),
+            _ => {},
+        }
+    }
+
+    // Statements
+
+    fn visit_empty_statement(&mut self, _node: &EmptyStatement) {}
+
+    fn visit_expression_statement(&mut self, node: &ExpressionStatement) {
+        self.walk_expression_statement(node);
+    }
+
+    fn walk_expression_statement(&mut self, node: &ExpressionStatement) {
+        self.visit_expression(&node.expression);
+    }
+
+    fn visit_super_statement(&mut self, node: &SuperStatement) {
+        self.walk_super_statement(node);
+    }
+
+    fn walk_super_statement(&mut self, node: &SuperStatement) {
+        for argument in &node.arguments {
+            self.visit_expression(argument);
+        }
+    }
+
+    fn visit_block(&mut self, node: &Block) {
+        self.walk_block(node);
+    }
+
+    fn walk_block(&mut self, node: &Block) {
+        for attribute in node.metadata.iter().flatten() {
+            self.visit_attribute(attribute);
+        }
+        for directive in &node.directives {
+            self.visit_directive(directive);
+        }
+    }
+
+    fn visit_labeled_statement(&mut self, node: &LabeledStatement) {
+        self.walk_labeled_statement(node);
+    }
+
+    fn walk_labeled_statement(&mut self, node: &LabeledStatement) {
+        self.visit_directive(&node.substatement);
+    }
+
+    fn visit_if_statement(&mut self, node: &IfStatement) {
+        self.walk_if_statement(node);
+    }
+
+    fn walk_if_statement(&mut self, node: &IfStatement) {
+        self.visit_expression(&node.test);
+        self.visit_directive(&node.consequent);
+        if let Some(alternative) = &node.alternative {
+            self.visit_directive(alternative);
+        }
+    }
+
+    fn visit_switch_statement(&mut self, node: &SwitchStatement) {
+        self.walk_switch_statement(node);
+    }
+
+    fn walk_switch_statement(&mut self, node: &SwitchStatement) {
+        self.visit_expression(&node.discriminant);
+        for case in &node.cases {
+            self.visit_case(case);
+        }
+    }
+
+    fn visit_case(&mut self, node: &Case) {
+        self.walk_case(node);
+    }
+
+    fn walk_case(&mut self, node: &Case) {
+        for label in &node.labels {
+            if let CaseLabel::Case((expression, _)) = label {
+                self.visit_expression(expression);
+            }
+        }
+        for directive in &node.directives {
+            self.visit_directive(directive);
+        }
+    }
+
+    fn visit_switch_type_statement(&mut self, node: &SwitchTypeStatement) {
+        self.walk_switch_type_statement(node);
+    }
+
+    fn walk_switch_type_statement(&mut self, node: &SwitchTypeStatement) {
+        self.visit_expression(&node.discriminant);
+        for case in &node.cases {
+            self.visit_type_case(case);
+        }
+    }
+
+    fn visit_type_case(&mut self, node: &TypeCase) {
+        self.walk_type_case(node);
+    }
+
+    fn walk_type_case(&mut self, node: &TypeCase) {
+        if let Some(parameter) = &node.parameter {
+            self.visit_typed_destructuring(parameter);
+        }
+        self.visit_block(&node.block);
+    }
+
+    fn visit_do_statement(&mut self, node: &DoStatement) {
+        self.walk_do_statement(node);
+    }
+
+    fn walk_do_statement(&mut self, node: &DoStatement) {
+        self.visit_directive(&node.body);
+        self.visit_expression(&node.test);
+    }
+
+    fn visit_while_statement(&mut self, node: &WhileStatement) {
+        self.walk_while_statement(node);
+    }
+
+    fn walk_while_statement(&mut self, node: &WhileStatement) {
+        self.visit_expression(&node.test);
+        self.visit_directive(&node.body);
+    }
+
+    fn visit_for_statement(&mut self, node: &ForStatement) {
+        self.walk_for_statement(node);
+    }
+
+    fn walk_for_statement(&mut self, node: &ForStatement) {
+        match &node.init {
+            Some(ForInitializer::Expression(init)) => self.visit_expression(init),
+            Some(ForInitializer::VariableDefinition(init)) => self.visit_simple_variable_definition(init),
+            None => {},
+        }
+        if let Some(test) = &node.test {
+            self.visit_expression(test);
+        }
+        if let Some(update) = &node.update {
+            self.visit_expression(update);
+        }
+        self.visit_directive(&node.body);
+    }
+
+    fn visit_for_in_statement(&mut self, node: &ForInStatement) {
+        self.walk_for_in_statement(node);
+    }
+
+    fn walk_for_in_statement(&mut self, node: &ForInStatement) {
+        match &node.left {
+            ForInBinding::Expression(left) => self.visit_expression(left),
+            ForInBinding::VariableDefinition(left) => self.visit_simple_variable_definition(left),
+        }
+        self.visit_expression(&node.right);
+        self.visit_directive(&node.body);
+    }
+
+    fn visit_break_statement(&mut self, _node: &BreakStatement) {}
+
+    fn visit_continue_statement(&mut self, _node: &ContinueStatement) {}
+
+    fn visit_with_statement(&mut self, node: &WithStatement) {
+        self.walk_with_statement(node);
+    }
+
+    fn walk_with_statement(&mut self, node: &WithStatement) {
+        self.visit_expression(&node.object);
+        self.visit_directive(&node.body);
+    }
+
+    fn visit_return_statement(&mut self, node: &ReturnStatement) {
+        self.walk_return_statement(node);
+    }
+
+    fn walk_return_statement(&mut self, node: &ReturnStatement) {
+        if let Some(expression) = &node.expression {
+            self.visit_expression(expression);
+        }
+    }
+
+    fn visit_throw_statement(&mut self, node: &ThrowStatement) {
+        self.walk_throw_statement(node);
+    }
+
+    fn walk_throw_statement(&mut self, node: &ThrowStatement) {
+        self.visit_expression(&node.expression);
+    }
+
+    fn visit_default_xml_namespace_statement(&mut self, node: &DefaultXmlNamespaceStatement) {
+        self.walk_default_xml_namespace_statement(node);
+    }
+
+    fn walk_default_xml_namespace_statement(&mut self, node: &DefaultXmlNamespaceStatement) {
+        self.visit_expression(&node.right);
+    }
+
+    fn visit_try_statement(&mut self, node: &TryStatement) {
+        self.walk_try_statement(node);
+    }
+
+    fn walk_try_statement(&mut self, node: &TryStatement) {
+        self.visit_block(&node.block);
+        for catch_clause in &node.catch_clauses {
+            self.visit_catch_clause(catch_clause);
+        }
+        if let Some(finally_clause) = &node.finally_clause {
+            self.visit_block(&finally_clause.block);
+        }
+    }
+
+    fn visit_catch_clause(&mut self, node: &CatchClause) {
+        self.walk_catch_clause(node);
+    }
+
+    fn walk_catch_clause(&mut self, node: &CatchClause) {
+        self.visit_typed_destructuring(&node.parameter);
+        self.visit_block(&node.block);
+    }
+
+    fn visit_invalidated_directive(&mut self, _node: &InvalidatedDirective) {}
+
+    fn visit_configuration_directive(&mut self, node: &ConfigurationDirective) {
+        self.walk_configuration_directive(node);
+    }
+
+    fn walk_configuration_directive(&mut self, node: &ConfigurationDirective) {
+        self.visit_directive(&node.directive);
+    }
+
+    fn visit_import_directive(&mut self, _node: &ImportDirective) {}
+
+    fn visit_use_package_directive(&mut self, _node: &UsePackageDirective) {}
+
+    // Destructuring
+
+    fn visit_typed_destructuring(&mut self, node: &TypedDestructuring) {
+        self.walk_typed_destructuring(node);
+    }
+
+    fn walk_typed_destructuring(&mut self, node: &TypedDestructuring) {
+        self.visit_expression(&node.destructuring);
+        if let Some(type_annotation) = &node.type_annotation {
+            self.visit_expression(type_annotation);
+        }
+    }
+
+    // Expressions
+
+    fn visit_expression(&mut self, node: &Rc<Expression>) {
+        self.walk_expression(node);
+    }
+
+    fn walk_expression(&mut self, node: &Rc<Expression>) {
+        match node.as_ref() {
+            Expression::QualifiedIdentifier(e) => self.visit_qualified_identifier(e),
+            Expression::Embed(e) => self.visit_embed_expression(e),
+            Expression::Paren(e) => self.visit_paren_expression(e),
+            Expression::NullLiteral(e) => self.visit_null_literal(e),
+            Expression::BooleanLiteral(e) => self.visit_boolean_literal(e),
+            Expression::NumericLiteral(e) => self.visit_numeric_literal(e),
+            Expression::StringLiteral(e) => self.visit_string_literal(e),
+            Expression::ThisLiteral(e) => self.visit_this_literal(e),
+            Expression::RegExpLiteral(e) => self.visit_regexp_literal(e),
+            Expression::Xml(e) => self.visit_xml_expression(e),
+            Expression::XmlMarkup(e) => self.visit_xml_markup_expression(e),
+            Expression::XmlList(e) => self.visit_xml_list_expression(e),
+            Expression::ArrayLiteral(e) => self.visit_array_literal(e),
+            Expression::ObjectInitializer(e) => self.visit_object_initializer(e),
+            Expression::Function(e) => self.visit_function_expression(e),
+            Expression::ImportMeta(e) => self.visit_import_meta(e),
+            Expression::New(e) => self.visit_new_expression(e),
+            Expression::Member(e) => self.visit_member_expression(e),
+            Expression::ComputedMember(e) => self.visit_computed_member_expression(e),
+            Expression::Descendants(e) => self.visit_descendants_expression(e),
+            Expression::Filter(e) => self.visit_filter_expression(e),
+            Expression::Super(e) => self.visit_super_expression(e),
+            Expression::Call(e) => self.visit_call_expression(e),
+            Expression::WithTypeArguments(e) => self.visit_expression_with_type_arguments(e),
+            Expression::Unary(e) => self.visit_unary_expression(e),
+            Expression::OptionalChaining(e) => self.visit_optional_chaining_expression(e),
+            Expression::OptionalChainingPlaceholder(e) => self.visit_optional_chaining_placeholder(e),
+            Expression::Binary(e) => self.visit_binary_expression(e),
+            Expression::Conditional(e) => self.visit_conditional_expression(e),
+            Expression::Assignment(e) => self.visit_assignment_expression(e),
+            Expression::Sequence(e) => self.visit_sequence_expression(e),
+            Expression::NullableType(e) => self.visit_nullable_type_expression(e),
+            Expression::NonNullableType(e) => self.visit_non_nullable_type_expression(e),
+            Expression::AnyType(e) => self.visit_any_type_expression(e),
+            Expression::VoidType(e) => self.visit_void_type_expression(e),
+            Expression::ArrayType(e) => self.visit_array_type_expression(e),
+            Expression::TupleType(e) => self.visit_tuple_type_expression(e),
+            Expression::FunctionType(e) => self.visit_function_type_expression(e),
+            Expression::Invalidated(e) => self.visit_invalidated_expression(e),
+        }
+    }
+
+    fn visit_qualified_identifier(&mut self, node: &QualifiedIdentifier) {
+        self.walk_qualified_identifier(node);
+    }
+
+    fn walk_qualified_identifier(&mut self, node: &QualifiedIdentifier) {
+        if let Some(qualifier) = &node.qualifier {
+            self.visit_expression(qualifier);
+        }
+        if let QualifiedIdentifierIdentifier::Brackets(key) = &node.id {
+            self.visit_expression(key);
+        }
+    }
+
+    fn visit_embed_expression(&mut self, node: &EmbedExpression) {
+        self.walk_embed_expression(node);
+    }
+
+    fn walk_embed_expression(&mut self, node: &EmbedExpression) {
+        self.visit_object_initializer(&node.description);
+    }
+
+    fn visit_paren_expression(&mut self, node: &ParenExpression) {
+        self.walk_paren_expression(node);
+    }
+
+    fn walk_paren_expression(&mut self, node: &ParenExpression) {
+        self.visit_expression(&node.expression);
+    }
+
+    fn visit_null_literal(&mut self, _node: &NullLiteral) {}
+
+    fn visit_boolean_literal(&mut self, _node: &BooleanLiteral) {}
+
+    fn visit_numeric_literal(&mut self, _node: &NumericLiteral) {}
+
+    fn visit_string_literal(&mut self, _node: &StringLiteral) {}
+
+    fn visit_this_literal(&mut self, _node: &ThisLiteral) {}
+
+    fn visit_regexp_literal(&mut self, _node: &RegExpLiteral) {}
+
+    fn visit_xml_expression(&mut self, node: &XmlExpression) {
+        self.walk_xml_expression(node);
+    }
+
+    fn walk_xml_expression(&mut self, node: &XmlExpression) {
+        self.visit_xml_element(&node.element);
+    }
+
+    fn visit_xml_markup_expression(&mut self, _node: &XmlMarkupExpression) {}
+
+    fn visit_xml_list_expression(&mut self, node: &XmlListExpression) {
+        self.walk_xml_list_expression(node);
+    }
+
+    fn walk_xml_list_expression(&mut self, node: &XmlListExpression) {
+        for content in &node.content {
+            self.visit_xml_element_content(content);
+        }
+    }
+
+    fn visit_xml_element(&mut self, node: &XmlElement) {
+        self.walk_xml_element(node);
+    }
+
+    fn walk_xml_element(&mut self, node: &XmlElement) {
+        if let XmlTagName::Expression(name) = &node.name {
+            self.visit_expression(name);
+        }
+        for attribute in &node.attributes {
+            if let XmlAttributeValue::Expression(value) = &attribute.value {
+                self.visit_expression(value);
+            }
+        }
+        if let Some(attribute_expression) = &node.attribute_expression {
+            self.visit_expression(attribute_expression);
+        }
+        for content in node.content.iter().flatten() {
+            self.visit_xml_element_content(content);
+        }
+        if let Some(XmlTagName::Expression(name)) = &node.closing_name {
+            self.visit_expression(name);
+        }
+    }
+
+    fn visit_xml_element_content(&mut self, node: &XmlElementContent) {
+        self.walk_xml_element_content(node);
+    }
+
+    fn walk_xml_element_content(&mut self, node: &XmlElementContent) {
+        match node {
+            XmlElementContent::XmlElement(element) => self.visit_xml_element(element),
+            XmlElementContent::Expression(expression) => self.visit_expression(expression),
+            XmlElementContent::XmlText(_) | XmlElementContent::XmlMarkup(_) => {},
+        }
+    }
+
+    fn visit_array_literal(&mut self, node: &ArrayLiteral) {
+        self.walk_array_literal(node);
+    }
+
+    fn walk_array_literal(&mut self, node: &ArrayLiteral) {
+        for element in &node.elements {
+            match element {
+                Element::Expression(expression) | Element::Rest((expression, _)) => self.visit_expression(expression),
+                Element::Elision => {},
+            }
+        }
+    }
+
+    fn visit_object_initializer(&mut self, node: &ObjectInitializer) {
+        self.walk_object_initializer(node);
+    }
+
+    fn walk_object_initializer(&mut self, node: &ObjectInitializer) {
+        for field in &node.fields {
+            self.visit_initializer_field(field);
+        }
+    }
+
+    fn visit_initializer_field(&mut self, node: &InitializerField) {
+        self.walk_initializer_field(node);
+    }
+
+    fn walk_initializer_field(&mut self, node: &InitializerField) {
+        match node {
+            InitializerField::Field { name, value, .. } => {
+                match &name.0 {
+                    FieldName::Brackets(key) | FieldName::StringLiteral(key) | FieldName::NumericLiteral(key) => self.visit_expression(key),
+                    FieldName::Identifier(_) => {},
+                }
+                if let Some(value) = value {
+                    self.visit_expression(value);
+                }
+            },
+            InitializerField::Rest((expression, _)) => self.visit_expression(expression),
+        }
+    }
+
+    fn visit_function_expression(&mut self, node: &FunctionExpression) {
+        self.walk_function_expression(node);
+    }
+
+    fn walk_function_expression(&mut self, node: &FunctionExpression) {
+        self.visit_function_common(&node.common);
+    }
+
+    fn visit_import_meta(&mut self, _node: &ImportMeta) {}
+
+    fn visit_new_expression(&mut self, node: &NewExpression) {
+        self.walk_new_expression(node);
+    }
+
+    fn walk_new_expression(&mut self, node: &NewExpression) {
+        self.visit_expression(&node.base);
+        for argument in node.arguments.iter().flatten() {
+            self.visit_expression(argument);
+        }
+    }
+
+    fn visit_member_expression(&mut self, node: &MemberExpression) {
+        self.walk_member_expression(node);
+    }
+
+    fn walk_member_expression(&mut self, node: &MemberExpression) {
+        self.visit_expression(&node.base);
+        self.visit_qualified_identifier(&node.identifier);
+    }
+
+    fn visit_computed_member_expression(&mut self, node: &ComputedMemberExpression) {
+        self.walk_computed_member_expression(node);
+    }
+
+    fn walk_computed_member_expression(&mut self, node: &ComputedMemberExpression) {
+        self.visit_expression(&node.base);
+        self.visit_expression(&node.key);
+    }
+
+    fn visit_descendants_expression(&mut self, node: &DescendantsExpression) {
+        self.walk_descendants_expression(node);
+    }
+
+    fn walk_descendants_expression(&mut self, node: &DescendantsExpression) {
+        self.visit_expression(&node.base);
+        self.visit_qualified_identifier(&node.identifier);
+    }
+
+    fn visit_filter_expression(&mut self, node: &FilterExpression) {
+        self.walk_filter_expression(node);
+    }
+
+    fn walk_filter_expression(&mut self, node: &FilterExpression) {
+        self.visit_expression(&node.base);
+        self.visit_expression(&node.test);
+    }
+
+    fn visit_super_expression(&mut self, node: &SuperExpression) {
+        self.walk_super_expression(node);
+    }
+
+    fn walk_super_expression(&mut self, node: &SuperExpression) {
+        for argument in node.object.iter().flatten() {
+            self.visit_expression(argument);
+        }
+    }
+
+    fn visit_call_expression(&mut self, node: &CallExpression) {
+        self.walk_call_expression(node);
+    }
+
+    fn walk_call_expression(&mut self, node: &CallExpression) {
+        self.visit_expression(&node.base);
+        for argument in &node.arguments {
+            self.visit_expression(argument);
+        }
+    }
+
+    fn visit_expression_with_type_arguments(&mut self, node: &ExpressionWithTypeArguments) {
+        self.walk_expression_with_type_arguments(node);
+    }
+
+    fn walk_expression_with_type_arguments(&mut self, node: &ExpressionWithTypeArguments) {
+        self.visit_expression(&node.base);
+        for argument in &node.arguments {
+            self.visit_expression(argument);
+        }
+    }
+
+    fn visit_unary_expression(&mut self, node: &UnaryExpression) {
+        self.walk_unary_expression(node);
+    }
+
+    fn walk_unary_expression(&mut self, node: &UnaryExpression) {
+        self.visit_expression(&node.expression);
+    }
+
+    fn visit_optional_chaining_expression(&mut self, node: &OptionalChainingExpression) {
+        self.walk_optional_chaining_expression(node);
+    }
+
+    fn walk_optional_chaining_expression(&mut self, node: &OptionalChainingExpression) {
+        self.visit_expression(&node.base);
+        self.visit_expression(&node.expression);
+    }
+
+    fn visit_optional_chaining_placeholder(&mut self, _node: &OptionalChainingPlaceholder) {}
+
+    fn visit_binary_expression(&mut self, node: &BinaryExpression) {
+        self.walk_binary_expression(node);
+    }
+
+    fn walk_binary_expression(&mut self, node: &BinaryExpression) {
+        self.visit_expression(&node.left);
+        self.visit_expression(&node.right);
+    }
+
+    fn visit_conditional_expression(&mut self, node: &ConditionalExpression) {
+        self.walk_conditional_expression(node);
+    }
+
+    fn walk_conditional_expression(&mut self, node: &ConditionalExpression) {
+        self.visit_expression(&node.test);
+        self.visit_expression(&node.consequent);
+        self.visit_expression(&node.alternative);
+    }
+
+    fn visit_assignment_expression(&mut self, node: &AssignmentExpression) {
+        self.walk_assignment_expression(node);
+    }
+
+    fn walk_assignment_expression(&mut self, node: &AssignmentExpression) {
+        self.visit_expression(&node.left);
+        self.visit_expression(&node.right);
+    }
+
+    fn visit_sequence_expression(&mut self, node: &SequenceExpression) {
+        self.walk_sequence_expression(node);
+    }
+
+    fn walk_sequence_expression(&mut self, node: &SequenceExpression) {
+        self.visit_expression(&node.left);
+        self.visit_expression(&node.right);
+    }
+
+    fn visit_nullable_type_expression(&mut self, node: &NullableTypeExpression) {
+        self.walk_nullable_type_expression(node);
+    }
+
+    fn walk_nullable_type_expression(&mut self, node: &NullableTypeExpression) {
+        self.visit_expression(&node.base);
+    }
+
+    fn visit_non_nullable_type_expression(&mut self, node: &NonNullableTypeExpression) {
+        self.walk_non_nullable_type_expression(node);
+    }
+
+    fn walk_non_nullable_type_expression(&mut self, node: &NonNullableTypeExpression) {
+        self.visit_expression(&node.base);
+    }
+
+    fn visit_any_type_expression(&mut self, _node: &AnyTypeExpression) {}
+
+    fn visit_void_type_expression(&mut self, _node: &VoidTypeExpression) {}
+
+    fn visit_array_type_expression(&mut self, node: &ArrayTypeExpression) {
+        self.walk_array_type_expression(node);
+    }
+
+    fn walk_array_type_expression(&mut self, node: &ArrayTypeExpression) {
+        self.visit_expression(&node.expression);
+    }
+
+    fn visit_tuple_type_expression(&mut self, node: &TupleTypeExpression) {
+        self.walk_tuple_type_expression(node);
+    }
+
+    fn walk_tuple_type_expression(&mut self, node: &TupleTypeExpression) {
+        for expression in &node.expressions {
+            self.visit_expression(expression);
+        }
+    }
+
+    fn visit_function_type_expression(&mut self, node: &FunctionTypeExpression) {
+        self.walk_function_type_expression(node);
+    }
+
+    fn walk_function_type_expression(&mut self, node: &FunctionTypeExpression) {
+        self.visit_function_signature(&node.signature);
+    }
+
+    fn visit_invalidated_expression(&mut self, _node: &InvalidatedExpression) {}
+}
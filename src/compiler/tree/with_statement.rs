@@ -6,4 +6,25 @@ pub struct WithStatement {
     pub location: Location,
     pub object: Rc<Expression>,
     pub body: Rc<Directive>,
-}
\ No newline at end of file
+}
+
+impl WithStatement {
+    /// Verifies the statement. The directive is assigned the
+    /// `with` scope of the statement.
+    pub(crate) fn verify(&self, verifier: &mut VerifierVerifier, directive: &Rc<Directive>) -> Result<(), DeferVerificationError> {
+        let Some(object) = verifier.verify_expression(&self.object, &default())? else {
+            return Ok(());
+        };
+        let scope = if let Some(scope) = verifier.ast_to_symbol.get(directive) {
+            scope
+        } else {
+            let scope = verifier.host.factory().create_with_scope(&object);
+            verifier.ast_to_symbol.set(directive, Some(scope.clone()));
+            scope
+        };
+        verifier.enter_scope(&scope);
+        verifier.verify_substatement(&self.body);
+        verifier.exit_scope();
+        Ok(())
+    }
+}
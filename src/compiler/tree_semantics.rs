@@ -2,6 +2,10 @@ use crate::ns::*;
 use by_address::ByAddress;
 
 /// Structure that assigns semantic symbols to syntactic nodes.
+///
+/// Besides expressions and directives, programs are assigned their activation scope,
+/// package definitions are assigned their package scope, and function commons
+/// are assigned their function symbol.
 pub struct TreeSemantics {
     compilation_units: RefCell<HashMap<ByAddress<Rc<CompilationUnit>>, TreeSemantics1>>,
 }
@@ -13,6 +17,10 @@ struct TreeSemantics1 {
     blocks: HashMap<AstAsKey<Rc<Block>>, Option<Symbol>>,
     programs: HashMap<AstAsKey<Rc<Program>>, Option<Symbol>>,
     function_commons: HashMap<AstAsKey<Rc<FunctionCommon>>, Option<Symbol>>,
+    package_definitions: HashMap<AstAsKey<Rc<PackageDefinition>>, Option<Symbol>>,
+    narrowed_types: HashMap<AstAsKey<Rc<Expression>>, Symbol>,
+    proxy_calls: HashMap<AstAsKey<Rc<Expression>>, Symbol>,
+    configuration_branches: HashMap<AstAsKey<Rc<Directive>>, Option<Rc<Directive>>>,
 }
 
 impl TreeSemantics1 {
@@ -24,6 +32,10 @@ impl TreeSemantics1 {
             blocks: HashMap::new(),
             programs: HashMap::new(),
             function_commons: HashMap::new(),
+            package_definitions: HashMap::new(),
+            narrowed_types: HashMap::new(),
+            proxy_calls: HashMap::new(),
+            configuration_branches: HashMap::new(),
         }
     }
 }
@@ -34,6 +46,72 @@ impl TreeSemantics {
             compilation_units: RefCell::new(HashMap::new()),
         })
     }
+
+    /// Returns the type that a variable read expression has been narrowed to
+    /// by flow analysis, such as `T` for a `?T` variable after a null check.
+    pub fn narrowed_type(&self, node: &Rc<Expression>) -> Option<Symbol> {
+        let compilation_units = self.compilation_units.borrow();
+        let m1 = compilation_units.get(&ByAddress(node.location().compilation_unit()));
+        if let Some(m1) = m1 {
+            m1.narrowed_types.get(&AstAsKey(node.clone())).cloned()
+        } else {
+            None
+        }
+    }
+
+    pub fn set_narrowed_type(&self, node: &Rc<Expression>, narrowed_type: Option<Symbol>) {
+        let compilation_unit = node.location().compilation_unit();
+        let mut compilation_units = self.compilation_units.borrow_mut();
+        let m1 = compilation_units.entry(ByAddress(compilation_unit)).or_insert_with(TreeSemantics1::new);
+        if let Some(narrowed_type) = narrowed_type {
+            m1.narrowed_types.insert(AstAsKey(node.clone()), narrowed_type);
+        } else {
+            m1.narrowed_types.remove(&AstAsKey(node.clone()));
+        }
+    }
+
+    /// Returns the proxy method that an operator or computed member
+    /// expression calls, such as the `add` proxy for `a + b`.
+    pub fn proxy_call(&self, node: &Rc<Expression>) -> Option<Symbol> {
+        let compilation_units = self.compilation_units.borrow();
+        let m1 = compilation_units.get(&ByAddress(node.location().compilation_unit()));
+        if let Some(m1) = m1 {
+            m1.proxy_calls.get(&AstAsKey(node.clone())).cloned()
+        } else {
+            None
+        }
+    }
+
+    pub fn set_proxy_call(&self, node: &Rc<Expression>, proxy: Option<Symbol>) {
+        let compilation_unit = node.location().compilation_unit();
+        let mut compilation_units = self.compilation_units.borrow_mut();
+        let m1 = compilation_units.entry(ByAddress(compilation_unit)).or_insert_with(TreeSemantics1::new);
+        if let Some(proxy) = proxy {
+            m1.proxy_calls.insert(AstAsKey(node.clone()), proxy);
+        } else {
+            m1.proxy_calls.remove(&AstAsKey(node.clone()));
+        }
+    }
+
+    /// Returns the branch that a `configuration` directive selects, which is
+    /// `Some(None)` if the directive selects no branch, or `None` if
+    /// the directive has not been evaluated.
+    pub fn configuration_branch(&self, node: &Rc<Directive>) -> Option<Option<Rc<Directive>>> {
+        let compilation_units = self.compilation_units.borrow();
+        let m1 = compilation_units.get(&ByAddress(node.location().compilation_unit()));
+        if let Some(m1) = m1 {
+            m1.configuration_branches.get(&AstAsKey(node.clone())).cloned()
+        } else {
+            None
+        }
+    }
+
+    pub fn set_configuration_branch(&self, node: &Rc<Directive>, branch: Option<Rc<Directive>>) {
+        let compilation_unit = node.location().compilation_unit();
+        let mut compilation_units = self.compilation_units.borrow_mut();
+        let m1 = compilation_units.entry(ByAddress(compilation_unit)).or_insert_with(TreeSemantics1::new);
+        m1.configuration_branches.insert(AstAsKey(node.clone()), branch);
+    }
 }
 
 pub trait TreeSemanticsAccessor<T> {
@@ -41,9 +119,9 @@ pub trait TreeSemanticsAccessor<T> {
     fn set(&self, node: &Rc<T>, symbol: Option<Symbol>);
     fn delete(&self, node: &Rc<T>) -> bool;
 
-    fn has(&self, node: &Rc<T>) -> bool {
-        self.get(node).is_some()
-    }
+    /// Indicates whether the node has been assigned, including
+    /// assignments of `None`.
+    fn has(&self, node: &Rc<T>) -> bool;
 }
 
 impl TreeSemanticsAccessor<Expression> for TreeSemantics {
@@ -51,7 +129,7 @@ impl TreeSemanticsAccessor<Expression> for TreeSemantics {
         let compilation_units = self.compilation_units.borrow();
         let m1 = compilation_units.get(&ByAddress(node.location().compilation_unit()));
         if let Some(m1) = m1 {
-            m1.expressions.get(&AstAsKey(node.clone())).map(|v| v.clone().unwrap())
+            m1.expressions.get(&AstAsKey(node.clone())).and_then(|v| v.clone())
         } else {
             None
         }
@@ -80,6 +158,16 @@ impl TreeSemanticsAccessor<Expression> for TreeSemantics {
             false
         }
     }
+
+    fn has(&self, node: &Rc<Expression>) -> bool {
+        let compilation_units = self.compilation_units.borrow();
+        let m1 = compilation_units.get(&ByAddress(node.location().compilation_unit()));
+        if let Some(m1) = m1 {
+            m1.expressions.contains_key(&AstAsKey(node.clone()))
+        } else {
+            false
+        }
+    }
 }
 
 impl TreeSemanticsAccessor<Directive> for TreeSemantics {
@@ -87,7 +175,7 @@ impl TreeSemanticsAccessor<Directive> for TreeSemantics {
         let compilation_units = self.compilation_units.borrow();
         let m1 = compilation_units.get(&ByAddress(node.location().compilation_unit()));
         if let Some(m1) = m1 {
-            m1.directives.get(&AstAsKey(node.clone())).map(|v| v.clone().unwrap())
+            m1.directives.get(&AstAsKey(node.clone())).and_then(|v| v.clone())
         } else {
             None
         }
@@ -116,6 +204,16 @@ impl TreeSemanticsAccessor<Directive> for TreeSemantics {
             false
         }
     }
+
+    fn has(&self, node: &Rc<Directive>) -> bool {
+        let compilation_units = self.compilation_units.borrow();
+        let m1 = compilation_units.get(&ByAddress(node.location().compilation_unit()));
+        if let Some(m1) = m1 {
+            m1.directives.contains_key(&AstAsKey(node.clone()))
+        } else {
+            false
+        }
+    }
 }
 
 impl TreeSemanticsAccessor<SimpleVariableDefinition> for TreeSemantics {
@@ -123,7 +221,7 @@ impl TreeSemanticsAccessor<SimpleVariableDefinition> for TreeSemantics {
         let compilation_units = self.compilation_units.borrow();
         let m1 = compilation_units.get(&ByAddress(node.location.compilation_unit()));
         if let Some(m1) = m1 {
-            m1.simple_variable_definitions.get(&AstAsKey(node.clone())).map(|v| v.clone().unwrap())
+            m1.simple_variable_definitions.get(&AstAsKey(node.clone())).and_then(|v| v.clone())
         } else {
             None
         }
@@ -152,6 +250,16 @@ impl TreeSemanticsAccessor<SimpleVariableDefinition> for TreeSemantics {
             false
         }
     }
+
+    fn has(&self, node: &Rc<SimpleVariableDefinition>) -> bool {
+        let compilation_units = self.compilation_units.borrow();
+        let m1 = compilation_units.get(&ByAddress(node.location.compilation_unit()));
+        if let Some(m1) = m1 {
+            m1.simple_variable_definitions.contains_key(&AstAsKey(node.clone()))
+        } else {
+            false
+        }
+    }
 }
 
 impl TreeSemanticsAccessor<Block> for TreeSemantics {
@@ -159,7 +267,7 @@ impl TreeSemanticsAccessor<Block> for TreeSemantics {
         let compilation_units = self.compilation_units.borrow();
         let m1 = compilation_units.get(&ByAddress(node.location.compilation_unit()));
         if let Some(m1) = m1 {
-            m1.blocks.get(&AstAsKey(node.clone())).map(|v| v.clone().unwrap())
+            m1.blocks.get(&AstAsKey(node.clone())).and_then(|v| v.clone())
         } else {
             None
         }
@@ -188,6 +296,16 @@ impl TreeSemanticsAccessor<Block> for TreeSemantics {
             false
         }
     }
+
+    fn has(&self, node: &Rc<Block>) -> bool {
+        let compilation_units = self.compilation_units.borrow();
+        let m1 = compilation_units.get(&ByAddress(node.location.compilation_unit()));
+        if let Some(m1) = m1 {
+            m1.blocks.contains_key(&AstAsKey(node.clone()))
+        } else {
+            false
+        }
+    }
 }
 
 impl TreeSemanticsAccessor<Program> for TreeSemantics {
@@ -195,7 +313,7 @@ impl TreeSemanticsAccessor<Program> for TreeSemantics {
         let compilation_units = self.compilation_units.borrow();
         let m1 = compilation_units.get(&ByAddress(node.location.compilation_unit()));
         if let Some(m1) = m1 {
-            m1.programs.get(&AstAsKey(node.clone())).map(|v| v.clone().unwrap())
+            m1.programs.get(&AstAsKey(node.clone())).and_then(|v| v.clone())
         } else {
             None
         }
@@ -224,6 +342,16 @@ impl TreeSemanticsAccessor<Program> for TreeSemantics {
             false
         }
     }
+
+    fn has(&self, node: &Rc<Program>) -> bool {
+        let compilation_units = self.compilation_units.borrow();
+        let m1 = compilation_units.get(&ByAddress(node.location.compilation_unit()));
+        if let Some(m1) = m1 {
+            m1.programs.contains_key(&AstAsKey(node.clone()))
+        } else {
+            false
+        }
+    }
 }
 
 impl TreeSemanticsAccessor<FunctionCommon> for TreeSemantics {
@@ -231,7 +359,7 @@ impl TreeSemanticsAccessor<FunctionCommon> for TreeSemantics {
         let compilation_units = self.compilation_units.borrow();
         let m1 = compilation_units.get(&ByAddress(node.location.compilation_unit()));
         if let Some(m1) = m1 {
-            m1.function_commons.get(&AstAsKey(node.clone())).map(|v| v.clone().unwrap())
+            m1.function_commons.get(&AstAsKey(node.clone())).and_then(|v| v.clone())
         } else {
             None
         }
@@ -260,4 +388,60 @@ impl TreeSemanticsAccessor<FunctionCommon> for TreeSemantics {
             false
         }
     }
-}
\ No newline at end of file
+
+    fn has(&self, node: &Rc<FunctionCommon>) -> bool {
+        let compilation_units = self.compilation_units.borrow();
+        let m1 = compilation_units.get(&ByAddress(node.location.compilation_unit()));
+        if let Some(m1) = m1 {
+            m1.function_commons.contains_key(&AstAsKey(node.clone()))
+        } else {
+            false
+        }
+    }
+}
+
+impl TreeSemanticsAccessor<PackageDefinition> for TreeSemantics {
+    fn get(&self, node: &Rc<PackageDefinition>) -> Option<Symbol> {
+        let compilation_units = self.compilation_units.borrow();
+        let m1 = compilation_units.get(&ByAddress(node.location.compilation_unit()));
+        if let Some(m1) = m1 {
+            m1.package_definitions.get(&AstAsKey(node.clone())).and_then(|v| v.clone())
+        } else {
+            None
+        }
+    }
+
+    fn set(&self, node: &Rc<PackageDefinition>, symbol: Option<Symbol>) {
+        let compilation_unit = node.location.compilation_unit();
+        let mut compilation_units = self.compilation_units.borrow_mut();
+        let m1 = compilation_units.get_mut(&ByAddress(compilation_unit.clone()));
+        if let Some(m1) = m1 {
+            m1.package_definitions.insert(AstAsKey(node.clone()), symbol);
+        } else {
+            let mut m1 = TreeSemantics1::new();
+            m1.package_definitions.insert(AstAsKey(node.clone()), symbol);
+            compilation_units.insert(ByAddress(compilation_unit), m1);
+        }
+    }
+
+    fn delete(&self, node: &Rc<PackageDefinition>) -> bool {
+        let compilation_unit = node.location.compilation_unit();
+        let mut compilation_units = self.compilation_units.borrow_mut();
+        let m1 = compilation_units.get_mut(&ByAddress(compilation_unit));
+        if let Some(m1) = m1 {
+            m1.package_definitions.remove(&AstAsKey(node.clone())).is_some()
+        } else {
+            false
+        }
+    }
+
+    fn has(&self, node: &Rc<PackageDefinition>) -> bool {
+        let compilation_units = self.compilation_units.borrow();
+        let m1 = compilation_units.get(&ByAddress(node.location.compilation_unit()));
+        if let Some(m1) = m1 {
+            m1.package_definitions.contains_key(&AstAsKey(node.clone()))
+        } else {
+            false
+        }
+    }
+}
@@ -18,4 +18,14 @@ pub use std::rc::{Rc, Weak};
 
 pub fn default<T: Default>() -> T {
     T::default()
+}
+
+/// 64-bit FNV-1a hash of a byte string.
+pub(crate) fn fnv1a_hash(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
 }
\ No newline at end of file
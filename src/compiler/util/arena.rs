@@ -4,6 +4,12 @@ pub struct Arena<T> {
     data: RefCell<Vec<Rc<T>>>,
 }
 
+impl<T> Default for Arena<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T> Arena<T> {
     pub fn new() -> Self {
         Self {
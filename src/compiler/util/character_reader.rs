@@ -99,7 +99,7 @@ impl<'a> CharacterReader<'a> {
                 break;
             }
         }
-        CharacterReader { length: string.len() - index, char_indices: indices }
+        CharacterReader { length: string.len(), char_indices: indices }
     }
 }
 
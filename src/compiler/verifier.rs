@@ -1,5 +1,9 @@
 use crate::ns::*;
 
+mod flow_analysis;
+use flow_analysis::*;
+mod narrowing;
+
 /// Jet verifier.
 ///
 /// `Verifier` is both a type checker, a symbol solver and strictness verifier,
@@ -25,7 +29,7 @@ use crate::ns::*;
 /// Such methods may alter the `parent_scope()` field of the scope to use the enclosing
 /// scope as the parent.
 ///
-/// ```
+/// ```ignore
 /// verifier.enter_scope(&scope);
 /// verifier.exit_scope();
 /// ```
@@ -51,21 +55,26 @@ impl Verifier {
                 ast_to_symbol: TreeSemantics::new(),
                 deferred_directives: vec![],
                 deferred_function_commons: vec![],
+                verified_function_commons: vec![],
                 invalidated: false,
                 deferred_counter: 0,
                 scope: host.root_scope(),
+                optional_chaining_bases: vec![],
+                defer_location: None,
+                type_argument_checks: vec![],
+                definition_locations: HashMap::new(),
             },
         }
     }
 
     pub fn ast_to_symbol(&self) -> &Rc<TreeSemantics> {
-        &self.verifier.ast_to_symbol
+        self.verifier.ast_to_symbol()
     }
 
     /// Indicates whether a syntax or verify error occurred while
     /// verifying.
     pub fn invalidated(&self) -> bool {
-        self.verifier.invalidated
+        self.verifier.invalidated()
     }
 
     /// # Panics
@@ -76,12 +85,14 @@ impl Verifier {
             panic!("Verifier already invalidated.");
         }
         self.verifier.reset_state();
-
-        todo_here();
+        self.verifier.verify_programs(&programs);
     }
 
     /// Verifies an expression. Returns `None` if verification failed.
     ///
+    /// If the expression refers to a symbol that remains unresolved,
+    /// an unresolved reference is reported.
+    ///
     /// # Panics
     ///
     /// Panics if the verifier is already invalidated before verifying.
@@ -91,7 +102,16 @@ impl Verifier {
         }
         self.verifier.reset_state();
 
-        ()
+        let result = match self.verifier.verify_expression(exp, context) {
+            Ok(result) => result,
+            Err(DeferVerificationError) => {
+                let location = self.verifier.defer_location.take().unwrap_or(exp.location());
+                self.verifier.add_verify_error(&location, DiagnosticKind::UnresolvedReference, diagnostic_arguments![]);
+                None
+            },
+        };
+        self.verifier.check_type_arguments();
+        result
     }
 
     pub fn enter_scope(&mut self, scope: &Symbol) {
@@ -110,9 +130,23 @@ pub(crate) struct VerifierVerifier {
     pub deferred_directives: Vec<(usize, Symbol, Rc<Directive>)>,
     /// List of (phase, scope, common).
     pub deferred_function_commons: Vec<(usize, Symbol, Rc<FunctionCommon>)>,
+    /// List of (activation, common) whose body has been verified,
+    /// pending flow analysis.
+    pub verified_function_commons: Vec<(Symbol, Rc<FunctionCommon>)>,
     invalidated: bool,
     pub deferred_counter: usize,
     pub scope: Symbol,
+    /// Stack of non-null bases used by `OptionalChainingPlaceholder`.
+    pub optional_chaining_bases: Vec<Symbol>,
+    /// Location of the innermost expression whose verification
+    /// was last deferred.
+    pub defer_location: Option<Location>,
+    /// List of (location, type after explicit type substitution) whose
+    /// type arguments are checked against type parameter bounds.
+    pub type_argument_checks: Vec<(Location, Symbol)>,
+    /// Locations of the names of definitions, referenced by
+    /// duplicate definition errors.
+    pub definition_locations: HashMap<Symbol, Location>,
 }
 
 impl VerifierVerifier {
@@ -130,6 +164,10 @@ impl VerifierVerifier {
         self.deferred_counter = 0;
         self.deferred_directives.clear();
         self.deferred_function_commons.clear();
+        self.verified_function_commons.clear();
+        self.type_argument_checks.clear();
+        self.optional_chaining_bases.clear();
+        self.defer_location = None;
     }
 
     pub fn add_syntax_error(&mut self, location: &Location, kind: DiagnosticKind, arguments: Vec<DiagnosticArgument>) {
@@ -142,8 +180,24 @@ impl VerifierVerifier {
         self.invalidated = true;
     }
 
-    pub fn add_warning(&self, location: &Location, kind: DiagnosticKind, arguments: Vec<DiagnosticArgument>) {
-        location.compilation_unit().add_diagnostic(Diagnostic::new_warning(location, kind, arguments));
+    /// Reports a duplicate definition of `name`, labeling the location
+    /// of the existing definition if it is known.
+    pub fn add_duplicate_definition_error(&mut self, location: &Location, name: &str, existing: Option<&Symbol>) {
+        let mut diagnostic = Diagnostic::new_verify_error(location, DiagnosticKind::DuplicateDefinition, diagnostic_arguments![String(name.to_owned())]);
+        if let Some(existing_location) = existing.and_then(|existing| self.definition_locations.get(existing)) {
+            diagnostic = diagnostic.with_label(existing_location, "previously defined here");
+        }
+        location.compilation_unit().add_diagnostic(diagnostic);
+        self.invalidated = true;
+    }
+
+    /// Reports a warning, which invalidates the verifier if the compiler
+    /// options promote it to an error.
+    pub fn add_warning(&mut self, location: &Location, kind: DiagnosticKind, arguments: Vec<DiagnosticArgument>) {
+        if location.compilation_unit().compiler_options().warning_level(kind) == WarningLevel::Error {
+            self.invalidated = true;
+        }
+        location.compilation_unit().add_diagnostic(Diagnostic::new_verify_warning(location, kind, arguments));
     }
 
     pub fn enter_scope(&mut self, scope: &Symbol) {
@@ -158,59 +212,601 @@ impl VerifierVerifier {
         self.scope = self.scope.parent().unwrap();
     }
 
-    pub fn verify_expression(&mut self, exp: &Rc<Expression>, context: &ExpressionVerifyContext) -> Result<Option<Symbol>, DeferVerificationError> {
-        let pre_result = self.ast_to_symbol.get(exp);
-        if let Some(pre_result) = pre_result {
-            return Ok(Some(pre_result));
+    pub fn verify_programs(&mut self, programs: &[Rc<Program>]) {
+        for phase in VerifierPhase::ALL {
+            for program in programs {
+                self.verify_program(program, phase);
+            }
+            self.verify_deferred(phase);
         }
-        let result: Option<Symbol>;
-        match exp.as_ref() {
-            Expression::QualifiedIdentifier(id) => {
-                result = id.verify_as_exp(self, &context)?;
+        self.report_unresolved();
+        self.check_type_arguments();
+        self.analyze_flow(programs);
+    }
+
+    /// Performs flow analysis of the verified function bodies
+    /// and program directives.
+    fn analyze_flow(&mut self, programs: &[Rc<Program>]) {
+        for (activation, common) in std::mem::take(&mut self.verified_function_commons) {
+            FlowAnalysis::new(self, &activation).analyze_function(&common);
+        }
+        for program in programs {
+            if let Some(activation) = self.ast_to_symbol.get(program) {
+                FlowAnalysis::new(self, &activation).analyze_program(program);
+            }
+        }
+    }
+
+    fn verify_program(&mut self, program: &Rc<Program>, phase: VerifierPhase) {
+        for pckg in &program.packages {
+            pckg.verify(self, phase);
+        }
+
+        // The program directives are verified within an activation
+        // whose signature is `function(): void`.
+        let activation = if let Some(activation) = self.ast_to_symbol.get(program) {
+            activation
+        } else {
+            let signature = self.host.factory().create_function_type(vec![], self.host.void_type());
+            let function = self.host.factory().create_function(String::new(), &signature);
+            let activation = self.host.factory().create_activation_scope(&function);
+            function.set_activation_scope(Some(activation.clone()));
+            self.ast_to_symbol.set(program, Some(activation.clone()));
+            activation
+        };
+
+        self.enter_scope(&activation);
+        self.verify_directives(&program.directives, phase);
+        self.exit_scope();
+    }
+
+    /// Verifies a list of directives at a given phase. Directives whose
+    /// verification is deferred are skipped.
+    pub fn verify_directives(&mut self, list: &[Rc<Directive>], phase: VerifierPhase) {
+        let mut narrowing_scopes = 0;
+        for directive in list {
+            if self.deferred_directives.iter().any(|(_, _, d)| Rc::ptr_eq(d, directive)) {
+                continue;
+            }
+            if self.verify_directive(directive, phase).is_err() {
+                self.deferred_directives.push((phase as usize, self.scope.clone(), directive.clone()));
+                continue;
+            }
+
+            // An `if` statement one of whose branches never completes normally
+            // narrows variables for the remaining directives, as in
+            // `if (x == null) return;`.
+            if let (VerifierPhase::Bodies, Directive::IfStatement(stmt)) = (phase, directive.as_ref()) {
+                if let Some(scope) = self.create_narrowing_scope(self.if_statement_narrowings(stmt)) {
+                    self.enter_scope(&scope);
+                    narrowing_scopes += 1;
+                }
+            }
+        }
+        for _ in 0..narrowing_scopes {
+            self.exit_scope();
+        }
+    }
+
+    /// Verifies a list of directives through all phases, such as
+    /// the directives of a block statement.
+    pub fn verify_nested_directives(&mut self, list: &[Rc<Directive>]) {
+        for phase in VerifierPhase::ALL {
+            self.verify_directives(list, phase);
+        }
+    }
+
+    /// Verifies a substatement, such as the body of a loop.
+    pub fn verify_substatement(&mut self, directive: &Rc<Directive>) {
+        self.verify_nested_directives(std::slice::from_ref(directive));
+    }
+
+    pub fn verify_directive(&mut self, directive: &Rc<Directive>, phase: VerifierPhase) -> Result<(), DeferVerificationError> {
+        match directive.as_ref() {
+            Directive::ExpressionStatement(stmt) => {
+                if phase == VerifierPhase::Bodies {
+                    self.verify_expression(&stmt.expression, &default())?;
+                }
+                Ok(())
             },
-            Expression::Embed(emb) => {
-                result = emb.verify(self, &context)?;
+            Directive::SuperStatement(stmt) => {
+                if phase == VerifierPhase::Bodies {
+                    for argument in &stmt.arguments {
+                        self.verify_expression(argument, &default())?;
+                    }
+                }
+                Ok(())
             },
-            Expression::Paren(paren_exp) => {
-                result = self.verify_expression(&paren_exp.expression, &context)?;
+            Directive::Block(block) => {
+                if phase == VerifierPhase::Bodies {
+                    block.verify(self, directive);
+                }
+                Ok(())
             },
-            Expression::NullLiteral(nl) => {
-                result = nl.verify(self, &context)?;
+            Directive::LabeledStatement(stmt) => {
+                self.verify_directive(&stmt.substatement, phase)
             },
-            Expression::BooleanLiteral(bl) => {
-                result = Some(self.host.factory().create_boolean_constant(bl.value, &self.host.boolean_type()));
+            Directive::IfStatement(stmt) => {
+                if phase == VerifierPhase::Bodies {
+                    stmt.verify(self)?;
+                }
+                Ok(())
             },
-            Expression::NumericLiteral(nl) => {
-                result = nl.verify(self, &context)?;
+            Directive::SwitchStatement(stmt) => {
+                if phase == VerifierPhase::Bodies {
+                    stmt.verify(self, directive)?;
+                }
+                Ok(())
             },
-            Expression::StringLiteral(sl) => {
-                result = sl.verify(self, &context)?;
+            Directive::SwitchTypeStatement(stmt) => {
+                if phase == VerifierPhase::Bodies {
+                    stmt.verify(self)?;
+                }
+                Ok(())
             },
-            Expression::ThisLiteral(tl) => {
-                result = tl.verify(self)?;
+            Directive::DoStatement(stmt) => {
+                if phase == VerifierPhase::Bodies {
+                    self.verify_condition(&stmt.test)?;
+                    self.verify_substatement(&stmt.body);
+                }
+                Ok(())
             },
-            Expression::RegExpLiteral(rl) => {
-                result = rl.verify(self)?;
+            Directive::WhileStatement(stmt) => {
+                if phase == VerifierPhase::Bodies {
+                    self.verify_condition(&stmt.test)?;
+                    let narrowings = self.condition_narrowings(&stmt.test, true);
+                    self.with_narrowings(narrowings, |verifier| verifier.verify_substatement(&stmt.body));
+                }
+                Ok(())
             },
-            Expression::Xml(xml) => {
-                xml.element.verify(self)?;
-                result = Some(self.host.factory().create_value(&self.host.xml_type()));
+            Directive::ForStatement(stmt) => {
+                if phase == VerifierPhase::Bodies {
+                    stmt.verify(self, directive)?;
+                }
+                Ok(())
             },
-            Expression::XmlMarkup(xml) => {
-                result = Some(self.host.factory().create_value(&self.host.xml_type()));
+            Directive::ForInStatement(stmt) => {
+                if phase == VerifierPhase::Bodies {
+                    stmt.verify(self, directive)?;
+                }
+                Ok(())
             },
-            Expression::XmlList(xml) => {
-                for content in &xml.content {
-                    content.verify(self)?;
+            Directive::WithStatement(stmt) => {
+                if phase == VerifierPhase::Bodies {
+                    stmt.verify(self, directive)?;
                 }
-                result = Some(self.host.factory().create_value(&self.host.xml_list_type()));
+                Ok(())
             },
-            Expression::ArrayLiteral(al) => {
-                result = al.verify(self, context)?;
+            Directive::ReturnStatement(stmt) => {
+                if phase == VerifierPhase::Bodies {
+                    stmt.verify(self)?;
+                }
+                Ok(())
             },
-            Expression::ObjectInitializer(oi) => {
-                result = oi.verify(self, context)?;
+            Directive::ThrowStatement(stmt) => {
+                if phase == VerifierPhase::Bodies {
+                    self.verify_expression(&stmt.expression, &default())?;
+                }
+                Ok(())
             },
+            Directive::TryStatement(stmt) => {
+                if phase == VerifierPhase::Bodies {
+                    stmt.verify(self)?;
+                }
+                Ok(())
+            },
+            Directive::DefaultXmlNamespaceStatement(stmt) => {
+                if phase == VerifierPhase::Bodies {
+                    self.limit_expression_type(&stmt.right, &self.host.namespace_type())?;
+                }
+                Ok(())
+            },
+            Directive::ImportDirective(impdrtv) => {
+                if phase == VerifierPhase::Imports {
+                    impdrtv.verify(self);
+                }
+                Ok(())
+            },
+            Directive::UsePackageDirective(usepkg) => {
+                if phase == VerifierPhase::Imports {
+                    usepkg.verify(self);
+                }
+                Ok(())
+            },
+            Directive::VariableDefinition(defn) => defn.verify(self, directive, phase),
+            Directive::FunctionDefinition(defn) => defn.verify(self, phase),
+            Directive::ClassDefinition(defn) => defn.verify(self, directive, phase),
+            Directive::EnumDefinition(defn) => defn.verify(self, directive, phase),
+            Directive::InterfaceDefinition(defn) => defn.verify(self, directive, phase),
+            Directive::TypeDefinition(defn) => defn.verify(self, directive, phase),
+            Directive::ConfigurationDirective(cfgdrtv) => cfgdrtv.verify(self, directive, phase),
+            _ => Ok(()),
+        }
+    }
+
+    /// Verifies the body of a function whose activation scope
+    /// is the current scope.
+    pub fn verify_function_body(&mut self, common: &Rc<FunctionCommon>) -> Result<(), DeferVerificationError> {
+        common.verify_body(self)
+    }
+
+    /// Re-verifies deferred directives and function bodies until
+    /// no further progress is made.
+    fn verify_deferred(&mut self, target_phase: VerifierPhase) {
+        loop {
+            let mut progressed = false;
+
+            for (phase, scope, directive) in std::mem::take(&mut self.deferred_directives) {
+                let k = std::mem::replace(&mut self.scope, scope.clone());
+                let mut phase_1 = phase;
+                let mut failed = false;
+                while phase_1 <= target_phase as usize {
+                    if self.verify_directive(&directive, VerifierPhase::ALL[phase_1]).is_err() {
+                        failed = true;
+                        break;
+                    }
+                    phase_1 += 1;
+                }
+                self.scope = k;
+                if failed {
+                    self.deferred_directives.push((phase_1, scope, directive));
+                }
+                if !failed || phase_1 != phase {
+                    progressed = true;
+                }
+            }
+
+            // Function bodies are verified in the last phase only.
+            let function_commons = if target_phase == VerifierPhase::Bodies {
+                std::mem::take(&mut self.deferred_function_commons)
+            } else {
+                vec![]
+            };
+            for (phase, scope, common) in function_commons {
+                let k = std::mem::replace(&mut self.scope, scope.clone());
+                let r = self.verify_function_body(&common);
+                self.scope = k;
+                if r.is_err() {
+                    self.deferred_function_commons.push((phase, scope, common));
+                } else {
+                    self.verified_function_commons.push((scope, common));
+                    progressed = true;
+                }
+            }
+
+            self.deferred_counter += 1;
+
+            if !progressed {
+                break;
+            }
+        }
+    }
+
+    /// Reports what remains deferred as unresolved references.
+    fn report_unresolved(&mut self) {
+        for (phase, scope, directive) in std::mem::take(&mut self.deferred_directives) {
+            self.defer_location = None;
+            let k = std::mem::replace(&mut self.scope, scope);
+            let r = self.verify_directive(&directive, VerifierPhase::ALL[phase]);
+            self.scope = k;
+            if r.is_ok() {
+                continue;
+            }
+            let location = self.defer_location.take().unwrap_or(directive.location());
+            self.add_verify_error(&location, DiagnosticKind::UnresolvedReference, diagnostic_arguments![]);
+        }
+        for (_, scope, common) in std::mem::take(&mut self.deferred_function_commons) {
+            self.defer_location = None;
+            let k = std::mem::replace(&mut self.scope, scope.clone());
+            let r = self.verify_function_body(&common);
+            self.scope = k;
+            if r.is_ok() {
+                self.verified_function_commons.push((scope, common));
+                continue;
+            }
+            let location = self.defer_location.take().unwrap_or(common.location.clone());
+            self.add_verify_error(&location, DiagnosticKind::UnresolvedReference, diagnostic_arguments![]);
+        }
+    }
+
+    /// Returns the properties and the parent definition for
+    /// definitions in the current scope. Within a class or enum block,
+    /// `is_static` selects the static properties rather than the prototype.
+    pub fn definition_target(&self, is_static: bool) -> (SharedMap<String, Symbol>, Symbol) {
+        if self.scope.is_package_scope() {
+            let package = self.scope.package();
+            (package.properties(&self.host), package)
+        } else if self.scope.is_class_scope() || self.scope.is_enum_scope() {
+            let class = self.scope.class();
+            if is_static {
+                (class.static_properties(&self.host), class)
+            } else {
+                (class.prototype(&self.host), class)
+            }
+        } else if self.scope.is_interface_scope() {
+            let interface = self.scope.interface();
+            (interface.prototype(&self.host), interface)
+        } else {
+            (self.scope.properties(&self.host), self.scope.clone())
+        }
+    }
+
+    /// Creates the type parameters of a generic definition and
+    /// declares them in the given scope.
+    pub fn declare_type_parameters(&mut self, type_parameters: &[Rc<TypeParameter>], scope: &Symbol) -> SharedArray<Symbol> {
+        let mut result = SharedArray::new();
+        let mut properties = scope.properties(&self.host);
+        for type_parameter in type_parameters {
+            let name = type_parameter.name.0.clone();
+            if let Some(existing) = properties.get(&name) {
+                self.add_duplicate_definition_error(&type_parameter.name.1, &name, Some(&existing));
+                continue;
+            }
+            let t = self.host.factory().create_type_parameter_type(name.clone());
+            self.definition_locations.insert(t.clone(), type_parameter.name.1.clone());
+            properties.set(name, t.clone());
+            result.push(t);
+        }
+        result
+    }
+
+    /// Resolves the bounds of the type parameters declared in the given scope.
+    /// Returns a list of (type parameter, bound, location, whether the bound
+    /// is an `extends` bound), to be passed to [`Self::set_type_parameter_bounds`]
+    /// once no further verification may be deferred.
+    pub fn resolve_type_parameter_bounds(&mut self, type_parameters: &[Rc<TypeParameter>], scope: &Symbol) -> Result<Vec<(Symbol, Symbol, Location, bool)>, DeferVerificationError> {
+        let mut result = vec![];
+        for type_parameter in type_parameters {
+            let Some(t) = scope.properties(&self.host).get(&type_parameter.name.0).filter(|t| t.is_type_parameter_type()) else {
+                continue;
+            };
+            if let Some(exp) = type_parameter.extends_clause.as_ref() {
+                if let Some(bound) = self.verify_type_expression(exp)? {
+                    result.push((t.clone(), bound, exp.location(), true));
+                }
+            }
+            for exp in type_parameter.implements_clause.iter().flatten() {
+                if let Some(bound) = self.verify_type_expression(exp)? {
+                    result.push((t.clone(), bound, exp.location(), false));
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Attaches resolved type parameter bounds, reporting
+    /// `extends` bounds that are not classes and `implements` bounds
+    /// that are not interfaces.
+    pub fn set_type_parameter_bounds(&mut self, bounds: Vec<(Symbol, Symbol, Location, bool)>) {
+        for (t, bound, location, extends) in bounds {
+            if extends && !bound.is_class_type() {
+                self.add_verify_error(&location, DiagnosticKind::NotAClass, diagnostic_arguments![Symbol(bound)]);
+            } else if !extends && !bound.is_interface_type() {
+                self.add_verify_error(&location, DiagnosticKind::NotAnInterface, diagnostic_arguments![Symbol(bound)]);
+            } else if !t.type_parameter_bounds().includes(&bound) {
+                t.type_parameter_bounds().push(bound);
+            }
+        }
+    }
+
+    /// Checks the type arguments of a type after explicit type substitution
+    /// against the bounds of the type parameters once every bound is resolved.
+    pub fn check_type_arguments_later(&mut self, location: &Location, t: &Symbol) {
+        self.type_argument_checks.push((location.clone(), t.clone()));
+    }
+
+    /// Checks the type arguments of the types after explicit type substitution
+    /// interned since `start`, such as those resulting from substituting
+    /// the signature of a method, unless they are already checked at `location`.
+    fn check_interned_type_arguments_later(&mut self, location: &Location, start: usize) {
+        let interned = {
+            let mut unchecked_taets = self.host.unchecked_taets.borrow_mut();
+            let start = start.min(unchecked_taets.len());
+            unchecked_taets.split_off(start)
+        };
+        for t in interned {
+            if !self.type_argument_checks.iter().any(|(_, t1)| t1 == &t) {
+                self.check_type_arguments_later(location, &t);
+            }
+        }
+    }
+
+    fn check_type_arguments(&mut self) {
+        self.host.unchecked_taets.borrow_mut().clear();
+        for (location, t) in std::mem::take(&mut self.type_argument_checks) {
+            let type_parameters = t.origin().type_parameters().unwrap();
+            let substitute_types = t.substitute_types();
+            for (type_parameter, argument) in type_parameters.iter().zip(substitute_types.iter()) {
+                if argument.is_unresolved() {
+                    continue;
+                }
+                for bound in type_parameter.type_parameter_bounds().iter() {
+                    let bound = bound.type_substitution(&self.host, &type_parameters, &substitute_types);
+                    if !argument.is_equals_or_subtype_of(&bound, &self.host) {
+                        self.add_verify_error(&location, DiagnosticKind::TypeArgumentOutOfBounds, diagnostic_arguments![Symbol(argument.clone()), Symbol(bound), String(type_parameter.name())]);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Determines whether `t` is, or inherits from, `base`, comparing
+    /// the origins of parameterized types.
+    pub fn inherits_from(&self, t: &Symbol, base: &Symbol) -> bool {
+        let t = if t.is_type_after_explicit_type_substitution() { t.origin() } else { t.clone() };
+        if &t == base {
+            return true;
+        }
+        if t.is_unresolved() {
+            return false;
+        }
+        t.direct_ascending_types(&self.host).iter().any(|t1| self.inherits_from(t1, base))
+    }
+
+    /// Returns the scope of a block, creating it if necessary.
+    pub fn block_scope(&mut self, block: &Rc<Block>) -> Symbol {
+        if let Some(scope) = self.ast_to_symbol.get(block) {
+            return scope;
+        }
+        let scope = self.host.factory().create_scope();
+        scope.set_parent(Some(&self.scope));
+        self.ast_to_symbol.set(block, Some(scope.clone()));
+        scope
+    }
+
+    /// Verifies the condition of a statement, which must be a `Boolean`.
+    pub fn verify_condition(&mut self, exp: &Rc<Expression>) -> Result<Option<Symbol>, DeferVerificationError> {
+        let boolean_type = self.host.boolean_type();
+        boolean_type.throw_if_unresolved()?;
+        self.limit_expression_type(exp, &boolean_type)
+    }
+
+    /// Finds the proxy that overloads an operator for a class or enum type.
+    pub fn find_operator_proxy(&self, t: &Symbol, operator: Operator) -> Result<Option<Symbol>, DeferVerificationError> {
+        let t = t.non_null_type();
+        if !(t.is_class_type() || t.is_enum_type()) || self.host.is_numeric_type(&t) {
+            return Ok(None);
+        }
+        let Some(kind) = ProxyKind::from_operator(operator) else {
+            return Ok(None);
+        };
+        t.find_proxy(kind, &self.host)
+    }
+
+    /// Verifies a call to an operator proxy whose parameters are given
+    /// by `arguments`, recording the proxy as called by `exp`.
+    /// The result is a value of the proxy's result type.
+    pub fn verify_proxy_call(&mut self, exp: &Rc<Expression>, proxy: &Symbol, arguments: &[&Rc<Expression>]) -> Result<Option<Symbol>, DeferVerificationError> {
+        let signature = proxy.signature(&self.host);
+        signature.throw_if_unresolved()?;
+        let parameters = signature.parameters();
+        let mut valid = true;
+        for (i, argument) in arguments.iter().enumerate() {
+            let v = if let Some(parameter) = parameters.get(i) {
+                self.limit_expression_type(argument, &parameter.static_type)?
+            } else {
+                self.verify_expression(argument, &default())?
+            };
+            valid = valid && v.is_some();
+        }
+        self.ast_to_symbol.set_proxy_call(exp, Some(proxy.clone()));
+        Ok(valid.then(|| self.host.factory().create_value(&signature.result_type())))
+    }
+
+    /// Verifies the arguments of a call against the parameters of
+    /// a function type, limiting each argument to its parameter type.
+    /// Returns `false` if any argument is invalid.
+    pub fn verify_arguments(&mut self, call_location: &Location, signature: &Symbol, arguments: &[Rc<Expression>]) -> Result<bool, DeferVerificationError> {
+        let parameters = signature.parameters();
+        let rest_parameter = parameters.iter().last().filter(|p| p.kind == ParameterKind::Rest);
+        let mut valid = true;
+        for (i, argument) in arguments.iter().enumerate() {
+            let parameter = parameters.get(i).filter(|p| p.kind != ParameterKind::Rest).or(rest_parameter.clone());
+            let v = if let Some(parameter) = parameter {
+                let parameter_type = if parameter.kind == ParameterKind::Rest {
+                    parameter.static_type.array_element_type(&self.host)?.unwrap_or(self.host.any_type())
+                } else {
+                    parameter.static_type.clone()
+                };
+                self.limit_expression_type(argument, &parameter_type)?
+            } else {
+                self.verify_expression(argument, &default())?
+            };
+            valid = valid && v.is_some();
+        }
+
+        let required = parameters.iter().filter(|p| p.kind == ParameterKind::Required).count();
+        let maximum = if rest_parameter.is_some() { None } else { Some(parameters.length()) };
+        if arguments.len() < required {
+            self.add_verify_error(call_location, DiagnosticKind::TooFewArguments, diagnostic_arguments![String(required.to_string()), String(arguments.len().to_string())]);
+            valid = false;
+        } else if let Some(maximum) = maximum.filter(|maximum| arguments.len() > *maximum) {
+            self.add_verify_error(&arguments[maximum].location(), DiagnosticKind::TooManyArguments, diagnostic_arguments![String(maximum.to_string()), String(arguments.len().to_string())]);
+            valid = false;
+        }
+        Ok(valid)
+    }
+
+    /// Infers the type parameters not yet assigned in `inferred` by matching
+    /// the parameter types of a function type against the static types of
+    /// the call arguments, returning the substitute types. Reports
+    /// the type parameters that could not be inferred.
+    pub fn infer_type_arguments_from_arguments(&mut self, call_location: &Location, signature: Option<&Symbol>, arguments: &[Rc<Expression>], type_parameters: &SharedArray<Symbol>, mut inferred: Vec<Option<Symbol>>) -> Result<Option<SharedArray<Symbol>>, DeferVerificationError> {
+        let host = self.host.clone();
+        let inference = TypeInference(&host);
+        if let Some(signature) = signature {
+            let parameters = signature.parameters();
+            let rest_parameter = parameters.iter().last().filter(|p| p.kind == ParameterKind::Rest);
+            for (i, argument) in arguments.iter().enumerate() {
+                let Some(parameter) = parameters.get(i).filter(|p| p.kind != ParameterKind::Rest).or(rest_parameter.clone()) else {
+                    break;
+                };
+                let parameter_type = if parameter.kind == ParameterKind::Rest {
+                    parameter.static_type.array_element_type(&host)?.unwrap_or(host.any_type())
+                } else {
+                    parameter.static_type.clone()
+                };
+                let pending = SharedArray::from(type_parameters.iter().zip(inferred.iter()).filter(|(_, t)| t.is_none()).map(|(p, _)| p).collect::<Vec<_>>());
+                if !inference.mentions_type_parameters(&parameter_type, &pending) {
+                    continue;
+                }
+                if let Some(v) = self.verify_expression(argument, &default())? {
+                    inference.infer(&parameter_type, &v.static_type(&host), type_parameters, &mut inferred);
+                }
+            }
+        }
+
+        let uninferred = type_parameters.iter().zip(inferred.iter()).filter(|(_, t)| t.is_none()).map(|(p, _)| p.name().to_string()).collect::<Vec<_>>();
+        if !uninferred.is_empty() {
+            self.add_verify_error(call_location, DiagnosticKind::CouldNotInferTypeArguments, diagnostic_arguments![String(uninferred.join(", "))]);
+            return Ok(None);
+        }
+        Ok(Some(SharedArray::from(inferred.into_iter().map(|t| t.unwrap()).collect::<Vec<_>>())))
+    }
+
+    /// Looks up an existing package by its fully qualified name.
+    pub fn lookup_package(&self, name: &[String]) -> Option<Symbol> {
+        let mut result = self.host.top_level_package();
+        for name_1 in name {
+            result = result.subpackages().get(name_1)?;
+        }
+        Some(result)
+    }
+
+    pub fn verify_expression(&mut self, exp: &Rc<Expression>, context: &ExpressionVerifyContext) -> Result<Option<Symbol>, DeferVerificationError> {
+        if self.ast_to_symbol.has(exp) {
+            let result = self.ast_to_symbol.get(exp);
+            if let Some(r) = result.as_ref() {
+                if context.mode == VerifyMode::Write {
+                    self.invalidate_narrowings(r);
+                } else if let Some(t) = self.ast_to_symbol.narrowed_type(exp) {
+                    return Ok(Some(self.host.factory().create_conversion_value(r, TypeConversionRelationship::Narrowing, false, &t)));
+                }
+            }
+            return Ok(result);
+        }
+
+        let unchecked_taets_start = self.host.unchecked_taets.borrow().len();
+        let result = self.verify_expression_variant(exp, context);
+        self.check_interned_type_arguments_later(&exp.location(), unchecked_taets_start);
+        let result = match result {
+            Ok(result) => result,
+            Err(e) => {
+                if self.defer_location.is_none() {
+                    self.defer_location = Some(exp.location());
+                }
+                return Err(e);
+            },
+        };
+
+        // Values whose static type is unresolved defer verification.
+        if let Some(r) = result.as_ref() {
+            if r.is_value() && r.expect_type().is_err() && r.static_type(&self.host).is_unresolved() {
+                if self.defer_location.is_none() {
+                    self.defer_location = Some(exp.location());
+                }
+                return Err(DeferVerificationError);
+            }
         }
 
         self.ast_to_symbol.set(exp, result.clone());
@@ -225,11 +821,19 @@ impl VerifierVerifier {
                 if result.write_only(&self.host) {
                     self.add_verify_error(&exp.location(), DiagnosticKind::ReferenceIsWriteOnly, diagnostic_arguments![]);
                 }
+
+                // Variables narrowed by a preceding test, such as `x != null`,
+                // are read through a conversion to the narrowed type.
+                if let Some(t) = self.narrowed_reference_type(&result) {
+                    self.ast_to_symbol.set_narrowed_type(exp, Some(t.clone()));
+                    return Ok(Some(self.host.factory().create_conversion_value(&result, TypeConversionRelationship::Narrowing, false, &t)));
+                }
             },
             VerifyMode::Write => {
                 if result.read_only(&self.host) {
                     self.add_verify_error(&exp.location(), DiagnosticKind::ReferenceIsReadOnly, diagnostic_arguments![]);
                 }
+                self.invalidate_narrowings(&result);
             },
             VerifyMode::Delete => {
                 if !result.deletable(&self.host) {
@@ -241,8 +845,203 @@ impl VerifierVerifier {
         Ok(Some(result))
     }
 
+    fn verify_expression_variant(&mut self, exp: &Rc<Expression>, context: &ExpressionVerifyContext) -> Result<Option<Symbol>, DeferVerificationError> {
+        let result = match exp.as_ref() {
+            Expression::QualifiedIdentifier(id) => {
+                id.verify_as_exp(self, context)?
+            },
+            Expression::Embed(emb) => {
+                emb.verify(self, context)?
+            },
+            Expression::Paren(paren_exp) => {
+                self.verify_expression(&paren_exp.expression, context)?
+            },
+            Expression::NullLiteral(nl) => {
+                nl.verify(self, context)?
+            },
+            Expression::BooleanLiteral(bl) => {
+                Some(self.host.factory().create_boolean_constant(bl.value, &self.host.boolean_type()))
+            },
+            Expression::NumericLiteral(nl) => {
+                nl.verify(self, context)?
+            },
+            Expression::StringLiteral(sl) => {
+                sl.verify(self, context)?
+            },
+            Expression::ThisLiteral(tl) => {
+                tl.verify(self)?
+            },
+            Expression::RegExpLiteral(rl) => {
+                rl.verify(self)?
+            },
+            Expression::Xml(xml) => {
+                xml.element.verify(self)?;
+                Some(self.host.factory().create_value(&self.host.xml_type()))
+            },
+            Expression::XmlMarkup(_) => {
+                Some(self.host.factory().create_value(&self.host.xml_type()))
+            },
+            Expression::XmlList(xml) => {
+                for content in &xml.content {
+                    content.verify(self)?;
+                }
+                Some(self.host.factory().create_value(&self.host.xml_list_type()))
+            },
+            Expression::ArrayLiteral(al) => {
+                al.verify(self, context)?
+            },
+            Expression::ObjectInitializer(oi) => {
+                oi.verify(self, context)?
+            },
+            Expression::Function(fe) => {
+                fe.verify(self, context)?
+            },
+            Expression::ImportMeta(_) => {
+                Some(self.host.import_meta())
+            },
+            Expression::New(ne) => {
+                ne.verify(self, context)?
+            },
+            Expression::Member(me) => {
+                me.verify(self, context)?
+            },
+            Expression::ComputedMember(cme) => {
+                cme.verify(self, exp, context)?
+            },
+            Expression::Descendants(de) => {
+                de.verify(self)?
+            },
+            Expression::Filter(fe) => {
+                fe.verify(self)?
+            },
+            Expression::Super(se) => {
+                se.verify(self)?
+            },
+            Expression::Call(ce) => {
+                ce.verify(self, context)?
+            },
+            Expression::WithTypeArguments(wta) => {
+                wta.verify(self)?
+            },
+            Expression::Unary(ue) => {
+                ue.verify(self, exp, context)?
+            },
+            Expression::OptionalChaining(oce) => {
+                oce.verify(self, context)?
+            },
+            Expression::OptionalChainingPlaceholder(_) => {
+                self.optional_chaining_bases.last().cloned()
+            },
+            Expression::Binary(be) => {
+                be.verify(self, exp, context)?
+            },
+            Expression::Conditional(ce) => {
+                ce.verify(self, context)?
+            },
+            Expression::Assignment(ae) => {
+                ae.verify(self)?
+            },
+            Expression::Sequence(se) => {
+                self.verify_expression(&se.left, &default())?;
+                self.verify_expression(&se.right, context)?
+            },
+            Expression::NullableType(nte) => {
+                nte.verify(self)?
+            },
+            Expression::NonNullableType(nnte) => {
+                nnte.verify(self)?
+            },
+            Expression::AnyType(_) => {
+                Some(self.host.any_type())
+            },
+            Expression::VoidType(_) => {
+                Some(self.host.void_type())
+            },
+            Expression::ArrayType(ate) => {
+                ate.verify(self)?
+            },
+            Expression::TupleType(tte) => {
+                tte.verify(self)?
+            },
+            Expression::FunctionType(fte) => {
+                fte.verify(self)?
+            },
+            Expression::Invalidated(_) => {
+                None
+            },
+        };
+        Ok(result)
+    }
+
+    /// Resolves a property as an expression, reporting diagnostics
+    /// at `location` on failure.
+    pub fn resolve_property_as_exp(&mut self, base: &Symbol, qual: Option<Symbol>, key: SemanticPropertyKey, disamb: PropertyDisambiguation, location: &Location, context: &ExpressionVerifyContext) -> Result<Option<Symbol>, DeferVerificationError> {
+        let r = base.resolve_property_with_disambiguation(qual.clone(), key.clone(), &self.host, disamb);
+        if let Err(error) = &r {
+            match error {
+                PropertyResolutionError::AmbiguousReference { name } => {
+                    self.add_verify_error(location, DiagnosticKind::AmbiguousReference, diagnostic_arguments![String(name.clone())]);
+                    return Ok(None);
+                },
+                PropertyResolutionError::DeferVerification => {
+                    return Err(DeferVerificationError);
+                },
+                PropertyResolutionError::VoidBase => {
+                    self.add_verify_error(location, DiagnosticKind::AccessingPropertyOfVoidBase, diagnostic_arguments![]);
+                    return Ok(None);
+                },
+                PropertyResolutionError::NullableBase { nullable_type } => {
+                    self.add_verify_error(location, DiagnosticKind::AccessingPropertyOfNullableBase, diagnostic_arguments![Symbol(nullable_type.clone())]);
+                    return Ok(None);
+                },
+            }
+        }
+        let r = r.unwrap();
+        if r.is_none() {
+            // In dynamic mode, undefined properties of values are resolved at runtime.
+            if base.is_value() && !location.compilation_unit().compiler_options().is_strict() {
+                let k = key.symbol(&self.host);
+                return Ok(Some(self.host.factory().create_dynamic_reference_value(base, qual, &k, disamb)));
+            }
+            self.add_verify_error(location, DiagnosticKind::UndefinedProperty, diagnostic_arguments![String(key.string_value().unwrap_or(key.number_value().map(|n| n.to_string()).unwrap_or_default()))]);
+            return Ok(None);
+        }
+        let r = r.unwrap();
+
+        if r.is_package() {
+            return Ok(Some(r));
+        }
+
+        if !r.property_is_visible(&self.scope, &self.host) {
+            self.add_verify_error(location, DiagnosticKind::InaccessibleProperty, diagnostic_arguments![String(key.string_value().unwrap_or_default())]);
+        }
+
+        self.scope.check_property_has_capture(&r);
+
+        if r.is_reference_value() && (r.is_static_reference_value() || r.is_instance_reference_value() || r.is_scope_reference_value() || r.is_package_reference_value()) {
+            let p = r.property();
+
+            // Require type arguments
+            if (p.is_origin_class_type() || p.is_origin_interface_type()) && p.type_parameters().is_some() && !context.followed_by_type_arguments {
+                self.add_verify_error(location, DiagnosticKind::ParameterizedTypeMustBeArgumented, diagnostic_arguments![]);
+            }
+
+            // Compile-time constant
+            if p.is_origin_variable_property() && p.read_only(&self.host) && p.constant_initializer().is_some() {
+                let r = p.constant_initializer().unwrap();
+                return Ok(Some(r));
+            }
+        }
+
+        Ok(Some(r))
+    }
+
     pub fn verify_type_expression(&mut self, exp: &Rc<Expression>) -> Result<Option<Symbol>, DeferVerificationError> {
-        let v = self.verify_expression(exp, &ExpressionVerifyContext { ..default() })?;
+        self.verify_type_expression_with_context(exp, &default())
+    }
+
+    pub fn verify_type_expression_with_context(&mut self, exp: &Rc<Expression>, context: &ExpressionVerifyContext) -> Result<Option<Symbol>, DeferVerificationError> {
+        let v = self.verify_expression(exp, context)?;
         if v.is_none() {
             return Ok(None);
         }
@@ -270,7 +1069,7 @@ impl VerifierVerifier {
         let got_type = v.static_type(&self.host);
         let v = TypeConversions(&self.host).implicit_conversion(&v, limit_type, false)?;
         if v.is_none() {
-            self.add_verify_error(&exp.location(), DiagnosticKind::IncompatibleTypes, diagnostic_arguments![Symbol(got_type), Symbol(limit_type.clone())]);
+            self.add_verify_error(&exp.location(), DiagnosticKind::IncompatibleTypes, diagnostic_arguments![Symbol(limit_type.clone()), Symbol(got_type)]);
             self.ast_to_symbol.set(exp, None);
             return Ok(None);
         }
@@ -280,6 +1079,37 @@ impl VerifierVerifier {
     }
 }
 
+/// Phase of directive verification.
+///
+/// Directives are verified phase by phase across all programs, allowing
+/// definitions to refer to each other regardless of their order. The
+/// verification of a directive that encounters an `Unresolved` symbol
+/// is deferred and resumed from the same phase later.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum VerifierPhase {
+    /// Declares packages, types and properties. Static types and
+    /// signatures are initially `Unresolved`.
+    Declarations = 0,
+    /// Resolves `import` and `use package` directives.
+    Imports = 1,
+    /// Resolves type aliases and type inheritance.
+    Heritage = 2,
+    /// Resolves static types of variables and signatures of functions.
+    Signatures = 3,
+    /// Verifies initializers, statements and function bodies.
+    Bodies = 4,
+}
+
+impl VerifierPhase {
+    pub const ALL: [VerifierPhase; 5] = [
+        Self::Declarations,
+        Self::Imports,
+        Self::Heritage,
+        Self::Signatures,
+        Self::Bodies,
+    ];
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum VerifyMode {
     Read,
@@ -304,4 +1134,587 @@ impl Default for ExpressionVerifyContext {
             preceded_by_negative: false,
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+
+    fn verify(text: &str) -> Vec<String> {
+        let host = SymbolHost::new("", "");
+        let compilation_unit = CompilationUnit::new(None, text.into(), &CompilerOptions::new());
+        let program = ParserFacade::parse_program(&compilation_unit).unwrap();
+        let mut verifier = Verifier::new(&host);
+        verifier.verify_programs(vec![program]);
+        compilation_unit.sort_diagnostics();
+        compilation_unit.diagnostics().iter().map(|d| d.format_message_english()).collect()
+    }
+
+    #[test]
+    fn verifying_out_of_order_definitions() {
+        let diagnostics = verify(r#"
+            package jet.lang {
+                public class Object {}
+                public class Boolean {}
+                public class Class {}
+                public class Function {}
+                public class Number {}
+                public class String {}
+            }
+            package p {
+                public const x: T = y;
+                public type T = jet.lang.Number;
+                public const y = 10;
+                public function f(a: T): T {
+                    return a;
+                }
+            }
+        "#);
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+    }
+
+    #[test]
+    fn reporting_unresolved_references() {
+        let diagnostics = verify(r#"
+            package p {
+                public const x = y;
+                public const y = x;
+            }
+        "#);
+        assert!(diagnostics.iter().all(|d| d == "Unresolved reference"), "{:?}", diagnostics);
+        assert!(!diagnostics.is_empty());
+    }
+
+    const JET_LANG: &str = r#"
+        package jet.lang {
+            public class Object {}
+            public class Boolean {}
+            public class Class {}
+            public class Function {}
+            public class Number {}
+            public class String {}
+            public interface Iterator.<T> {}
+        }
+    "#;
+
+    #[test]
+    fn reporting_duplicate_definitions() {
+        let diagnostics = verify(&(JET_LANG.to_owned() + r#"
+            package p {
+                public class C {
+                    public function f(): void {}
+                    public function f(): void {}
+                }
+                public const C = 10;
+            }
+        "#));
+        assert_eq!(diagnostics, vec!["'f' is already defined", "'C' is already defined"]);
+    }
+
+    #[test]
+    fn verifying_inheritance() {
+        let diagnostics = verify(&(JET_LANG.to_owned() + r#"
+            package p {
+                public interface I {
+                    function m(): void;
+                }
+                public abstract class A implements I {
+                    public function m(): void {}
+                    public abstract function n(): void;
+                }
+                public final class B extends A {
+                    public override function n(): void {}
+                }
+                public class C extends B {
+                    public override function o(): void {}
+                }
+                public class D implements I {}
+                public class E extends F {}
+                public class F extends E {}
+            }
+        "#));
+        assert_eq!(diagnostics, vec![
+            "Cannot extend final class 'p.B'",
+            "Method 'o' must override a method",
+            "Method 'm' from 'p.I' is not implemented",
+            "Circular inheritance involving 'p.F'",
+        ]);
+    }
+
+    #[test]
+    fn assigning_enumeration_members() {
+        let host = SymbolHost::new("", "");
+        let compilation_unit = CompilationUnit::new(None, JET_LANG.to_owned() + r#"
+            package p {
+                public enum E {
+                    const FOO_BAR;
+                    const QUX = "q";
+                    const BAZ = 10;
+                    const ZED;
+                }
+            }
+        "#, &CompilerOptions::new());
+        let program = ParserFacade::parse_program(&compilation_unit).unwrap();
+        let mut verifier = Verifier::new(&host);
+        verifier.verify_programs(vec![program]);
+        assert!(compilation_unit.diagnostics().is_empty());
+
+        let p = host.top_level_package().subpackages().get(&"p".to_owned()).unwrap();
+        let e = p.properties(&host).get(&"E".to_owned()).unwrap();
+        let members = e.enumeration_members();
+        let number = |name: &str| members.get(&name.to_owned()).unwrap();
+        assert!(members.get(&"fooBar".to_owned()).is_some());
+        assert!(number("q") == number("fooBar").increase_by_one());
+        assert!(number("baz").increase_by_one() == number("zed"));
+    }
+
+    #[test]
+    fn verifying_statements() {
+        let diagnostics = verify(&(JET_LANG.to_owned() + r#"
+            package p {
+                public class Bag {
+                    native proxy function values(): Iterator.<String>;
+                }
+                public function f(a: Number, bag: Bag): Number {
+                    if (a) {}
+                    for each (var s in bag) {
+                        const n: Number = s;
+                    }
+                    for each (var x in a) {}
+                    return;
+                }
+                public function g(): void {
+                    try {} catch (e) {}
+                    return 10;
+                }
+            }
+        "#));
+        assert_eq!(diagnostics, vec![
+            "Incompatible types: expected 'jet.lang.Boolean', got 'jet.lang.Number'",
+            "Incompatible types: expected 'jet.lang.Number', got 'jet.lang.String'",
+            "Cannot iterate type 'jet.lang.Number'",
+            "Return statement must return a value",
+            "Catch parameter must have a type annotation",
+            "Incompatible types: expected 'void', got 'jet.lang.Number'",
+        ]);
+    }
+
+    #[test]
+    fn analyzing_flow() {
+        let diagnostics = verify(&(JET_LANG.to_owned() + r#"
+            package p {
+                public function f(a: Boolean): Number {
+                    if (a) {
+                        return 0;
+                    }
+                }
+                public function g(a: Boolean): Number {
+                    while (true) {
+                        if (a) break;
+                    }
+                    throw 0;
+                    a = false;
+                }
+                public function h(): Number {
+                    const b: Number = c;
+                    const c: Number = 10;
+                    outer: for (;;) {
+                        for (;;) {
+                            continue outer;
+                        }
+                    }
+                }
+            }
+        "#));
+        assert_eq!(diagnostics, vec![
+            "Not all code paths return a value",
+            "Unreachable code",
+            "Constant 'c' is used before being assigned",
+        ]);
+    }
+
+    #[test]
+    fn narrowing_nullable_types() {
+        let diagnostics = verify(&(JET_LANG.to_owned() + r#"
+            package p {
+                public class C {
+                    public function m(): void {}
+                    public function n(): Boolean {
+                        return true;
+                    }
+                }
+                public function f(a: ?C, b: ?C, c: ?C, o: Object): void {
+                    if (a != null) a.m();
+                    if (o is C) o.m();
+                    const x: Boolean = a === null || a.n();
+                    if (b == null) {
+                        return;
+                    }
+                    b.m();
+                    while (c !== null) {
+                        c.m();
+                        c = null;
+                    }
+                    a.m();
+                }
+            }
+        "#));
+        assert_eq!(diagnostics, vec![
+            "Accessing property of nullable base with static type 'p.C?'",
+        ]);
+    }
+
+    #[test]
+    fn folding_constants() {
+        let diagnostics = verify(&(JET_LANG.to_owned() + r#"
+            package p {
+                public const K: Number = 2 ** 3 % 5;
+                public function f(n: Number, s: String): void {
+                    switch (n) {
+                        case 1 + 1:
+                            break;
+                        case 2:
+                            break;
+                        case K:
+                            break;
+                        case -(-3):
+                            break;
+                        case ~~4:
+                            break;
+                        case 4:
+                            break;
+                    }
+                    switch (s) {
+                        case "a" + "b" + 1:
+                            break;
+                        case "ab1":
+                            break;
+                    }
+                }
+            }
+        "#));
+        assert_eq!(diagnostics, vec![
+            "Duplicate case label",
+            "Duplicate case label",
+            "Duplicate case label",
+            "Duplicate case label",
+        ]);
+
+        let host = SymbolHost::new("", "");
+        let compilation_unit = CompilationUnit::new(None, JET_LANG.to_owned() + r#"
+            package p {
+                public enum E {
+                    const A = 1 << 2;
+                    const B = (2 + 3) * -2;
+                }
+            }
+        "#, &CompilerOptions::new());
+        let program = ParserFacade::parse_program(&compilation_unit).unwrap();
+        let mut verifier = Verifier::new(&host);
+        verifier.verify_programs(vec![program]);
+        assert!(compilation_unit.diagnostics().is_empty());
+
+        let p = host.top_level_package().subpackages().get(&"p".to_owned()).unwrap();
+        let e = p.properties(&host).get(&"E".to_owned()).unwrap();
+        let members = e.enumeration_members();
+        assert!(members.get(&"a".to_owned()) == Some(AbstractRangeNumber::Number(4.0)));
+        assert!(members.get(&"b".to_owned()) == Some(AbstractRangeNumber::Number(-10.0)));
+    }
+
+    #[test]
+    fn dispatching_operator_proxies() {
+        let diagnostics = verify(&(JET_LANG.to_owned() + r#"
+            package p {
+                public class V {
+                    proxy function add(right: V): V {
+                        return this;
+                    }
+                    proxy function negate(): V {
+                        return this;
+                    }
+                    proxy function has(key: String): Boolean {
+                        return true;
+                    }
+                    proxy function getProperty(key: Number): String {
+                        return "";
+                    }
+                    proxy function setProperty(key: Number, value: String): void {}
+                }
+                public function f(a: V, b: V): void {
+                    const c: V = a + b;
+                    const d: V = -a;
+                    const e: Boolean = "k" in a;
+                    const s: String = a[0];
+                    a[1] = "x";
+                    a + "x";
+                }
+            }
+        "#));
+        assert_eq!(diagnostics, vec!["Incompatible types: expected 'p.V', got 'jet.lang.String'"]);
+    }
+
+    #[test]
+    fn verifying_call_arguments() {
+        let diagnostics = verify(&(JET_LANG.to_owned() + r#"
+            package jet.lang {
+                public class Array.<T> {}
+            }
+            package p {
+                public class C {
+                    public function C(a: Number, b: String = "") {}
+                }
+                public class D extends C {}
+                public function f(a: Number, b: String = "", ...rest: [Boolean]): void {}
+                public function g(callback: function(a: Number): String): void {}
+                public function h(): void {
+                    f(0);
+                    f(0, "", true, false);
+                    f();
+                    f(0, false);
+                    f(0, "", "x");
+                    new C(0);
+                    new C(0, "", 1);
+                    new D();
+                    g(function(a) "" + a);
+                    g(function(a) a);
+                }
+            }
+        "#));
+        assert_eq!(diagnostics, vec![
+            "Expected at least 1 arguments, got 0",
+            "Incompatible types: expected 'jet.lang.String', got 'jet.lang.Boolean'",
+            "Incompatible types: expected 'jet.lang.Boolean', got 'jet.lang.String'",
+            "Expected at most 2 arguments, got 3",
+            "Expected at least 1 arguments, got 0",
+            "Incompatible types: expected 'jet.lang.String', got 'jet.lang.Number'",
+        ]);
+    }
+
+    #[test]
+    fn inferring_type_arguments() {
+        let diagnostics = verify(&(JET_LANG.to_owned() + r#"
+            package p {
+                public class Box.<T> {
+                    public function Box(value: T) {}
+                }
+                public class Empty.<T> {}
+                public class Wrapper.<T> extends Empty.<T> {}
+                public function h(): void {
+                    const a: Box.<String> = new Box("x");
+                    const b: String = new Box(true);
+                    const c: Box.<String> = new Box(true);
+                    const d: Empty.<Number> = new Empty();
+                    const e: Empty.<String> = new Wrapper();
+                    new Empty();
+                }
+            }
+        "#));
+        assert_eq!(diagnostics, vec![
+            "Incompatible types: expected 'jet.lang.String', got 'p.Box.<jet.lang.Boolean>'",
+            "Incompatible types: expected 'jet.lang.String', got 'jet.lang.Boolean'",
+            "Could not infer type arguments for type parameters T",
+        ]);
+    }
+
+    #[test]
+    fn inferring_type_arguments_of_method_calls() {
+        let diagnostics = verify(&(JET_LANG.to_owned() + r#"
+            package jet.lang {
+                public class Array.<T> {
+                    public function push(value: T): void {}
+                    public static function from(value: T): Array.<T> {
+                        return null;
+                    }
+                }
+                public class Map.<K, V> {
+                    public function get(key: K): V {
+                        return null;
+                    }
+                    public static function of(key: K, value: V): Map.<K, V> {
+                        return null;
+                    }
+                    public static function empty(): Map.<K, V> {
+                        return null;
+                    }
+                }
+            }
+            package p {
+                public function h(): void {
+                    const a: Array.<String> = Array.from("x");
+                    const b: Array.<Number> = Array.from("x");
+                    a.push(true);
+                    const c = Map.of("k", 10);
+                    const d: Number = c.get("k");
+                    const e: String = c.get("k");
+                    const f: Map.<String, Boolean> = Map.empty();
+                    f.get(true);
+                    Map.empty();
+                }
+            }
+        "#));
+        assert_eq!(diagnostics, vec![
+            "Incompatible types: expected 'jet.lang.Number', got 'jet.lang.String'",
+            "Incompatible types: expected 'jet.lang.String', got 'jet.lang.Boolean'",
+            "Incompatible types: expected 'jet.lang.String', got 'jet.lang.Number'",
+            "Incompatible types: expected 'jet.lang.String', got 'jet.lang.Boolean'",
+            "Could not infer type arguments for type parameters K, V",
+        ]);
+    }
+
+    #[test]
+    fn bounding_type_parameters() {
+        let diagnostics = verify(&(JET_LANG.to_owned() + r#"
+            package p {
+                public class Shape {
+                    public function area(): Number {
+                        return 0;
+                    }
+                }
+                public interface Named {
+                    function name(): String;
+                }
+                public class Square extends Shape {}
+                public class Holder.<T extends Shape> {
+                    public function Holder(value: T) {
+                        const a: Number = value.area();
+                        const b: Boolean = value.area();
+                    }
+                }
+                public class Registry.<T implements Named> {
+                    public function Registry(value: T) {
+                        const a: String = value.name();
+                        const b: Number = value.name();
+                    }
+                }
+                public class Invalid.<T extends Named, U implements Shape> {}
+                public function g(value: Named): void {
+                    const a: String = value.name();
+                    const b: Number = value.name();
+                }
+                public function h(): void {
+                    const a: Holder.<Square> = null;
+                    const b: Holder.<String> = null;
+                    const c = new Holder(new Square());
+                    const d = new Holder("x");
+                    const e: Registry.<Square> = null;
+                }
+            }
+        "#));
+        assert_eq!(diagnostics, vec![
+            "Incompatible types: expected 'jet.lang.Boolean', got 'jet.lang.Number'",
+            "Incompatible types: expected 'jet.lang.Number', got 'jet.lang.String'",
+            "'p.Named' is not a class",
+            "'p.Shape' is not an interface",
+            "Incompatible types: expected 'jet.lang.Number', got 'jet.lang.String'",
+            "Type argument 'jet.lang.String' does not satisfy the bound 'p.Shape' of type parameter 'T'",
+            "Type argument 'jet.lang.String' does not satisfy the bound 'p.Shape' of type parameter 'T'",
+            "Type argument 'p.Square' does not satisfy the bound 'p.Named' of type parameter 'T'",
+        ]);
+    }
+
+    #[test]
+    fn checking_type_argument_variance() {
+        let diagnostics = verify(&(JET_LANG.to_owned() + r#"
+            package p {
+                public class Shape {}
+                public class Square extends Shape {}
+                public class Holder.<T extends Shape> {}
+                public function h(a: Holder.<Square>, b: Holder.<Shape>): void {
+                    const c: Holder.<Shape> = a;
+                    const d: Holder.<Square> = b;
+                    const e: Holder.<Square> = a;
+                }
+            }
+        "#));
+        assert_eq!(diagnostics, vec![
+            "Incompatible types: expected 'p.Holder.<p.Shape>', got 'p.Holder.<p.Square>'",
+            "Incompatible types: expected 'p.Holder.<p.Square>', got 'p.Holder.<p.Shape>'",
+        ]);
+    }
+
+    #[test]
+    fn bounding_substituted_type_parameters() {
+        let diagnostics = verify(&(JET_LANG.to_owned() + r#"
+            package p {
+                public class Shape {}
+                public class Holder.<T extends Shape> {}
+                public class Factory.<U> {
+                    public function make(): Holder.<U> {
+                        return null;
+                    }
+                }
+                public function h(f: Factory.<String>): void {
+                    f.make();
+                }
+            }
+        "#));
+        assert_eq!(diagnostics, vec![
+            "Type argument 'U' does not satisfy the bound 'p.Shape' of type parameter 'T'",
+            "Type argument 'jet.lang.String' does not satisfy the bound 'p.Shape' of type parameter 'T'",
+        ]);
+    }
+
+    #[test]
+    fn capturing_locals() {
+        let diagnostics = verify(&(JET_LANG.to_owned() + r#"
+            package p {
+                public function f(): Number {
+                    var x: Number = 0;
+                    const g = function(): void {
+                        x = 1;
+                    };
+                    return x;
+                }
+            }
+        "#));
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+    }
+
+    #[test]
+    fn applying_type_arguments_to_types_without_type_parameters() {
+        let diagnostics = verify(&(JET_LANG.to_owned() + r#"
+            package p {
+                public function f(): void.<Number> {}
+                public var x: Number.<Number>;
+                public var y: Iterator.<Number>.<Number>;
+            }
+        "#));
+        assert_eq!(diagnostics, vec![
+            "Wrong number of type arguments: expected 0",
+            "Wrong number of type arguments: expected 0",
+            "Wrong number of type arguments: expected 0",
+        ]);
+    }
+
+    #[test]
+    fn implementing_interfaces_with_nested_definitions() {
+        let diagnostics = verify(&(JET_LANG.to_owned() + r#"
+            package p {
+                public interface Shape {
+                    public enum Color { const RED; }
+                    public class Box {}
+                }
+                public class Square implements Shape {}
+            }
+        "#));
+        assert_eq!(diagnostics, vec![
+            "Directive not allowed in interface",
+            "Nested classes are not allowed",
+            "Directive not allowed in interface",
+            "Nested classes are not allowed",
+        ]);
+    }
+
+    #[test]
+    fn reporting_super_outside_of_classes() {
+        let diagnostics = verify(&(JET_LANG.to_owned() + r#"
+            package p {
+                public interface I {
+                    function f(): void { super.x; }
+                }
+                public function g(): void { super.x; }
+            }
+        "#));
+        assert_eq!(diagnostics, vec!["Illegal super expression", "Illegal super expression"]);
+    }
+}
@@ -0,0 +1,536 @@
+use crate::ns::*;
+use std::collections::HashSet;
+
+/// Flow analysis over the directives of a verified function body.
+///
+/// The analysis reports unreachable code, functions that may complete
+/// without returning a value, and local constants that are read
+/// before being assigned.
+///
+/// The flow at a given point is either `None`, indicating that the point
+/// is unreachable, or the set of local constants that are definitely
+/// assigned at that point.
+pub(crate) struct FlowAnalysis<'a> {
+    verifier: &'a mut VerifierVerifier,
+    activation: Symbol,
+    targets: Vec<JumpTarget>,
+    labels: Vec<String>,
+}
+
+type Flow = Option<HashSet<Symbol>>;
+
+/// Statement targeted by `break` and `continue`.
+struct JumpTarget {
+    labels: Vec<String>,
+    /// Indicates whether the target is a loop or `switch` statement,
+    /// rather than a labeled statement.
+    breakable: bool,
+    iteration: bool,
+    breaks: Flow,
+    continues: Flow,
+}
+
+impl<'a> FlowAnalysis<'a> {
+    pub fn new(verifier: &'a mut VerifierVerifier, activation: &Symbol) -> Self {
+        Self {
+            verifier,
+            activation: activation.clone(),
+            targets: vec![],
+            labels: vec![],
+        }
+    }
+
+    /// Analyzes a function body. The activation is expected to be
+    /// the activation scope of the function.
+    pub fn analyze_function(&mut self, common: &Rc<FunctionCommon>) {
+        match common.body.as_ref() {
+            Some(FunctionBody::Block(block)) => {
+                let flow = self.directives(&block.directives, Some(HashSet::new()));
+                if flow.is_some() && self.requires_return_value() {
+                    self.verifier.add_verify_error(&common.location, DiagnosticKind::MissingReturn, diagnostic_arguments![]);
+                }
+            },
+            Some(FunctionBody::Expression(exp)) => {
+                self.expression(exp, &Some(HashSet::new()));
+            },
+            None => {},
+        }
+    }
+
+    /// Analyzes the top-level directives of a program.
+    pub fn analyze_program(&mut self, program: &Rc<Program>) {
+        self.directives(&program.directives, Some(HashSet::new()));
+    }
+
+    fn requires_return_value(&self) -> bool {
+        let function = self.activation.function();
+        if function.is_constructor() {
+            return false;
+        }
+        match ReturnStatement::expected_result_type(&function, &self.verifier.host) {
+            Ok(Some(t)) => !(t.is_void_type() || t.is_any_type()),
+            _ => false,
+        }
+    }
+
+    fn merge(a: Flow, b: Flow) -> Flow {
+        match (a, b) {
+            (None, b) => b,
+            (a, None) => a,
+            (Some(a), Some(b)) => Some(a.intersection(&b).cloned().collect()),
+        }
+    }
+
+    fn directives(&mut self, list: &[Rc<Directive>], mut flow: Flow) -> Flow {
+        // Unreachable code is reported once, at the first
+        // unreachable directive of a list.
+        let mut reported = flow.is_none();
+        for directive in list {
+            if flow.is_none() && !reported && Self::is_executable(directive) {
+                self.verifier.add_warning(&directive.location(), DiagnosticKind::UnreachableCode, diagnostic_arguments![]);
+                reported = true;
+            }
+            flow = self.directive(directive, flow);
+        }
+        flow
+    }
+
+    fn is_executable(directive: &Rc<Directive>) -> bool {
+        !matches!(
+            directive.as_ref(),
+            Directive::EmptyStatement(_) | Directive::InvalidatedDirective(_) | Directive::ConfigurationDirective(_) |
+            Directive::ImportDirective(_) | Directive::UsePackageDirective(_) | Directive::FunctionDefinition(_) |
+            Directive::ClassDefinition(_) | Directive::EnumDefinition(_) | Directive::InterfaceDefinition(_) |
+            Directive::TypeDefinition(_)
+        )
+    }
+
+    fn directive(&mut self, directive: &Rc<Directive>, flow: Flow) -> Flow {
+        let labels = std::mem::take(&mut self.labels);
+        match directive.as_ref() {
+            Directive::ExpressionStatement(stmt) => {
+                self.expression(&stmt.expression, &flow);
+                flow
+            },
+            Directive::SuperStatement(stmt) => {
+                for argument in &stmt.arguments {
+                    self.expression(argument, &flow);
+                }
+                flow
+            },
+            Directive::Block(block) => self.directives(&block.directives, flow),
+            Directive::LabeledStatement(stmt) => {
+                self.labels = labels.clone();
+                self.labels.push(stmt.label.0.clone());
+                self.targets.push(JumpTarget::new(vec![stmt.label.0.clone()], false, false));
+                let flow = self.directive(&stmt.substatement, flow);
+                let target = self.targets.pop().unwrap();
+                Self::merge(flow, target.breaks)
+            },
+            Directive::IfStatement(stmt) => {
+                self.expression(&stmt.test, &flow);
+                let test = self.boolean_constant(&stmt.test);
+                let consequent = self.directive(&stmt.consequent, if test == Some(false) { None } else { flow.clone() });
+                let alternative_flow = if test == Some(true) { None } else { flow };
+                let alternative = if let Some(alternative) = stmt.alternative.as_ref() {
+                    self.directive(alternative, alternative_flow)
+                } else {
+                    alternative_flow
+                };
+                Self::merge(consequent, alternative)
+            },
+            Directive::SwitchStatement(stmt) => {
+                self.expression(&stmt.discriminant, &flow);
+                for case in &stmt.cases {
+                    for label in &case.labels {
+                        if let CaseLabel::Case((exp, _)) = label {
+                            self.expression(exp, &flow);
+                        }
+                    }
+                }
+                self.targets.push(JumpTarget::new(labels, true, false));
+                let mut fallthrough: Flow = None;
+                for case in &stmt.cases {
+                    fallthrough = self.directives(&case.directives, Self::merge(fallthrough, flow.clone()));
+                }
+                let target = self.targets.pop().unwrap();
+                let has_default = stmt.cases.iter().any(|case| case.labels.iter().any(|label| matches!(label, CaseLabel::Default(_))));
+                let exit = Self::merge(fallthrough, target.breaks);
+                if has_default { exit } else { Self::merge(exit, flow) }
+            },
+            Directive::SwitchTypeStatement(stmt) => {
+                self.expression(&stmt.discriminant, &flow);
+                self.targets.push(JumpTarget::new(labels, true, false));
+                let mut exit: Flow = None;
+                for case in &stmt.cases {
+                    let case_flow = self.directives(&case.block.directives, flow.clone());
+                    exit = Self::merge(exit, case_flow);
+                }
+                let target = self.targets.pop().unwrap();
+                let exit = Self::merge(exit, target.breaks);
+                if stmt.cases.iter().any(|case| case.parameter.is_none()) { exit } else { Self::merge(exit, flow) }
+            },
+            Directive::DoStatement(stmt) => {
+                self.targets.push(JumpTarget::new(labels, true, true));
+                let body = self.directive(&stmt.body, flow);
+                let target = self.targets.pop().unwrap();
+                let flow = Self::merge(body, target.continues);
+                self.expression(&stmt.test, &flow);
+                let flow = if self.boolean_constant(&stmt.test) == Some(true) { None } else { flow };
+                Self::merge(flow, target.breaks)
+            },
+            Directive::WhileStatement(stmt) => {
+                self.expression(&stmt.test, &flow);
+                let test = self.boolean_constant(&stmt.test);
+                self.targets.push(JumpTarget::new(labels, true, true));
+                self.directive(&stmt.body, if test == Some(false) { None } else { flow.clone() });
+                let target = self.targets.pop().unwrap();
+                let flow = if test == Some(true) { None } else { flow };
+                Self::merge(flow, target.breaks)
+            },
+            Directive::ForStatement(stmt) => {
+                let flow = match stmt.init.as_ref() {
+                    Some(ForInitializer::Expression(exp)) => {
+                        self.expression(exp, &flow);
+                        flow
+                    },
+                    Some(ForInitializer::VariableDefinition(defn)) => self.bindings(&defn.bindings, flow),
+                    None => flow,
+                };
+                let test = if let Some(test) = stmt.test.as_ref() {
+                    self.expression(test, &flow);
+                    self.boolean_constant(test)
+                } else {
+                    Some(true)
+                };
+                self.targets.push(JumpTarget::new(labels, true, true));
+                let body = self.directive(&stmt.body, if test == Some(false) { None } else { flow.clone() });
+                let target = self.targets.pop().unwrap();
+                if let Some(update) = stmt.update.as_ref() {
+                    self.expression(update, &Self::merge(body, target.continues));
+                }
+                let flow = if test == Some(true) { None } else { flow };
+                Self::merge(flow, target.breaks)
+            },
+            Directive::ForInStatement(stmt) => {
+                self.expression(&stmt.right, &flow);
+                let flow = match &stmt.left {
+                    ForInBinding::Expression(exp) => {
+                        self.expression(exp, &flow);
+                        flow
+                    },
+                    ForInBinding::VariableDefinition(defn) => {
+                        let mut flow = flow;
+                        if let Some(assigned) = flow.as_mut() {
+                            for binding in &defn.bindings {
+                                self.assign(&binding.destructuring.destructuring, assigned);
+                            }
+                        }
+                        flow
+                    },
+                };
+                self.targets.push(JumpTarget::new(labels, true, true));
+                self.directive(&stmt.body, flow.clone());
+                let target = self.targets.pop().unwrap();
+                Self::merge(flow, target.breaks)
+            },
+            Directive::BreakStatement(stmt) => {
+                let label = stmt.label.as_ref().map(|label| label.0.clone());
+                let target = self.targets.iter_mut().rev().find(|target| {
+                    if let Some(label) = label.as_ref() { target.labels.contains(label) } else { target.breakable }
+                });
+                if let Some(target) = target {
+                    target.breaks = Self::merge(target.breaks.take(), flow);
+                }
+                None
+            },
+            Directive::ContinueStatement(stmt) => {
+                let label = stmt.label.as_ref().map(|label| label.0.clone());
+                let target = self.targets.iter_mut().rev().find(|target| {
+                    target.iteration && label.as_ref().map(|label| target.labels.contains(label)).unwrap_or(true)
+                });
+                if let Some(target) = target {
+                    target.continues = Self::merge(target.continues.take(), flow);
+                }
+                None
+            },
+            Directive::WithStatement(stmt) => {
+                self.expression(&stmt.object, &flow);
+                self.directive(&stmt.body, flow)
+            },
+            Directive::ReturnStatement(stmt) => {
+                if let Some(exp) = stmt.expression.as_ref() {
+                    self.expression(exp, &flow);
+                }
+                None
+            },
+            Directive::ThrowStatement(stmt) => {
+                self.expression(&stmt.expression, &flow);
+                None
+            },
+            Directive::DefaultXmlNamespaceStatement(stmt) => {
+                self.expression(&stmt.right, &flow);
+                flow
+            },
+            Directive::TryStatement(stmt) => {
+                // Any point of the `try` block may throw, thus the catch
+                // clauses and the finally clause start from the flow
+                // preceding the statement.
+                let mut exit = self.directives(&stmt.block.directives, flow.clone());
+                for catch_clause in &stmt.catch_clauses {
+                    let catch_flow = self.directives(&catch_clause.block.directives, flow.clone());
+                    exit = Self::merge(exit, catch_flow);
+                }
+                if let Some(finally_clause) = stmt.finally_clause.as_ref() {
+                    let finally_flow = self.directives(&finally_clause.block.directives, flow);
+                    exit = match (exit, finally_flow) {
+                        (Some(a), Some(b)) => Some(a.union(&b).cloned().collect()),
+                        _ => None,
+                    };
+                }
+                exit
+            },
+            Directive::VariableDefinition(defn) => self.bindings(&defn.bindings, flow),
+            Directive::ConfigurationDirective(_) => {
+                match self.verifier.ast_to_symbol.configuration_branch(directive).flatten() {
+                    Some(branch) => self.directive(&branch, flow),
+                    None => flow,
+                }
+            },
+            _ => flow,
+        }
+    }
+
+    fn bindings(&mut self, bindings: &[Rc<VariableBinding>], mut flow: Flow) -> Flow {
+        for binding in bindings {
+            if let Some(initializer) = binding.initializer.as_ref() {
+                self.expression(initializer, &flow);
+                if let Some(assigned) = flow.as_mut() {
+                    self.assign(&binding.destructuring.destructuring, assigned);
+                }
+            }
+        }
+        flow
+    }
+
+    /// Marks the variables of a destructuring pattern as assigned.
+    fn assign(&self, pattern: &Rc<Expression>, assigned: &mut HashSet<Symbol>) {
+        match pattern.as_ref() {
+            Expression::QualifiedIdentifier(_) => {
+                if let Some(variable) = self.verifier.ast_to_symbol.get(pattern) {
+                    assigned.insert(variable);
+                }
+            },
+            Expression::Unary(e) if e.operator == Operator::NonNull => {
+                self.assign(&e.expression, assigned);
+            },
+            Expression::ArrayLiteral(al) => {
+                for element in &al.elements {
+                    if let Element::Expression(e) | Element::Rest((e, _)) = element {
+                        self.assign(e, assigned);
+                    }
+                }
+            },
+            Expression::ObjectInitializer(oi) => {
+                for field in &oi.fields {
+                    match field.as_ref() {
+                        InitializerField::Field { value: Some(value), .. } => {
+                            self.assign(value, assigned);
+                        },
+                        InitializerField::Rest((e, _)) => {
+                            self.assign(e, assigned);
+                        },
+                        _ => {},
+                    }
+                }
+            },
+            _ => {},
+        }
+    }
+
+    fn boolean_constant(&self, exp: &Rc<Expression>) -> Option<bool> {
+        let value = self.verifier.ast_to_symbol.get(exp)?;
+        value.is_boolean_constant().then(|| value.boolean_value())
+    }
+
+    /// Reports reads of local constants that are not definitely assigned.
+    /// Nested functions are analyzed separately.
+    fn expression(&mut self, exp: &Rc<Expression>, flow: &Flow) {
+        match exp.as_ref() {
+            Expression::QualifiedIdentifier(id) => {
+                self.qualified_identifier(id, flow);
+                self.read(exp, flow);
+            },
+            Expression::Embed(e) => {
+                self.object_initializer(&e.description, flow);
+            },
+            Expression::Paren(e) => self.expression(&e.expression, flow),
+            Expression::Xml(e) => self.xml_element(&e.element, flow),
+            Expression::XmlList(e) => {
+                for content in &e.content {
+                    self.xml_content(content, flow);
+                }
+            },
+            Expression::ArrayLiteral(e) => {
+                for element in &e.elements {
+                    if let Element::Expression(e) | Element::Rest((e, _)) = element {
+                        self.expression(e, flow);
+                    }
+                }
+            },
+            Expression::ObjectInitializer(e) => self.object_initializer(e, flow),
+            Expression::New(e) => {
+                self.expression(&e.base, flow);
+                for argument in e.arguments.iter().flatten() {
+                    self.expression(argument, flow);
+                }
+            },
+            Expression::Member(e) => {
+                self.expression(&e.base, flow);
+                self.qualified_identifier(&e.identifier, flow);
+            },
+            Expression::ComputedMember(e) => {
+                self.expression(&e.base, flow);
+                self.expression(&e.key, flow);
+            },
+            Expression::Descendants(e) => {
+                self.expression(&e.base, flow);
+                self.qualified_identifier(&e.identifier, flow);
+            },
+            Expression::Filter(e) => {
+                self.expression(&e.base, flow);
+                self.expression(&e.test, flow);
+            },
+            Expression::Super(e) => {
+                for object in e.object.iter().flatten() {
+                    self.expression(object, flow);
+                }
+            },
+            Expression::Call(e) => {
+                self.expression(&e.base, flow);
+                for argument in &e.arguments {
+                    self.expression(argument, flow);
+                }
+            },
+            Expression::WithTypeArguments(e) => self.expression(&e.base, flow),
+            Expression::Unary(e) => self.expression(&e.expression, flow),
+            Expression::OptionalChaining(e) => {
+                self.expression(&e.base, flow);
+                self.expression(&e.expression, flow);
+            },
+            Expression::Binary(e) => {
+                self.expression(&e.left, flow);
+                self.expression(&e.right, flow);
+            },
+            Expression::Conditional(e) => {
+                self.expression(&e.test, flow);
+                self.expression(&e.consequent, flow);
+                self.expression(&e.alternative, flow);
+            },
+            Expression::Assignment(e) => {
+                // A simple assignment to an identifier does not read it.
+                if e.compound.is_some() || !matches!(e.left.as_ref(), Expression::QualifiedIdentifier(_)) {
+                    self.expression(&e.left, flow);
+                }
+                self.expression(&e.right, flow);
+            },
+            Expression::Sequence(e) => {
+                self.expression(&e.left, flow);
+                self.expression(&e.right, flow);
+            },
+            _ => {},
+        }
+    }
+
+    fn qualified_identifier(&mut self, id: &QualifiedIdentifier, flow: &Flow) {
+        if let Some(qualifier) = id.qualifier.as_ref() {
+            self.expression(qualifier, flow);
+        }
+        if let QualifiedIdentifierIdentifier::Brackets(e) = &id.id {
+            self.expression(e, flow);
+        }
+    }
+
+    fn object_initializer(&mut self, initializer: &ObjectInitializer, flow: &Flow) {
+        for field in &initializer.fields {
+            match field.as_ref() {
+                InitializerField::Field { name, value, .. } => {
+                    if let FieldName::Brackets(e) = &name.0 {
+                        self.expression(e, flow);
+                    }
+                    if let Some(value) = value.as_ref() {
+                        self.expression(value, flow);
+                    }
+                },
+                InitializerField::Rest((e, _)) => {
+                    self.expression(e, flow);
+                },
+            }
+        }
+    }
+
+    fn xml_element(&mut self, element: &Rc<XmlElement>, flow: &Flow) {
+        if let XmlTagName::Expression(e) = &element.name {
+            self.expression(e, flow);
+        }
+        for attribute in &element.attributes {
+            if let XmlAttributeValue::Expression(e) = &attribute.value {
+                self.expression(e, flow);
+            }
+        }
+        if let Some(e) = element.attribute_expression.as_ref() {
+            self.expression(e, flow);
+        }
+        for content in element.content.iter().flatten() {
+            self.xml_content(content, flow);
+        }
+    }
+
+    fn xml_content(&mut self, content: &Rc<XmlElementContent>, flow: &Flow) {
+        match content.as_ref() {
+            XmlElementContent::XmlElement(element) => self.xml_element(element, flow),
+            XmlElementContent::Expression(e) => self.expression(e, flow),
+            _ => {},
+        }
+    }
+
+    fn read(&mut self, exp: &Rc<Expression>, flow: &Flow) {
+        let Some(assigned) = flow.as_ref() else {
+            return;
+        };
+        let Some(value) = self.verifier.ast_to_symbol.get(exp) else {
+            return;
+        };
+        if !value.is_scope_reference_value() {
+            return;
+        }
+        let variable = value.property();
+        if self.is_local_constant(&variable) && !assigned.contains(&variable) {
+            self.verifier.add_verify_error(&exp.location(), DiagnosticKind::UseBeforeAssignment, diagnostic_arguments![String(variable.name())]);
+        }
+    }
+
+    /// Indicates whether a property is a constant declared
+    /// by the analyzed function.
+    fn is_local_constant(&self, property: &Symbol) -> bool {
+        if !(property.is_variable_property() && property.read_only(&self.verifier.host)) {
+            return false;
+        }
+        let Some(parent) = property.parent() else {
+            return false;
+        };
+        parent.is_scope() && parent.find_activation().as_ref() == Some(&self.activation)
+    }
+}
+
+impl JumpTarget {
+    fn new(labels: Vec<String>, breakable: bool, iteration: bool) -> Self {
+        Self {
+            labels,
+            breakable,
+            iteration,
+            breaks: None,
+            continues: None,
+        }
+    }
+}
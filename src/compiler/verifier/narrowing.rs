@@ -0,0 +1,188 @@
+use crate::ns::*;
+
+/// Flow-sensitive narrowing of variable types.
+///
+/// Tests such as `x != null` and `x is T` narrow the type of a variable
+/// within the region where the test is known to hold. Narrowed types
+/// are attached to a scope entered for that region, so that directives
+/// whose verification is deferred keep them.
+impl VerifierVerifier {
+    /// Returns the list of (variable, narrowed type) implied by
+    /// a verified condition evaluating to `when_true`.
+    pub fn condition_narrowings(&self, exp: &Rc<Expression>, when_true: bool) -> Vec<(Symbol, Symbol)> {
+        match exp.as_ref() {
+            Expression::Paren(paren_exp) => self.condition_narrowings(&paren_exp.expression, when_true),
+            Expression::Unary(ue) if ue.operator == Operator::LogicalNot => {
+                self.condition_narrowings(&ue.expression, !when_true)
+            },
+            Expression::Binary(be) => match be.operator {
+                Operator::LogicalAnd if when_true => {
+                    let mut result = self.condition_narrowings(&be.left, true);
+                    result.extend(self.condition_narrowings(&be.right, true));
+                    result
+                },
+                Operator::LogicalOr if !when_true => {
+                    let mut result = self.condition_narrowings(&be.left, false);
+                    result.extend(self.condition_narrowings(&be.right, false));
+                    result
+                },
+                Operator::Equals | Operator::StrictEquals |
+                Operator::NotEquals | Operator::StrictNotEquals => {
+                    let not_equals = matches!(be.operator, Operator::NotEquals | Operator::StrictNotEquals);
+                    if not_equals != when_true {
+                        return vec![];
+                    }
+                    let variable_exp = if Self::is_null_literal(&be.right) {
+                        &be.left
+                    } else if Self::is_null_literal(&be.left) {
+                        &be.right
+                    } else {
+                        return vec![];
+                    };
+                    let Some((variable, t)) = self.narrowable_variable(variable_exp) else {
+                        return vec![];
+                    };
+                    if t.is_nullable_type() {
+                        vec![(variable, t.non_null_type())]
+                    } else {
+                        vec![]
+                    }
+                },
+                Operator::Is | Operator::IsNot => {
+                    if (be.operator == Operator::Is) != when_true {
+                        return vec![];
+                    }
+                    let Some((variable, t)) = self.narrowable_variable(&be.left) else {
+                        return vec![];
+                    };
+                    let Some(narrowed_type) = self.ast_to_symbol.get(&be.right) else {
+                        return vec![];
+                    };
+                    if !narrowed_type.is_type() || narrowed_type.is_unresolved() || narrowed_type == t {
+                        return vec![];
+                    }
+                    vec![(variable, narrowed_type)]
+                },
+                _ => vec![],
+            },
+            _ => vec![],
+        }
+    }
+
+    /// Returns the variable referenced by an expression
+    /// and its current type.
+    fn narrowable_variable(&self, exp: &Rc<Expression>) -> Option<(Symbol, Symbol)> {
+        if let Expression::Paren(paren_exp) = exp.as_ref() {
+            return self.narrowable_variable(&paren_exp.expression);
+        }
+        let reference = self.ast_to_symbol.get(exp)?;
+        if !(reference.is_scope_reference_value() && reference.property().is_variable_property()) {
+            return None;
+        }
+        let t = self.ast_to_symbol.narrowed_type(exp).unwrap_or(reference.static_type(&self.host));
+        Some((reference.property(), t))
+    }
+
+    fn is_null_literal(exp: &Rc<Expression>) -> bool {
+        match exp.as_ref() {
+            Expression::NullLiteral(_) => true,
+            Expression::Paren(paren_exp) => Self::is_null_literal(&paren_exp.expression),
+            _ => false,
+        }
+    }
+
+    /// Creates a scope within the current scope carrying
+    /// the given narrowings, if any.
+    pub fn create_narrowing_scope(&self, narrowings: Vec<(Symbol, Symbol)>) -> Option<Symbol> {
+        if narrowings.is_empty() {
+            return None;
+        }
+        let scope = self.host.factory().create_scope();
+        scope.set_parent(Some(&self.scope));
+        let mut narrowed_types = scope.narrowed_types();
+        for (variable, t) in narrowings {
+            narrowed_types.set(variable, t);
+        }
+        Some(scope)
+    }
+
+    /// Runs `f` within a scope carrying the given narrowings.
+    pub fn with_narrowings<T>(&mut self, narrowings: Vec<(Symbol, Symbol)>, f: impl FnOnce(&mut Self) -> T) -> T {
+        let Some(scope) = self.create_narrowing_scope(narrowings) else {
+            return f(self);
+        };
+        self.enter_scope(&scope);
+        let result = f(self);
+        self.exit_scope();
+        result
+    }
+
+    /// Returns the type that a variable reference is narrowed to
+    /// in the current scope.
+    ///
+    /// Narrowings of writable variables do not cross function boundaries,
+    /// as the variable may be assigned before the nested function is called.
+    pub fn narrowed_reference_type(&self, reference: &Symbol) -> Option<Symbol> {
+        if !(reference.is_scope_reference_value() && reference.property().is_variable_property()) {
+            return None;
+        }
+        let variable = reference.property();
+        let declaring_scope = reference.base();
+        for scope in self.scope.descending_scope_hierarchy() {
+            if let Some(t) = scope.narrowed_types().get(&variable) {
+                return Some(t);
+            }
+            if scope == declaring_scope || (scope.is_activation_scope() && !variable.read_only(&self.host)) {
+                break;
+            }
+        }
+        None
+    }
+
+    /// Discards narrowings of a variable reference that is assigned.
+    pub fn invalidate_narrowings(&self, reference: &Symbol) {
+        if !(reference.is_scope_reference_value() && reference.property().is_variable_property()) {
+            return;
+        }
+        let variable = reference.property();
+        let declaring_scope = reference.base();
+        for scope in self.scope.descending_scope_hierarchy() {
+            scope.narrowed_types().remove(&variable);
+            if scope == declaring_scope {
+                break;
+            }
+        }
+    }
+
+    /// Indicates whether a statement never completes normally, such as
+    /// a `return` statement or a block ending in one.
+    pub fn exits_unconditionally(directive: &Rc<Directive>) -> bool {
+        match directive.as_ref() {
+            Directive::ReturnStatement(_) |
+            Directive::ThrowStatement(_) |
+            Directive::BreakStatement(_) |
+            Directive::ContinueStatement(_) => true,
+            Directive::Block(block) => block.directives.last().is_some_and(Self::exits_unconditionally),
+            Directive::IfStatement(stmt) => {
+                stmt.alternative.as_ref().is_some_and(|alternative| {
+                    Self::exits_unconditionally(&stmt.consequent) && Self::exits_unconditionally(alternative)
+                })
+            },
+            _ => false,
+        }
+    }
+
+    /// Returns the narrowings that hold after an `if` statement
+    /// one of whose branches never completes normally.
+    pub fn if_statement_narrowings(&self, stmt: &IfStatement) -> Vec<(Symbol, Symbol)> {
+        let consequent_exits = Self::exits_unconditionally(&stmt.consequent);
+        let alternative_exits = stmt.alternative.as_ref().is_some_and(Self::exits_unconditionally);
+        if consequent_exits && !alternative_exits {
+            self.condition_narrowings(&stmt.test, false)
+        } else if alternative_exits && !consequent_exits {
+            self.condition_narrowings(&stmt.test, true)
+        } else {
+            vec![]
+        }
+    }
+}
@@ -0,0 +1,669 @@
+use crate::occurrences::*;
+use crate::panics::*;
+use crate::position::*;
+use crate::workspace::*;
+use hydroper_jet_compiler::ns::*;
+use lsp_types::{self as lsp, CompletionItem, CompletionItemKind, DiagnosticSeverity, DocumentSymbol, Hover, HoverContents, MarkupContent, MarkupKind, NumberOrString, Position, Url};
+use std::collections::{BTreeMap, HashSet};
+use std::rc::Rc;
+
+/// Results of verifying the documents of a workspace,
+/// answering the queries of the language server.
+pub struct Analysis {
+    host: Rc<SymbolHost>,
+    semantics: Option<Rc<TreeSemantics>>,
+    files: BTreeMap<Url, FileAnalysis>,
+    panics: Rc<PanicLog>,
+}
+
+struct FileAnalysis {
+    compilation_unit: Rc<CompilationUnit>,
+    program: Option<Rc<Program>>,
+    line_index: LineIndex,
+    occurrences: Occurrences,
+}
+
+impl FileAnalysis {
+    fn offset(&self, position: Position) -> usize {
+        self.line_index.offset(&self.compilation_unit.current_text(), position)
+    }
+
+    fn range(&self, location: &Location) -> lsp::Range {
+        self.line_index.range(&self.compilation_unit.current_text(), location)
+    }
+}
+
+impl Analysis {
+    /// Verifies the documents of a workspace together
+    /// as the programs of a single symbol host.
+    pub fn new(workspace: &Workspace) -> Self {
+        let host = SymbolHost::new("", "");
+        let panics = workspace.panics().clone();
+        for document in workspace.documents().values() {
+            document.compilation_unit.clear_verify_diagnostics();
+        }
+
+        let programs: Vec<Rc<Program>> = workspace.documents().values().filter_map(|document| document.program.clone()).collect();
+        let semantics = panics.catch("Verifying", || {
+            let mut verifier = Verifier::new(&host);
+            verifier.verify_programs(programs);
+            verifier.ast_to_symbol().clone()
+        });
+
+        let mut files = BTreeMap::new();
+        for (url, document) in workspace.documents() {
+            let compilation_unit = document.compilation_unit.clone();
+            let program = document.program.clone();
+            compilation_unit.sort_diagnostics();
+            let occurrences = match (&program, &semantics) {
+                (Some(program), Some(semantics)) => panics.catch("Collecting occurrences", || Occurrences::collect(program, semantics, &host)),
+                _ => None,
+            };
+            let line_index = LineIndex::new(&compilation_unit.current_text());
+            files.insert(url.clone(), FileAnalysis {
+                compilation_unit,
+                program,
+                line_index,
+                occurrences: occurrences.unwrap_or(Occurrences {
+                    occurrences: vec![],
+                    scopes: vec![],
+                    expressions: vec![],
+                }),
+            });
+        }
+
+        Self { host, semantics, files, panics }
+    }
+
+    pub fn urls(&self) -> impl Iterator<Item = &Url> {
+        self.files.keys()
+    }
+
+    pub fn diagnostics(&self, url: &Url) -> Vec<lsp::Diagnostic> {
+        let Some(file) = self.files.get(url) else {
+            return vec![];
+        };
+        file.compilation_unit.diagnostics().iter().map(|diagnostic| lsp::Diagnostic {
+            range: file.range(&diagnostic.location()),
+            severity: Some(if diagnostic.is_warning() { DiagnosticSeverity::WARNING } else { DiagnosticSeverity::ERROR }),
+            code: Some(NumberOrString::Number(diagnostic.id())),
+            source: Some("jet".into()),
+            message: diagnostic.format_message_english(),
+            ..Default::default()
+        }).collect()
+    }
+
+    /// Describes the identifier at a position with its static type
+    /// and JetDoc comment.
+    pub fn hover(&self, url: &Url, position: Position) -> Option<Hover> {
+        let file = self.files.get(url)?;
+        let occurrence = file.occurrences.occurrence_at(file.offset(position))?;
+        let symbol = &occurrence.symbol;
+        let mut value = format!("```jet\n{}\n```", self.describe(symbol));
+        if let Some(jetdoc) = jetdoc(symbol) {
+            for (tag, _) in &jetdoc.tags {
+                if let JetDocTag::Deprecated { message } = tag {
+                    value.push_str("\n\n**Deprecated.**");
+                    if let Some(message) = message {
+                        value.push(' ');
+                        value.push_str(message);
+                    }
+                }
+            }
+            if let Some((main_body, _)) = &jetdoc.main_body {
+                value.push_str("\n\n");
+                value.push_str(main_body);
+            }
+        }
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value,
+            }),
+            range: Some(file.range(&occurrence.location)),
+        })
+    }
+
+    /// Returns the declaration of the identifier at a position.
+    pub fn definition(&self, url: &Url, position: Position) -> Option<lsp::Location> {
+        let file = self.files.get(url)?;
+        let mut symbol = file.occurrences.occurrence_at(file.offset(position))?.symbol.clone();
+        loop {
+            for (url, file) in &self.files {
+                if let Some(occurrence) = file.occurrences.occurrences.iter().find(|occurrence| occurrence.declaration && occurrence.symbol == symbol) {
+                    return Some(lsp::Location::new(url.clone(), file.range(&occurrence.location)));
+                }
+            }
+            // Aliases produced by imports are declared by
+            // the property they refer to.
+            if !symbol.is_alias() {
+                return None;
+            }
+            let alias_of = symbol.alias_of();
+            if alias_of == symbol {
+                return None;
+            }
+            symbol = alias_of;
+        }
+    }
+
+    /// Returns the occurrences of the property referred to by
+    /// the identifier at a position.
+    pub fn references(&self, url: &Url, position: Position, include_declaration: bool) -> Vec<lsp::Location> {
+        let Some(file) = self.files.get(url) else {
+            return vec![];
+        };
+        let Some(occurrence) = file.occurrences.occurrence_at(file.offset(position)) else {
+            return vec![];
+        };
+        let mut locations = vec![];
+        for (url, file) in &self.files {
+            for occurrence_1 in &file.occurrences.occurrences {
+                if occurrence_1.symbol == occurrence.symbol && (include_declaration || !occurrence_1.declaration) {
+                    locations.push(lsp::Location::new(url.clone(), file.range(&occurrence_1.location)));
+                }
+            }
+        }
+        locations
+    }
+
+    /// Returns the outline of a document, built from its syntax tree.
+    pub fn document_symbols(&self, url: &Url) -> Vec<DocumentSymbol> {
+        let Some(file) = self.files.get(url) else {
+            return vec![];
+        };
+        let Some(program) = &file.program else {
+            return vec![];
+        };
+        let mut symbols = vec![];
+        for package in &program.packages {
+            let children = directive_symbols(file, &package.block.directives, DefinitionContext::Package);
+            if package.name.is_empty() {
+                symbols.extend(children);
+                continue;
+            }
+            let name_location = package.name[0].1.combine_with(package.name.last().unwrap().1.clone());
+            symbols.push(document_symbol(
+                file,
+                package.name.iter().map(|(name, _)| name.clone()).collect::<Vec<_>>().join("."),
+                lsp::SymbolKind::PACKAGE,
+                &package.location,
+                &name_location,
+                Some(children),
+            ));
+        }
+        symbols.extend(directive_symbols(file, &program.directives, DefinitionContext::Other));
+        symbols
+    }
+
+    /// Returns the properties that may complete the identifier
+    /// at a position. After a dot, these are the properties of the
+    /// base expression; otherwise, they are the properties
+    /// visible from the enclosing scope.
+    pub fn completion(&self, url: &Url, position: Position) -> Vec<CompletionItem> {
+        let Some(file) = self.files.get(url) else {
+            return vec![];
+        };
+        let offset = file.offset(position);
+        let text = file.compilation_unit.current_text();
+        let mut start = offset;
+        while let Some(ch) = text[..start].chars().next_back() {
+            if !CharacterValidator::is_identifier_part(ch) {
+                break;
+            }
+            start -= ch.len_utf8();
+        }
+
+        let (resolution_base, names) = if text[..start].ends_with('.') {
+            let Some(base) = file.occurrences.expression_ending_at(start - 1) else {
+                return vec![];
+            };
+            let Some(base) = self.member_base(base) else {
+                return vec![];
+            };
+            let names = self.member_names(&base);
+            (base, names)
+        } else {
+            let Some(scope) = file.occurrences.scope_at(offset) else {
+                return vec![];
+            };
+            (scope.clone(), self.scope_names(scope))
+        };
+        drop(text);
+
+        let mut items = vec![];
+        let mut seen = HashSet::new();
+        for name in names {
+            if !seen.insert(name.clone()) {
+                continue;
+            }
+            let Ok(Some(resolved)) = resolution_base.resolve_property(None, SemanticPropertyKey::String(name.clone()), &self.host) else {
+                continue;
+            };
+            let Some(property) = referenced_property(&resolved, &self.host) else {
+                continue;
+            };
+            items.push(CompletionItem {
+                label: name,
+                kind: Some(self.completion_item_kind(&property)),
+                detail: Some(self.describe(&property)),
+                deprecated: Some(jetdoc(&property).is_some_and(|jetdoc| jetdoc.tags.iter().any(|(tag, _)| matches!(tag, JetDocTag::Deprecated { .. })))),
+                ..Default::default()
+            });
+        }
+        items.sort_by(|a, b| a.label.cmp(&b.label));
+        items
+    }
+
+    /// Returns the semantic tokens of a document, encoded
+    /// as in the Language Server Protocol.
+    pub fn semantic_tokens(&self, url: &Url) -> Vec<u32> {
+        let Some(file) = self.files.get(url) else {
+            return vec![];
+        };
+        let (Some(program), Some(semantics)) = (&file.program, &self.semantics) else {
+            return vec![];
+        };
+        self.panics.catch("Classifying semantic tokens", || SemanticTokens::encode(&SemanticTokens::classify(program, semantics, &self.host))).unwrap_or_default()
+    }
+
+    /// Returns the symbol on which properties after a dot are resolved.
+    fn member_base(&self, base: &Symbol) -> Option<Symbol> {
+        let mut base = base.clone();
+        while base.is_conversion_value() {
+            base = base.base();
+        }
+        if base.is_type_as_reference_value() {
+            return Some(base.referenced_type());
+        }
+        if base.is_package_reference_value() || base.is_static_reference_value() && base.property().is_type() {
+            base = base.property();
+        }
+        (base.is_package() || base.is_class_type() || base.is_enum_type() || base.is_value()).then_some(base)
+    }
+
+    fn member_names(&self, base: &Symbol) -> Vec<String> {
+        let mut names = vec![];
+        if base.is_package() {
+            names.extend(base.properties(&self.host).borrow().keys().cloned());
+            names.extend(base.subpackages().borrow().keys().cloned());
+        } else if base.is_class_type() || base.is_enum_type() {
+            for class in base.descending_class_hierarchy(&self.host) {
+                if class.is_class_type() || class.is_enum_type() {
+                    names.extend(class.static_properties(&self.host).borrow().keys().cloned());
+                }
+            }
+        } else {
+            let base_type = base.static_type(&self.host);
+            if base_type.is_class_type() || base_type.is_enum_type() {
+                for class in base_type.descending_class_hierarchy(&self.host) {
+                    if class.is_class_type() || class.is_enum_type() {
+                        names.extend(class.prototype(&self.host).borrow().keys().cloned());
+                    }
+                }
+            } else if base_type.is_interface_type() {
+                names.extend(base_type.prototype(&self.host).borrow().keys().cloned());
+                for interface in base_type.all_ascending_types(&self.host) {
+                    if interface.is_interface_type() {
+                        names.extend(interface.prototype(&self.host).borrow().keys().cloned());
+                    }
+                }
+            }
+        }
+        names.sort();
+        names
+    }
+
+    /// Collects the names visible from a scope, from the innermost
+    /// scope outwards.
+    fn scope_names(&self, scope: &Symbol) -> Vec<String> {
+        let mut names = vec![];
+        for scope in scope.descending_scope_hierarchy() {
+            if !scope.is_scope() {
+                continue;
+            }
+            names.extend(scope.properties(&self.host).borrow().keys().cloned());
+            names.extend(scope.imports().borrow().keys().cloned());
+            names.extend(scope.package_aliases().borrow().keys().cloned());
+            for package in scope.open_packages().iter() {
+                names.extend(package.properties(&self.host).borrow().keys().cloned());
+            }
+            if scope.is_class_scope() || scope.is_enum_scope() {
+                names.extend(self.member_names(&scope.class()));
+            }
+            if scope.is_package_scope() {
+                names.extend(scope.package().properties(&self.host).borrow().keys().cloned());
+            }
+        }
+        names.extend(self.host.top_level_package().subpackages().borrow().keys().cloned());
+        names
+    }
+
+    fn completion_item_kind(&self, property: &Symbol) -> CompletionItemKind {
+        let parent = if property.is_variable_property() || property.is_virtual_property() || property.is_method() {
+            property.parent()
+        } else {
+            None
+        };
+        let member = parent.as_ref().is_some_and(|parent| parent.is_type());
+        if property.is_class_type() {
+            CompletionItemKind::CLASS
+        } else if property.is_interface_type() {
+            CompletionItemKind::INTERFACE
+        } else if property.is_enum_type() {
+            CompletionItemKind::ENUM
+        } else if property.is_type() || property.is_alias() {
+            CompletionItemKind::TYPE_PARAMETER
+        } else if property.is_package() || property.is_package_set() {
+            CompletionItemKind::MODULE
+        } else if property.is_method() {
+            if member { CompletionItemKind::METHOD } else { CompletionItemKind::FUNCTION }
+        } else if property.is_virtual_property() {
+            CompletionItemKind::PROPERTY
+        } else if parent.as_ref().is_some_and(|parent| parent.is_enum_type()) && property.read_only(&self.host) {
+            CompletionItemKind::ENUM_MEMBER
+        } else if member {
+            CompletionItemKind::FIELD
+        } else if property.read_only(&self.host) {
+            CompletionItemKind::CONSTANT
+        } else {
+            CompletionItemKind::VARIABLE
+        }
+    }
+
+    /// Describes a property as it would be declared in source code.
+    fn describe(&self, symbol: &Symbol) -> String {
+        if symbol.is_class_type() {
+            format!("class {}", symbol.to_string())
+        } else if symbol.is_interface_type() {
+            format!("interface {}", symbol.to_string())
+        } else if symbol.is_enum_type() {
+            format!("enum {}", symbol.to_string())
+        } else if symbol.is_type() {
+            type_name(symbol)
+        } else if symbol.is_alias() {
+            // The aliased type remains unresolved if it could
+            // not be resolved, such as in cyclic definitions.
+            let alias_of = symbol.alias_of();
+            let alias_of = if alias_of.is_type() || alias_of.is_package() || alias_of.is_unresolved() { type_name(&alias_of) } else { alias_of.fully_qualified_name() };
+            format!("type {} = {}", symbol.fully_qualified_name(), alias_of)
+        } else if symbol.is_package() || symbol.is_package_set() {
+            format!("package {}", symbol.fully_qualified_name())
+        } else if symbol.is_method() {
+            let signature = symbol.signature(&self.host);
+            let signature = type_name(&signature);
+            format!("function {}{}", symbol.name(), signature.strip_prefix("function").unwrap_or(""))
+        } else if symbol.is_variable_property() || symbol.is_virtual_property() {
+            let kind = if symbol.read_only(&self.host) { "const" } else { "var" };
+            format!("{} {}: {}", kind, symbol.name(), type_name(&symbol.static_type(&self.host)))
+        } else {
+            symbol.name()
+        }
+    }
+}
+
+fn type_name(symbol: &Symbol) -> String {
+    if symbol.is_type() || symbol.is_package() || symbol.is_package_set() || symbol.is_alias() {
+        symbol.to_string()
+    } else {
+        "*".into()
+    }
+}
+
+/// Returns the JetDoc comment of a property, if it may have one.
+fn jetdoc(symbol: &Symbol) -> Option<Rc<JetDoc>> {
+    let documented = symbol.is_class_type() || symbol.is_interface_type() || symbol.is_enum_type()
+        || symbol.is_alias() || symbol.is_package() || symbol.is_package_set()
+        || symbol.is_variable_property() || symbol.is_virtual_property() || symbol.is_method();
+    if documented { symbol.jetdoc() } else { None }
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum DefinitionContext {
+    Package,
+    Class,
+    Enum,
+    Other,
+}
+
+fn directive_symbols(file: &FileAnalysis, directives: &[Rc<Directive>], context: DefinitionContext) -> Vec<DocumentSymbol> {
+    let mut symbols = vec![];
+    for directive in directives {
+        match directive.as_ref() {
+            Directive::ClassDefinition(defn) => {
+                let children = directive_symbols(file, &defn.block.directives, DefinitionContext::Class);
+                symbols.push(document_symbol(file, defn.name.0.clone(), lsp::SymbolKind::CLASS, &defn.location, &defn.name.1, Some(children)));
+            },
+            Directive::EnumDefinition(defn) => {
+                let children = directive_symbols(file, &defn.block.directives, DefinitionContext::Enum);
+                symbols.push(document_symbol(file, defn.name.0.clone(), lsp::SymbolKind::ENUM, &defn.location, &defn.name.1, Some(children)));
+            },
+            Directive::InterfaceDefinition(defn) => {
+                let children = directive_symbols(file, &defn.block.directives, DefinitionContext::Class);
+                symbols.push(document_symbol(file, defn.name.0.clone(), lsp::SymbolKind::INTERFACE, &defn.location, &defn.name.1, Some(children)));
+            },
+            Directive::TypeDefinition(defn) => {
+                symbols.push(document_symbol(file, defn.left.0.clone(), lsp::SymbolKind::TYPE_PARAMETER, &defn.location, &defn.left.1, None));
+            },
+            Directive::FunctionDefinition(defn) => {
+                let (name, kind) = match &defn.name {
+                    FunctionName::Identifier((name, _)) => (name.clone(), if context == DefinitionContext::Class || context == DefinitionContext::Enum { lsp::SymbolKind::METHOD } else { lsp::SymbolKind::FUNCTION }),
+                    FunctionName::Getter((name, _)) => (format!("get {name}"), lsp::SymbolKind::PROPERTY),
+                    FunctionName::Setter((name, _)) => (format!("set {name}"), lsp::SymbolKind::PROPERTY),
+                    FunctionName::Constructor((name, _)) => (name.clone(), lsp::SymbolKind::CONSTRUCTOR),
+                    FunctionName::Proxy(_, (name, _)) => (format!("proxy::{name}"), lsp::SymbolKind::METHOD),
+                };
+                symbols.push(document_symbol(file, name, kind, &defn.location, &defn.name.location(), None));
+            },
+            Directive::VariableDefinition(defn) => {
+                let kind = match context {
+                    DefinitionContext::Class => lsp::SymbolKind::FIELD,
+                    DefinitionContext::Enum if defn.kind.0 == VariableDefinitionKind::Const => lsp::SymbolKind::ENUM_MEMBER,
+                    DefinitionContext::Enum => lsp::SymbolKind::FIELD,
+                    _ if defn.kind.0 == VariableDefinitionKind::Const => lsp::SymbolKind::CONSTANT,
+                    _ => lsp::SymbolKind::VARIABLE,
+                };
+                for binding in &defn.bindings {
+                    if let Some((name, location)) = binding.destructuring.destructuring.to_identifier_name() {
+                        symbols.push(document_symbol(file, name, kind, &defn.location, &location, None));
+                    }
+                }
+            },
+            Directive::Block(block) => {
+                symbols.extend(directive_symbols(file, &block.directives, context));
+            },
+            Directive::ConfigurationDirective(cd) => {
+                symbols.extend(directive_symbols(file, std::slice::from_ref(&cd.directive), context));
+            },
+            _ => {},
+        }
+    }
+    symbols
+}
+
+fn document_symbol(file: &FileAnalysis, name: String, kind: lsp::SymbolKind, location: &Location, name_location: &Location, children: Option<Vec<DocumentSymbol>>) -> DocumentSymbol {
+    #[allow(deprecated)]
+    DocumentSymbol {
+        name,
+        detail: None,
+        kind,
+        tags: None,
+        deprecated: None,
+        range: file.range(location),
+        selection_range: file.range(name_location),
+        children,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JET_LANG: &str = r#"
+        package jet.lang {
+            public class Object {}
+            public class Boolean {}
+            public class Class {}
+            public class Function {}
+            public class Number {}
+            public class String {}
+        }
+    "#;
+
+    const MAIN: &str = r#"
+        package p {
+            /** A point. */
+            public class Point {
+                public var x: Number = 0;
+                public function get y(): Number {
+                    return 0;
+                }
+                public function move(dx: Number): void {
+                    x = dx;
+                }
+            }
+            public function f(point: Point): void {
+                point.x = point.y;
+                point.move(1);
+            }
+        }
+    "#;
+
+    fn workspace(text: &str) -> (Workspace, Url) {
+        let url = Url::parse("file:///main.jet").unwrap();
+        let mut workspace = Workspace::new(CompilerOptions::new(), &Rc::new(PanicLog::default()));
+        workspace.open(Url::parse("file:///jet_lang.jet").unwrap(), JET_LANG.to_owned());
+        workspace.open(url.clone(), text.to_owned());
+        (workspace, url)
+    }
+
+    fn analyze(text: &str) -> (Analysis, Url) {
+        let (workspace, url) = workspace(text);
+        (Analysis::new(&workspace), url)
+    }
+
+    /// Returns the position of the start of the `nth` occurrence
+    /// of a string.
+    fn position_of(text: &str, needle: &str, nth: usize) -> Position {
+        let offset = text.match_indices(needle).nth(nth).unwrap().0;
+        LineIndex::new(text).position(text, offset)
+    }
+
+    fn hover_text(analysis: &Analysis, url: &Url, position: Position) -> String {
+        match analysis.hover(url, position).unwrap().contents {
+            HoverContents::Markup(markup) => markup.value,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn reporting_diagnostics() {
+        let (analysis, url) = analyze(MAIN);
+        assert!(analysis.diagnostics(&url).is_empty());
+        let (analysis, url) = analyze("package q { public var z: Undefined; }");
+        let diagnostics = analysis.diagnostics(&url);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::ERROR));
+        assert_eq!(diagnostics[0].range, lsp::Range::new(Position::new(0, 26), Position::new(0, 35)));
+    }
+
+    #[test]
+    fn reanalyzing_changed_documents() {
+        let text = "package q { public var z: Undefined; }";
+        let (mut workspace, url) = workspace(text);
+        let compilation_unit = workspace.documents()[&url].compilation_unit.clone();
+        assert_eq!(Analysis::new(&workspace).diagnostics(&url).len(), 1);
+
+        let change = |range: lsp::Range, text: &str| lsp::TextDocumentContentChangeEvent {
+            range: Some(range),
+            range_length: None,
+            text: text.into(),
+        };
+        workspace.change(&url, vec![change(lsp::Range::new(Position::new(0, 38), Position::new(0, 38)), "\npackage r {}")]);
+        assert!(Rc::ptr_eq(&workspace.documents()[&url].compilation_unit, &compilation_unit));
+        let diagnostics = Analysis::new(&workspace).diagnostics(&url);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, lsp::Range::new(Position::new(0, 26), Position::new(0, 35)));
+
+        workspace.change(&url, vec![change(lsp::Range::new(Position::new(0, 26), Position::new(0, 35)), "Number")]);
+        assert_eq!(*compilation_unit.current_text(), "package q { public var z: Number; }\npackage r {}");
+        assert!(Analysis::new(&workspace).diagnostics(&url).is_empty());
+        assert!(workspace.panics().take().is_empty());
+    }
+
+    #[test]
+    fn hovering() {
+        let (analysis, url) = analyze(MAIN);
+        assert_eq!(hover_text(&analysis, &url, position_of(MAIN, "Point", 1)), "```jet\nclass p.Point\n```\n\nA point.");
+        assert_eq!(hover_text(&analysis, &url, position_of(MAIN, "x", 4)), "```jet\nvar x: jet.lang.Number\n```");
+        assert_eq!(hover_text(&analysis, &url, position_of(MAIN, "y", 1)), "```jet\nconst y: jet.lang.Number\n```");
+        assert_eq!(hover_text(&analysis, &url, position_of(MAIN, "move", 1)), "```jet\nfunction move(dx: jet.lang.Number): void\n```");
+
+        let text = "package q { public type T = U; public type U = T; }";
+        let (analysis, url) = analyze(text);
+        assert_eq!(hover_text(&analysis, &url, position_of(text, "T", 0)), "```jet\ntype q.T = *\n```");
+    }
+
+    #[test]
+    fn finding_definitions_and_references() {
+        let (analysis, url) = analyze(MAIN);
+        let definition = analysis.definition(&url, position_of(MAIN, "y", 1)).unwrap();
+        assert_eq!(definition.range.start, position_of(MAIN, "y", 0));
+        let definition = analysis.definition(&url, position_of(MAIN, "dx", 1)).unwrap();
+        assert_eq!(definition.range.start, position_of(MAIN, "dx", 0));
+
+        let references = analysis.references(&url, position_of(MAIN, "x", 2), true);
+        assert_eq!(references.iter().map(|location| location.range.start).collect::<Vec<_>>(), vec![
+            position_of(MAIN, "x", 0),
+            position_of(MAIN, "x", 2),
+            position_of(MAIN, "x", 4),
+        ]);
+        assert_eq!(analysis.references(&url, position_of(MAIN, "x", 2), false).len(), 2);
+    }
+
+    #[test]
+    fn listing_document_symbols() {
+        let (analysis, url) = analyze(MAIN);
+        fn outline(symbols: &[DocumentSymbol]) -> Vec<(String, lsp::SymbolKind)> {
+            symbols.iter().map(|symbol| (symbol.name.clone(), symbol.kind)).collect()
+        }
+        let symbols = analysis.document_symbols(&url);
+        assert_eq!(outline(&symbols), vec![("p".into(), lsp::SymbolKind::PACKAGE)]);
+        let package_children = symbols[0].children.as_ref().unwrap();
+        assert_eq!(outline(package_children), vec![
+            ("Point".into(), lsp::SymbolKind::CLASS),
+            ("f".into(), lsp::SymbolKind::FUNCTION),
+        ]);
+        assert_eq!(outline(package_children[0].children.as_ref().unwrap()), vec![
+            ("x".into(), lsp::SymbolKind::FIELD),
+            ("get y".into(), lsp::SymbolKind::PROPERTY),
+            ("move".into(), lsp::SymbolKind::METHOD),
+        ]);
+        assert_eq!(package_children[1].children, None);
+    }
+
+    #[test]
+    fn completing_identifiers() {
+        let text = MAIN.replace("point.move(1);", "point.mo");
+        let (analysis, url) = analyze(&text);
+        let position = position_of(&text, "point.mo", 0);
+        let items = analysis.completion(&url, Position::new(position.line, position.character + 8));
+        assert_eq!(items.iter().map(|item| (item.label.as_str(), item.kind.unwrap())).collect::<Vec<_>>(), vec![
+            ("move", CompletionItemKind::METHOD),
+            ("x", CompletionItemKind::FIELD),
+            ("y", CompletionItemKind::PROPERTY),
+        ]);
+
+        let text = MAIN.replace("point.move(1);", "point.");
+        let (analysis_1, url_1) = analyze(&text);
+        let items = analysis_1.completion(&url_1, Position::new(position.line, position.character + 6));
+        assert_eq!(items.iter().map(|item| item.label.as_str()).collect::<Vec<_>>(), vec!["move", "x", "y"]);
+
+        let items = analysis.completion(&url, position);
+        let labels = items.iter().map(|item| item.label.as_str()).collect::<Vec<_>>();
+        for label in ["Number", "Point", "f", "jet", "p", "point"] {
+            assert!(labels.contains(&label), "{label} in {labels:?}");
+        }
+        assert!(!labels.contains(&"dx"));
+    }
+}
@@ -0,0 +1,17 @@
+mod analysis;
+mod occurrences;
+mod panics;
+mod position;
+mod server;
+mod workspace;
+
+use lsp_server::Connection;
+use std::error::Error;
+
+/// Runs the language server over the standard input and output.
+fn main() -> Result<(), Box<dyn Error + Sync + Send>> {
+    let (connection, io_threads) = Connection::stdio();
+    server::Server::run(connection)?;
+    io_threads.join()?;
+    Ok(())
+}
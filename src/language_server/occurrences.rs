@@ -0,0 +1,232 @@
+use hydroper_jet_compiler::ns::*;
+use std::rc::Rc;
+
+/// An identifier that refers to or declares a property.
+pub struct Occurrence {
+    pub location: Location,
+    pub symbol: Symbol,
+    pub declaration: bool,
+}
+
+/// Identifiers, scopes and verified expressions of a program,
+/// indexed by their location.
+pub struct Occurrences {
+    pub occurrences: Vec<Occurrence>,
+    /// Scopes along with the location of the node that introduces them.
+    pub scopes: Vec<(Location, Symbol)>,
+    /// Expressions along with the symbol that the verifier assigned to them.
+    pub expressions: Vec<(Location, Symbol)>,
+}
+
+impl Occurrences {
+    pub fn collect(program: &Rc<Program>, semantics: &TreeSemantics, host: &SymbolHost) -> Self {
+        let mut collector = OccurrenceCollector {
+            semantics,
+            host,
+            occurrences: Occurrences {
+                occurrences: vec![],
+                scopes: vec![],
+                expressions: vec![],
+            },
+        };
+        if let Some(activation) = semantics.get(program) {
+            collector.occurrences.scopes.push((program.location.clone(), activation));
+        }
+        for package in &program.packages {
+            if let Some(scope) = semantics.get(package) {
+                collector.occurrences.scopes.push((package.location.clone(), scope));
+            }
+        }
+        collector.visit_program(program);
+        collector.occurrences
+    }
+
+    /// Returns the innermost occurrence at a byte offset.
+    pub fn occurrence_at(&self, offset: usize) -> Option<&Occurrence> {
+        self.occurrences.iter()
+            .filter(|occurrence| occurrence.location.first_offset() <= offset && offset <= occurrence.location.last_offset())
+            .min_by_key(|occurrence| occurrence.location.last_offset() - occurrence.location.first_offset())
+    }
+
+    /// Returns the innermost scope enclosing a byte offset.
+    pub fn scope_at(&self, offset: usize) -> Option<&Symbol> {
+        self.scopes.iter()
+            .filter(|(location, _)| location.first_offset() <= offset && offset <= location.last_offset())
+            .min_by_key(|(location, _)| location.last_offset() - location.first_offset())
+            .map(|(_, scope)| scope)
+    }
+
+    /// Returns the outermost expression that ends at a byte offset.
+    pub fn expression_ending_at(&self, offset: usize) -> Option<&Symbol> {
+        self.expressions.iter()
+            .filter(|(location, _)| location.last_offset() == offset)
+            .min_by_key(|(location, _)| location.first_offset())
+            .map(|(_, symbol)| symbol)
+    }
+}
+
+/// Returns the property, type or package that a symbol assigned
+/// to an identifier refers to.
+pub fn referenced_property(symbol: &Symbol, host: &SymbolHost) -> Option<Symbol> {
+    let mut symbol = symbol.clone();
+    while symbol.is_conversion_value() {
+        symbol = symbol.base();
+    }
+    let property = if symbol.is_type_as_reference_value() {
+        symbol.referenced_type()
+    } else if symbol.is_static_reference_value() || symbol.is_instance_reference_value() || symbol.is_scope_reference_value() || symbol.is_package_reference_value() {
+        symbol.property()
+    } else if symbol.is_type() || symbol.is_package() || symbol.is_package_set()
+        || symbol.is_alias() || symbol.is_variable_property() || symbol.is_virtual_property() || symbol.is_method() {
+        symbol
+    } else {
+        return None;
+    };
+    Some(origin_property(&property, host))
+}
+
+/// Returns the original property of a property obtained through
+/// type substitution.
+fn origin_property(property: &Symbol, host: &SymbolHost) -> Symbol {
+    let mut property = property.clone();
+    while property.is_type_after_explicit_type_substitution()
+        || property.is_variable_property_after_indirect_type_substitution()
+        || property.is_virtual_property_after_indirect_type_substitution()
+        || property.is_method_after_indirect_type_substitution() {
+        property = property.origin();
+    }
+    if property.is_method() {
+        if let Some(virtual_property) = property.of_virtual_property(host) {
+            return virtual_property;
+        }
+    }
+    property
+}
+
+struct OccurrenceCollector<'a> {
+    semantics: &'a TreeSemantics,
+    host: &'a SymbolHost,
+    occurrences: Occurrences,
+}
+
+impl OccurrenceCollector<'_> {
+    fn push(&mut self, location: &Location, symbol: &Symbol, declaration: bool) {
+        self.occurrences.occurrences.push(Occurrence {
+            location: location.clone(),
+            symbol: origin_property(symbol, self.host),
+            declaration,
+        });
+    }
+
+    fn push_scope(&mut self, location: &Location, scope: Option<Symbol>) {
+        if let Some(scope) = scope.filter(|scope| scope.is_scope()) {
+            self.occurrences.scopes.push((location.clone(), scope));
+        }
+    }
+
+    /// Adds occurrences for the names of a package, returning the package
+    /// if it exists.
+    fn add_package_name(&mut self, name: &[(String, Location)], declaration: bool) -> Option<Symbol> {
+        let mut package = Some(self.host.top_level_package());
+        for (i, (name_1, location)) in name.iter().enumerate() {
+            package = package.and_then(|package| package.subpackages().get(name_1));
+            if let Some(package) = &package {
+                self.push(location, package, declaration && i + 1 == name.len());
+            }
+        }
+        package
+    }
+
+    fn add_function(&mut self, common: &Rc<FunctionCommon>) {
+        if let Some(function) = self.semantics.get(common) {
+            self.push_scope(&common.location, function.activation_scope());
+        }
+    }
+}
+
+impl Visitor for OccurrenceCollector<'_> {
+    fn visit_package_definition(&mut self, node: &PackageDefinition) {
+        self.add_package_name(&node.name, true);
+        self.walk_package_definition(node);
+    }
+
+    fn visit_import_directive(&mut self, node: &ImportDirective) {
+        let package = self.add_package_name(&node.package_name, false);
+        if let (Some(package), ImportSpecifier::Identifier((name, location))) = (package, &node.import_specifier) {
+            if let Some(property) = package.properties(self.host).get(name) {
+                self.push(location, &property, false);
+            }
+        }
+    }
+
+    fn visit_directive(&mut self, node: &Rc<Directive>) {
+        let symbol = self.semantics.get(node);
+        match node.as_ref() {
+            Directive::ClassDefinition(ClassDefinition { name: (_, location), .. }) |
+            Directive::EnumDefinition(EnumDefinition { name: (_, location), .. }) |
+            Directive::InterfaceDefinition(InterfaceDefinition { name: (_, location), .. }) |
+            Directive::TypeDefinition(TypeDefinition { left: (_, location), .. }) => {
+                if let Some(symbol) = &symbol {
+                    self.push(location, symbol, true);
+                }
+            },
+            _ => self.push_scope(&node.location(), symbol),
+        }
+        self.walk_directive(node);
+    }
+
+    fn visit_class_definition(&mut self, node: &ClassDefinition) {
+        self.push_scope(&node.block.location, self.semantics.get(&node.block));
+        self.walk_class_definition(node);
+    }
+
+    fn visit_enum_definition(&mut self, node: &EnumDefinition) {
+        self.push_scope(&node.block.location, self.semantics.get(&node.block));
+        self.walk_enum_definition(node);
+    }
+
+    fn visit_interface_definition(&mut self, node: &InterfaceDefinition) {
+        self.push_scope(&node.block.location, self.semantics.get(&node.block));
+        self.walk_interface_definition(node);
+    }
+
+    fn visit_function_definition(&mut self, node: &FunctionDefinition) {
+        if let Some(function) = self.semantics.get(&node.common) {
+            self.push(&node.name.location(), &function, true);
+        }
+        self.add_function(&node.common);
+        self.walk_function_definition(node);
+    }
+
+    fn visit_function_expression(&mut self, node: &FunctionExpression) {
+        self.add_function(&node.common);
+        self.walk_function_expression(node);
+    }
+
+    fn visit_catch_clause(&mut self, node: &CatchClause) {
+        self.push_scope(&node.block.location, self.semantics.get(&node.block));
+        self.walk_catch_clause(node);
+    }
+
+    fn visit_expression(&mut self, node: &Rc<Expression>) {
+        if let Some(symbol) = self.semantics.get(node) {
+            self.occurrences.expressions.push((node.location(), symbol.clone()));
+            match node.as_ref() {
+                Expression::QualifiedIdentifier(QualifiedIdentifier { id: QualifiedIdentifierIdentifier::Id((_, location)), .. }) |
+                Expression::Member(MemberExpression { identifier: QualifiedIdentifier { id: QualifiedIdentifierIdentifier::Id((_, location)), .. }, .. }) => {
+                    if let Some(property) = referenced_property(&symbol, self.host) {
+                        // Destructuring patterns are assigned the variables they declare.
+                        let declaration = symbol.is_variable_property();
+                        self.occurrences.occurrences.push(Occurrence {
+                            location: location.clone(),
+                            symbol: property,
+                            declaration,
+                        });
+                    }
+                },
+                _ => {},
+            }
+        }
+        self.walk_expression(node);
+    }
+}
@@ -0,0 +1,56 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// Collects the panics caught while serving the client, which
+/// the server reports through `window/logMessage`.
+///
+/// Panics are bugs of the compiler; catching them only keeps the
+/// server alive until they are fixed.
+#[derive(Default)]
+pub struct PanicLog {
+    messages: RefCell<Vec<String>>,
+}
+
+impl PanicLog {
+    /// Runs a task, returning `None` and recording the panic
+    /// message if it panics.
+    pub fn catch<T>(&self, task: &str, f: impl FnOnce() -> T) -> Option<T> {
+        match catch_unwind(AssertUnwindSafe(f)) {
+            Ok(result) => Some(result),
+            Err(payload) => {
+                self.messages.borrow_mut().push(format!("{task} panicked: {}", panic_message(payload.as_ref())));
+                None
+            },
+        }
+    }
+
+    /// Removes and returns the recorded messages.
+    pub fn take(&self) -> Vec<String> {
+        std::mem::take(&mut self.messages.borrow_mut())
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).into()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recording_panics() {
+        let panics = PanicLog::default();
+        assert_eq!(panics.catch("Parsing", || 10), Some(10));
+        assert_eq!(panics.catch("Verifying", || -> i32 { panic!("index {} out of bounds", 4) }), None);
+        assert_eq!(panics.take(), vec!["Verifying panicked: index 4 out of bounds".to_owned()]);
+        assert!(panics.take().is_empty());
+    }
+}
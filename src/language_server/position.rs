@@ -0,0 +1,68 @@
+use hydroper_jet_compiler::ns::*;
+use lsp_types::{Position, Range};
+
+/// Converts between byte offsets into a source text and the positions
+/// of the Language Server Protocol, whose characters are counted
+/// in UTF-16 code units.
+pub struct LineIndex {
+    /// Offset of the first character of each line.
+    line_offsets: Vec<usize>,
+}
+
+impl LineIndex {
+    pub fn new(text: &str) -> Self {
+        let mut line_offsets = vec![0];
+        for (offset, ch) in text.char_indices() {
+            if ch == '\n' {
+                line_offsets.push(offset + 1);
+            }
+        }
+        Self { line_offsets }
+    }
+
+    pub fn position(&self, text: &str, offset: usize) -> Position {
+        let offset = offset.min(text.len());
+        let line = self.line_offsets.partition_point(|line_offset| *line_offset <= offset) - 1;
+        let character = text[self.line_offsets[line]..offset].encode_utf16().count();
+        Position::new(line as u32, character as u32)
+    }
+
+    /// Returns the offset of a position. Positions past the end of
+    /// a line are clamped to the end of that line.
+    pub fn offset(&self, text: &str, position: Position) -> usize {
+        let Some(line_offset) = self.line_offsets.get(position.line as usize) else {
+            return text.len();
+        };
+        let mut character = 0;
+        for (offset, ch) in text[*line_offset..].char_indices() {
+            if character >= position.character as usize || ch == '\n' {
+                return line_offset + offset;
+            }
+            character += ch.len_utf16();
+        }
+        text.len()
+    }
+
+    pub fn range(&self, text: &str, location: &Location) -> Range {
+        Range::new(self.position(text, location.first_offset()), self.position(text, location.last_offset()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converting_positions() {
+        let text = "a\n\u{10000}b\r\nc";
+        let index = LineIndex::new(text);
+        assert_eq!(index.position(text, 0), Position::new(0, 0));
+        assert_eq!(index.position(text, 2), Position::new(1, 0));
+        assert_eq!(index.position(text, 6), Position::new(1, 2));
+        assert_eq!(index.position(text, 9), Position::new(2, 0));
+        assert_eq!(index.offset(text, Position::new(1, 2)), 6);
+        assert_eq!(index.offset(text, Position::new(1, 40)), 8);
+        assert_eq!(index.offset(text, Position::new(2, 1)), 10);
+        assert_eq!(index.offset(text, Position::new(5, 0)), 10);
+    }
+}
@@ -0,0 +1,331 @@
+use crate::analysis::*;
+use crate::panics::*;
+use crate::workspace::*;
+use hydroper_jet_compiler::ns::{CompilerOptions, SemanticTokenModifiers, SemanticTokenType};
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::notification::{DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, LogMessage, Notification as _, PublishDiagnostics};
+use lsp_types::request::{Completion, DocumentSymbolRequest, GotoDefinition, HoverRequest, References, Request as _, SemanticTokensFullRequest};
+use lsp_types::{
+    CompletionOptions, CompletionResponse, DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
+    DocumentSymbolResponse, GotoDefinitionResponse, HoverProviderCapability, InitializeParams, InitializeResult, LogMessageParams, MessageType, OneOf,
+    PublishDiagnosticsParams, SemanticTokensFullOptions, SemanticTokensLegend, SemanticTokensOptions,
+    SemanticTokensResult, SemanticTokensServerCapabilities, ServerCapabilities, TextDocumentSyncCapability,
+    TextDocumentSyncKind, Url,
+};
+use std::error::Error;
+use std::rc::Rc;
+
+/// A language server that answers requests of a client connected
+/// through JSON-RPC.
+///
+/// At initialization, the server loads the compiler options of the
+/// project and the sources under its source roots. Documents are
+/// synchronized incrementally, and every change verifies the
+/// workspace again.
+pub struct Server {
+    connection: Connection,
+    workspace: Workspace,
+    analysis: Analysis,
+    panics: Rc<PanicLog>,
+}
+
+impl Server {
+    pub fn capabilities() -> ServerCapabilities {
+        ServerCapabilities {
+            text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::INCREMENTAL)),
+            hover_provider: Some(HoverProviderCapability::Simple(true)),
+            definition_provider: Some(OneOf::Left(true)),
+            references_provider: Some(OneOf::Left(true)),
+            document_symbol_provider: Some(OneOf::Left(true)),
+            completion_provider: Some(CompletionOptions {
+                trigger_characters: Some(vec![".".into()]),
+                ..Default::default()
+            }),
+            semantic_tokens_provider: Some(SemanticTokensServerCapabilities::SemanticTokensOptions(SemanticTokensOptions {
+                legend: SemanticTokensLegend {
+                    token_types: SemanticTokenType::ALL.iter().map(|token_type| lsp_types::SemanticTokenType::new(token_type.name())).collect(),
+                    token_modifiers: SemanticTokenModifiers::NAMES.iter().map(|name| lsp_types::SemanticTokenModifier::new(name)).collect(),
+                },
+                full: Some(SemanticTokensFullOptions::Bool(true)),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+    }
+
+    /// Performs the initialization handshake and serves requests
+    /// until the client shuts the server down.
+    pub fn run(connection: Connection) -> Result<(), Box<dyn Error + Sync + Send>> {
+        let (id, params) = connection.initialize_start()?;
+        let params: InitializeParams = serde_json::from_value(params)?;
+        connection.initialize_finish(id, serde_json::to_value(InitializeResult {
+            capabilities: Self::capabilities(),
+            server_info: None,
+        })?)?;
+
+        #[allow(deprecated)]
+        let root = match &params.workspace_folders {
+            Some(folders) => folders.first().map(|folder| folder.uri.clone()),
+            None => params.root_uri.clone(),
+        };
+        let root = root.and_then(|root| root.to_file_path().ok());
+        let mut messages = vec![];
+        let compiler_options = Workspace::load_compiler_options(root.as_deref(), params.initialization_options.as_ref()).unwrap_or_else(|error| {
+            messages.push((MessageType::ERROR, error));
+            CompilerOptions::default()
+        });
+        let panics = Rc::new(PanicLog::default());
+        let mut workspace = Workspace::new(Rc::new(compiler_options), &panics);
+        match Workspace::load_ast_cache(root.as_deref(), params.initialization_options.as_ref()) {
+            Ok(Some(ast_cache)) => workspace.set_ast_cache(ast_cache),
+            Ok(None) => {},
+            Err(error) => messages.push((MessageType::ERROR, error)),
+        }
+        for path in workspace.load_source_roots() {
+            messages.push((MessageType::WARNING, format!("Could not read {}.", path.display())));
+        }
+        let mut server = Server {
+            connection,
+            analysis: Analysis::new(&workspace),
+            workspace,
+            panics,
+        };
+        for (typ, message) in messages {
+            server.log_message(typ, message)?;
+        }
+        server.publish_all_diagnostics()?;
+        server.log_panics()?;
+
+        let receiver = server.connection.receiver.clone();
+        for message in &receiver {
+            match message {
+                Message::Request(request) => {
+                    if server.connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    let response = server.handle_request(request);
+                    server.connection.sender.send(Message::Response(response))?;
+                    server.log_panics()?;
+                },
+                Message::Notification(notification) => {
+                    server.handle_notification(notification)?;
+                },
+                Message::Response(_) => {},
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_request(&self, request: Request) -> Response {
+        match request.method.as_str() {
+            HoverRequest::METHOD => self.respond::<HoverRequest>(request, |analysis, params| {
+                let params = params.text_document_position_params;
+                analysis.hover(&params.text_document.uri, params.position)
+            }),
+            GotoDefinition::METHOD => self.respond::<GotoDefinition>(request, |analysis, params| {
+                let params = params.text_document_position_params;
+                analysis.definition(&params.text_document.uri, params.position).map(GotoDefinitionResponse::Scalar)
+            }),
+            References::METHOD => self.respond::<References>(request, |analysis, params| {
+                let position = params.text_document_position;
+                Some(analysis.references(&position.text_document.uri, position.position, params.context.include_declaration))
+            }),
+            DocumentSymbolRequest::METHOD => self.respond::<DocumentSymbolRequest>(request, |analysis, params| {
+                Some(DocumentSymbolResponse::Nested(analysis.document_symbols(&params.text_document.uri)))
+            }),
+            Completion::METHOD => self.respond::<Completion>(request, |analysis, params| {
+                let params = params.text_document_position;
+                Some(CompletionResponse::Array(analysis.completion(&params.text_document.uri, params.position)))
+            }),
+            SemanticTokensFullRequest::METHOD => self.respond::<SemanticTokensFullRequest>(request, |analysis, params| {
+                let data = analysis.semantic_tokens(&params.text_document.uri).chunks(5).map(|token| lsp_types::SemanticToken {
+                    delta_line: token[0],
+                    delta_start: token[1],
+                    length: token[2],
+                    token_type: token[3],
+                    token_modifiers_bitset: token[4],
+                }).collect();
+                Some(SemanticTokensResult::Tokens(lsp_types::SemanticTokens { result_id: None, data }))
+            }),
+            _ => Response::new_err(request.id, ErrorCode::MethodNotFound as i32, format!("Unsupported request: {}", request.method)),
+        }
+    }
+
+    fn respond<R: lsp_types::request::Request>(&self, request: Request, handler: impl FnOnce(&Analysis, R::Params) -> R::Result) -> Response {
+        match serde_json::from_value::<R::Params>(request.params) {
+            Ok(params) => match self.panics.catch(&format!("Answering {}", R::METHOD), || handler(&self.analysis, params)) {
+                Some(result) => Response::new_ok(request.id, result),
+                None => Response::new_err(request.id, ErrorCode::InternalError as i32, format!("Failed to answer {}.", R::METHOD)),
+            },
+            Err(error) => Response::new_err(request.id, ErrorCode::InvalidParams as i32, error.to_string()),
+        }
+    }
+
+    fn handle_notification(&mut self, notification: Notification) -> Result<(), Box<dyn Error + Sync + Send>> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams = serde_json::from_value(notification.params)?;
+                self.workspace.open(params.text_document.uri, params.text_document.text);
+            },
+            DidChangeTextDocument::METHOD => {
+                let params: DidChangeTextDocumentParams = serde_json::from_value(notification.params)?;
+                self.workspace.change(&params.text_document.uri, params.content_changes);
+            },
+            DidCloseTextDocument::METHOD => {
+                let params: DidCloseTextDocumentParams = serde_json::from_value(notification.params)?;
+                if self.workspace.close(&params.text_document.uri) {
+                    self.publish_diagnostics(params.text_document.uri, vec![])?;
+                }
+            },
+            _ => return Ok(()),
+        }
+        self.analysis = Analysis::new(&self.workspace);
+        self.publish_all_diagnostics()?;
+        self.log_panics()
+    }
+
+    fn publish_all_diagnostics(&self) -> Result<(), Box<dyn Error + Sync + Send>> {
+        for url in self.analysis.urls() {
+            self.publish_diagnostics(url.clone(), self.analysis.diagnostics(url))?;
+        }
+        Ok(())
+    }
+
+    /// Reports the panics caught since the last report.
+    fn log_panics(&self) -> Result<(), Box<dyn Error + Sync + Send>> {
+        for message in self.panics.take() {
+            self.log_message(MessageType::ERROR, message)?;
+        }
+        Ok(())
+    }
+
+    fn log_message(&self, typ: MessageType, message: String) -> Result<(), Box<dyn Error + Sync + Send>> {
+        let params = LogMessageParams { typ, message };
+        self.connection.sender.send(Message::Notification(Notification::new(LogMessage::METHOD.into(), params)))?;
+        Ok(())
+    }
+
+    fn publish_diagnostics(&self, uri: Url, diagnostics: Vec<lsp_types::Diagnostic>) -> Result<(), Box<dyn Error + Sync + Send>> {
+        let params = PublishDiagnosticsParams { uri, diagnostics, version: None };
+        self.connection.sender.send(Message::Notification(Notification::new(PublishDiagnostics::METHOD.into(), params)))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+    use std::thread;
+
+    const JET_LANG: &str = "package jet.lang { public class Object {} public class Boolean {} public class Class {} public class Function {} public class Number {} public class String {} }";
+
+    fn request(client: &Connection, id: i32, method: &str, params: Value) -> Value {
+        client.sender.send(Message::Request(Request::new(id.into(), method.into(), params))).unwrap();
+        match client.receiver.recv().unwrap() {
+            Message::Response(response) => {
+                assert_eq!(response.id, id.into());
+                response.result.unwrap_or_else(|| serde_json::to_value(response.error.unwrap().code).unwrap())
+            },
+            _ => panic!("Expected a response."),
+        }
+    }
+
+    fn notify(client: &Connection, method: &str, params: Value) {
+        client.sender.send(Message::Notification(Notification::new(method.into(), params))).unwrap();
+    }
+
+    /// Receives the diagnostics of a document, skipping
+    /// those of other documents.
+    fn receive_diagnostics(client: &Connection, uri: &Url) -> Value {
+        loop {
+            match client.receiver.recv().unwrap() {
+                Message::Notification(notification) => {
+                    assert_eq!(notification.method, PublishDiagnostics::METHOD, "{:?}", notification.params);
+                    if notification.params["uri"] == json!(uri) {
+                        return notification.params["diagnostics"].clone();
+                    }
+                },
+                _ => panic!("Expected a notification."),
+            }
+        }
+    }
+
+    #[test]
+    fn serving_requests() {
+        // The project configuration file adds the source root containing
+        // `jet.lang`, which is not opened in the editor.
+        let root = std::env::temp_dir().join(format!("jet-language-server-test-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("lib")).unwrap();
+        std::fs::write(root.join(CONFIG_FILE_NAME), r#"{ "sourceRoots": ["lib"] }"#).unwrap();
+        std::fs::write(root.join("lib/lang.jet"), JET_LANG).unwrap();
+        let jet_lang_uri = Url::from_file_path(root.join("lib/lang.jet")).unwrap();
+        let uri = Url::from_file_path(root.join("main.jet")).unwrap();
+
+        let (connection, client) = Connection::memory();
+        let server = thread::spawn(move || Server::run(connection).unwrap());
+
+        let result = request(&client, 1, "initialize", json!({ "capabilities": {}, "rootUri": Url::from_directory_path(&root).unwrap() }));
+        assert_eq!(result["capabilities"]["hoverProvider"], json!(true));
+        notify(&client, "initialized", json!({}));
+        assert_eq!(receive_diagnostics(&client, &jet_lang_uri), json!([]));
+
+        notify(&client, DidOpenTextDocument::METHOD, json!({
+            "textDocument": { "uri": uri, "languageId": "jet", "version": 1, "text": "package p { public var v: Undefined; }" },
+        }));
+        let diagnostics = receive_diagnostics(&client, &uri);
+        assert_eq!(diagnostics.as_array().unwrap().len(), 1);
+        assert_eq!(diagnostics[0]["range"]["start"], json!({ "line": 0, "character": 26 }));
+
+        notify(&client, DidChangeTextDocument::METHOD, json!({
+            "textDocument": { "uri": uri, "version": 2 },
+            "contentChanges": [{ "range": { "start": { "line": 0, "character": 26 }, "end": { "line": 0, "character": 35 } }, "text": "Number" }],
+        }));
+        assert_eq!(receive_diagnostics(&client, &uri), json!([]));
+
+        let hover = request(&client, 2, HoverRequest::METHOD, json!({
+            "textDocument": { "uri": uri },
+            "position": { "line": 0, "character": 23 },
+        }));
+        assert_eq!(hover["contents"]["value"], json!("```jet\nvar v: jet.lang.Number\n```"));
+
+        let definition = request(&client, 3, GotoDefinition::METHOD, json!({
+            "textDocument": { "uri": uri },
+            "position": { "line": 0, "character": 27 },
+        }));
+        assert_eq!(definition["uri"], json!(jet_lang_uri));
+        assert_eq!(definition["range"]["start"], json!({ "line": 0, "character": 126 }));
+
+        let error = request(&client, 4, "textDocument/unknown", json!({}));
+        assert_eq!(error, json!(ErrorCode::MethodNotFound as i32));
+
+        notify(&client, DidCloseTextDocument::METHOD, json!({ "textDocument": { "uri": uri } }));
+        assert_eq!(receive_diagnostics(&client, &uri), json!([]));
+        assert_eq!(receive_diagnostics(&client, &jet_lang_uri), json!([]));
+
+        request(&client, 5, "shutdown", Value::Null);
+        notify(&client, "exit", Value::Null);
+        server.join().unwrap();
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn logging_configuration_errors() {
+        let (connection, client) = Connection::memory();
+        let server = thread::spawn(move || Server::run(connection).unwrap());
+
+        request(&client, 1, "initialize", json!({ "capabilities": {}, "initializationOptions": { "arguments": ["--unknown"] } }));
+        notify(&client, "initialized", json!({}));
+        match client.receiver.recv().unwrap() {
+            Message::Notification(notification) => {
+                assert_eq!(notification.method, LogMessage::METHOD);
+                assert_eq!(notification.params["type"], json!(MessageType::ERROR));
+            },
+            _ => panic!("Expected a notification."),
+        }
+
+        request(&client, 2, "shutdown", Value::Null);
+        notify(&client, "exit", Value::Null);
+        server.join().unwrap();
+    }
+}
@@ -0,0 +1,304 @@
+use crate::panics::*;
+use crate::position::*;
+use hydroper_jet_compiler::ns::*;
+use lsp_types::{TextDocumentContentChangeEvent, Url};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// Name of the project configuration file looked up
+/// at the root of the workspace.
+pub const CONFIG_FILE_NAME: &str = "jet.json";
+
+/// A parsed source of the workspace.
+pub struct Document {
+    pub compilation_unit: Rc<CompilationUnit>,
+    pub program: Option<Rc<Program>>,
+}
+
+/// The sources verified together by the language server: the sources
+/// under the source roots of the project, which include the `jet.lang`
+/// package, and the documents open in the editor.
+///
+/// Documents keep their compilation unit while open, and changes
+/// to them are reparsed incrementally.
+pub struct Workspace {
+    compiler_options: Rc<CompilerOptions>,
+    source_roots: Vec<PathBuf>,
+    documents: BTreeMap<Url, Document>,
+    ast_cache: Option<AstCache>,
+    panics: Rc<PanicLog>,
+}
+
+impl Workspace {
+    pub fn new(compiler_options: Rc<CompilerOptions>, panics: &Rc<PanicLog>) -> Self {
+        let source_roots = compiler_options.source_roots.iter().map(PathBuf::from).collect();
+        Self {
+            compiler_options,
+            source_roots,
+            documents: BTreeMap::new(),
+            ast_cache: None,
+            panics: panics.clone(),
+        }
+    }
+
+    /// Loads the compiler options of a project from the initialization
+    /// options of the client, given as
+    /// `{ "config": "jet.json", "arguments": ["--source-root", "lib"] }`.
+    ///
+    /// The configuration file defaults to `jet.json` at the root of the
+    /// workspace, if it exists. Relative paths are resolved from the root.
+    pub fn load_compiler_options(root: Option<&Path>, initialization_options: Option<&Value>) -> Result<CompilerOptions, String> {
+        let resolve = |path: &str| match root {
+            Some(root) => root.join(path),
+            None => PathBuf::from(path),
+        };
+        let mut compiler_options = CompilerOptions::default();
+        let config = initialization_options.and_then(|options| options.get("config"));
+        let config_path = match config {
+            Some(config) => Some(resolve(config.as_str().ok_or("The \"config\" initialization option must be a string.")?)),
+            None => Some(resolve(CONFIG_FILE_NAME)).filter(|path| root.is_some() && path.is_file()),
+        };
+        if let Some(config_path) = config_path {
+            compiler_options.apply_config_file(&config_path.to_string_lossy())
+                .map_err(|error| format!("Could not load {}: {error:?}", config_path.display()))?;
+        }
+        let arguments = match initialization_options.and_then(|options| options.get("arguments")) {
+            Some(arguments) => serde_json::from_value::<Vec<String>>(arguments.clone())
+                .map_err(|_| "The \"arguments\" initialization option must be an array of strings.")?,
+            None => vec![],
+        };
+        compiler_options.apply_arguments(&arguments).map_err(|error| format!("Invalid compiler flags: {error:?}"))?;
+        for source_root in &mut compiler_options.source_roots {
+            *source_root = resolve(source_root).to_string_lossy().into_owned();
+        }
+        Ok(compiler_options)
+    }
+
+    /// Loads the AST cache named by the `astCache` initialization option,
+    /// a directory relative to the root of the workspace.
+    pub fn load_ast_cache(root: Option<&Path>, initialization_options: Option<&Value>) -> Result<Option<AstCache>, String> {
+        let Some(directory) = initialization_options.and_then(|options| options.get("astCache")) else {
+            return Ok(None);
+        };
+        let directory = directory.as_str().ok_or("The \"astCache\" initialization option must be a string.")?;
+        Ok(Some(AstCache::new(root.map_or_else(|| PathBuf::from(directory), |root| root.join(directory)))))
+    }
+
+    /// Sets the cache from which the sources read from
+    /// the file system are reloaded when unchanged.
+    pub fn set_ast_cache(&mut self, ast_cache: AstCache) {
+        self.ast_cache = Some(ast_cache);
+    }
+
+    /// Parses the sources under the source roots, returning
+    /// the paths that could not be read.
+    pub fn load_source_roots(&mut self) -> Vec<PathBuf> {
+        let mut failures = vec![];
+        for source_root in self.source_roots.clone() {
+            let mut paths = vec![];
+            if collect_sources(&source_root, &mut paths).is_err() {
+                failures.push(source_root);
+                continue;
+            }
+            for path in paths {
+                let Ok(url) = Url::from_file_path(&path) else {
+                    continue;
+                };
+                match std::fs::read_to_string(&path) {
+                    Ok(text) => {
+                        let document = self.parse_file(&url, text);
+                        self.documents.insert(url, document);
+                    },
+                    Err(_) => failures.push(path),
+                }
+            }
+        }
+        failures
+    }
+
+    pub fn documents(&self) -> &BTreeMap<Url, Document> {
+        &self.documents
+    }
+
+    pub fn panics(&self) -> &Rc<PanicLog> {
+        &self.panics
+    }
+
+    /// Opens a document, whose text is then synchronized
+    /// from the editor instead of the file system.
+    pub fn open(&mut self, url: Url, text: String) {
+        if self.documents.get(&url).is_some_and(|document| *document.compilation_unit.current_text() == text) {
+            return;
+        }
+        let document = self.parse(&url, text);
+        self.documents.insert(url, document);
+    }
+
+    /// Applies the changes of a document, in order, reparsing
+    /// the parts of the program affected by them.
+    pub fn change(&mut self, url: &Url, changes: Vec<TextDocumentContentChangeEvent>) {
+        let Some(document) = self.documents.get(url) else {
+            return;
+        };
+        let compilation_unit = document.compilation_unit.clone();
+        // Verify diagnostics are reported again once the workspace
+        // is verified, and must not stop the parser from reusing directives.
+        compilation_unit.clear_verify_diagnostics();
+        let mut program = document.program.clone();
+        let mut text = compilation_unit.current_text().clone();
+        let mut reparse = program.is_none();
+        for change in changes {
+            let range = match change.range {
+                Some(range) => {
+                    let line_index = LineIndex::new(&text);
+                    let start = line_index.offset(&text, range.start);
+                    start..line_index.offset(&text, range.end).max(start)
+                },
+                None => 0..text.len(),
+            };
+            text.replace_range(range.clone(), &change.text);
+            if reparse {
+                continue;
+            }
+            let edit = TextEdit::new(range, &change.text);
+            program = self.panics.catch("Reparsing a change", || compilation_unit.apply_edits(program.as_ref().unwrap(), &[edit]).ok()).flatten();
+            // A failed reparse leaves the compilation unit
+            // in an unknown state, so the text is parsed again.
+            reparse = program.is_none();
+        }
+        if reparse {
+            let document = self.parse(url, text);
+            self.documents.insert(url.clone(), document);
+        } else {
+            self.documents.get_mut(url).unwrap().program = program;
+        }
+    }
+
+    /// Closes a document, restoring the text of the file system if
+    /// it is under a source root. Returns whether the document was
+    /// removed from the workspace.
+    pub fn close(&mut self, url: &Url) -> bool {
+        let path = url.to_file_path().ok().filter(|path| self.source_roots.iter().any(|root| path.starts_with(root)));
+        match path.and_then(|path| std::fs::read_to_string(path).ok()) {
+            Some(text) => {
+                let document = self.parse_file(url, text);
+                self.documents.insert(url.clone(), document);
+                false
+            },
+            None => self.documents.remove(url).is_some(),
+        }
+    }
+
+    fn parse(&self, url: &Url, text: String) -> Document {
+        let compilation_unit = self.compilation_unit(url, text);
+        let program = self.panics.catch("Parsing", || ParserFacade::parse_program(&compilation_unit)).flatten();
+        Document { compilation_unit, program }
+    }
+
+    /// Parses a source read from the file system, reloading
+    /// it from the AST cache if it is unchanged.
+    fn parse_file(&self, url: &Url, text: String) -> Document {
+        let Some(ast_cache) = &self.ast_cache else {
+            return self.parse(url, text);
+        };
+        let compilation_unit = self.compilation_unit(url, text);
+        let program = self.panics.catch("Parsing", || ParserFacade::parse_program_with_cache(&compilation_unit, ast_cache)).flatten();
+        Document { compilation_unit, program }
+    }
+
+    fn compilation_unit(&self, url: &Url, text: String) -> Rc<CompilationUnit> {
+        let file_path = url.to_file_path().ok().map(|path| path.to_string_lossy().into_owned());
+        CompilationUnit::new(file_path, text, &self.compiler_options)
+    }
+}
+
+/// Collects the paths of the `.jet` files under a directory.
+fn collect_sources(directory: &Path, paths: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    let mut entries = std::fs::read_dir(directory)?.collect::<Result<Vec<_>, _>>()?;
+    entries.sort_by_key(|entry| entry.path());
+    for entry in entries {
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            collect_sources(&path, paths)?;
+        } else if path.extension().is_some_and(|extension| extension == "jet") {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn loading_source_roots() {
+        let root = std::env::temp_dir().join(format!("jet-workspace-test-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("lib/jet")).unwrap();
+        std::fs::write(root.join(CONFIG_FILE_NAME), r#"{ "mode": "dynamic", "sourceRoots": ["lib"] }"#).unwrap();
+        std::fs::write(root.join("lib/jet/lang.jet"), "package jet.lang {}").unwrap();
+        std::fs::write(root.join("lib/notes.txt"), "").unwrap();
+
+        let options = json!({ "arguments": ["--source-root", "missing"] });
+        let compiler_options = Workspace::load_compiler_options(Some(&root), Some(&options)).unwrap();
+        assert!(!compiler_options.is_strict());
+        assert_eq!(compiler_options.source_roots.iter().map(PathBuf::from).collect::<Vec<_>>(), vec![root.join("lib"), root.join("missing")]);
+
+        let mut workspace = Workspace::new(Rc::new(compiler_options), &Rc::new(PanicLog::default()));
+        assert_eq!(workspace.load_source_roots(), vec![root.join("missing")]);
+        let url = Url::from_file_path(root.join("lib/jet/lang.jet")).unwrap();
+        assert_eq!(workspace.documents().keys().collect::<Vec<_>>(), vec![&url]);
+
+        // Closing a document of a source root restores its saved text.
+        workspace.open(url.clone(), "package jet.lang { public class Object {} }".into());
+        assert!(!workspace.close(&url));
+        assert_eq!(*workspace.documents()[&url].compilation_unit.current_text(), "package jet.lang {}");
+
+        assert!(Workspace::load_compiler_options(Some(&root), Some(&json!({ "config": "other.json" }))).is_err());
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn reloading_source_roots_from_the_ast_cache() {
+        let root = std::env::temp_dir().join(format!("jet-workspace-cache-test-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("lib")).unwrap();
+        let text = "package jet.lang {\n    // Comment\n    public class Object {}\n}\n";
+        std::fs::write(root.join("lib/lang.jet"), text).unwrap();
+
+        let options = json!({ "arguments": ["--source-root", "lib"], "astCache": "cache" });
+        let load = || {
+            let compiler_options = Workspace::load_compiler_options(Some(&root), Some(&options)).unwrap();
+            let mut workspace = Workspace::new(Rc::new(compiler_options), &Rc::new(PanicLog::default()));
+            workspace.set_ast_cache(Workspace::load_ast_cache(Some(&root), Some(&options)).unwrap().unwrap());
+            assert!(workspace.load_source_roots().is_empty());
+            workspace
+        };
+        let url = Url::from_file_path(root.join("lib/lang.jet")).unwrap();
+        let workspace = load();
+        let compilation_unit = workspace.documents()[&url].compilation_unit.clone();
+        let entry_path = AstCache::new(root.join("cache")).entry_path(&compilation_unit);
+        assert!(entry_path.exists());
+
+        // The reloaded program is reparsed incrementally like a parsed one.
+        let mut workspace = load();
+        assert_eq!(workspace.documents()[&url].compilation_unit.comments().len(), 1);
+        workspace.open(url.clone(), text.into());
+        let end = lsp_types::Position::new(2, 26);
+        workspace.change(&url, vec![TextDocumentContentChangeEvent {
+            range: Some(lsp_types::Range::new(end, end)),
+            range_length: None,
+            text: "\n    public class Boolean {}".into(),
+        }]);
+        let document = &workspace.documents()[&url];
+        let reparsed = CompilationUnit::new(None, document.compilation_unit.current_text().clone(), &CompilerOptions::new());
+        let expected = ParserFacade::parse_program(&reparsed).unwrap();
+        assert_eq!(AstJson::serialize_program(document.program.as_ref().unwrap()).split_once("\"program\"").unwrap().1, AstJson::serialize_program(&expected).split_once("\"program\"").unwrap().1);
+        assert!(workspace.panics().take().is_empty());
+
+        assert!(Workspace::load_ast_cache(Some(&root), Some(&json!({ "astCache": 1 }))).is_err());
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
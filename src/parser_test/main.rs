@@ -1,6 +1,7 @@
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use file_paths::FlexPath;
 use std::{env, fs, io};
+use std::io::IsTerminal;
 use hydroper_jet_compiler::ns::*;
 
 /// Simple program to greet a person
@@ -12,6 +13,28 @@ struct Arguments {
 
     #[arg(short, long)]
     file_log: bool,
+
+    /// Format of the diagnostics.
+    #[arg(long, value_enum, default_value_t = DiagnosticFormat::Text)]
+    diagnostic_format: DiagnosticFormat,
+
+    /// Directory of the AST cache, from which the program
+    /// is reloaded if the source is unchanged.
+    #[arg(long)]
+    ast_cache: Option<String>,
+
+    /// Compiler flags, such as `--dynamic` or `--config jet.json`.
+    #[arg(last = true)]
+    compiler_options: Vec<String>,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, ValueEnum)]
+enum DiagnosticFormat {
+    Text,
+    /// Source snippets with markers under the diagnostic spans.
+    Rich,
+    JsonLines,
+    Sarif,
 }
 
 fn main() -> io::Result<()> {
@@ -21,35 +44,52 @@ fn main() -> io::Result<()> {
     // Canonicalize path
     // let source_path = std::path::Path::new(&source_path).canonicalize().unwrap().to_string_lossy().into_owned();
 
-    let jetdependencies_target_path = FlexPath::new_native(&source_path).resolve("../jetpm-target").to_string_with_flex_separator();
+    let _jetdependencies_target_path = FlexPath::new_native(&source_path).resolve("../jetpm-target").to_string_with_flex_separator();
     let source_path_ast_json = FlexPath::new_native(&source_path).change_extension(".ast.json").to_string_with_flex_separator();
     let source_path_diagnostics = FlexPath::new_native(&source_path).change_extension(".diag").to_string_with_flex_separator();
     let source_content = fs::read_to_string(&source_path)?;
-    let compilation_unit = CompilationUnit::new(Some(source_path), source_content, &CompilerOptions::new());
-    if let Some(program) = ParserFacade::parse_program(&compilation_unit) {
+    let mut compiler_options = CompilerOptions::default();
+    if let Err(error) = compiler_options.apply_arguments(&arguments.compiler_options) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("{error:?}")));
+    }
+    let messages = match compiler_options.message_catalog() {
+        Ok(messages) => messages,
+        Err(error) => return Err(io::Error::new(io::ErrorKind::InvalidData, format!("{error:?}"))),
+    };
+    let compilation_unit = CompilationUnit::new(Some(source_path), source_content, &Rc::new(compiler_options));
+    let program = match &arguments.ast_cache {
+        Some(ast_cache) => ParserFacade::parse_program_with_cache(&compilation_unit, &AstCache::new(ast_cache)),
+        None => ParserFacade::parse_program(&compilation_unit),
+    };
+    if let Some(program) = program {
         if arguments.file_log {
-            fs::write(&source_path_ast_json, serde_json::to_string_pretty(&program).unwrap())?;
-        } else {
+            fs::write(&source_path_ast_json, AstJson::serialize_program(&program))?;
+        } else if matches!(arguments.diagnostic_format, DiagnosticFormat::Text | DiagnosticFormat::Rich) {
             println!("Jet program successfuly parsed.");
         }
     } else {
         if arguments.file_log {
             fs::write(&source_path_ast_json, "{}")?;
-        } else {
+        } else if matches!(arguments.diagnostic_format, DiagnosticFormat::Text | DiagnosticFormat::Rich) {
             println!("Jet program failed to parse.");
         }
     }
-    let mut diagnostics = vec![];
     compilation_unit.sort_diagnostics();
-    for diagnostic in compilation_unit.diagnostics() {
-        diagnostics.push(diagnostic.format_english());
-    }
+    let diagnostics = match arguments.diagnostic_format {
+        DiagnosticFormat::Text => {
+            compilation_unit.diagnostics().iter().map(|diagnostic| diagnostic.format_with_catalog(&messages)).collect::<Vec<_>>().join("\n")
+        },
+        DiagnosticFormat::Rich => {
+            let renderer = DiagnosticRenderer::new(&messages, !arguments.file_log && io::stdout().is_terminal());
+            compilation_unit.diagnostics().iter().map(|diagnostic| renderer.render(diagnostic)).collect::<Vec<_>>().join("\n")
+        },
+        DiagnosticFormat::JsonLines => DiagnosticExport::to_json_lines(&compilation_unit.diagnostics(), &messages),
+        DiagnosticFormat::Sarif => DiagnosticExport::to_sarif(&compilation_unit.diagnostics(), &messages),
+    };
     if arguments.file_log {
-        fs::write(&source_path_diagnostics, diagnostics.join("\n"))?;
-    } else {
-        for diagnostic in diagnostics {
-            println!("{diagnostic}");
-        }
+        fs::write(&source_path_diagnostics, diagnostics)?;
+    } else if !diagnostics.is_empty() {
+        println!("{}", diagnostics.trim_end());
     }
     Ok(())
 }
\ No newline at end of file